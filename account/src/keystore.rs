@@ -0,0 +1,271 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Account;
+use snarkvm::{console::network::prelude::*, prelude::*};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm,
+    Nonce,
+};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// The version of the keystore format.
+const KEYSTORE_VERSION: u8 = 1;
+/// The cipher used to encrypt the private key.
+const CIPHER: &str = "aes-256-gcm";
+/// The key derivation function used to derive the encryption key from the password.
+const KDF: &str = "scrypt";
+
+/// The default scrypt cost parameter (log2 of N).
+const SCRYPT_LOG_N: u8 = 15;
+/// The default scrypt block size parameter.
+const SCRYPT_R: u32 = 8;
+/// The default scrypt parallelization parameter.
+const SCRYPT_P: u32 = 1;
+/// The maximum scrypt cost parameter (log2 of N) of a keystore.
+/// Note: Along with the maximum block size, this bounds the memory of the key derivation to 1 GiB.
+const MAX_SCRYPT_LOG_N: u8 = 20;
+/// The maximum scrypt block size parameter of a keystore.
+const MAX_SCRYPT_R: u32 = 8;
+/// The maximum scrypt parallelization parameter of a keystore.
+const MAX_SCRYPT_P: u32 = 16;
+
+/// The length of the salt in bytes.
+const SALT_LENGTH: usize = 32;
+/// The length of the nonce in bytes.
+const NONCE_LENGTH: usize = 12;
+/// The length of the encryption key in bytes.
+const KEY_LENGTH: usize = 32;
+
+/// A password-encrypted keystore for an account private key.
+///
+/// The encryption key is derived from the password with scrypt, and the private key is encrypted
+/// with AES-256-GCM, using the address as associated data, so that neither can be swapped out.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    /// The version of the keystore format.
+    version: u8,
+    /// The address of the account.
+    address: String,
+    /// The encrypted private key.
+    crypto: KeystoreCrypto,
+}
+
+/// The encrypted private key, with the parameters needed to decrypt it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct KeystoreCrypto {
+    /// The cipher used to encrypt the private key.
+    cipher: String,
+    /// The encrypted private key, in hex.
+    ciphertext: String,
+    /// The nonce of the cipher, in hex.
+    nonce: String,
+    /// The key derivation function.
+    kdf: String,
+    /// The parameters of the key derivation function.
+    kdfparams: ScryptParams,
+}
+
+/// The scrypt parameters, used to derive the encryption key from the password.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct ScryptParams {
+    /// The cost parameter (log2 of N).
+    log_n: u8,
+    /// The block size parameter.
+    r: u32,
+    /// The parallelization parameter.
+    p: u32,
+    /// The salt, in hex.
+    salt: String,
+}
+
+impl Keystore {
+    /// Encrypts the given private key with the given password.
+    pub fn encrypt<N: Network, R: Rng + CryptoRng>(
+        private_key: &PrivateKey<N>,
+        password: &str,
+        rng: &mut R,
+    ) -> Result<Self> {
+        Self::encrypt_with_cost(private_key, password, SCRYPT_LOG_N, rng)
+    }
+
+    /// Encrypts the given private key with the given password, using the given scrypt cost parameter.
+    fn encrypt_with_cost<N: Network, R: Rng + CryptoRng>(
+        private_key: &PrivateKey<N>,
+        password: &str,
+        log_n: u8,
+        rng: &mut R,
+    ) -> Result<Self> {
+        ensure!(!password.is_empty(), "The keystore password must not be empty");
+
+        // Derive the encryption key from the password.
+        let kdfparams =
+            ScryptParams { log_n, r: SCRYPT_R, p: SCRYPT_P, salt: hex::encode(rng.gen::<[u8; SALT_LENGTH]>()) };
+        let key = kdfparams.derive_key(password)?;
+
+        // Encrypt the private key, binding it to the address.
+        let address = Address::try_from(private_key)?.to_string();
+        let nonce = rng.gen::<[u8; NONCE_LENGTH]>();
+        let plaintext = Zeroizing::new(private_key.to_string());
+        let ciphertext = Aes256Gcm::new_from_slice(key.as_ref())
+            .map_err(|_| anyhow!("Invalid keystore encryption key"))?
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext.as_bytes(), aad: address.as_bytes() })
+            .map_err(|_| anyhow!("Failed to encrypt the private key"))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            address,
+            crypto: KeystoreCrypto {
+                cipher: CIPHER.to_string(),
+                ciphertext: hex::encode(ciphertext),
+                nonce: hex::encode(nonce),
+                kdf: KDF.to_string(),
+                kdfparams,
+            },
+        })
+    }
+
+    /// Decrypts the account with the given password.
+    pub fn decrypt<N: Network>(&self, password: &str) -> Result<Account<N>> {
+        ensure!(self.version == KEYSTORE_VERSION, "Unsupported keystore version '{}'", self.version);
+        ensure!(self.crypto.cipher == CIPHER, "Unsupported keystore cipher '{}'", self.crypto.cipher);
+        ensure!(self.crypto.kdf == KDF, "Unsupported keystore key derivation function '{}'", self.crypto.kdf);
+
+        // Derive the encryption key from the password.
+        let key = self.crypto.kdfparams.derive_key(password)?;
+
+        // Decrypt the private key.
+        let nonce = hex::decode(&self.crypto.nonce)?;
+        ensure!(nonce.len() == NONCE_LENGTH, "Invalid keystore nonce");
+        let ciphertext = hex::decode(&self.crypto.ciphertext)?;
+        let plaintext = Zeroizing::new(
+            Aes256Gcm::new_from_slice(key.as_ref())
+                .map_err(|_| anyhow!("Invalid keystore encryption key"))?
+                .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: self.address.as_bytes() })
+                .map_err(|_| anyhow!("Failed to decrypt the keystore (incorrect password)"))?,
+        );
+
+        // Construct the account.
+        let account = Account::from_str(std::str::from_utf8(&plaintext)?)?;
+        ensure!(account.address().to_string() == self.address, "The keystore address does not match its private key");
+        Ok(account)
+    }
+
+    /// Returns the address of the account, which is stored unencrypted.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+}
+
+impl ScryptParams {
+    /// Derives the encryption key from the given password.
+    fn derive_key(&self, password: &str) -> Result<Zeroizing<[u8; KEY_LENGTH]>> {
+        // Ensure the parameters are bounded, as a crafted keystore could otherwise exhaust the CPU and memory.
+        ensure!(self.log_n <= MAX_SCRYPT_LOG_N, "The keystore scrypt cost exceeds the maximum ({MAX_SCRYPT_LOG_N})");
+        ensure!(self.r <= MAX_SCRYPT_R, "The keystore scrypt block size exceeds the maximum ({MAX_SCRYPT_R})");
+        ensure!(self.p <= MAX_SCRYPT_P, "The keystore scrypt parallelization exceeds the maximum ({MAX_SCRYPT_P})");
+        let salt = hex::decode(&self.salt)?;
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, KEY_LENGTH)
+            .map_err(|_| anyhow!("Invalid keystore scrypt parameters"))?;
+        let mut key = Zeroizing::new([0u8; KEY_LENGTH]);
+        scrypt::scrypt(password.as_bytes(), &salt, &params, key.as_mut())
+            .map_err(|_| anyhow!("Failed to derive the keystore encryption key"))?;
+        Ok(key)
+    }
+}
+
+impl FromStr for Keystore {
+    type Err = Error;
+
+    /// Initializes the keystore from a JSON string.
+    fn from_str(keystore: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(keystore)?)
+    }
+}
+
+impl Display for Keystore {
+    /// Renders the keystore as a JSON string.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    /// A low scrypt cost, to keep the tests fast.
+    const TEST_LOG_N: u8 = 4;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let mut rng = TestRng::default();
+        let account = Account::<CurrentNetwork>::new(&mut rng).unwrap();
+
+        let keystore = Keystore::encrypt_with_cost(account.private_key(), "password", TEST_LOG_N, &mut rng).unwrap();
+        assert_eq!(keystore.address(), account.address().to_string());
+        // The keystore does not contain the private key in plaintext.
+        assert!(!keystore.to_string().contains(&account.private_key().to_string()));
+
+        // Round-trip the keystore through its string representation.
+        let keystore = Keystore::from_str(&keystore.to_string()).unwrap();
+        let decrypted = keystore.decrypt::<CurrentNetwork>("password").unwrap();
+        assert_eq!(decrypted.private_key(), account.private_key());
+        assert_eq!(decrypted.address(), account.address());
+    }
+
+    #[test]
+    fn test_decrypt_failures() {
+        let mut rng = TestRng::default();
+        let account = Account::<CurrentNetwork>::new(&mut rng).unwrap();
+        let keystore = Keystore::encrypt_with_cost(account.private_key(), "password", TEST_LOG_N, &mut rng).unwrap();
+
+        // An incorrect password is rejected.
+        assert!(keystore.decrypt::<CurrentNetwork>("incorrect").is_err());
+
+        // A swapped address is rejected.
+        let mut swapped = keystore.clone();
+        swapped.address = Account::<CurrentNetwork>::new(&mut rng).unwrap().address().to_string();
+        assert!(swapped.decrypt::<CurrentNetwork>("password").is_err());
+
+        // A tampered ciphertext is rejected.
+        let mut tampered = keystore.clone();
+        tampered
+            .crypto
+            .ciphertext
+            .replace_range(0..2, if tampered.crypto.ciphertext.starts_with("00") { "01" } else { "00" });
+        assert!(tampered.decrypt::<CurrentNetwork>("password").is_err());
+
+        // Excessive scrypt parameters are rejected.
+        let mut costly = keystore.clone();
+        costly.crypto.kdfparams.log_n = MAX_SCRYPT_LOG_N + 1;
+        assert!(costly.decrypt::<CurrentNetwork>("password").is_err());
+        let mut costly = keystore.clone();
+        costly.crypto.kdfparams.r = MAX_SCRYPT_R + 1;
+        assert!(costly.decrypt::<CurrentNetwork>("password").is_err());
+        let mut costly = keystore;
+        costly.crypto.kdfparams.p = MAX_SCRYPT_P + 1;
+        assert!(costly.decrypt::<CurrentNetwork>("password").is_err());
+
+        // An empty password is rejected.
+        assert!(Keystore::encrypt_with_cost(account.private_key(), "", TEST_LOG_N, &mut rng).is_err());
+    }
+}
@@ -14,6 +14,12 @@
 
 #![forbid(unsafe_code)]
 
+mod keystore;
+pub use keystore::*;
+
+mod signer;
+pub use signer::*;
+
 use snarkvm::{
     console::{network::prelude::*, types::Field},
     prelude::*,
@@ -0,0 +1,121 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::{network::prelude::*, types::Field},
+    ledger::narwhal::{BatchHeader, TransmissionID},
+    prelude::*,
+};
+
+use indexmap::IndexSet;
+
+/// A request sent to the signer process, encoded as a line of JSON.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", bound = "")]
+pub enum SignRequest<N: Network> {
+    /// Requests the address of the signer.
+    Address,
+    /// Requests a signature over the batch ID of this signer's proposal in the given round.
+    ProposeBatch {
+        round: u64,
+        timestamp: i64,
+        committee_id: Field<N>,
+        transmission_ids: IndexSet<TransmissionID<N>>,
+        previous_certificate_ids: IndexSet<Field<N>>,
+    },
+    /// Requests a signature over the batch ID of a batch header proposed by another validator.
+    SignBatch { batch_header: BatchHeader<N> },
+    /// Requests a signature over a handshake challenge.
+    Challenge { nonce: u64, response_nonce: u64 },
+}
+
+/// A response sent by the signer process, encoded as a line of JSON.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", bound = "")]
+pub enum SignResponse<N: Network> {
+    /// The address of the signer.
+    Address { address: Address<N> },
+    /// The requested signature.
+    Signature { signature: Signature<N> },
+    /// The request was refused.
+    Error { message: String },
+}
+
+impl<N: Network> SignRequest<N> {
+    /// Returns the request name.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Address => "Address",
+            Self::ProposeBatch { .. } => "ProposeBatch",
+            Self::SignBatch { .. } => "SignBatch",
+            Self::Challenge { .. } => "Challenge",
+        }
+    }
+
+    /// Serializes the request as a line of JSON (without the trailing newline).
+    pub fn to_line(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes the request from a line of JSON.
+    pub fn from_line(line: &str) -> Result<Self> {
+        Ok(serde_json::from_str(line)?)
+    }
+}
+
+impl<N: Network> SignResponse<N> {
+    /// Serializes the response as a line of JSON (without the trailing newline).
+    pub fn to_line(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes the response from a line of JSON.
+    pub fn from_line(line: &str) -> Result<Self> {
+        Ok(serde_json::from_str(line)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{MainnetV0, TestRng};
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_sign_message_round_trip() {
+        let rng = &mut TestRng::default();
+
+        let requests = [
+            SignRequest::<CurrentNetwork>::Address,
+            SignRequest::ProposeBatch {
+                round: 5,
+                timestamp: 1_700_000_000,
+                committee_id: Field::rand(rng),
+                transmission_ids: Default::default(),
+                previous_certificate_ids: [Field::rand(rng)].into_iter().collect(),
+            },
+            SignRequest::Challenge { nonce: 1, response_nonce: 2 },
+        ];
+        for request in requests {
+            let line = request.to_line().unwrap();
+            assert!(!line.contains('\n'));
+            assert_eq!(SignRequest::from_line(&line).unwrap(), request);
+        }
+
+        let response = SignResponse::<CurrentNetwork>::Error { message: "refused".to_string() };
+        assert_eq!(response.to_line().unwrap(), r#"{"type":"error","message":"refused"}"#);
+        assert_eq!(SignResponse::from_line(&response.to_line().unwrap()).unwrap(), response);
+    }
+}
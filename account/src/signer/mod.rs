@@ -0,0 +1,172 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod messages;
+pub use messages::*;
+
+mod protection;
+pub use protection::*;
+
+#[cfg(target_family = "unix")]
+mod remote;
+#[cfg(target_family = "unix")]
+pub use remote::*;
+
+#[cfg(target_family = "unix")]
+mod server;
+#[cfg(target_family = "unix")]
+pub use server::*;
+
+use crate::Account;
+use snarkvm::{
+    console::{network::prelude::*, types::Field},
+    ledger::narwhal::{BatchHeader, TransmissionID},
+    prelude::*,
+};
+
+use indexmap::IndexSet;
+
+/// The signer of a validator, which signs batch headers, batch certificates, and handshake challenges.
+#[derive(Clone)]
+pub enum Signer<N: Network> {
+    /// A signer holding the account private key in process memory.
+    Local(Account<N>),
+    /// A signer delegating to an external signer process.
+    #[cfg(target_family = "unix")]
+    Remote(RemoteSigner<N>),
+}
+
+impl<N: Network> From<Account<N>> for Signer<N> {
+    /// Initializes a local signer from the given account.
+    fn from(account: Account<N>) -> Self {
+        Self::Local(account)
+    }
+}
+
+impl<N: Network> Signer<N> {
+    /// Returns the address of the signer.
+    pub fn address(&self) -> Address<N> {
+        match self {
+            Self::Local(account) => account.address(),
+            #[cfg(target_family = "unix")]
+            Self::Remote(remote) => remote.address(),
+        }
+    }
+
+    /// Returns the account of the signer, if the private key is held in process memory.
+    pub fn account(&self) -> Option<&Account<N>> {
+        match self {
+            Self::Local(account) => Some(account),
+            #[cfg(target_family = "unix")]
+            Self::Remote(..) => None,
+        }
+    }
+
+    /// Returns a new batch header for this signer's proposal in the given round.
+    pub fn sign_batch_header<R: Rng + CryptoRng>(
+        &self,
+        round: u64,
+        timestamp: i64,
+        committee_id: Field<N>,
+        transmission_ids: IndexSet<TransmissionID<N>>,
+        previous_certificate_ids: IndexSet<Field<N>>,
+        rng: &mut R,
+    ) -> Result<BatchHeader<N>> {
+        match self {
+            Self::Local(account) => BatchHeader::new(
+                account.private_key(),
+                round,
+                timestamp,
+                committee_id,
+                transmission_ids,
+                previous_certificate_ids,
+                rng,
+            ),
+            #[cfg(target_family = "unix")]
+            Self::Remote(remote) => {
+                let signature = remote.sign(SignRequest::ProposeBatch {
+                    round,
+                    timestamp,
+                    committee_id,
+                    transmission_ids: transmission_ids.clone(),
+                    previous_certificate_ids: previous_certificate_ids.clone(),
+                })?;
+                BatchHeader::from(
+                    remote.address(),
+                    round,
+                    timestamp,
+                    committee_id,
+                    transmission_ids,
+                    previous_certificate_ids,
+                    signature,
+                )
+            }
+        }
+    }
+
+    /// Returns a signature over the batch ID of the given batch header, authored by another validator.
+    pub fn sign_batch<R: Rng + CryptoRng>(&self, batch_header: &BatchHeader<N>, rng: &mut R) -> Result<Signature<N>> {
+        match self {
+            Self::Local(account) => account.sign(&[batch_header.batch_id()], rng),
+            #[cfg(target_family = "unix")]
+            Self::Remote(remote) => remote.sign(SignRequest::SignBatch { batch_header: batch_header.clone() }),
+        }
+    }
+
+    /// Returns a signature over the given handshake challenge nonce and response nonce.
+    pub fn sign_challenge<R: Rng + CryptoRng>(
+        &self,
+        nonce: u64,
+        response_nonce: u64,
+        rng: &mut R,
+    ) -> Result<Signature<N>> {
+        match self {
+            Self::Local(account) => account.sign_bytes(&challenge_message(nonce, response_nonce), rng),
+            #[cfg(target_family = "unix")]
+            Self::Remote(remote) => remote.sign(SignRequest::Challenge { nonce, response_nonce }),
+        }
+    }
+}
+
+/// Returns the message that is signed in response to a handshake challenge.
+pub fn challenge_message(nonce: u64, response_nonce: u64) -> Vec<u8> {
+    [nonce.to_le_bytes(), response_nonce.to_le_bytes()].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{MainnetV0, TestRng};
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_local_signer() {
+        let rng = &mut TestRng::default();
+
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let signer = Signer::from(account.clone());
+        assert_eq!(signer.address(), account.address());
+
+        // Sign a batch header.
+        let batch_header =
+            signer.sign_batch_header(1, 0, Field::rand(rng), Default::default(), Default::default(), rng).unwrap();
+        assert_eq!(batch_header.author(), account.address());
+        assert!(batch_header.signature().verify(&account.address(), &[batch_header.batch_id()]));
+
+        // Sign a challenge.
+        let signature = signer.sign_challenge(1, 2, rng).unwrap();
+        assert!(signature.verify_bytes(&account.address(), &challenge_message(1, 2)));
+    }
+}
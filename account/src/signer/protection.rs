@@ -0,0 +1,166 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_bft_storage_service::{SignedBatch, SigningHistory};
+use snarkvm::{
+    console::{network::prelude::*, types::Field},
+    prelude::*,
+};
+
+use std::path::PathBuf;
+use time::OffsetDateTime;
+
+/// The double-sign protection of a signer, which refuses to sign two different batches from the same author
+/// in the same round.
+///
+/// The signed batches are kept in a signing history, in the same format as the signing history of a validator.
+/// Batches below the rounds remembered by the history are refused.
+/// As for a validator, a second batch may be signed once the previously-signed batch has expired.
+#[derive(Debug)]
+pub struct SigningProtection<N: Network> {
+    /// The signing history.
+    history: SigningHistory<N>,
+    /// The number of seconds a proposal is valid for before it expires.
+    expiration_in_secs: i64,
+}
+
+impl<N: Network> SigningProtection<N> {
+    /// Initializes a signing protection, which is not persisted,
+    /// for proposals expiring after the given number of seconds.
+    pub fn new(expiration_in_secs: i64) -> Self {
+        Self { history: Default::default(), expiration_in_secs }
+    }
+
+    /// Opens the signing protection persisted at the given path, creating it if it does not exist,
+    /// for proposals expiring after the given number of seconds.
+    pub fn open(path: PathBuf, expiration_in_secs: i64) -> Result<Self> {
+        Ok(Self { history: SigningHistory::open(path)?, expiration_in_secs })
+    }
+
+    /// Returns the lowest round that may still be signed.
+    pub fn min_round(&self) -> u64 {
+        self.history.min_round()
+    }
+
+    /// Records the given batch from the given author, which may be this signer, before it is signed.
+    /// Returns an error if a different batch from this author was already signed in this round,
+    /// and has not expired yet.
+    pub fn check(&mut self, author: Address<N>, round: u64, batch_id: Field<N>, timestamp: i64) -> Result<()> {
+        let min_round = self.min_round();
+        ensure!(round >= min_round, "Refusing to sign a batch in round {round} (below round {min_round})");
+        if let Some(previous) = self.history.get(author, round) {
+            // If this batch was already signed, there is nothing to record.
+            if previous.batch_id == batch_id {
+                return Ok(());
+            }
+            // Ensure the previously-signed batch has expired.
+            let now = OffsetDateTime::now_utc().unix_timestamp();
+            ensure!(
+                now.saturating_sub(previous.timestamp) >= self.expiration_in_secs,
+                "Refusing to sign a second batch from '{author}' in round {round} prior to expiration",
+            );
+        }
+        // Note: The batch is persisted before this method returns, if the history is persisted.
+        self.history.insert(author, round, SignedBatch { batch_id, timestamp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{MainnetV0, PrivateKey, TestRng};
+
+    type CurrentNetwork = MainnetV0;
+
+    /// The number of seconds a proposal is valid for before it expires, in these tests.
+    const EXPIRATION_IN_SECS: i64 = 60;
+
+    fn now() -> i64 {
+        OffsetDateTime::now_utc().unix_timestamp()
+    }
+
+    fn sample_address(rng: &mut TestRng) -> Address<CurrentNetwork> {
+        Address::try_from(PrivateKey::new(rng).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_check() {
+        let rng = &mut TestRng::default();
+        let mut protection = SigningProtection::<CurrentNetwork>::new(EXPIRATION_IN_SECS);
+
+        let (author_a, author_b) = (sample_address(rng), sample_address(rng));
+        let (batch_a, batch_b) = (Field::rand(rng), Field::rand(rng));
+        let timestamp = now();
+        assert!(protection.check(author_a, 1, batch_a, timestamp).is_ok());
+        // Signing the same batch again is allowed.
+        assert!(protection.check(author_a, 1, batch_a, timestamp).is_ok());
+        // Signing a different batch from the same author in the same round is refused.
+        assert!(protection.check(author_a, 1, batch_b, timestamp + 1).is_err());
+        assert!(protection.check(author_a, 2, batch_b, timestamp + 1).is_ok());
+        // A different author may propose a different batch in the same round.
+        assert!(protection.check(author_b, 1, batch_b, timestamp).is_ok());
+    }
+
+    #[test]
+    fn test_check_expired() {
+        let rng = &mut TestRng::default();
+        let mut protection = SigningProtection::<CurrentNetwork>::new(EXPIRATION_IN_SECS);
+
+        let author = sample_address(rng);
+        let (batch_a, batch_b, batch_c) = (Field::rand(rng), Field::rand(rng), Field::rand(rng));
+        // Sign a batch which has already expired.
+        let timestamp = now() - EXPIRATION_IN_SECS;
+        assert!(protection.check(author, 1, batch_a, timestamp).is_ok());
+        // Signing a different batch from the same author in the same round is allowed once the first one expired.
+        assert!(protection.check(author, 1, batch_b, now()).is_ok());
+        // The new batch replaces the expired one, and has not expired yet.
+        assert!(protection.check(author, 1, batch_c, now()).is_err());
+        assert!(protection.check(author, 1, batch_b, now()).is_ok());
+    }
+
+    #[test]
+    fn test_protection_window() {
+        let rng = &mut TestRng::default();
+        let mut protection = SigningProtection::<CurrentNetwork>::new(EXPIRATION_IN_SECS);
+        let author = sample_address(rng);
+        let num_rounds = SigningHistory::<CurrentNetwork>::NUM_ROUNDS;
+
+        assert!(protection.check(author, 1, Field::rand(rng), 0).is_ok());
+        assert!(protection.check(author, num_rounds + 10, Field::rand(rng), 0).is_ok());
+        assert_eq!(protection.min_round(), 10);
+        // Rounds below the window are refused.
+        assert!(protection.check(author, 1, Field::rand(rng), 0).is_err());
+        assert!(protection.check(author, 9, Field::rand(rng), 0).is_err());
+        assert!(protection.check(author, 10, Field::rand(rng), 0).is_ok());
+    }
+
+    #[test]
+    fn test_persistence() {
+        let rng = &mut TestRng::default();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signing-history");
+        let author = sample_address(rng);
+        let (batch_a, batch_b) = (Field::rand(rng), Field::rand(rng));
+        let timestamp = now();
+
+        let mut protection = SigningProtection::<CurrentNetwork>::open(path.clone(), EXPIRATION_IN_SECS).unwrap();
+        assert!(protection.check(author, 1, batch_a, timestamp).is_ok());
+        drop(protection);
+
+        // Ensure a restarted signer still refuses a conflicting batch.
+        let mut protection = SigningProtection::<CurrentNetwork>::open(path, EXPIRATION_IN_SECS).unwrap();
+        assert!(protection.check(author, 1, batch_b, timestamp + 1).is_err());
+        assert!(protection.check(author, 1, batch_a, timestamp).is_ok());
+    }
+}
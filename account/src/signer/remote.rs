@@ -0,0 +1,84 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{SignRequest, SignResponse};
+use snarkvm::{console::network::prelude::*, prelude::*};
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The maximum time to wait for the signer process to respond.
+const SIGNER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A client for an external signer process, listening on a local Unix socket.
+#[derive(Clone, Debug)]
+pub struct RemoteSigner<N: Network> {
+    /// The path to the signer socket.
+    path: PathBuf,
+    /// The address of the signer.
+    address: Address<N>,
+}
+
+impl<N: Network> RemoteSigner<N> {
+    /// Connects to the signer process listening on the given socket, and retrieves its address.
+    pub fn connect<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        match send_request::<N>(&path, &SignRequest::Address)? {
+            SignResponse::Address { address } => Ok(Self { path, address }),
+            response => bail!("Unexpected response from the signer at '{}': {response:?}", path.display()),
+        }
+    }
+
+    /// Returns the path to the signer socket.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the address of the signer.
+    pub const fn address(&self) -> Address<N> {
+        self.address
+    }
+
+    /// Returns the signature for the given request.
+    pub fn sign(&self, request: SignRequest<N>) -> Result<Signature<N>> {
+        match send_request::<N>(&self.path, &request)? {
+            SignResponse::Signature { signature } => Ok(signature),
+            SignResponse::Error { message } => bail!("The signer refused the '{}' request - {message}", request.name()),
+            response => bail!("Unexpected response to the '{}' request: {response:?}", request.name()),
+        }
+    }
+}
+
+/// Sends the given request to the signer listening on the given socket, and returns its response.
+fn send_request<N: Network>(path: &Path, request: &SignRequest<N>) -> Result<SignResponse<N>> {
+    let mut stream = UnixStream::connect(path)
+        .map_err(|e| anyhow!("Failed to connect to the signer at '{}' - {e}", path.display()))?;
+    stream.set_read_timeout(Some(SIGNER_TIMEOUT))?;
+    stream.set_write_timeout(Some(SIGNER_TIMEOUT))?;
+
+    // Send the request.
+    let mut line = request.to_line()?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+
+    // Read the response.
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    ensure!(!line.is_empty(), "The signer at '{}' closed the connection", path.display());
+    SignResponse::from_line(line.trim_end())
+}
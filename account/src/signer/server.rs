@@ -0,0 +1,284 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{challenge_message, SignRequest, SignResponse, SigningProtection};
+use crate::Account;
+use snarkvm::{console::network::prelude::*, ledger::narwhal::BatchHeader, prelude::*};
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    os::unix::{
+        fs::{DirBuilderExt, FileTypeExt, PermissionsExt},
+        net::{UnixListener, UnixStream},
+    },
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+        Mutex,
+        MutexGuard,
+    },
+    thread,
+    time::Duration,
+};
+use tracing::{error, warn};
+
+/// The maximum number of connections that are served at once, each on its own thread.
+const MAX_CONNECTIONS: usize = 64;
+/// The maximum time to wait for a request on an idle connection.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A signer process, which holds the account private key of a validator and signs on its behalf
+/// over a local Unix socket, refusing to sign two different batches for the same round.
+#[derive(Clone)]
+pub struct SignerServer<N: Network> {
+    /// The account of the validator.
+    account: Account<N>,
+    /// The double-sign protection.
+    protection: Arc<Mutex<SigningProtection<N>>>,
+    /// The number of connections being served.
+    num_connections: Arc<AtomicUsize>,
+}
+
+impl<N: Network> SignerServer<N> {
+    /// Initializes a new signer server for the given account, with the given double-sign protection.
+    pub fn new(account: Account<N>, protection: SigningProtection<N>) -> Self {
+        Self { account, protection: Arc::new(Mutex::new(protection)), num_connections: Default::default() }
+    }
+
+    /// Returns the address of the signer.
+    pub const fn address(&self) -> Address<N> {
+        self.account.address()
+    }
+
+    /// Returns the response to the given request.
+    pub fn handle<R: Rng + CryptoRng>(&self, request: SignRequest<N>, rng: &mut R) -> SignResponse<N> {
+        match self.process(request, rng) {
+            Ok(response) => response,
+            Err(error) => SignResponse::Error { message: error.to_string() },
+        }
+    }
+
+    /// Listens on the given socket, and serves requests until the process is terminated.
+    pub fn serve<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        // Remove the socket left behind by a previous run, refusing to remove any other file.
+        if let Ok(metadata) = fs::symlink_metadata(path) {
+            ensure!(metadata.file_type().is_socket(), "'{}' already exists and is not a socket", path.display());
+            fs::remove_file(path)?;
+        }
+        let listener = bind_private(path)?;
+
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    // Refuse the connection if too many connections are being served already.
+                    if self.num_connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                        self.num_connections.fetch_sub(1, Ordering::SeqCst);
+                        warn!("Refusing a signer connection, as {MAX_CONNECTIONS} connections are being served");
+                        continue;
+                    }
+                    let server = self.clone();
+                    thread::spawn(move || {
+                        if let Err(error) = server.handle_connection(stream) {
+                            warn!("Signer connection failed - {error}");
+                        }
+                        server.num_connections.fetch_sub(1, Ordering::SeqCst);
+                    });
+                }
+                Err(error) => error!("Failed to accept a signer connection - {error}"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Serves the requests on the given connection, one line of JSON at a time.
+    fn handle_connection(&self, stream: UnixStream) -> Result<()> {
+        stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+        let mut writer = stream.try_clone()?;
+        for line in BufReader::new(stream).lines() {
+            let response = match SignRequest::from_line(&line?) {
+                Ok(request) => self.handle(request, &mut rand::thread_rng()),
+                Err(error) => SignResponse::Error { message: format!("Invalid request - {error}") },
+            };
+            let mut line = response.to_line()?;
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Processes the given request, enforcing the double-sign protection.
+    fn process<R: Rng + CryptoRng>(&self, request: SignRequest<N>, rng: &mut R) -> Result<SignResponse<N>> {
+        let signature = match request {
+            SignRequest::Address => return Ok(SignResponse::Address { address: self.address() }),
+            SignRequest::ProposeBatch {
+                round,
+                timestamp,
+                committee_id,
+                transmission_ids,
+                previous_certificate_ids,
+            } => {
+                let batch_id = BatchHeader::compute_batch_id(
+                    self.address(),
+                    round,
+                    timestamp,
+                    committee_id,
+                    &transmission_ids,
+                    &previous_certificate_ids,
+                )?;
+                self.protection().check(self.address(), round, batch_id, timestamp)?;
+                self.account.sign(&[batch_id], rng)?
+            }
+            SignRequest::SignBatch { batch_header } => {
+                let author = batch_header.author();
+                ensure!(author != self.address(), "Refusing to sign a batch proposed by this signer");
+                let (round, batch_id, timestamp) =
+                    (batch_header.round(), batch_header.batch_id(), batch_header.timestamp());
+                self.protection().check(author, round, batch_id, timestamp)?;
+                self.account.sign(&[batch_header.batch_id()], rng)?
+            }
+            SignRequest::Challenge { nonce, response_nonce } => {
+                self.account.sign_bytes(&challenge_message(nonce, response_nonce), rng)?
+            }
+        };
+        Ok(SignResponse::Signature { signature })
+    }
+
+    /// Returns the double-sign protection.
+    fn protection(&self) -> MutexGuard<'_, SigningProtection<N>> {
+        // A poisoned lock holds consistent state, as each check completes before any panic can occur.
+        self.protection.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+/// Binds a Unix socket at the given path, which only the current user may connect to.
+///
+/// The socket is bound inside a directory that only the current user may access, and moved into place
+/// once its permissions are restricted, so that no other user can connect to it in the meantime.
+fn bind_private(path: &Path) -> Result<UnixListener> {
+    let Some(file_name) = path.file_name() else {
+        bail!("'{}' is not a valid socket path", path.display());
+    };
+    let parent = path.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let mut staging_name = std::ffi::OsString::from(".");
+    staging_name.push(file_name);
+    staging_name.push(format!(".{}", std::process::id()));
+    let staging_dir = parent.join(staging_name);
+    fs::DirBuilder::new().mode(0o700).create(&staging_dir)?;
+
+    let staging_path = staging_dir.join("socket");
+    let result = UnixListener::bind(&staging_path).and_then(|listener| {
+        fs::set_permissions(&staging_path, fs::Permissions::from_mode(0o600))?;
+        fs::rename(&staging_path, path)?;
+        Ok(listener)
+    });
+    // Remove the staging directory, whether or not the socket was moved into place.
+    let _ = fs::remove_file(&staging_path);
+    fs::remove_dir(&staging_dir)?;
+    Ok(result?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{RemoteSigner, Signer};
+    use snarkvm::{
+        console::types::Field,
+        prelude::{MainnetV0, TestRng},
+    };
+
+    use std::time::Duration;
+    use time::OffsetDateTime;
+
+    type CurrentNetwork = MainnetV0;
+
+    /// The number of seconds a proposal is valid for before it expires, in these tests.
+    const EXPIRATION_IN_SECS: i64 = 60;
+
+    #[test]
+    fn test_double_sign_protection() {
+        let rng = &mut TestRng::default();
+
+        let protection = SigningProtection::new(EXPIRATION_IN_SECS);
+        let server = SignerServer::new(Account::<CurrentNetwork>::new(rng).unwrap(), protection);
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        let committee_id = Field::rand(rng);
+        let propose = |timestamp| SignRequest::ProposeBatch {
+            round: 1,
+            timestamp,
+            committee_id,
+            transmission_ids: Default::default(),
+            previous_certificate_ids: Default::default(),
+        };
+
+        // The first proposal in the round is signed, and may be signed again.
+        assert!(matches!(server.handle(propose(timestamp), rng), SignResponse::Signature { .. }));
+        assert!(matches!(server.handle(propose(timestamp), rng), SignResponse::Signature { .. }));
+        // A different proposal in the same round is refused.
+        assert!(matches!(server.handle(propose(timestamp + 1), rng), SignResponse::Error { .. }));
+
+        // Batches proposed by the signer itself cannot be signed as endorsements.
+        let signer = Signer::from(server.account.clone());
+        let batch_header =
+            signer.sign_batch_header(2, timestamp, committee_id, Default::default(), Default::default(), rng).unwrap();
+        let response = server.handle(SignRequest::SignBatch { batch_header }, rng);
+        assert!(matches!(response, SignResponse::Error { .. }));
+    }
+
+    #[test]
+    fn test_remote_signer() {
+        let rng = &mut TestRng::default();
+
+        let account = Account::<CurrentNetwork>::new(rng).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signer.sock");
+        let server = SignerServer::new(account.clone(), SigningProtection::new(EXPIRATION_IN_SECS));
+        let server_path = path.clone();
+        thread::spawn(move || server.serve(server_path).unwrap());
+        // Wait for the server to start listening.
+        while !path.exists() {
+            thread::sleep(Duration::from_millis(10));
+        }
+        // Ensure only the current user may connect to the signer.
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+
+        let remote = Signer::Remote(RemoteSigner::<CurrentNetwork>::connect(&path).unwrap());
+        assert_eq!(remote.address(), account.address());
+        assert!(remote.account().is_none());
+
+        // Sign a batch header remotely, and ensure it is valid.
+        let committee_id = Field::rand(rng);
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        let batch_header =
+            remote.sign_batch_header(1, timestamp, committee_id, Default::default(), Default::default(), rng).unwrap();
+        assert_eq!(batch_header.author(), account.address());
+        // A second batch in the same round is refused.
+        let result =
+            remote.sign_batch_header(1, timestamp + 1, committee_id, Default::default(), Default::default(), rng);
+        assert!(result.is_err());
+
+        // Sign a batch from another validator.
+        let other = Signer::from(Account::<CurrentNetwork>::new(rng).unwrap());
+        let other_header =
+            other.sign_batch_header(1, timestamp, committee_id, Default::default(), Default::default(), rng).unwrap();
+        let signature = remote.sign_batch(&other_header, rng).unwrap();
+        assert!(signature.verify(&account.address(), &[other_header.batch_id()]));
+
+        // Sign a challenge.
+        let signature = remote.sign_challenge(1, 2, rng).unwrap();
+        assert!(signature.verify_bytes(&account.address(), &challenge_message(1, 2)));
+    }
+}
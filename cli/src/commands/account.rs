@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use snarkos_account::Keystore;
 use snarkvm::{
     console::{
         account::{Address, PrivateKey, Signature},
@@ -32,9 +33,9 @@ use rand_chacha::ChaChaRng;
 use rayon::prelude::*;
 use std::{
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
 
 type Network = snarkvm::prelude::MainnetV0;
 
@@ -52,6 +53,24 @@ pub enum Account {
         /// Print sensitive information (such as the private key) discreetly in an alternate screen
         #[clap(long)]
         discreet: bool,
+        /// Write the private key to an encrypted keystore at the given path, instead of printing it
+        #[clap(long)]
+        keystore: Option<String>,
+        /// Specify the path to a file containing the keystore password (otherwise read from the environment or a prompt)
+        #[clap(long = "password-file", requires = "keystore")]
+        password_file: Option<String>,
+    },
+    /// Imports an existing private key into an encrypted keystore
+    Import {
+        /// Specify the path to a file containing the private key to import (otherwise read from a prompt)
+        #[clap(long = "private-key-file")]
+        private_key_file: Option<String>,
+        /// Specify the path to write the encrypted keystore to
+        #[clap(long)]
+        keystore: String,
+        /// Specify the path to a file containing the keystore password (otherwise read from the environment or a prompt)
+        #[clap(long = "password-file")]
+        password_file: Option<String>,
     },
     Sign {
         /// Specify the account private key of the node
@@ -94,21 +113,36 @@ fn aleo_literal_to_fields(input: &str) -> Result<Vec<Field<Network>>> {
 impl Account {
     pub fn parse(self) -> Result<String> {
         match self {
-            Self::New { seed, vanity, discreet } => {
+            Self::New { seed, vanity, discreet, keystore, password_file } => {
                 // Ensure only the seed or the vanity string is specified.
                 if seed.is_some() && vanity.is_some() {
                     bail!("Cannot specify both the '--seed' and '--vanity' flags");
                 }
 
                 // Generate a vanity account.
-                if let Some(vanity) = vanity {
-                    Self::new_vanity(&vanity, discreet)
+                let account = if let Some(vanity) = vanity {
+                    Self::new_vanity(&vanity)?
                 }
                 // Default to generating a normal account, with an optional seed.
                 else {
-                    Self::new_seeded(seed, discreet)
+                    Self::new_seeded(seed)?
+                };
+
+                // Write the account to a keystore, or print it.
+                match keystore {
+                    Some(keystore) => Self::write_keystore(&account, &keystore, password_file.as_deref()),
+                    None => Self::display(&account, discreet),
                 }
             }
+            Self::Import { private_key_file, keystore, password_file } => {
+                // Read the private key from the file, or a prompt.
+                let private_key = match private_key_file {
+                    Some(path) => Zeroizing::new(std::fs::read_to_string(path)?),
+                    None => crate::helpers::prompt_secret("Enter the private key: ")?,
+                };
+                let account = snarkos_account::Account::<Network>::from_str(private_key.trim())?;
+                Self::write_keystore(&account, &keystore, password_file.as_deref())
+            }
             Self::Sign { message, seed, raw, private_key, private_key_file } => {
                 let key = match (private_key, private_key_file) {
                     (Some(private_key), None) => private_key,
@@ -128,7 +162,7 @@ impl Account {
     }
 
     /// Generates a new Aleo account with the given vanity string.
-    fn new_vanity(vanity: &str, discreet: bool) -> Result<String> {
+    fn new_vanity(vanity: &str) -> Result<snarkos_account::Account<Network>> {
         // A closure to generate a new Aleo account.
         let sample_account = || snarkos_account::Account::<Network>::new(&mut rand::thread_rng());
 
@@ -176,22 +210,7 @@ impl Account {
             // Return the result if a candidate was found.
             if let Some(account) = account {
                 println!(); // Add a newline for formatting.
-                if !discreet {
-                    return Ok(account.to_string());
-                }
-                display_string_discreetly(
-                    &format!("{:>12}  {}", "Private Key".cyan().bold(), account.private_key()),
-                    "### Do not share or lose this private key! Press any key to complete. ###",
-                )
-                .unwrap();
-                let account_info = format!(
-                    " {:>12}  {}\n {:>12}  {}",
-                    "View Key".cyan().bold(),
-                    account.view_key(),
-                    "Address".cyan().bold(),
-                    account.address()
-                );
-                return Ok(account_info);
+                return Ok(account);
             } else {
                 let rate = ITERATIONS / timer.elapsed().as_millis();
                 let rate = format!("[{rate} a/ms]");
@@ -201,7 +220,7 @@ impl Account {
     }
 
     /// Generates a new Aleo account with an optional seed.
-    fn new_seeded(seed: Option<String>, discreet: bool) -> Result<String> {
+    fn new_seeded(seed: Option<String>) -> Result<snarkos_account::Account<Network>> {
         // Recover the seed.
         let seed = match seed {
             // Recover the field element deterministically.
@@ -215,7 +234,11 @@ impl Account {
         let private_key =
             PrivateKey::try_from(seed).map_err(|_| anyhow!("Failed to convert the seed into a valid private key"))?;
         // Construct the account.
-        let account = snarkos_account::Account::<Network>::try_from(private_key)?;
+        snarkos_account::Account::<Network>::try_from(private_key)
+    }
+
+    /// Returns the given Aleo account for printing, or prints its private key discreetly.
+    fn display(account: &snarkos_account::Account<Network>, discreet: bool) -> Result<String> {
         // Print the new Aleo account.
         if !discreet {
             return Ok(account.to_string());
@@ -235,6 +258,25 @@ impl Account {
         Ok(account_info)
     }
 
+    /// Writes the given Aleo account to an encrypted keystore, and returns its address.
+    fn write_keystore(
+        account: &snarkos_account::Account<Network>,
+        path: &str,
+        password_file: Option<&str>,
+    ) -> Result<String> {
+        // Read the password, confirming it if prompted.
+        let password = crate::helpers::read_keystore_password(password_file.map(Path::new), true)?;
+        // Encrypt the private key, and write the keystore.
+        let keystore = Keystore::encrypt(account.private_key(), &password, &mut rand::thread_rng())?;
+        crate::helpers::write_keystore(&keystore, Path::new(path))?;
+        Ok(format!(
+            " {:>12}  {}\n {:>12}  {path}",
+            "Address".cyan().bold(),
+            account.address(),
+            "Keystore".cyan().bold()
+        ))
+    }
+
     // Sign a message with an Aleo private key
     fn sign(key: String, message: String, seed: Option<String>, raw: bool) -> Result<String> {
         // Recover the seed.
@@ -317,13 +359,16 @@ fn wait_for_keypress() {
 #[cfg(test)]
 mod tests {
     use crate::commands::Account;
+    use snarkos_account::Keystore;
 
     use colored::Colorize;
+    use core::str::FromStr;
 
     #[test]
     fn test_new() {
         for _ in 0..3 {
-            let account = Account::New { seed: None, vanity: None, discreet: false };
+            let account =
+                Account::New { seed: None, vanity: None, discreet: false, keystore: None, password_file: None };
             assert!(account.parse().is_ok());
         }
     }
@@ -349,7 +394,7 @@ mod tests {
         );
 
         let vanity = None;
-        let account = Account::New { seed, vanity, discreet: false };
+        let account = Account::New { seed, vanity, discreet: false, keystore: None, password_file: None };
         let actual = account.parse().unwrap();
         assert_eq!(expected, actual);
     }
@@ -375,11 +420,42 @@ mod tests {
         );
 
         let vanity = None;
-        let account = Account::New { seed, vanity, discreet: false };
+        let account = Account::New { seed, vanity, discreet: false, keystore: None, password_file: None };
         let actual = account.parse().unwrap();
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_import_keystore() {
+        let directory = std::env::temp_dir().join(format!("snarkos-account-import-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let private_key_file = directory.join("private_key");
+        let password_file = directory.join("password");
+        let keystore = directory.join("keystore.json");
+
+        let key = "APrivateKey1zkp61PAYmrYEKLtRWeWhUoDpFnGLNuHrCciSqN49T86dw3p";
+        std::fs::write(&private_key_file, key).unwrap();
+        std::fs::write(&password_file, "password").unwrap();
+
+        let account = Account::Import {
+            private_key_file: Some(private_key_file.to_str().unwrap().to_string()),
+            keystore: keystore.to_str().unwrap().to_string(),
+            password_file: Some(password_file.to_str().unwrap().to_string()),
+        };
+        let output = account.parse().unwrap();
+        assert!(output.contains("aleo1zecnqchckrzw7dlsyf65g6z5le2rmys403ecwmcafrag0e030yxqrnlg8j"));
+        assert!(!output.contains(key));
+
+        // The keystore decrypts to the imported private key.
+        let contents = std::fs::read_to_string(&keystore).unwrap();
+        assert!(!contents.contains(key));
+        let keystore = Keystore::from_str(&contents).unwrap();
+        let decrypted = keystore.decrypt::<snarkvm::prelude::MainnetV0>("password").unwrap();
+        assert_eq!(decrypted.private_key().to_string(), key);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
     #[test]
     fn test_signature_raw() {
         let key = "APrivateKey1zkp61PAYmrYEKLtRWeWhUoDpFnGLNuHrCciSqN49T86dw3p".to_string();
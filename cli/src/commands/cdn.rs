@@ -0,0 +1,67 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::commands::LedgerStorage;
+
+use anyhow::Result;
+use clap::Parser;
+use std::net::SocketAddr;
+
+/// Commands to share the ledger with other nodes, in the same bundle format as the CDN.
+#[derive(Debug, Parser)]
+pub enum Cdn {
+    /// Serves the blocks of a stopped node's ledger over HTTP, for other nodes to sync from with `--cdn`.
+    /// A running node serves them on its REST server instead, at `http://<node>:3030/mainnet/cdn`
+    Serve {
+        /// Specify the IP address and port to serve the block bundles on
+        #[clap(default_value = "0.0.0.0:3040", long = "listen")]
+        listen: SocketAddr,
+        #[clap(flatten)]
+        storage: LedgerStorage,
+    },
+}
+
+impl Cdn {
+    /// Serves the ledger.
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Serve { listen, storage } => {
+                let ledger = storage.open()?;
+                println!("🌐 Serving the blocks up to block {} on http://{listen}", ledger.latest_height());
+                // Serve the block bundles, until the process is stopped.
+                tokio::runtime::Runtime::new()?.block_on(snarkos_node_cdn::serve_blocks(ledger, listen))?;
+                Ok(String::new())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_cdn_serve() {
+        let arg_vec = vec!["snarkos", "cdn", "serve", "--listen", "127.0.0.1:4000", "--dev", "2"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Cdn(Cdn::Serve { listen, storage }) = cli.command {
+            assert_eq!(listen, "127.0.0.1:4000".parse().unwrap());
+            assert_eq!(storage.dev, Some(2));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
@@ -0,0 +1,459 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Start;
+
+use anyhow::{bail, ensure, Result};
+use clap::{parser::ValueSource, Arg, ArgAction, ArgMatches, CommandFactory, Parser};
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    iter,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+/// The prefix of the environment variables that override the configurations of `snarkos start`.
+/// Note: Each option maps to an environment variable, e.g. `--rest-rps` maps to `SNARKOS_REST_RPS`.
+pub const ENV_PREFIX: &str = "SNARKOS_";
+
+/// The value of an option of `snarkos start`, in a layer of configurations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum OptionValue {
+    /// The value of a flag, which may be explicitly unset.
+    Flag(bool),
+    /// The value of an option.
+    Value(OsString),
+}
+
+/// Commands to manage the config file of `snarkos start` (in TOML, or in YAML given a `.yaml` or `.yml` extension).
+#[derive(Debug, Parser)]
+pub enum Config {
+    /// Checks the config file, and prints the effective configurations of the node
+    Validate {
+        /// Specify the path to the config file
+        path: PathBuf,
+    },
+}
+
+impl Config {
+    /// Parses the config command.
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Validate { path } => {
+                // Load the configurations, including the environment overrides.
+                let start =
+                    Start::load([OsString::from("--config"), path.into_os_string()], |name| std::env::var_os(name))?;
+                // Check the configurations for consistency.
+                start.validate()?;
+                start.to_toml()
+            }
+        }
+    }
+}
+
+impl Start {
+    /// Returns the configurations of the node from the config file, the environment variables (read with `env`),
+    /// and the given CLI arguments, in increasing order of precedence.
+    pub fn load<I, T>(cli_args: I, env: impl Fn(&str) -> Option<OsString>) -> Result<Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let matches = with_explicit_flags(Self::command())
+            .try_get_matches_from(iter::once("start".into()).chain(cli_args.into_iter().map(Into::into)))?;
+        Self::load_from_matches(&matches, env)
+    }
+
+    /// Returns the configurations of the node from the config file, the environment variables (read with `env`),
+    /// and the options given on the command line in the matches, in increasing order of precedence.
+    pub fn load_from_matches(matches: &ArgMatches, env: impl Fn(&str) -> Option<OsString>) -> Result<Self> {
+        let cli_args = cli_args(matches);
+        let env_args = env_args(env)?;
+        // Determine the config file, which may be specified on the command line or in the environment.
+        let config = Self::try_parse_from(merge_args([env_args.clone(), cli_args.clone()]))?.config;
+        // Load the config file, if one is specified.
+        let file_args = match config {
+            Some(path) => file_args(&path)?,
+            None => vec![],
+        };
+        Ok(Self::try_parse_from(merge_args([file_args, env_args, cli_args]))?)
+    }
+
+    /// Checks the configurations for consistency.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(self.network == 0, "Invalid network ID '{}'", self.network);
+        ensure!(
+            [self.validator, self.prover, self.client].into_iter().filter(|is_set| *is_set).count() <= 1,
+            "Only one of 'validator', 'prover', or 'client' may be set"
+        );
+        ensure!(
+            [self.private_key.is_some(), self.private_key_file.is_some(), self.keystore.is_some()]
+                .into_iter()
+                .filter(|is_set| *is_set)
+                .count()
+                <= 1,
+            "Only one of 'private-key', 'private-key-file', or 'keystore' may be set"
+        );
+        ensure!(self.signer.is_none() || self.validator, "The 'signer' may only be set for validators");
+        for (name, value) in [
+            ("mempool-deployments", self.mempool_deployments),
+            ("mempool-executions", self.mempool_executions),
+            ("mempool-per-sender", self.mempool_per_sender),
+            ("mempool-private-fee", self.mempool_private_fee),
+        ] {
+            ensure!(value > 0, "The '{name}' must be greater than zero");
+        }
+        for (name, ips) in [("peers", &self.peers), ("validators", &self.validators)] {
+            for ip in ips.split(',').filter(|ip| !ip.is_empty()) {
+                if let Err(error) = ip.parse::<SocketAddr>() {
+                    bail!("The IP supplied to '{name}' ('{ip}') is malformed: {error}");
+                }
+            }
+        }
+        self.parse_inbound_limits()?;
+        self.parse_outbound_limits()?;
+        Ok(())
+    }
+
+    /// Returns the configurations in TOML, with the private key redacted.
+    pub fn to_toml(&self) -> Result<String> {
+        let mut config = toml::Value::try_from(self)?;
+        if let Some(private_key) = config.get_mut("private_key") {
+            *private_key = toml::Value::String("<redacted>".to_string());
+        }
+        Ok(toml::to_string_pretty(&config)?)
+    }
+}
+
+/// Returns the options of `snarkos start` that may be set in a config file or the environment.
+fn options() -> Vec<Arg> {
+    Start::command()
+        .get_arguments()
+        .filter(|arg| arg.get_long().is_some() && !matches!(arg.get_id().as_str(), "help" | "version"))
+        .cloned()
+        .collect()
+}
+
+/// Returns `true` if the given option is a flag, which does not take a value.
+fn is_flag(option: &Arg) -> bool {
+    matches!(option.get_action(), ArgAction::SetTrue)
+}
+
+/// Returns the given command of `snarkos start`, whose flags may also be explicitly unset on the command line,
+/// e.g. `--norest=false`, in order to override a flag that is set in the config file or the environment.
+pub fn with_explicit_flags(command: clap::Command) -> clap::Command {
+    let flags =
+        command.get_arguments().filter(|option| is_flag(option)).map(|flag| flag.get_id().clone()).collect::<Vec<_>>();
+    flags.into_iter().fold(command, |command, id| {
+        command.mut_arg(id, |flag| {
+            flag.action(ArgAction::Set)
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_value("false")
+                .default_missing_value("true")
+        })
+    })
+}
+
+/// Returns the arguments of `snarkos start` from the given layers of options, where each option
+/// is taken from the last layer that sets it (or unsets it, for a flag).
+fn merge_args<const L: usize>(layers: [Vec<(String, OptionValue)>; L]) -> Vec<OsString> {
+    let options = layers.into_iter().flatten().collect::<BTreeMap<_, _>>();
+    let args = options.into_iter().filter_map(|(long, value)| match value {
+        // Note: An unset flag is omitted, as flags are unset by default.
+        OptionValue::Flag(is_set) => is_set.then(|| format!("--{long}").into()),
+        OptionValue::Value(value) => {
+            let mut arg = OsString::from(format!("--{long}="));
+            arg.push(value);
+            Some(arg)
+        }
+    });
+    iter::once("start".into()).chain(args).collect()
+}
+
+/// Returns the options given on the command line in the matches.
+fn cli_args(matches: &ArgMatches) -> Vec<(String, OptionValue)> {
+    let mut args = Vec::new();
+    for option in options() {
+        let (id, Some(long)) = (option.get_id().as_str(), option.get_long()) else { continue };
+        if matches.value_source(id) != Some(ValueSource::CommandLine) {
+            continue;
+        }
+        match is_flag(&option) {
+            true => args.push((long.to_string(), OptionValue::Flag(matches.get_flag(id)))),
+            false => {
+                if let Some(value) = matches.get_raw(id).and_then(|mut values| values.next()) {
+                    args.push((long.to_string(), OptionValue::Value(value.to_owned())));
+                }
+            }
+        }
+    }
+    args
+}
+
+/// Returns the options set in the given config file, which is in YAML if it has a `.yaml` or `.yml` extension,
+/// and in TOML otherwise.
+fn file_args(path: &Path) -> Result<Vec<(String, OptionValue)>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) => bail!("Failed to read the config file '{}': {error}", path.display()),
+    };
+    // Note: The YAML values are read as TOML values, which have the same data model for the options.
+    let table = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("yaml" | "yml") => {
+            serde_yaml::from_str::<toml::value::Table>(&contents).map_err(|error| error.to_string())
+        }
+        _ => toml::from_str::<toml::value::Table>(&contents).map_err(|error| error.to_string()),
+    };
+    let table = match table {
+        Ok(table) => table,
+        Err(error) => bail!("Failed to parse the config file '{}': {error}", path.display()),
+    };
+
+    let options = options();
+    let mut args = Vec::with_capacity(table.len());
+    for (key, value) in table {
+        // Note: Keys may be given in either `snake_case` or `kebab-case`.
+        let long = key.replace('_', "-");
+        let Some(option) = options.iter().find(|option| option.get_long() == Some(long.as_str())) else {
+            bail!("Unknown option '{key}' in the config file");
+        };
+        ensure!(long != "config", "The config file cannot specify another config file");
+
+        let value = match (is_flag(option), value) {
+            (true, toml::Value::Boolean(is_set)) => OptionValue::Flag(is_set),
+            (true, _) => bail!("Option '{key}' in the config file must be a boolean"),
+            (false, toml::Value::String(value)) => OptionValue::Value(value.into()),
+            (false, toml::Value::Array(values)) => {
+                // Note: Lists, such as the peers, are comma-separated on the command line.
+                let values = values
+                    .into_iter()
+                    .map(|value| match value {
+                        toml::Value::String(value) => Ok(value),
+                        toml::Value::Integer(value) => Ok(value.to_string()),
+                        _ => bail!("Option '{key}' in the config file must be a list of strings"),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                OptionValue::Value(values.join(",").into())
+            }
+            // Note: Objects, such as the bonded balances, are given as JSON on the command line.
+            (false, toml::Value::Table(value)) => OptionValue::Value(serde_json::to_string(&value)?.into()),
+            (false, toml::Value::Integer(value)) => OptionValue::Value(value.to_string().into()),
+            (false, toml::Value::Float(value)) => OptionValue::Value(value.to_string().into()),
+            (false, toml::Value::Boolean(value)) => OptionValue::Value(value.to_string().into()),
+            (false, toml::Value::Datetime(value)) => OptionValue::Value(value.to_string().into()),
+        };
+        args.push((long, value));
+    }
+    Ok(args)
+}
+
+/// Returns the options set in the environment variables, as read with `env`.
+fn env_args(env: impl Fn(&str) -> Option<OsString>) -> Result<Vec<(String, OptionValue)>> {
+    let mut args = Vec::new();
+    for option in options() {
+        let Some(long) = option.get_long() else { continue };
+        let name = format!("{ENV_PREFIX}{}", long.to_uppercase().replace('-', "_"));
+        let Some(value) = env(&name) else { continue };
+
+        let value = match is_flag(&option) {
+            true => match value.to_str() {
+                Some("true" | "1") => OptionValue::Flag(true),
+                Some("false" | "0" | "") => OptionValue::Flag(false),
+                _ => bail!("Environment variable '{name}' must be a boolean"),
+            },
+            false => OptionValue::Value(value),
+        };
+        args.push((long.to_string(), value));
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::{Command, CLI},
+        helpers::LogFormat,
+    };
+
+    /// Reads no environment variables.
+    fn no_env(_: &str) -> Option<OsString> {
+        None
+    }
+
+    /// Writes the given config file, and returns its path.
+    fn write_config(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("snarkos-config-{name}-{}.toml", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_precedence() {
+        let path = write_config(
+            "precedence",
+            r#"
+            nodisplay = true
+            rest_rps = 20
+            log-retention = 2
+            peers = ["127.0.0.1:4130", "127.0.0.1:4131"]
+            log_format = "json"
+            "#,
+        );
+        let config = path.to_str().unwrap();
+
+        // The config file overrides the defaults.
+        let start = Start::load(["--config", config], no_env).unwrap();
+        assert!(start.nodisplay);
+        assert_eq!(start.rest_rps, 20);
+        assert_eq!(start.log_retention, 2);
+        assert_eq!(start.peers, "127.0.0.1:4130,127.0.0.1:4131");
+        assert_eq!(start.log_format, LogFormat::Json);
+        // The defaults are retained otherwise.
+        assert_eq!(start.rest, "0.0.0.0:3030".parse().unwrap());
+
+        // The environment overrides the config file.
+        let env = |name: &str| (name == "SNARKOS_LOG_RETENTION").then(|| OsString::from("3"));
+        let start = Start::load(["--config", config], env).unwrap();
+        assert_eq!(start.log_retention, 3);
+
+        // The CLI arguments override the environment.
+        let start = Start::load(["--config", config, "--log-retention", "4", "--rest-rps", "30"], env).unwrap();
+        assert_eq!(start.log_retention, 4);
+        assert_eq!(start.rest_rps, 30);
+
+        // The CLI arguments override the config file, even if they are set to the default value.
+        let start = Start::load(["--config", config, "--rest-rps", "10"], no_env).unwrap();
+        assert_eq!(start.rest_rps, 10);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_flag_precedence() {
+        let path = write_config("flags", "norest = true\nnodisplay = true");
+        let config = path.to_str().unwrap();
+
+        // The config file sets the flags.
+        let start = Start::load(["--config", config], no_env).unwrap();
+        assert!(start.norest);
+        assert!(start.nodisplay);
+
+        // The environment unsets a flag that is set in the config file.
+        let env = |name: &str| (name == "SNARKOS_NOREST").then(|| OsString::from("false"));
+        let start = Start::load(["--config", config], env).unwrap();
+        assert!(!start.norest);
+        assert!(start.nodisplay);
+
+        // The CLI arguments set a flag that is unset in the environment, and unset a flag set in the config file.
+        let start = Start::load(["--config", config, "--norest", "--nodisplay=false"], env).unwrap();
+        assert!(start.norest);
+        assert!(!start.nodisplay);
+
+        // The flags may be explicitly unset on the command line of `snarkos start` as well.
+        let matches = CLI::command()
+            .mut_subcommand("start", with_explicit_flags)
+            .try_get_matches_from(["snarkos", "start", "--config", config, "--nodisplay=false"])
+            .unwrap();
+        let Some(("start", matches)) = matches.subcommand() else { panic!("Unexpected result of clap parsing!") };
+        let start = Start::load_from_matches(matches, no_env).unwrap();
+        assert!(start.norest);
+        assert!(!start.nodisplay);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_yaml() {
+        let path = std::env::temp_dir().join(format!("snarkos-config-yaml-{}.yaml", std::process::id()));
+        std::fs::write(
+            &path,
+            "nodisplay: true\nrest_rps: 20\npeers:\n  - 127.0.0.1:4130\n  - 127.0.0.1:4131\nlog-format: json\n",
+        )
+        .unwrap();
+
+        // The YAML config file is loaded like a TOML one.
+        let start = Start::load(["--config", path.to_str().unwrap()], no_env).unwrap();
+        assert!(start.nodisplay);
+        assert_eq!(start.rest_rps, 20);
+        assert_eq!(start.peers, "127.0.0.1:4130,127.0.0.1:4131");
+        assert_eq!(start.log_format, LogFormat::Json);
+
+        // Malformed YAML is rejected.
+        std::fs::write(&path, "nodisplay: [true").unwrap();
+        assert!(Start::load(["--config", path.to_str().unwrap()], no_env).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_invalid() {
+        // Unknown options are rejected.
+        let path = write_config("unknown", "unknown_option = 1");
+        assert!(Start::load(["--config", path.to_str().unwrap()], no_env).is_err());
+        std::fs::remove_file(path).unwrap();
+
+        // Flags must be booleans.
+        let path = write_config("flag", "nodisplay = \"yes\"");
+        assert!(Start::load(["--config", path.to_str().unwrap()], no_env).is_err());
+        std::fs::remove_file(path).unwrap();
+
+        // Values must match the type of the option.
+        let path = write_config("value", "rest = \"not an address\"");
+        assert!(Start::load(["--config", path.to_str().unwrap()], no_env).is_err());
+        std::fs::remove_file(path).unwrap();
+
+        // Missing files are rejected.
+        assert!(Start::load(["--config", "/nonexistent/snarkos.toml"], no_env).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let path = write_config(
+            "validate",
+            "validator = true\nprivate_key = \"PRIVATE_KEY\"\nvalidators = \"127.0.0.1:5000\"",
+        );
+        let start = Start::load(["--config", path.to_str().unwrap()], no_env).unwrap();
+        assert!(start.validate().is_ok());
+        // The private key is redacted.
+        let output = start.to_toml().unwrap();
+        assert!(output.contains("validator = true"));
+        assert!(!output.contains("PRIVATE_KEY"));
+
+        // Conflicting node types are rejected.
+        let start = Start::load(["--config", path.to_str().unwrap(), "--prover"], no_env).unwrap();
+        assert!(start.validate().is_err());
+        // Malformed IPs are rejected.
+        let start = Start::load(["--config", path.to_str().unwrap(), "--peers", "127.0.0.1"], no_env).unwrap();
+        assert!(start.validate().is_err());
+        // Malformed rate limits are rejected.
+        let start = Start::load(["--config", path.to_str().unwrap(), "--peer-rate-limit", "-1"], no_env).unwrap();
+        assert!(start.validate().is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn clap_snarkos_config_validate() {
+        let cli = CLI::parse_from(["snarkos", "config", "validate", "node.toml"]);
+
+        if let Command::Config(Config::Validate { path }) = cli.command {
+            assert_eq!(path, PathBuf::from("node.toml"));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
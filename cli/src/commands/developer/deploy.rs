@@ -12,21 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{CurrentAleo, CurrentNetwork, Developer};
-use snarkvm::{
-    console::program::ProgramOwner,
-    prelude::{
-        block::Transaction,
-        deployment_cost,
-        query::Query,
-        store::{helpers::memory::ConsensusMemory, ConsensusStore},
-        PrivateKey,
-        ProgramID,
-        VM,
-    },
-};
-
-use aleo_std::StorageMode;
+use super::{CurrentAleo, CurrentNetwork, Developer, TransactionBuilder};
+use snarkvm::prelude::{PrivateKey, ProgramID};
+
 use anyhow::{bail, Result};
 use clap::Parser;
 use colored::Colorize;
@@ -82,12 +70,15 @@ impl Deploy {
             bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
         }
 
-        // Specify the query
-        let query = Query::from(&self.query);
-
         // Retrieve the private key.
         let private_key = PrivateKey::from_str(&self.private_key)?;
 
+        // Prepare the fee.
+        let fee_record = match &self.record {
+            Some(record_string) => Some(Developer::parse_record(&private_key, record_string)?),
+            None => None,
+        };
+
         // Fetch the package from the directory.
         let package = Developer::parse_package(self.program_id, &self.path)?;
 
@@ -95,57 +86,13 @@ impl Deploy {
 
         // Generate the deployment
         let deployment = package.deploy::<CurrentAleo>(None)?;
-        let deployment_id = deployment.to_deployment_id()?;
 
         // Generate the deployment transaction.
-        let transaction = {
-            // Initialize an RNG.
-            let rng = &mut rand::thread_rng();
-
-            // Initialize the storage.
-            let storage_mode = match &self.storage_path {
-                Some(path) => StorageMode::Custom(path.clone()),
-                None => StorageMode::Production,
-            };
-            let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(storage_mode)?;
-
-            // Initialize the VM.
-            let vm = VM::from(store)?;
-
-            // Compute the minimum deployment cost.
-            let (minimum_deployment_cost, (_, _, _)) = deployment_cost(&deployment)?;
-
-            // Prepare the fees.
-            let fee = match &self.record {
-                Some(record) => {
-                    let fee_record = Developer::parse_record(&private_key, record)?;
-                    let fee_authorization = vm.authorize_fee_private(
-                        &private_key,
-                        fee_record,
-                        minimum_deployment_cost,
-                        self.priority_fee,
-                        deployment_id,
-                        rng,
-                    )?;
-                    vm.execute_fee_authorization(fee_authorization, Some(query), rng)?
-                }
-                None => {
-                    let fee_authorization = vm.authorize_fee_public(
-                        &private_key,
-                        minimum_deployment_cost,
-                        self.priority_fee,
-                        deployment_id,
-                        rng,
-                    )?;
-                    vm.execute_fee_authorization(fee_authorization, Some(query), rng)?
-                }
-            };
-            // Construct the owner.
-            let owner = ProgramOwner::new(&private_key, deployment_id, rng)?;
-
-            // Create a new transaction.
-            Transaction::from_deployment(owner, deployment, fee)?
-        };
+        let transaction = TransactionBuilder::new(private_key, &self.query)
+            .priority_fee(self.priority_fee)
+            .fee_record(fee_record)
+            .storage_path(self.storage_path.clone())
+            .deploy(deployment)?;
         println!("✅ Created deployment transaction for '{}'", self.program_id.to_string().bold());
 
         // Determine if the transaction should be broadcast, stored, or displayed to the user.
@@ -0,0 +1,110 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{CurrentNetwork, TransactionBuilder};
+use snarkvm::prelude::{Identifier, PrivateKey, ProgramID, Value};
+
+use anyhow::Result;
+use clap::Parser;
+use std::{path::PathBuf, str::FromStr};
+use zeroize::Zeroize;
+
+/// Estimates the fee of executing an Aleo program function.
+#[derive(Debug, Parser)]
+pub struct EstimateFee {
+    /// The program identifier.
+    program_id: ProgramID<CurrentNetwork>,
+    /// The function name.
+    function: Identifier<CurrentNetwork>,
+    /// The function inputs.
+    inputs: Vec<Value<CurrentNetwork>>,
+    /// The private key used to generate the execution.
+    #[clap(short, long)]
+    private_key: String,
+    /// The endpoint to query node state from.
+    #[clap(short, long)]
+    query: String,
+    /// The priority fee in microcredits.
+    #[clap(long, default_value_t = 0)]
+    priority_fee: u64,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+}
+
+impl Drop for EstimateFee {
+    /// Zeroize the private key when the `EstimateFee` struct goes out of scope.
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+impl EstimateFee {
+    /// Returns the estimated fee in microcredits, as JSON.
+    pub fn parse(self) -> Result<String> {
+        // Retrieve the private key.
+        let private_key = PrivateKey::from_str(&self.private_key)?;
+
+        // Estimate the fee of the execution.
+        let estimate = TransactionBuilder::new(private_key, &self.query)
+            .priority_fee(self.priority_fee)
+            .storage_path(self.storage_path.clone())
+            .estimate_fee(&self.program_id.to_string(), &self.function.to_string(), self.inputs.clone())?;
+
+        Ok(serde_json::to_string_pretty(&serde_json::json!({
+            "storage_cost": estimate.storage_cost,
+            "finalize_cost": estimate.finalize_cost,
+            "base_fee": estimate.base_fee(),
+            "priority_fee": estimate.priority_fee,
+            "total_fee": estimate.total(),
+        }))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, Developer, CLI};
+
+    #[test]
+    fn clap_snarkos_estimate_fee() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "estimate-fee",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--priority-fee",
+            "5",
+            "credits.aleo",
+            "transfer_public",
+            "aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px",
+            "1u64",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::EstimateFee(estimate)) = cli.command {
+            assert_eq!(estimate.private_key, "PRIVATE_KEY");
+            assert_eq!(estimate.query, "QUERY");
+            assert_eq!(estimate.priority_fee, 5);
+            assert_eq!(estimate.program_id, "credits.aleo".try_into().unwrap());
+            assert_eq!(estimate.function, "transfer_public".try_into().unwrap());
+            assert_eq!(estimate.inputs.len(), 2);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
@@ -12,22 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{CurrentNetwork, Developer};
-use snarkvm::prelude::{
-    query::Query,
-    store::{helpers::memory::ConsensusMemory, ConsensusStore},
-    Address,
-    Identifier,
-    Locator,
-    PrivateKey,
-    Process,
-    ProgramID,
-    Value,
-    VM,
-};
-
-use aleo_std::StorageMode;
-use anyhow::{anyhow, bail, Result};
+use super::{CurrentNetwork, Developer, TransactionBuilder};
+use snarkvm::prelude::{Identifier, Locator, PrivateKey, Process, ProgramID, Value};
+
+use anyhow::{bail, Result};
 use clap::Parser;
 use colored::Colorize;
 use std::{path::PathBuf, str::FromStr};
@@ -77,85 +65,30 @@ impl Drop for Execute {
 
 impl Execute {
     /// Executes an Aleo program function with the provided inputs.
-    #[allow(clippy::format_in_format_args)]
     pub fn parse(self) -> Result<String> {
         // Ensure that the user has specified an action.
         if !self.dry_run && self.broadcast.is_none() && self.store.is_none() {
             bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
         }
 
-        // Specify the query
-        let query = Query::from(&self.query);
-
         // Retrieve the private key.
         let private_key = PrivateKey::from_str(&self.private_key)?;
 
+        // Prepare the fee.
+        let fee_record = match &self.record {
+            Some(record_string) => Some(Developer::parse_record(&private_key, record_string)?),
+            None => None,
+        };
+
         let locator = Locator::<CurrentNetwork>::from_str(&format!("{}/{}", self.program_id, self.function))?;
         println!("📦 Creating execution transaction for '{}'...\n", &locator.to_string().bold());
 
         // Generate the execution transaction.
-        let transaction = {
-            // Initialize an RNG.
-            let rng = &mut rand::thread_rng();
-
-            // Initialize the storage.
-            let storage_mode = match &self.storage_path {
-                Some(path) => StorageMode::Custom(path.clone()),
-                None => StorageMode::Production,
-            };
-            let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(storage_mode)?;
-
-            // Initialize the VM.
-            let vm = VM::from(store)?;
-
-            // Load the program and it's imports into the process.
-            load_program(&self.query, &mut vm.process().write(), &self.program_id)?;
-
-            // Prepare the fee.
-            let fee_record = match &self.record {
-                Some(record_string) => Some(Developer::parse_record(&private_key, record_string)?),
-                None => None,
-            };
-            let priority_fee = self.priority_fee.unwrap_or(0);
-
-            // Create a new transaction.
-            vm.execute(
-                &private_key,
-                (self.program_id, self.function),
-                self.inputs.iter(),
-                fee_record,
-                priority_fee,
-                Some(query),
-                rng,
-            )?
-        };
-
-        // Check if the public balance is sufficient.
-        if self.record.is_none() {
-            // Fetch the public balance.
-            let address = Address::try_from(&private_key)?;
-            let public_balance = Developer::get_public_balance(&address, &self.query)?;
-
-            // Check if the public balance is sufficient.
-            let storage_cost = transaction
-                .execution()
-                .ok_or_else(|| anyhow!("The transaction does not contain an execution"))?
-                .size_in_bytes()?;
-
-            // Calculate the base fee.
-            // This fee is the minimum fee required to pay for the transaction,
-            // excluding any finalize fees that the execution may incur.
-            let base_fee = storage_cost.saturating_add(self.priority_fee.unwrap_or(0));
-
-            // If the public balance is insufficient, return an error.
-            if public_balance < base_fee {
-                bail!(
-                    "❌ The public balance of {} is insufficient to pay the base fee for `{}`",
-                    public_balance,
-                    locator.to_string().bold()
-                );
-            }
-        }
+        let transaction = TransactionBuilder::new(private_key, &self.query)
+            .priority_fee(self.priority_fee.unwrap_or(0))
+            .fee_record(fee_record)
+            .storage_path(self.storage_path.clone())
+            .execute(&self.program_id.to_string(), &self.function.to_string(), self.inputs.clone())?;
 
         println!("✅ Created execution transaction for '{}'", locator.to_string().bold());
 
@@ -165,7 +98,7 @@ impl Execute {
 }
 
 /// A helper function to recursively load the program and all of its imports into the process.
-fn load_program(
+pub(super) fn load_program(
     endpoint: &str,
     process: &mut Process<CurrentNetwork>,
     program_id: &ProgramID<CurrentNetwork>,
@@ -0,0 +1,153 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{Developer, TransactionBuilder};
+use snarkvm::prelude::PrivateKey;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::{path::PathBuf, str::FromStr};
+use zeroize::Zeroize;
+
+/// Executes the `join` function in the `credits.aleo` program.
+#[derive(Debug, Parser)]
+pub struct Join {
+    /// The first record to join. If neither record is specified, the two largest unspent records
+    /// in the wallet are joined.
+    #[clap(long, requires = "second_record")]
+    first_record: Option<String>,
+    /// The second record to join.
+    #[clap(long, requires = "first_record")]
+    second_record: Option<String>,
+    /// The private key used to generate the execution.
+    #[clap(short, long)]
+    private_key: String,
+    /// The endpoint to query node state from.
+    #[clap(short, long)]
+    query: String,
+    /// The priority fee in microcredits.
+    #[clap(long, default_value_t = 0)]
+    priority_fee: u64,
+    /// The record to spend the fee from. If not specified, the fee is paid from the public balance.
+    #[clap(long)]
+    fee_record: Option<String>,
+    /// The endpoint used to broadcast the generated transaction.
+    #[clap(short, long, conflicts_with = "dry_run")]
+    broadcast: Option<String>,
+    /// Performs a dry-run of transaction generation.
+    #[clap(short, long, conflicts_with = "broadcast")]
+    dry_run: bool,
+    /// Store generated deployment transaction to a local file.
+    #[clap(long)]
+    store: Option<String>,
+    /// The directory of the wallet files to select the records from, defaults to `~/.aleo/wallets/mainnet`.
+    #[clap(long)]
+    wallet_dir: Option<PathBuf>,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+}
+
+impl Drop for Join {
+    /// Zeroize the private key when the `Join` struct goes out of scope.
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+impl Join {
+    /// Joins two Aleo credits records into one.
+    pub fn parse(self) -> Result<String> {
+        // Ensure that the user has specified an action.
+        if !self.dry_run && self.broadcast.is_none() && self.store.is_none() {
+            bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
+        }
+
+        // Retrieve the private key.
+        let private_key = PrivateKey::from_str(&self.private_key)?;
+
+        // Prepare the fee and the records to join.
+        let fee_record = match &self.fee_record {
+            Some(record_string) => Some(Developer::parse_record(&private_key, record_string)?),
+            None => None,
+        };
+        let records = match (&self.first_record, &self.second_record) {
+            (Some(first), Some(second)) => {
+                Some((Developer::parse_record(&private_key, first)?, Developer::parse_record(&private_key, second)?))
+            }
+            _ => None,
+        };
+
+        println!("📦 Creating join of two records...\n");
+
+        // Generate the join transaction.
+        let transaction = TransactionBuilder::new(private_key, &self.query)
+            .priority_fee(self.priority_fee)
+            .fee_record(fee_record)
+            .wallet_dir(self.wallet_dir.clone())
+            .storage_path(self.storage_path.clone())
+            .join(records)?;
+
+        println!("✅ Created join of two records\n");
+
+        // Determine if the transaction should be broadcast, stored, or displayed to the user.
+        Developer::handle_transaction(
+            &self.broadcast,
+            self.dry_run,
+            &self.store,
+            transaction,
+            "credits.aleo/join".to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_join() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "join",
+            "--first-record",
+            "FIRST",
+            "--second-record",
+            "SECOND",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--dry-run",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Join(join)) = cli.command {
+            assert_eq!(join.first_record, Some("FIRST".to_string()));
+            assert_eq!(join.second_record, Some("SECOND".to_string()));
+            assert_eq!(join.private_key, "PRIVATE_KEY");
+            assert_eq!(join.query, "QUERY");
+            assert_eq!(join.fee_record, None);
+            assert!(join.dry_run);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        // Both records must be given, or neither.
+        let arg_vec = vec!["snarkos", "developer", "join", "--first-record", "FIRST", "-p", "KEY", "-q", "QUERY", "-d"];
+        assert!(CLI::try_parse_from(arg_vec).is_err());
+    }
+}
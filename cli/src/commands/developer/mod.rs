@@ -18,15 +18,39 @@ pub use decrypt::*;
 mod deploy;
 pub use deploy::*;
 
+mod estimate_fee;
+pub use estimate_fee::*;
+
 mod execute;
 pub use execute::*;
 
+mod join;
+pub use join::*;
+
 mod scan;
 pub use scan::*;
 
+mod split;
+pub use split::*;
+
+mod transaction_builder;
+pub use transaction_builder::*;
+
 mod transfer_private;
 pub use transfer_private::*;
 
+mod transfer_private_to_public;
+pub use transfer_private_to_public::*;
+
+mod transfer_public;
+pub use transfer_public::*;
+
+mod transfer_public_to_private;
+pub use transfer_public_to_private::*;
+
+mod wallet;
+pub use wallet::*;
+
 use snarkvm::{
     package::Package,
     prelude::{
@@ -61,12 +85,24 @@ pub enum Developer {
     Decrypt(Decrypt),
     /// Deploy a program.
     Deploy(Deploy),
+    /// Estimate the fee of executing a program function.
+    EstimateFee(EstimateFee),
     /// Execute a program function.
     Execute(Execute),
+    /// Execute the `credits.aleo/join` function.
+    Join(Join),
     /// Scan the node for records.
     Scan(Scan),
+    /// Execute the `credits.aleo/split` function.
+    Split(Split),
     /// Execute the `credits.aleo/transfer_private` function.
     TransferPrivate(TransferPrivate),
+    /// Execute the `credits.aleo/transfer_private_to_public` function.
+    TransferPrivateToPublic(TransferPrivateToPublic),
+    /// Execute the `credits.aleo/transfer_public` function.
+    TransferPublic(TransferPublic),
+    /// Execute the `credits.aleo/transfer_public_to_private` function.
+    TransferPublicToPrivate(TransferPublicToPrivate),
 }
 
 impl Developer {
@@ -74,9 +110,15 @@ impl Developer {
         match self {
             Self::Decrypt(decrypt) => decrypt.parse(),
             Self::Deploy(deploy) => deploy.parse(),
+            Self::EstimateFee(estimate_fee) => estimate_fee.parse(),
             Self::Execute(execute) => execute.parse(),
+            Self::Join(join) => join.parse(),
             Self::Scan(scan) => scan.parse(),
+            Self::Split(split) => split.parse(),
             Self::TransferPrivate(transfer_private) => transfer_private.parse(),
+            Self::TransferPrivateToPublic(transfer_private_to_public) => transfer_private_to_public.parse(),
+            Self::TransferPublic(transfer_public) => transfer_public.parse(),
+            Self::TransferPublicToPrivate(transfer_public_to_private) => transfer_public_to_private.parse(),
         }
     }
 
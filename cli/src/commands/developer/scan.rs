@@ -14,14 +14,15 @@
 
 #![allow(clippy::type_complexity)]
 
-use super::CurrentNetwork;
-use snarkvm::prelude::{block::Block, Ciphertext, Field, FromBytes, Network, Plaintext, PrivateKey, Record, ViewKey};
+use super::{microcredits, CurrentNetwork, Wallet, WalletRecord};
+use snarkvm::prelude::{block::Block, Field, FromBytes, Network, Plaintext, PrivateKey, Record, ViewKey};
 
 use anyhow::{bail, ensure, Result};
 use clap::Parser;
 use parking_lot::RwLock;
 use std::{
     io::{stdout, Write},
+    path::PathBuf,
     str::FromStr,
     sync::Arc,
 };
@@ -56,6 +57,19 @@ pub struct Scan {
     /// The endpoint to scan blocks from.
     #[clap(long)]
     endpoint: String,
+
+    /// Keep the records in a local wallet, and continue scanning from the last scanned block.
+    #[clap(long)]
+    wallet: bool,
+
+    /// The directory of the wallet files, defaults to `~/.aleo/wallets/mainnet`.
+    #[clap(long, requires = "wallet")]
+    #[zeroize(skip)]
+    wallet_dir: Option<PathBuf>,
+
+    /// Output the balance of the records in microcredits, instead of the records.
+    #[clap(long)]
+    balance: bool,
 }
 
 impl Scan {
@@ -63,11 +77,26 @@ impl Scan {
         // Derive the view key and optional private key.
         let (private_key, view_key) = self.parse_account()?;
 
-        // Find the start and end height to scan.
-        let (start_height, end_height) = self.parse_block_range()?;
+        // Fetch the records from the network, or update the wallet.
+        let records = match self.wallet {
+            true => self.scan_wallet(private_key, &view_key)?,
+            false => {
+                // Find the start and end height to scan.
+                let (start_height, end_height) = self.parse_block_range()?;
+
+                Self::fetch_records(private_key, &view_key, &self.endpoint, start_height, end_height)?
+            }
+        };
+
+        // Output the balance of the records associated with the view key.
+        if self.balance {
+            if private_key.is_none() {
+                println!("⚠️  This balance may include records that have already been spent.\n");
+            }
 
-        // Fetch the records from the network.
-        let records = Self::fetch_records(private_key, &view_key, &self.endpoint, start_height, end_height)?;
+            let balance: u64 = records.iter().filter_map(|record| microcredits(record).ok()).sum();
+            return Ok(format!("{balance} microcredits"));
+        }
 
         // Output the decrypted records associated with the view key.
         if records.is_empty() {
@@ -112,6 +141,55 @@ impl Scan {
         }
     }
 
+    /// Updates the wallet of the view key with the new blocks, and returns its unspent records.
+    fn scan_wallet(
+        &self,
+        private_key: Option<PrivateKey<CurrentNetwork>>,
+        view_key: &ViewKey<CurrentNetwork>,
+    ) -> Result<Vec<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>> {
+        let directory = self.wallet_dir.clone().unwrap_or_else(Wallet::default_directory);
+        let mut wallet = Wallet::open(&directory, view_key)?;
+
+        // Continue from the last scanned block, unless a block range is given.
+        let (start_height, end_height) = match (self.start, self.end, self.last, wallet.last_height()) {
+            (None, None, None, Some(_)) => (wallet.next_height(), Self::latest_height(&self.endpoint)?),
+            (None, None, None, None) => {
+                println!("⚠️  Attention - Scanning the entire chain. This may take a while...\n");
+                (0, Self::latest_height(&self.endpoint)?)
+            }
+            _ => self.parse_block_range()?,
+        };
+
+        Self::update_wallet(&mut wallet, private_key, view_key, &self.endpoint, start_height, end_height)?;
+
+        Ok(wallet.unspent_records().map(|record| record.record.clone()).collect())
+    }
+
+    /// Scans the given block range into the wallet, marks its spent records if the private key is given, and saves it.
+    pub(super) fn update_wallet(
+        wallet: &mut Wallet,
+        private_key: Option<PrivateKey<CurrentNetwork>>,
+        view_key: &ViewKey<CurrentNetwork>,
+        endpoint: &str,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<()> {
+        // Scan the new blocks.
+        if start_height <= end_height {
+            let records = Self::fetch_owned_records(view_key, endpoint, start_height, end_height)?;
+            wallet.insert_scan(start_height, end_height, records);
+        }
+
+        // Mark the spent records, which requires the private key to derive the serial numbers.
+        if let Some(private_key) = private_key {
+            wallet.refresh_spent(&private_key, |serial_number| Self::is_spent(endpoint, serial_number))?;
+        }
+
+        wallet.save()?;
+        println!("💾 Saved the wallet to {}\n", wallet.path().display());
+        Ok(())
+    }
+
     /// Returns the `start` and `end` blocks to scan.
     fn parse_block_range(&self) -> Result<(u32, u32)> {
         match (self.start, self.end, self.last) {
@@ -122,8 +200,7 @@ impl Scan {
             }
             (Some(start), None, None) => {
                 // Request the latest block height from the endpoint.
-                let endpoint = format!("{}/mainnet/latest/height", self.endpoint);
-                let latest_height = u32::from_str(&ureq::get(&endpoint).call()?.into_string()?)?;
+                let latest_height = Self::latest_height(&self.endpoint)?;
 
                 // Print a warning message if the user is attempting to scan the whole chain.
                 if start == 0 {
@@ -135,8 +212,7 @@ impl Scan {
             (None, Some(end), None) => Ok((0, end)),
             (None, None, Some(last)) => {
                 // Request the latest block height from the endpoint.
-                let endpoint = format!("{}/mainnet/latest/height", self.endpoint);
-                let latest_height = u32::from_str(&ureq::get(&endpoint).call()?.into_string()?)?;
+                let latest_height = Self::latest_height(&self.endpoint)?;
 
                 Ok((latest_height.saturating_sub(last), latest_height))
             }
@@ -145,7 +221,13 @@ impl Scan {
         }
     }
 
-    /// Fetch owned ciphertext records from the endpoint.
+    /// Returns the latest block height of the endpoint.
+    pub(super) fn latest_height(endpoint: &str) -> Result<u32> {
+        let endpoint = format!("{endpoint}/mainnet/latest/height");
+        Ok(u32::from_str(&ureq::get(&endpoint).call()?.into_string()?)?)
+    }
+
+    /// Fetch owned records from the endpoint, filtering spent records if a private key was provided.
     fn fetch_records(
         private_key: Option<PrivateKey<CurrentNetwork>>,
         view_key: &ViewKey<CurrentNetwork>,
@@ -153,6 +235,26 @@ impl Scan {
         start_height: u32,
         end_height: u32,
     ) -> Result<Vec<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>> {
+        let mut records = Self::fetch_owned_records(view_key, endpoint, start_height, end_height)?;
+
+        // Check if a private key was provided.
+        if let Some(private_key) = private_key {
+            for record in records.iter_mut() {
+                let serial_number = record.serial_number(&private_key)?;
+                record.spent = Self::is_spent(endpoint, &serial_number)?;
+            }
+        }
+
+        Ok(records.into_iter().filter(|record| !record.spent).map(|record| record.record).collect())
+    }
+
+    /// Fetch and decrypt the owned ciphertext records from the endpoint.
+    fn fetch_owned_records(
+        view_key: &ViewKey<CurrentNetwork>,
+        endpoint: &str,
+        start_height: u32,
+        end_height: u32,
+    ) -> Result<Vec<WalletRecord>> {
         // Check the bounds of the request.
         if start_height > end_height {
             bail!("Invalid block range");
@@ -186,8 +288,6 @@ impl Scan {
                     start_height,
                     end_height,
                     CDN_ENDPOINT.to_string(),
-                    endpoint.to_string(),
-                    private_key,
                     *view_key,
                     address_x_coordinate,
                     records.clone(),
@@ -216,7 +316,7 @@ impl Scan {
 
             // Scan the blocks for owned records.
             for block in &blocks {
-                Self::scan_block(block, endpoint, private_key, view_key, &address_x_coordinate, records.clone())?;
+                Self::scan_block(block, view_key, &address_x_coordinate, records.clone())?;
             }
 
             request_start = request_start.saturating_add(num_blocks_to_request);
@@ -231,16 +331,13 @@ impl Scan {
     }
 
     /// Scan the blocks from the CDN.
-    #[allow(clippy::too_many_arguments)]
     fn scan_from_cdn(
         start_height: u32,
         end_height: u32,
         cdn: String,
-        endpoint: String,
-        private_key: Option<PrivateKey<CurrentNetwork>>,
         view_key: ViewKey<CurrentNetwork>,
         address_x_coordinate: Field<CurrentNetwork>,
-        records: Arc<RwLock<Vec<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>>>,
+        records: Arc<RwLock<Vec<WalletRecord>>>,
     ) -> Result<()> {
         // Calculate the number of blocks to scan.
         let total_blocks = end_height.saturating_sub(start_height);
@@ -275,14 +372,7 @@ impl Scan {
                     stdout().flush()?;
 
                     // Scan the block for records.
-                    Self::scan_block(
-                        &block,
-                        &endpoint,
-                        private_key,
-                        &view_key,
-                        &address_x_coordinate,
-                        records.clone(),
-                    )?;
+                    Self::scan_block(&block, &view_key, &address_x_coordinate, records.clone())?;
 
                     Ok(())
                 },
@@ -296,60 +386,39 @@ impl Scan {
     /// Scan a block for owned records.
     fn scan_block(
         block: &Block<CurrentNetwork>,
-        endpoint: &str,
-        private_key: Option<PrivateKey<CurrentNetwork>>,
         view_key: &ViewKey<CurrentNetwork>,
         address_x_coordinate: &Field<CurrentNetwork>,
-        records: Arc<RwLock<Vec<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>>>,
+        records: Arc<RwLock<Vec<WalletRecord>>>,
     ) -> Result<()> {
         for (commitment, ciphertext_record) in block.records() {
             // Check if the record is owned by the given view key.
             if ciphertext_record.is_owner_with_address_x_coordinate(view_key, address_x_coordinate) {
-                // Decrypt and optionally filter the records.
-                if let Some(record) =
-                    Self::decrypt_record(private_key, view_key, endpoint, *commitment, ciphertext_record)?
-                {
-                    records.write().push(record);
-                }
+                // Decrypt the record.
+                records.write().push(WalletRecord {
+                    height: block.height(),
+                    commitment: *commitment,
+                    serial_number: None,
+                    record: ciphertext_record.decrypt(view_key)?,
+                    spent: false,
+                });
             }
         }
 
         Ok(())
     }
 
-    /// Decrypts the ciphertext record and filters spend record if a private key was provided.
-    fn decrypt_record(
-        private_key: Option<PrivateKey<CurrentNetwork>>,
-        view_key: &ViewKey<CurrentNetwork>,
-        endpoint: &str,
-        commitment: Field<CurrentNetwork>,
-        ciphertext_record: &Record<CurrentNetwork, Ciphertext<CurrentNetwork>>,
-    ) -> Result<Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>> {
-        // Check if a private key was provided.
-        if let Some(private_key) = private_key {
-            // Compute the serial number.
-            let serial_number =
-                Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::serial_number(private_key, commitment)?;
-
-            // Establish the endpoint.
-            let endpoint = format!("{endpoint}/mainnet/find/transitionID/{serial_number}");
-
-            // Check if the record is spent.
-            match ureq::get(&endpoint).call() {
-                // On success, skip as the record is spent.
-                Ok(_) => Ok(None),
-                // On error, add the record.
-                Err(_error) => {
-                    // TODO: Dedup the error types. We're adding the record as valid because the endpoint failed,
-                    //  meaning it couldn't find the serial number (ie. unspent). However if there's a DNS error or request error,
-                    //  we have a false positive here then.
-                    // Decrypt the record.
-                    Ok(Some(ciphertext_record.decrypt(view_key)?))
-                }
-            }
-        } else {
-            // If no private key was provided, return the record.
-            Ok(Some(ciphertext_record.decrypt(view_key)?))
+    /// Returns `true` if the endpoint knows of a transition spending the given serial number.
+    fn is_spent(endpoint: &str, serial_number: &Field<CurrentNetwork>) -> Result<bool> {
+        // Establish the endpoint.
+        let endpoint = format!("{endpoint}/mainnet/find/transitionID/{serial_number}");
+
+        match ureq::get(&endpoint).call() {
+            // On success, the record is spent.
+            Ok(_) => Ok(true),
+            // The endpoint responds with a 404 if it cannot find the serial number.
+            Err(ureq::Error::Status(404, _)) => Ok(false),
+            // Otherwise, the spent status is unknown.
+            Err(error) => bail!("Failed to check if the serial number {serial_number} is spent: {error}"),
         }
     }
 }
@@ -357,10 +426,50 @@ impl Scan {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use snarkvm::prelude::{MainnetV0, TestRng};
+    use snarkvm::prelude::{MainnetV0, TestRng, Uniform};
+
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
 
     type CurrentNetwork = MainnetV0;
 
+    /// Serves a single request with the given status and body, and returns the endpoint to send it to.
+    fn serve_once(status: &'static str, body: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            // Read the request, up to the empty line that ends its headers.
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let response =
+                format!("HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        endpoint
+    }
+
+    #[test]
+    fn test_is_spent() {
+        let rng = &mut TestRng::default();
+        let serial_number = Field::<CurrentNetwork>::rand(rng);
+
+        // The REST server responds with the ID of the transition spending a serial number.
+        let transition_id = "\"au1z3jl4ydzgmcd7ak3n9znzlaae8qzkzsnt3trqnj3cu6hwwe0l5ysq4rgd7\"".to_string();
+        assert!(Scan::is_spent(&serve_once("200 OK", transition_id), &serial_number).unwrap());
+        // The REST server responds with a 404 if no transition spends the serial number.
+        let not_found = format!("'{serial_number}' was not found");
+        assert!(!Scan::is_spent(&serve_once("404 Not Found", not_found), &serial_number).unwrap());
+        // Any other error leaves the spent status unknown.
+        let error = "Something went wrong: the ledger is unavailable".to_string();
+        assert!(Scan::is_spent(&serve_once("500 Internal Server Error", error), &serial_number).is_err());
+    }
+
     #[test]
     fn test_parse_account() {
         let rng = &mut TestRng::default();
@@ -443,4 +552,20 @@ mod tests {
             .is_err()
         );
     }
+
+    #[test]
+    fn test_parse_wallet() {
+        let config = Scan::try_parse_from(
+            ["snarkos", "--view-key", "", "--endpoint", "", "--wallet", "--wallet-dir", "wallets", "--balance"].iter(),
+        )
+        .unwrap();
+        assert!(config.wallet);
+        assert!(config.balance);
+        assert_eq!(config.wallet_dir, Some(PathBuf::from("wallets")));
+
+        // `wallet_dir` requires `wallet`
+        let result =
+            Scan::try_parse_from(["snarkos", "--view-key", "", "--endpoint", "", "--wallet-dir", "wallets"].iter());
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,135 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{Developer, TransactionBuilder};
+use snarkvm::prelude::PrivateKey;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::{path::PathBuf, str::FromStr};
+use zeroize::Zeroize;
+
+/// Executes the `split` function in the `credits.aleo` program, which does not pay a fee.
+#[derive(Debug, Parser)]
+pub struct Split {
+    /// The record to split. If not specified, the smallest unspent record in the wallet covering
+    /// the amount is split.
+    #[clap(long)]
+    record: Option<String>,
+    /// The number of microcredits to split off into a new record.
+    #[clap(long)]
+    amount: u64,
+    /// The private key used to generate the execution.
+    #[clap(short, long)]
+    private_key: String,
+    /// The endpoint to query node state from.
+    #[clap(short, long)]
+    query: String,
+    /// The endpoint used to broadcast the generated transaction.
+    #[clap(short, long, conflicts_with = "dry_run")]
+    broadcast: Option<String>,
+    /// Performs a dry-run of transaction generation.
+    #[clap(short, long, conflicts_with = "broadcast")]
+    dry_run: bool,
+    /// Store generated deployment transaction to a local file.
+    #[clap(long)]
+    store: Option<String>,
+    /// The directory of the wallet files to select the record from, defaults to `~/.aleo/wallets/mainnet`.
+    #[clap(long)]
+    wallet_dir: Option<PathBuf>,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+}
+
+impl Drop for Split {
+    /// Zeroize the private key when the `Split` struct goes out of scope.
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+impl Split {
+    /// Splits an Aleo credits record into two.
+    pub fn parse(self) -> Result<String> {
+        // Ensure that the user has specified an action.
+        if !self.dry_run && self.broadcast.is_none() && self.store.is_none() {
+            bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
+        }
+
+        // Retrieve the private key.
+        let private_key = PrivateKey::from_str(&self.private_key)?;
+
+        // Prepare the record to split.
+        let record = match &self.record {
+            Some(record_string) => Some(Developer::parse_record(&private_key, record_string)?),
+            None => None,
+        };
+
+        println!("📦 Creating split of {} microcredits...\n", self.amount);
+
+        // Generate the split transaction.
+        let transaction = TransactionBuilder::new(private_key, &self.query)
+            .wallet_dir(self.wallet_dir.clone())
+            .storage_path(self.storage_path.clone())
+            .split(record, self.amount)?;
+
+        println!("✅ Created split of {} microcredits\n", self.amount);
+
+        // Determine if the transaction should be broadcast, stored, or displayed to the user.
+        Developer::handle_transaction(
+            &self.broadcast,
+            self.dry_run,
+            &self.store,
+            transaction,
+            "credits.aleo/split".to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_split() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "split",
+            "--amount",
+            "100",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--wallet-dir",
+            "WALLETS",
+            "--dry-run",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Split(split)) = cli.command {
+            assert_eq!(split.record, None);
+            assert_eq!(split.amount, 100);
+            assert_eq!(split.private_key, "PRIVATE_KEY");
+            assert_eq!(split.query, "QUERY");
+            assert_eq!(split.wallet_dir, Some(PathBuf::from("WALLETS")));
+            assert!(split.dry_run);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
@@ -0,0 +1,526 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{load_program, CurrentNetwork, Developer, Scan, Wallet};
+use snarkvm::{
+    console::program::ProgramOwner,
+    prelude::{
+        block::{Deployment, Fee, Transaction},
+        cost_in_microcredits,
+        deployment_cost,
+        execution_cost,
+        query::Query,
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        Address,
+        Entry,
+        Field,
+        Identifier,
+        Literal,
+        Plaintext,
+        PrivateKey,
+        ProgramID,
+        Record,
+        Value,
+        ViewKey,
+        VM,
+    },
+};
+
+use aleo_std::StorageMode;
+use anyhow::{anyhow, bail, ensure, Result};
+use rand::{CryptoRng, Rng};
+use std::{path::PathBuf, str::FromStr};
+
+/// The cost of a `credits.aleo` execution, in microcredits.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// The cost of storing the execution.
+    pub storage_cost: u64,
+    /// The cost of running the finalize logic of the execution.
+    pub finalize_cost: u64,
+    /// The priority fee paid on top of the base fee.
+    pub priority_fee: u64,
+}
+
+impl FeeEstimate {
+    /// Returns the base fee, i.e. the minimum fee the network accepts for the execution.
+    pub const fn base_fee(&self) -> u64 {
+        self.storage_cost.saturating_add(self.finalize_cost)
+    }
+
+    /// Returns the total fee, i.e. the base fee and the priority fee.
+    pub const fn total(&self) -> u64 {
+        self.base_fee().saturating_add(self.priority_fee)
+    }
+}
+
+/// Builds deployments, and executions of any program function including the `credits.aleo` ones.
+///
+/// The fee of every transaction is estimated from its cost, and is paid publicly unless a fee
+/// record is provided; either way, it is checked to be affordable before it is proven. Functions
+/// that spend records select them from the unspent records in the wallet of the account when none
+/// are given.
+pub struct TransactionBuilder {
+    /// The private key used to authorize the transactions.
+    private_key: PrivateKey<CurrentNetwork>,
+    /// The endpoint to query node state from.
+    query: String,
+    /// The priority fee in microcredits.
+    priority_fee: u64,
+    /// The record to spend the fee from.
+    fee_record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+    /// The directory of the wallet files to select records from.
+    wallet_dir: Option<PathBuf>,
+    /// The path to a directory containing the ledger.
+    storage_path: Option<PathBuf>,
+}
+
+impl TransactionBuilder {
+    /// Initializes a new transaction builder, paying fees publicly and without a priority fee.
+    pub fn new(private_key: PrivateKey<CurrentNetwork>, query: impl Into<String>) -> Self {
+        Self {
+            private_key,
+            query: query.into(),
+            priority_fee: 0,
+            fee_record: None,
+            wallet_dir: None,
+            storage_path: None,
+        }
+    }
+
+    /// Sets the priority fee in microcredits.
+    pub fn priority_fee(mut self, priority_fee: u64) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
+
+    /// Sets the record to spend the fee from; fees are paid publicly if it is `None`.
+    pub fn fee_record(mut self, fee_record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>) -> Self {
+        self.fee_record = fee_record;
+        self
+    }
+
+    /// Sets the directory of the wallet files to select records from; defaults to [`Wallet::default_directory`].
+    pub fn wallet_dir(mut self, wallet_dir: Option<PathBuf>) -> Self {
+        self.wallet_dir = wallet_dir;
+        self
+    }
+
+    /// Sets the path to a directory containing the ledger.
+    pub fn storage_path(mut self, storage_path: Option<PathBuf>) -> Self {
+        self.storage_path = storage_path;
+        self
+    }
+
+    /// Returns the address of the account.
+    pub fn address(&self) -> Result<Address<CurrentNetwork>> {
+        Address::try_from(&self.private_key)
+    }
+
+    /// Builds a `credits.aleo/transfer_public` transaction.
+    pub fn transfer_public(
+        &self,
+        recipient: Address<CurrentNetwork>,
+        amount: u64,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        let inputs = vec![Value::from_str(&recipient.to_string())?, Value::from_str(&format!("{amount}u64"))?];
+        self.execute_with_public_amount("credits.aleo", "transfer_public", inputs, amount)
+    }
+
+    /// Builds a `credits.aleo/transfer_public_to_private` transaction.
+    pub fn transfer_public_to_private(
+        &self,
+        recipient: Address<CurrentNetwork>,
+        amount: u64,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        let inputs = vec![Value::from_str(&recipient.to_string())?, Value::from_str(&format!("{amount}u64"))?];
+        self.execute_with_public_amount("credits.aleo", "transfer_public_to_private", inputs, amount)
+    }
+
+    /// Builds a `credits.aleo/transfer_private` transaction.
+    /// If no record is given, the smallest unspent record covering the amount is spent.
+    pub fn transfer_private(
+        &self,
+        record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+        recipient: Address<CurrentNetwork>,
+        amount: u64,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        let record = match record {
+            Some(record) => record,
+            None => select_record(&self.unspent_records()?, amount, self.fee_record.as_ref())?,
+        };
+        let inputs = vec![
+            Value::Record(record),
+            Value::from_str(&recipient.to_string())?,
+            Value::from_str(&format!("{amount}u64"))?,
+        ];
+        self.execute("credits.aleo", "transfer_private", inputs)
+    }
+
+    /// Builds a `credits.aleo/transfer_private_to_public` transaction.
+    /// If no record is given, the smallest unspent record covering the amount is spent.
+    pub fn transfer_private_to_public(
+        &self,
+        record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+        recipient: Address<CurrentNetwork>,
+        amount: u64,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        let record = match record {
+            Some(record) => record,
+            None => select_record(&self.unspent_records()?, amount, self.fee_record.as_ref())?,
+        };
+        let inputs = vec![
+            Value::Record(record),
+            Value::from_str(&recipient.to_string())?,
+            Value::from_str(&format!("{amount}u64"))?,
+        ];
+        self.execute("credits.aleo", "transfer_private_to_public", inputs)
+    }
+
+    /// Builds a `credits.aleo/join` transaction.
+    /// If no records are given, the two largest unspent records are joined.
+    pub fn join(
+        &self,
+        records: Option<(
+            Record<CurrentNetwork, Plaintext<CurrentNetwork>>,
+            Record<CurrentNetwork, Plaintext<CurrentNetwork>>,
+        )>,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        let (first, second) = match records {
+            Some(records) => records,
+            None => select_records_to_join(&self.unspent_records()?, self.fee_record.as_ref())?,
+        };
+        self.execute("credits.aleo", "join", vec![Value::Record(first), Value::Record(second)])
+    }
+
+    /// Builds a `credits.aleo/split` transaction. Splits do not pay a fee.
+    /// If no record is given, the smallest unspent record covering the amount is split.
+    pub fn split(
+        &self,
+        record: Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+        amount: u64,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        let record = match record {
+            Some(record) => record,
+            None => select_record(&self.unspent_records()?, amount, self.fee_record.as_ref())?,
+        };
+        self.execute("credits.aleo", "split", vec![Value::Record(record), Value::from_str(&format!("{amount}u64"))?])
+    }
+
+    /// Builds a transaction executing the given program function, paying the estimated fee.
+    pub fn execute(
+        &self,
+        program_id: &str,
+        function: &str,
+        inputs: Vec<Value<CurrentNetwork>>,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        self.execute_with_public_amount(program_id, function, inputs, 0)
+    }
+
+    /// Builds a transaction deploying the given program, paying the minimum deployment cost.
+    pub fn deploy(&self, deployment: Deployment<CurrentNetwork>) -> Result<Transaction<CurrentNetwork>> {
+        let rng = &mut rand::thread_rng();
+        // Note: The fee is a `credits.aleo` execution, which does not require the deployed program.
+        let vm = self.vm("credits.aleo")?;
+
+        let deployment_id = deployment.to_deployment_id()?;
+        let (base_fee, _) = deployment_cost(&deployment)?;
+        let fee = self.execute_fee(&vm, base_fee, deployment_id, 0, rng)?;
+        // Construct the owner.
+        let owner = ProgramOwner::new(&self.private_key, deployment_id, rng)?;
+
+        Transaction::from_deployment(owner, deployment, fee)
+    }
+
+    /// Estimates the fee of executing the given program function, without building the fee.
+    pub fn estimate_fee(
+        &self,
+        program_id: &str,
+        function: &str,
+        inputs: Vec<Value<CurrentNetwork>>,
+    ) -> Result<FeeEstimate> {
+        let rng = &mut rand::thread_rng();
+        let vm = self.vm(program_id)?;
+
+        let authorization = vm.authorize(&self.private_key, program_id, function, inputs.iter(), rng)?;
+        let transaction = vm.execute_authorization(authorization, None, Some(Query::from(&self.query)), rng)?;
+        let execution =
+            transaction.execution().ok_or_else(|| anyhow!("The transaction does not contain an execution"))?;
+        let (_, (storage_cost, finalize_cost)) = execution_cost(&vm.process().read(), execution)?;
+
+        Ok(FeeEstimate { storage_cost, finalize_cost, priority_fee: self.priority_fee })
+    }
+
+    /// Returns the unspent `credits.aleo` records of the account, from its wallet.
+    /// The wallet is updated with the blocks since its last scan first, which scans the entire chain
+    /// if the wallet does not exist yet.
+    pub fn unspent_records(&self) -> Result<Vec<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>> {
+        let view_key = ViewKey::try_from(&self.private_key)?;
+        let directory = self.wallet_dir.clone().unwrap_or_else(Wallet::default_directory);
+        let mut wallet = Wallet::open(&directory, &view_key)?;
+
+        // Continue from the last scanned block.
+        if wallet.last_height().is_none() {
+            println!("⚠️  Attention - Scanning the entire chain into a new wallet. This may take a while...\n");
+        }
+        let (start_height, end_height) = (wallet.next_height(), Scan::latest_height(&self.query)?);
+        Scan::update_wallet(&mut wallet, Some(self.private_key), &view_key, &self.query, start_height, end_height)?;
+
+        // Keep only the records holding credits.
+        Ok(wallet
+            .unspent_records()
+            .map(|record| record.record.clone())
+            .filter(|record| microcredits(record).is_ok())
+            .collect())
+    }
+
+    /// Builds a transaction executing the given program function, paying the estimated fee.
+    /// The execution spends the given amount of microcredits from the public balance, in addition to a public fee.
+    fn execute_with_public_amount(
+        &self,
+        program_id: &str,
+        function: &str,
+        inputs: Vec<Value<CurrentNetwork>>,
+        public_amount: u64,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        let rng = &mut rand::thread_rng();
+        let vm = self.vm(program_id)?;
+
+        // Splits are the only executions that are accepted without a fee.
+        let is_split = program_id == "credits.aleo" && function == "split";
+        // Ensure the amount and the fee are affordable, before proving the execution.
+        // Note: The storage cost is only known once the execution is proven, so the fee is checked again then.
+        let minimum_fee = match is_split {
+            true => 0,
+            false => {
+                let function_name = Identifier::from_str(function)?;
+                let finalize_cost = cost_in_microcredits(vm.process().read().get_stack(program_id)?, &function_name)?;
+                finalize_cost.saturating_add(self.priority_fee)
+            }
+        };
+        self.ensure_affordable(minimum_fee, public_amount)?;
+
+        // Execute the function without a fee, to determine its cost.
+        let authorization = vm.authorize(&self.private_key, program_id, function, inputs.iter(), rng)?;
+        let execution_id = authorization.to_execution_id()?;
+        let transaction = vm.execute_authorization(authorization, None, Some(Query::from(&self.query)), rng)?;
+        if is_split {
+            return Ok(transaction);
+        }
+        let execution =
+            transaction.execution().ok_or_else(|| anyhow!("The transaction does not contain an execution"))?.clone();
+        let (base_fee, _) = execution_cost(&vm.process().read(), &execution)?;
+
+        let fee = self.execute_fee(&vm, base_fee, execution_id, public_amount, rng)?;
+        Transaction::from_execution(execution, Some(fee))
+    }
+
+    /// Authorizes and executes the fee of the transaction with the given ID, once it is known to be affordable.
+    /// The given amount of microcredits is spent from the public balance by the transaction itself.
+    fn execute_fee<R: Rng + CryptoRng>(
+        &self,
+        vm: &VM<CurrentNetwork, ConsensusMemory<CurrentNetwork>>,
+        base_fee: u64,
+        id: Field<CurrentNetwork>,
+        public_amount: u64,
+        rng: &mut R,
+    ) -> Result<Fee<CurrentNetwork>> {
+        self.ensure_affordable(base_fee.saturating_add(self.priority_fee), public_amount)?;
+        let fee_authorization = match &self.fee_record {
+            Some(record) => {
+                vm.authorize_fee_private(&self.private_key, record.clone(), base_fee, self.priority_fee, id, rng)?
+            }
+            None => vm.authorize_fee_public(&self.private_key, base_fee, self.priority_fee, id, rng)?,
+        };
+        vm.execute_fee_authorization(fee_authorization, Some(Query::from(&self.query)), rng)
+    }
+
+    /// Initializes a VM with the given program and its imports loaded.
+    fn vm(&self, program_id: &str) -> Result<VM<CurrentNetwork, ConsensusMemory<CurrentNetwork>>> {
+        // Initialize the storage.
+        let storage_mode = match &self.storage_path {
+            Some(path) => StorageMode::Custom(path.clone()),
+            None => StorageMode::Production,
+        };
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(storage_mode)?;
+
+        // Initialize the VM.
+        let vm = VM::from(store)?;
+
+        // Load the program and it's imports into the process.
+        let program_id = ProgramID::from_str(program_id)?;
+        if !vm.process().read().contains_program(&program_id) {
+            load_program(&self.query, &mut vm.process().write(), &program_id)?;
+        }
+        Ok(vm)
+    }
+
+    /// Ensures the given fee is affordable from the fee record, or else from the public balance,
+    /// along with the given amount of microcredits spent from the public balance by the transaction itself.
+    fn ensure_affordable(&self, fee: u64, public_amount: u64) -> Result<()> {
+        match &self.fee_record {
+            Some(record) => {
+                let credits = microcredits(record)?;
+                ensure!(credits >= fee, "❌ The fee record of {credits} is insufficient to pay {fee} microcredits");
+                match public_amount {
+                    0 => Ok(()),
+                    public_amount => self.ensure_public_balance(public_amount),
+                }
+            }
+            None => self.ensure_public_balance(public_amount.saturating_add(fee)),
+        }
+    }
+
+    /// Ensures the public balance of the account is at least the given amount.
+    fn ensure_public_balance(&self, required: u64) -> Result<()> {
+        let address = self.address()?;
+        let public_balance = Developer::get_public_balance(&address, &self.query)?;
+        ensure!(
+            public_balance >= required,
+            "❌ The public balance of {public_balance} is insufficient to pay {required} microcredits"
+        );
+        Ok(())
+    }
+}
+
+/// Returns the number of microcredits held by the given record.
+pub(super) fn microcredits(record: &Record<CurrentNetwork, Plaintext<CurrentNetwork>>) -> Result<u64> {
+    match record.data().get(&Identifier::from_str("microcredits")?) {
+        Some(Entry::Private(Plaintext::Literal(Literal::U64(amount), _)))
+        | Some(Entry::Public(Plaintext::Literal(Literal::U64(amount), _))) => Ok(**amount),
+        _ => bail!("The record does not hold microcredits"),
+    }
+}
+
+/// Returns the smallest record holding at least `amount` microcredits, other than the `excluded` one.
+fn select_record(
+    records: &[Record<CurrentNetwork, Plaintext<CurrentNetwork>>],
+    amount: u64,
+    excluded: Option<&Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+) -> Result<Record<CurrentNetwork, Plaintext<CurrentNetwork>>> {
+    records
+        .iter()
+        .filter(|record| Some(*record) != excluded)
+        .filter_map(|record| microcredits(record).ok().map(|credits| (credits, record)))
+        .filter(|(credits, _)| *credits >= amount)
+        .min_by_key(|(credits, _)| *credits)
+        .map(|(_, record)| record.clone())
+        .ok_or_else(|| anyhow!("❌ No unspent record holds at least {amount} microcredits"))
+}
+
+/// Returns the two largest records, other than the `excluded` one.
+#[allow(clippy::type_complexity)]
+fn select_records_to_join(
+    records: &[Record<CurrentNetwork, Plaintext<CurrentNetwork>>],
+    excluded: Option<&Record<CurrentNetwork, Plaintext<CurrentNetwork>>>,
+) -> Result<(Record<CurrentNetwork, Plaintext<CurrentNetwork>>, Record<CurrentNetwork, Plaintext<CurrentNetwork>>)> {
+    let mut candidates = records
+        .iter()
+        .filter(|record| Some(*record) != excluded)
+        .filter_map(|record| microcredits(record).ok().map(|credits| (credits, record)))
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(credits, _)| std::cmp::Reverse(*credits));
+    match candidates.as_slice() {
+        [(_, first), (_, second), ..] => Ok(((*first).clone(), (*second).clone())),
+        _ => bail!("❌ At least two unspent records are required to join"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::TestRng;
+
+    /// Returns a record owned by a random address, holding the given amount of microcredits.
+    fn sample_record(rng: &mut TestRng, amount: u64) -> Record<CurrentNetwork, Plaintext<CurrentNetwork>> {
+        let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        Record::from_str(&format!(
+            "{{ owner: {address}.private, microcredits: {amount}u64.private, _nonce: 0group.public }}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fee_estimate() {
+        let estimate = FeeEstimate { storage_cost: 1_000, finalize_cost: 200, priority_fee: 30 };
+        assert_eq!(estimate.base_fee(), 1_200);
+        assert_eq!(estimate.total(), 1_230);
+
+        // The fees saturate, rather than overflow.
+        let estimate = FeeEstimate { storage_cost: u64::MAX, finalize_cost: 1, priority_fee: 1 };
+        assert_eq!(estimate.total(), u64::MAX);
+    }
+
+    #[test]
+    fn test_ensure_affordable_from_fee_record() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        // Note: The query endpoint is never reached, as nothing is spent from the public balance.
+        let builder =
+            TransactionBuilder::new(private_key, "http://localhost:0").fee_record(Some(sample_record(rng, 100)));
+
+        assert!(builder.ensure_affordable(0, 0).is_ok());
+        assert!(builder.ensure_affordable(100, 0).is_ok());
+        assert!(builder.ensure_affordable(101, 0).is_err());
+    }
+
+    #[test]
+    fn test_execute_checks_fee_before_proving() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let recipient = Address::try_from(&private_key).unwrap();
+        // The priority fee alone exceeds the fee record, which is known before the execution is proven.
+        let builder = TransactionBuilder::new(private_key, "http://localhost:0")
+            .priority_fee(1_000)
+            .fee_record(Some(sample_record(rng, 999)));
+
+        let error = builder.transfer_private(Some(sample_record(rng, 10)), recipient, 5).unwrap_err();
+        assert!(error.to_string().contains("The fee record of 999 is insufficient to pay 1000 microcredits"));
+    }
+
+    #[test]
+    fn test_microcredits() {
+        let rng = &mut TestRng::default();
+        assert_eq!(microcredits(&sample_record(rng, 42)).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_select_record() {
+        let rng = &mut TestRng::default();
+        let records = vec![sample_record(rng, 50), sample_record(rng, 10), sample_record(rng, 20)];
+
+        // The smallest record covering the amount is selected.
+        assert_eq!(select_record(&records, 15, None).unwrap(), records[2]);
+        assert_eq!(select_record(&records, 10, None).unwrap(), records[1]);
+        // The excluded record is never selected.
+        assert_eq!(select_record(&records, 15, Some(&records[2])).unwrap(), records[0]);
+        // No record covers the amount.
+        assert!(select_record(&records, 51, None).is_err());
+    }
+
+    #[test]
+    fn test_select_records_to_join() {
+        let rng = &mut TestRng::default();
+        let records = vec![sample_record(rng, 50), sample_record(rng, 10), sample_record(rng, 20)];
+
+        // The two largest records are selected.
+        assert_eq!(select_records_to_join(&records, None).unwrap(), (records[0].clone(), records[2].clone()));
+        assert_eq!(
+            select_records_to_join(&records, Some(&records[0])).unwrap(),
+            (records[2].clone(), records[1].clone())
+        );
+        // At least two records are required.
+        assert!(select_records_to_join(&records[..1], None).is_err());
+    }
+}
@@ -12,18 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{CurrentNetwork, Developer};
-use snarkvm::prelude::{
-    query::Query,
-    store::{helpers::memory::ConsensusMemory, ConsensusStore},
-    Address,
-    Locator,
-    PrivateKey,
-    Value,
-    VM,
-};
-
-use aleo_std::StorageMode;
+use super::{CurrentNetwork, Developer, TransactionBuilder};
+use snarkvm::prelude::{Address, PrivateKey};
+
 use anyhow::{bail, Result};
 use clap::Parser;
 use std::{path::PathBuf, str::FromStr};
@@ -32,9 +23,10 @@ use zeroize::Zeroize;
 /// Executes the `transfer_private` function in the `credits.aleo` program.
 #[derive(Debug, Parser)]
 pub struct TransferPrivate {
-    /// The input record used to craft the transfer.
+    /// The input record used to craft the transfer. If not specified, the smallest unspent record
+    /// in the wallet covering the amount is spent.
     #[clap(long)]
-    input_record: String,
+    input_record: Option<String>,
     /// The recipient address.
     #[clap(long)]
     recipient: Address<CurrentNetwork>,
@@ -62,6 +54,9 @@ pub struct TransferPrivate {
     /// Store generated deployment transaction to a local file.
     #[clap(long)]
     store: Option<String>,
+    /// The directory of the wallet files to select the input record from, defaults to `~/.aleo/wallets/mainnet`.
+    #[clap(long)]
+    wallet_dir: Option<PathBuf>,
     /// Specify the path to a directory containing the ledger
     #[clap(long = "storage_path")]
     pub storage_path: Option<PathBuf>,
@@ -76,63 +71,83 @@ impl Drop for TransferPrivate {
 
 impl TransferPrivate {
     /// Creates an Aleo transfer with the provided inputs.
-    #[allow(clippy::format_in_format_args)]
     pub fn parse(self) -> Result<String> {
         // Ensure that the user has specified an action.
         if !self.dry_run && self.broadcast.is_none() && self.store.is_none() {
             bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
         }
 
-        // Specify the query
-        let query = Query::from(&self.query);
-
         // Retrieve the private key.
         let private_key = PrivateKey::from_str(&self.private_key)?;
 
+        // Prepare the fee and the input record.
+        let fee_record = Developer::parse_record(&private_key, &self.fee_record)?;
+        let input_record = match &self.input_record {
+            Some(record_string) => Some(Developer::parse_record(&private_key, record_string)?),
+            None => None,
+        };
+
         println!("📦 Creating private transfer of {} microcredits to {}...\n", self.amount, self.recipient);
 
         // Generate the transfer_private transaction.
-        let transaction = {
-            // Initialize an RNG.
-            let rng = &mut rand::thread_rng();
-
-            // Initialize the storage.
-            let storage_mode = match &self.storage_path {
-                Some(path) => StorageMode::Custom(path.clone()),
-                None => StorageMode::Production,
-            };
-            let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(storage_mode)?;
-
-            // Initialize the VM.
-            let vm = VM::from(store)?;
-
-            // Prepare the fee.
-            let fee_record = Developer::parse_record(&private_key, &self.fee_record)?;
-            let priority_fee = self.priority_fee;
-
-            // Prepare the inputs for a transfer.
-            let input_record = Developer::parse_record(&private_key, &self.input_record)?;
-            let inputs = vec![
-                Value::Record(input_record),
-                Value::from_str(&format!("{}", self.recipient))?,
-                Value::from_str(&format!("{}u64", self.amount))?,
-            ];
-
-            // Create a new transaction.
-            vm.execute(
-                &private_key,
-                ("credits.aleo", "transfer_private"),
-                inputs.iter(),
-                Some(fee_record),
-                priority_fee,
-                Some(query),
-                rng,
-            )?
-        };
-        let locator = Locator::<CurrentNetwork>::from_str("credits.aleo/transfer_private")?;
-        println!("✅ Created private transfer of {} microcredits to {}\n", &self.amount, self.recipient);
+        let transaction = TransactionBuilder::new(private_key, &self.query)
+            .priority_fee(self.priority_fee)
+            .fee_record(Some(fee_record))
+            .wallet_dir(self.wallet_dir.clone())
+            .storage_path(self.storage_path.clone())
+            .transfer_private(input_record, self.recipient, self.amount)?;
+
+        println!("✅ Created private transfer of {} microcredits to {}\n", self.amount, self.recipient);
 
         // Determine if the transaction should be broadcast, stored, or displayed to the user.
-        Developer::handle_transaction(&self.broadcast, self.dry_run, &self.store, transaction, locator.to_string())
+        Developer::handle_transaction(
+            &self.broadcast,
+            self.dry_run,
+            &self.store,
+            transaction,
+            "credits.aleo/transfer_private".to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_transfer_private() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "transfer-private",
+            "--recipient",
+            "aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px",
+            "--amount",
+            "100",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--priority-fee",
+            "77",
+            "--fee-record",
+            "RECORD",
+            "--dry-run",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::TransferPrivate(transfer)) = cli.command {
+            assert_eq!(transfer.input_record, None);
+            assert_eq!(transfer.amount, 100);
+            assert_eq!(transfer.private_key, "PRIVATE_KEY");
+            assert_eq!(transfer.query, "QUERY");
+            assert_eq!(transfer.priority_fee, 77);
+            assert_eq!(transfer.fee_record, "RECORD");
+            assert_eq!(transfer.wallet_dir, None);
+            assert!(transfer.dry_run);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
     }
 }
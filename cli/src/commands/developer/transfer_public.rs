@@ -0,0 +1,138 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{CurrentNetwork, Developer, TransactionBuilder};
+use snarkvm::prelude::{Address, PrivateKey};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::{path::PathBuf, str::FromStr};
+use zeroize::Zeroize;
+
+/// Executes the `transfer_public` function in the `credits.aleo` program.
+#[derive(Debug, Parser)]
+pub struct TransferPublic {
+    /// The recipient address.
+    #[clap(long)]
+    recipient: Address<CurrentNetwork>,
+    /// The number of microcredits to transfer.
+    #[clap(long)]
+    amount: u64,
+    /// The private key used to generate the execution.
+    #[clap(short, long)]
+    private_key: String,
+    /// The endpoint to query node state from.
+    #[clap(short, long)]
+    query: String,
+    /// The priority fee in microcredits.
+    #[clap(long, default_value_t = 0)]
+    priority_fee: u64,
+    /// The record to spend the fee from. If not specified, the fee is paid from the public balance.
+    #[clap(long)]
+    fee_record: Option<String>,
+    /// The endpoint used to broadcast the generated transaction.
+    #[clap(short, long, conflicts_with = "dry_run")]
+    broadcast: Option<String>,
+    /// Performs a dry-run of transaction generation.
+    #[clap(short, long, conflicts_with = "broadcast")]
+    dry_run: bool,
+    /// Store generated deployment transaction to a local file.
+    #[clap(long)]
+    store: Option<String>,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "storage_path")]
+    pub storage_path: Option<PathBuf>,
+}
+
+impl Drop for TransferPublic {
+    /// Zeroize the private key when the `TransferPublic` struct goes out of scope.
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+    }
+}
+
+impl TransferPublic {
+    /// Creates a public Aleo transfer with the provided inputs.
+    pub fn parse(self) -> Result<String> {
+        // Ensure that the user has specified an action.
+        if !self.dry_run && self.broadcast.is_none() && self.store.is_none() {
+            bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
+        }
+
+        // Retrieve the private key.
+        let private_key = PrivateKey::from_str(&self.private_key)?;
+
+        // Prepare the fee.
+        let fee_record = match &self.fee_record {
+            Some(record_string) => Some(Developer::parse_record(&private_key, record_string)?),
+            None => None,
+        };
+
+        println!("📦 Creating public transfer of {} microcredits to {}...\n", self.amount, self.recipient);
+
+        // Generate the transfer_public transaction.
+        let transaction = TransactionBuilder::new(private_key, &self.query)
+            .priority_fee(self.priority_fee)
+            .fee_record(fee_record)
+            .storage_path(self.storage_path.clone())
+            .transfer_public(self.recipient, self.amount)?;
+
+        println!("✅ Created public transfer of {} microcredits to {}\n", self.amount, self.recipient);
+
+        // Determine if the transaction should be broadcast, stored, or displayed to the user.
+        Developer::handle_transaction(
+            &self.broadcast,
+            self.dry_run,
+            &self.store,
+            transaction,
+            "credits.aleo/transfer_public".to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_transfer_public() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "transfer-public",
+            "--recipient",
+            "aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px",
+            "--amount",
+            "100",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--dry-run",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::TransferPublic(transfer)) = cli.command {
+            assert_eq!(transfer.amount, 100);
+            assert_eq!(transfer.private_key, "PRIVATE_KEY");
+            assert_eq!(transfer.query, "QUERY");
+            assert_eq!(transfer.priority_fee, 0);
+            assert_eq!(transfer.fee_record, None);
+            assert!(transfer.dry_run);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
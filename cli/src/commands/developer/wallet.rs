@@ -0,0 +1,249 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{microcredits, CurrentNetwork};
+use snarkvm::prelude::{Address, Field, Plaintext, PrivateKey, Record, ViewKey};
+
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// A record owned by the wallet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletRecord {
+    /// The height of the block containing the record.
+    pub height: u32,
+    /// The commitment of the record.
+    pub commitment: Field<CurrentNetwork>,
+    /// The serial number of the record, once it has been derived from the private key.
+    pub serial_number: Option<Field<CurrentNetwork>>,
+    /// The decrypted record.
+    pub record: Record<CurrentNetwork, Plaintext<CurrentNetwork>>,
+    /// Whether the record is known to be spent.
+    pub spent: bool,
+}
+
+impl WalletRecord {
+    /// Returns the serial number of the record, deriving it from the private key if needed.
+    pub fn serial_number(&mut self, private_key: &PrivateKey<CurrentNetwork>) -> Result<Field<CurrentNetwork>> {
+        match self.serial_number {
+            Some(serial_number) => Ok(serial_number),
+            None => {
+                let serial_number =
+                    Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::serial_number(*private_key, self.commitment)?;
+                self.serial_number = Some(serial_number);
+                Ok(serial_number)
+            }
+        }
+    }
+}
+
+/// The contents of a wallet file.
+#[derive(Debug, Serialize, Deserialize)]
+struct WalletState {
+    /// The address owning the records.
+    address: Address<CurrentNetwork>,
+    /// The last block height that was scanned, if any.
+    last_height: Option<u32>,
+    /// The records owned by the address, in the order they were found.
+    records: Vec<WalletRecord>,
+}
+
+/// A local database of the records owned by a view key.
+///
+/// The wallet is stored as a JSON file named after the address of the view key, and remembers
+/// the last scanned height so that subsequent scans only fetch the new blocks.
+#[derive(Debug)]
+pub struct Wallet {
+    /// The path to the wallet file.
+    path: PathBuf,
+    /// The contents of the wallet.
+    state: WalletState,
+}
+
+impl Wallet {
+    /// Opens the wallet of the given view key in the given directory, or starts an empty one.
+    pub fn open(directory: &Path, view_key: &ViewKey<CurrentNetwork>) -> Result<Self> {
+        let address = view_key.to_address();
+        let path = directory.join(format!("{address}.json"));
+
+        let state = match path.exists() {
+            true => {
+                let state: WalletState = serde_json::from_slice(&std::fs::read(&path)?)?;
+                ensure!(state.address == address, "The wallet file '{}' belongs to another address", path.display());
+                state
+            }
+            false => WalletState { address, last_height: None, records: Vec::new() },
+        };
+
+        Ok(Self { path, state })
+    }
+
+    /// Returns the default directory of the wallet files, `~/.aleo/wallets/mainnet`.
+    pub fn default_directory() -> PathBuf {
+        aleo_std::aleo_dir().join("wallets").join("mainnet")
+    }
+
+    /// Returns the path to the wallet file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the last block height that was scanned, if any.
+    pub fn last_height(&self) -> Option<u32> {
+        self.state.last_height
+    }
+
+    /// Returns the block height an incremental scan should start from.
+    pub fn next_height(&self) -> u32 {
+        self.state.last_height.map_or(0, |height| height.saturating_add(1))
+    }
+
+    /// Records a scan of the given (inclusive) block range, along with the records found in it.
+    /// The last scanned height only advances if the range leaves no gap after it.
+    pub fn insert_scan(&mut self, start_height: u32, end_height: u32, records: Vec<WalletRecord>) {
+        for record in records {
+            if !self.state.records.iter().any(|existing| existing.commitment == record.commitment) {
+                self.state.records.push(record);
+            }
+        }
+        if start_height <= self.next_height() {
+            self.state.last_height = Some(self.state.last_height.map_or(end_height, |height| height.max(end_height)));
+        }
+    }
+
+    /// Marks the records as spent, checking their serial numbers with the given function.
+    /// Records that are already known to be spent are not checked again.
+    pub fn refresh_spent(
+        &mut self,
+        private_key: &PrivateKey<CurrentNetwork>,
+        mut is_spent: impl FnMut(&Field<CurrentNetwork>) -> Result<bool>,
+    ) -> Result<()> {
+        for record in self.state.records.iter_mut().filter(|record| !record.spent) {
+            let serial_number = record.serial_number(private_key)?;
+            record.spent = is_spent(&serial_number)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the records that are not known to be spent.
+    pub fn unspent_records(&self) -> impl Iterator<Item = &WalletRecord> {
+        self.state.records.iter().filter(|record| !record.spent)
+    }
+
+    /// Returns the number of microcredits held by the records that are not known to be spent.
+    pub fn balance(&self) -> u64 {
+        self.unspent_records().filter_map(|record| microcredits(&record.record).ok()).sum()
+    }
+
+    /// Writes the wallet to its file, readable only by the owner.
+    pub fn save(&self) -> Result<()> {
+        if let Some(directory) = self.path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+
+        // Write to a temporary file first, so that an interrupted write does not corrupt the wallet.
+        let temporary_path = self.path.with_extension("json.tmp");
+        let mut options = File::options();
+        options.write(true).create(true).truncate(true);
+        #[cfg(target_family = "unix")]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&temporary_path)?;
+        file.write_all(&serde_json::to_vec_pretty(&self.state)?)?;
+        file.sync_all()?;
+        std::fs::rename(&temporary_path, &self.path)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{TestRng, Uniform};
+    use std::str::FromStr;
+
+    /// Returns a record of the given address holding the given amount of microcredits.
+    fn sample_record(rng: &mut TestRng, address: Address<CurrentNetwork>, height: u32, amount: u64) -> WalletRecord {
+        WalletRecord {
+            height,
+            commitment: Field::rand(rng),
+            serial_number: None,
+            record: Record::from_str(&format!(
+                "{{ owner: {address}.private, microcredits: {amount}u64.private, _nonce: 0group.public }}"
+            ))
+            .unwrap(),
+            spent: false,
+        }
+    }
+
+    #[test]
+    fn test_wallet() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let view_key = ViewKey::try_from(private_key).unwrap();
+        let address = view_key.to_address();
+
+        let directory = tempfile::tempdir().unwrap();
+        let mut wallet = Wallet::open(directory.path(), &view_key).unwrap();
+        assert_eq!(wallet.last_height(), None);
+        assert_eq!(wallet.next_height(), 0);
+
+        // Scan the first blocks.
+        let first = sample_record(rng, address, 3, 100);
+        let second = sample_record(rng, address, 7, 20);
+        wallet.insert_scan(0, 10, vec![first.clone(), second.clone()]);
+        assert_eq!(wallet.last_height(), Some(10));
+        assert_eq!(wallet.balance(), 120);
+
+        // Rescanning a range does not duplicate the records or move the height back.
+        wallet.insert_scan(5, 8, vec![second.clone()]);
+        assert_eq!(wallet.last_height(), Some(10));
+        assert_eq!(wallet.unspent_records().count(), 2);
+
+        // A scan leaving a gap does not advance the height.
+        wallet.insert_scan(20, 30, vec![]);
+        assert_eq!(wallet.last_height(), Some(10));
+        wallet.insert_scan(11, 30, vec![]);
+        assert_eq!(wallet.last_height(), Some(30));
+
+        // Mark the first record as spent.
+        let spent_serial_number =
+            Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::serial_number(private_key, first.commitment).unwrap();
+        wallet.refresh_spent(&private_key, |serial_number| Ok(*serial_number == spent_serial_number)).unwrap();
+        assert_eq!(wallet.balance(), 20);
+        assert_eq!(wallet.unspent_records().cloned().map(|record| record.commitment).collect::<Vec<_>>(), vec![
+            second.commitment
+        ]);
+
+        // The wallet is restored from its file.
+        wallet.save().unwrap();
+        let restored = Wallet::open(directory.path(), &view_key).unwrap();
+        assert_eq!(restored.last_height(), Some(30));
+        assert_eq!(restored.balance(), 20);
+        assert_eq!(restored.state.records, wallet.state.records);
+
+        // The wallet file of another address is rejected.
+        let other_view_key = ViewKey::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        std::fs::rename(wallet.path(), directory.path().join(format!("{}.json", other_view_key.to_address()))).unwrap();
+        assert!(Wallet::open(directory.path(), &other_view_key).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::network::{MainnetV0, Network},
+    ledger::{block::Block, store::helpers::rocksdb::ConsensusDB},
+    prelude::FromBytes,
+};
+
+use aleo_std::StorageMode;
+use anyhow::{bail, Result};
+use clap::Parser;
+use colored::Colorize;
+use std::{
+    io::{stdout, Write},
+    path::PathBuf,
+};
+
+type CurrentNetwork = MainnetV0;
+type CurrentLedger = snarkvm::prelude::Ledger<CurrentNetwork, ConsensusDB<CurrentNetwork>>;
+
+/// Commands to export and import ledger snapshots, in the same bundle format as the CDN.
+#[derive(Debug, Parser)]
+pub enum Ledger {
+    /// Exports the blocks of the ledger into a directory of block bundles
+    Export {
+        /// Specify the directory to write the block bundles to
+        #[clap(long)]
+        to: PathBuf,
+        /// Specify the block height to start exporting from (rounded down to the start of its bundle)
+        #[clap(default_value = "0", long)]
+        start: u32,
+        /// Specify the block height to stop exporting at (exclusive), which defaults to the latest block
+        #[clap(long)]
+        end: Option<u32>,
+        #[clap(flatten)]
+        storage: LedgerStorage,
+    },
+    /// Imports the blocks from a directory of block bundles into the ledger, verifying each block
+    Import {
+        /// Specify the directory to read the block bundles from
+        #[clap(long)]
+        from: PathBuf,
+        #[clap(flatten)]
+        storage: LedgerStorage,
+    },
+}
+
+/// The location of the ledger.
+#[derive(Debug, Parser)]
+pub struct LedgerStorage {
+    /// Specify the network of the ledger
+    #[clap(default_value = "0", long = "network")]
+    pub network: u16,
+    /// Enables development mode, specify the unique ID of the local node
+    #[clap(long)]
+    pub dev: Option<u16>,
+    /// Specify the path to a directory containing the ledger
+    #[clap(long = "path")]
+    pub path: Option<PathBuf>,
+    /// Specify the path to the genesis block of the ledger, which defaults to the genesis block of the network
+    #[clap(long = "genesis")]
+    pub genesis: Option<PathBuf>,
+}
+
+impl LedgerStorage {
+    /// Opens the ledger. Note: The node must not be running, as the ledger cannot be opened twice.
+    pub(crate) fn open(self) -> Result<CurrentLedger> {
+        // Ensure the network is supported.
+        if self.network != CurrentNetwork::ID {
+            bail!("Network {} is not supported", self.network);
+        }
+        // Load the genesis block.
+        let genesis = match self.genesis {
+            Some(path) => Block::from_bytes_le(&std::fs::read(path)?)?,
+            None => Block::from_bytes_le(CurrentNetwork::genesis_bytes())?,
+        };
+        // Determine the storage mode.
+        let storage_mode = match self.path {
+            Some(path) => StorageMode::Custom(path),
+            None => StorageMode::from(self.dev),
+        };
+        CurrentLedger::load(genesis, storage_mode)
+    }
+}
+
+impl Ledger {
+    /// Exports or imports the ledger.
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Export { to, start, end, storage } => {
+                let ledger = storage.open()?;
+                let latest_height = ledger.latest_height();
+                let end_height = snarkos_node_cdn::export_blocks(&ledger, &to, start, end, |height| {
+                    print!("\rExported up to block {height} (of {latest_height})...");
+                    let _ = stdout().flush();
+                })?;
+                println!();
+                let path_string = format!("(in \"{}\")", to.display()).dimmed();
+                Ok(format!("✅ Exported the blocks up to block {} {path_string}", end_height - 1))
+            }
+            Self::Import { from, storage } => {
+                let ledger = storage.open()?;
+                let start_height = ledger.latest_height();
+                let latest_height = snarkos_node_cdn::import_blocks(&ledger, &from, |height| {
+                    print!("\rImported and verified block {height}...");
+                    let _ = stdout().flush();
+                })?;
+                println!();
+                match latest_height > start_height {
+                    true => Ok(format!("✅ Imported blocks {} to {latest_height}", start_height + 1)),
+                    false => Ok(format!("✅ The ledger is already at block {latest_height}, no blocks were imported")),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_ledger_export() {
+        let arg_vec = vec!["snarkos", "ledger", "export", "--to", "snapshot", "--start", "100", "--dev", "1"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Ledger(Ledger::Export { to, start, end, storage }) = cli.command {
+            assert_eq!(to, PathBuf::from("snapshot"));
+            assert_eq!(start, 100);
+            assert_eq!(end, None);
+            assert_eq!(storage.network, 0);
+            assert_eq!(storage.dev, Some(1));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+
+    #[test]
+    fn clap_snarkos_ledger_import() {
+        let arg_vec = vec!["snarkos", "ledger", "import", "--from", "snapshot", "--path", "ledger"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Ledger(Ledger::Import { from, storage }) = cli.command {
+            assert_eq!(from, PathBuf::from("snapshot"));
+            assert_eq!(storage.path, Some(PathBuf::from("ledger")));
+            assert_eq!(storage.genesis, None);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
@@ -15,12 +15,27 @@
 mod account;
 pub use account::*;
 
+mod cdn;
+pub use cdn::*;
+
 mod clean;
 pub use clean::*;
 
+mod config;
+pub use config::*;
+
 mod developer;
 pub use developer::*;
 
+mod ledger;
+pub use ledger::*;
+
+mod pool;
+pub use pool::*;
+
+mod signer;
+pub use signer::*;
+
 mod start;
 pub use start::*;
 
@@ -29,7 +44,7 @@ pub use update::*;
 
 use anstyle::{AnsiColor, Color, Style};
 use anyhow::Result;
-use clap::{builder::Styles, Parser};
+use clap::{builder::Styles, CommandFactory, FromArgMatches, Parser};
 
 const HEADER_COLOR: Option<Color> = Some(Color::Ansi(AnsiColor::Yellow));
 const LITERAL_COLOR: Option<Color> = Some(Color::Ansi(AnsiColor::Green));
@@ -53,23 +68,51 @@ pub struct CLI {
 pub enum Command {
     #[clap(subcommand)]
     Account(Account),
+    #[clap(subcommand)]
+    Cdn(Cdn),
     #[clap(name = "clean")]
     Clean(Clean),
     #[clap(subcommand)]
+    Config(Config),
+    #[clap(subcommand)]
     Developer(Developer),
+    #[clap(subcommand)]
+    Ledger(Ledger),
+    #[clap(subcommand)]
+    Pool(Pool),
+    #[clap(name = "signer")]
+    Signer(Signer),
     #[clap(name = "start")]
     Start(Box<Start>),
     #[clap(name = "update")]
     Update(Update),
 }
 
+impl CLI {
+    /// Parses the CLI arguments of the process. The configurations of `snarkos start` are loaded from
+    /// its config file and the environment variables, beneath the options given on the command line.
+    pub fn parse_args() -> Result<Self> {
+        let matches = Self::command().mut_subcommand("start", with_explicit_flags).get_matches();
+        let mut cli = Self::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+        if let (Command::Start(start), Some(("start", matches))) = (&mut cli.command, matches.subcommand()) {
+            **start = Start::load_from_matches(matches, |name| std::env::var_os(name))?;
+        }
+        Ok(cli)
+    }
+}
+
 impl Command {
     /// Parses the command.
     pub fn parse(self) -> Result<String> {
         match self {
             Self::Account(command) => command.parse(),
+            Self::Cdn(command) => command.parse(),
             Self::Clean(command) => command.parse(),
+            Self::Config(command) => command.parse(),
             Self::Developer(command) => command.parse(),
+            Self::Ledger(command) => command.parse(),
+            Self::Pool(command) => command.parse(),
+            Self::Signer(command) => command.parse(),
             Self::Start(command) => command.parse(),
             Self::Update(command) => command.parse(),
         }
@@ -0,0 +1,111 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::start::check_permissions;
+use crate::helpers::{LogFormat, LogRotation};
+use snarkos_node::PoolWorker;
+use snarkvm::console::network::MainnetV0;
+
+use anyhow::Result;
+use clap::Parser;
+use std::{net::SocketAddr, path::PathBuf};
+use zeroize::Zeroizing;
+
+type CurrentNetwork = MainnetV0;
+
+/// Commands to take part in a prover pool.
+#[derive(Debug, Parser)]
+pub enum Pool {
+    /// Proves the work handed out by a pool coordinator (a prover started with `--pool`), and submits the solutions back
+    Worker {
+        /// Specify the IP address and port of the pool coordinator
+        #[clap(long = "coordinator")]
+        coordinator: SocketAddr,
+        /// Specify the name of this worker, as allowed by the coordinator
+        #[clap(long = "name")]
+        name: String,
+        /// Specify the path to a file containing the secret of this worker, shared with the coordinator
+        #[clap(long = "secret-file")]
+        secret_file: PathBuf,
+        /// Specify the number of puzzle instances to run (default: the number of CPUs, less two)
+        #[clap(long = "instances")]
+        instances: Option<usize>,
+        /// Specify the verbosity of the worker [options: 0, 1, 2]
+        #[clap(default_value = "0", long = "verbosity")]
+        verbosity: u8,
+    },
+}
+
+impl Pool {
+    /// Runs the pool command.
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::Worker { coordinator, name, secret_file, instances, verbosity } => {
+                // Initialize the logger.
+                let logfile = std::env::temp_dir().join("snarkos-pool-worker.log");
+                let _ = crate::helpers::initialize_logger(
+                    verbosity,
+                    true,
+                    logfile,
+                    LogFormat::default(),
+                    LogRotation::default(),
+                );
+
+                // Read the secret of the worker.
+                check_permissions(&secret_file)?;
+                let mut secret = Zeroizing::new(std::fs::read_to_string(&secret_file)?);
+                secret.truncate(secret.trim_end().len());
+
+                let instances = instances.unwrap_or_else(|| num_cpus::get().saturating_sub(2).max(1));
+                let worker = PoolWorker::<CurrentNetwork>::new(name, secret, coordinator, instances)?;
+                println!("⛏️  Proving for the pool coordinator at {coordinator} ({instances} instances)");
+                // Run the worker, until the process is stopped.
+                tokio::runtime::Runtime::new()?.block_on(worker.run())?;
+                Ok(String::new())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_pool_worker() {
+        let arg_vec = vec![
+            "snarkos",
+            "pool",
+            "worker",
+            "--coordinator",
+            "127.0.0.1:4150",
+            "--name",
+            "rig-1",
+            "--secret-file",
+            "/path/to/secret",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Pool(Pool::Worker { coordinator, name, secret_file, instances, verbosity }) = cli.command {
+            assert_eq!(coordinator, "127.0.0.1:4150".parse().unwrap());
+            assert_eq!(name, "rig-1");
+            assert_eq!(secret_file, PathBuf::from("/path/to/secret"));
+            assert_eq!(instances, None);
+            assert_eq!(verbosity, 0);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::start::check_permissions;
+use crate::helpers::{read_keystore_password, LogFormat, LogRotation};
+use snarkos_account::{Account, Keystore, SigningProtection};
+use snarkos_node::bft::PROPOSAL_EXPIRATION_IN_SECS;
+use snarkvm::console::network::MainnetV0;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use colored::Colorize;
+use core::str::FromStr;
+use std::path::PathBuf;
+use zeroize::Zeroizing;
+
+type CurrentNetwork = MainnetV0;
+
+/// Runs a signer process, which holds the account private key of a validator and signs on its behalf.
+#[derive(Debug, Parser)]
+pub struct Signer {
+    /// Specify the path of the Unix socket to listen on
+    #[clap(long = "socket")]
+    pub socket: PathBuf,
+    /// Specify the path to the signing history, which records the signed batches across restarts
+    #[clap(long = "signing-history")]
+    pub signing_history: PathBuf,
+    /// Specify the path to a file containing the account private key of the validator
+    #[clap(long = "private-key-file")]
+    pub private_key_file: Option<PathBuf>,
+    /// Specify the path to an encrypted keystore containing the account private key of the validator
+    #[clap(long = "keystore")]
+    pub keystore: Option<PathBuf>,
+    /// Specify the path to a file containing the keystore password (otherwise read from the environment or a prompt)
+    #[clap(long = "keystore-password-file", requires = "keystore")]
+    pub keystore_password_file: Option<PathBuf>,
+    /// Specify the verbosity of the signer [options: 0, 1, 2]
+    #[clap(default_value = "1", long = "verbosity")]
+    pub verbosity: u8,
+}
+
+impl Signer {
+    /// Starts the signer process, serving requests until it is terminated.
+    pub fn parse(self) -> Result<String> {
+        #[cfg(not(target_family = "unix"))]
+        bail!("The signer is only supported on Unix platforms");
+
+        #[cfg(target_family = "unix")]
+        {
+            // Initialize the logger.
+            let logfile = std::env::temp_dir().join("snarkos-signer.log");
+            let _ = crate::helpers::initialize_logger(
+                self.verbosity,
+                true,
+                logfile,
+                LogFormat::default(),
+                LogRotation::default(),
+            );
+
+            // Load the signing history, so that batches signed before a restart are not signed differently.
+            let protection = SigningProtection::open(self.signing_history.clone(), PROPOSAL_EXPIRATION_IN_SECS)?;
+            let server = snarkos_account::SignerServer::new(self.parse_account()?, protection);
+            println!(
+                "🔏 Signing for {} on {}.\n",
+                server.address().to_string().bold(),
+                self.socket.display().to_string().bold()
+            );
+            server.serve(&self.socket)?;
+            Ok(String::new())
+        }
+    }
+
+    /// Returns the account of the validator.
+    fn parse_account(&self) -> Result<Account<CurrentNetwork>> {
+        match (&self.private_key_file, &self.keystore) {
+            // Parse the private key from a file.
+            (Some(path), None) => {
+                check_permissions(path)?;
+                let private_key = Zeroizing::new(std::fs::read_to_string(path)?);
+                Account::from_str(private_key.trim())
+            }
+            // Decrypt the private key from a keystore.
+            (None, Some(path)) => {
+                let keystore = Keystore::from_str(&std::fs::read_to_string(path)?)?;
+                let password = read_keystore_password(self.keystore_password_file.as_deref(), false)?;
+                keystore.decrypt(&password)
+            }
+            (None, None) => bail!("Missing the '--private-key-file' or '--keystore' argument"),
+            (Some(_), Some(_)) => bail!("Cannot use '--private-key-file' and '--keystore' simultaneously"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_signer() {
+        let signer = Signer::try_parse_from(
+            ["snarkos", "--socket", "/tmp/signer.sock", "--signing-history", "history", "--keystore", "key.json"]
+                .iter(),
+        )
+        .unwrap();
+        assert_eq!(signer.socket, PathBuf::from("/tmp/signer.sock"));
+        assert_eq!(signer.signing_history, PathBuf::from("history"));
+        assert_eq!(signer.keystore, Some(PathBuf::from("key.json")));
+
+        // Ensure the keystore password file requires a keystore.
+        let signer = Signer::try_parse_from(
+            ["snarkos", "--socket", "s", "--signing-history", "h", "--keystore-password-file", "p"].iter(),
+        );
+        assert!(signer.is_err());
+        // Ensure the socket and the signing history are required.
+        assert!(Signer::try_parse_from(["snarkos", "--signing-history", "h", "--keystore", "key.json"].iter()).is_err());
+        assert!(Signer::try_parse_from(["snarkos", "--socket", "s", "--keystore", "key.json"].iter()).is_err());
+    }
+}
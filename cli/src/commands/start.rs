@@ -12,9 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use snarkos_account::Account;
+use crate::helpers::{read_keystore_password, LogFormat, LogRotation};
+use snarkos_account::{Account, Keystore, Signer};
 use snarkos_display::Display;
-use snarkos_node::{bft::MEMORY_POOL_PORT, router::messages::NodeType, Node};
+use snarkos_node::{
+    bft::MEMORY_POOL_PORT,
+    consensus::MemoryPoolConfig,
+    router::messages::NodeType,
+    tcp::{InboundLimits, IpFamily, OutboundLimits, RateLimit},
+    Node,
+    PoolConfig,
+};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -39,9 +47,16 @@ use core::str::FromStr;
 use indexmap::IndexMap;
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
-use serde::{Deserialize, Serialize};
-use std::{net::SocketAddr, path::PathBuf};
+use serde::{Deserialize, Serialize, Serializer};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::PathBuf,
+    time::Duration,
+};
 use tokio::runtime::{self, Runtime};
+use zeroize::{Zeroize, Zeroizing};
 
 /// The recommended minimum number of 'open files' limit for a validator.
 /// Validators should be able to handle at least 1000 concurrent connections, each requiring 2 sockets.
@@ -65,9 +80,30 @@ impl FromStr for BondedBalances {
     }
 }
 
+/// Serializes an optional value using its `Display` implementation.
+fn serialize_display<T: fmt::Display, S: Serializer>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Parses the rate limit supplied to the flag with the given name.
+fn parse_rate_limit(name: &str, limit: &str) -> Result<RateLimit> {
+    match RateLimit::from_str(limit) {
+        Ok(limit) => Ok(limit),
+        Err(error) => bail!("The limit supplied to '--{name}' is malformed: {error}"),
+    }
+}
+
 /// Starts the snarkOS node.
-#[derive(Clone, Debug, Parser)]
+#[derive(Clone, Debug, Parser, Serialize)]
 pub struct Start {
+    /// Specify the path to a TOML (or YAML) config file, with options that are overridden by `SNARKOS_*` environment variables and CLI flags
+    #[clap(long = "config")]
+    #[serde(skip)]
+    pub config: Option<PathBuf>,
+
     /// Specify the network ID of this node
     #[clap(default_value = "0", long = "network")]
     pub network: u16,
@@ -88,6 +124,15 @@ pub struct Start {
     /// Specify the path to a file containing the account private key of the node
     #[clap(long = "private-key-file")]
     pub private_key_file: Option<PathBuf>,
+    /// Specify the path to an encrypted keystore containing the account private key of the node
+    #[clap(long = "keystore")]
+    pub keystore: Option<PathBuf>,
+    /// Specify the path to a file containing the keystore password (otherwise read from the environment or a prompt)
+    #[clap(long = "keystore-password-file")]
+    pub keystore_password_file: Option<PathBuf>,
+    /// Specify the path to the Unix socket of a signer process holding the validator private key (validator only)
+    #[clap(long = "signer")]
+    pub signer: Option<PathBuf>,
 
     /// Specify the IP address and port for the node server
     #[clap(long = "node")]
@@ -95,6 +140,12 @@ pub struct Start {
     /// Specify the IP address and port for the BFT
     #[clap(long = "bft")]
     pub bft: Option<SocketAddr>,
+    /// Specify the IP address and port to hand out work to pool workers on (prover only)
+    #[clap(long = "pool", requires = "pool_workers")]
+    pub pool: Option<SocketAddr>,
+    /// Specify the path to a file of the allowed pool workers, with one `<name> <secret>` pair per line (prover only)
+    #[clap(long = "pool-workers", requires = "pool")]
+    pub pool_workers: Option<PathBuf>,
     /// Specify the IP address and port of the peer(s) to connect to
     #[clap(default_value = "", long = "peers")]
     pub peers: String,
@@ -104,6 +155,38 @@ pub struct Start {
     /// If the flag is set, a node will allow untrusted peers to connect
     #[clap(long = "allow-external-peers")]
     pub allow_external_peers: bool,
+    /// Specify the IP family to prefer when connecting to peers, either 'ipv4' or 'ipv6'
+    #[clap(long = "prefer-ip-family")]
+    #[serde(serialize_with = "serialize_display")]
+    pub prefer_ip_family: Option<IpFamily>,
+    /// Specify the limit on requests per second from a single peer, as '<MESSAGES>[:<BYTES>]' where 0 is unlimited
+    #[clap(default_value = "200", long = "peer-rate-limit")]
+    pub peer_rate_limit: String,
+    /// Specify the limit on requests per second from all peers combined, as '<MESSAGES>[:<BYTES>]' where 0 is unlimited
+    #[clap(default_value = "0", long = "global-rate-limit")]
+    pub global_rate_limit: String,
+    /// Specify the limits on each type of request per second from a single peer, as '<TYPE>=<MESSAGES>[:<BYTES>],...'
+    #[clap(default_value = "", long = "request-rate-limits")]
+    pub request_rate_limits: String,
+    /// Specify the limit on messages per second to a single peer, as '<MESSAGES>[:<BYTES>]' where 0 is unlimited
+    #[clap(default_value = "0", long = "peer-outbound-rate-limit")]
+    pub peer_outbound_rate_limit: String,
+    /// Specify the limit on messages per second to all peers combined, as '<MESSAGES>[:<BYTES>]' where 0 is unlimited
+    #[clap(default_value = "0", long = "global-outbound-rate-limit")]
+    pub global_outbound_rate_limit: String,
+
+    /// Specify the maximum number of deployments in the memory pool queue (validator only)
+    #[clap(default_value = "1024", long = "mempool-deployments")]
+    pub mempool_deployments: usize,
+    /// Specify the maximum number of executions in the memory pool queue (validator only)
+    #[clap(default_value = "1024", long = "mempool-executions")]
+    pub mempool_executions: usize,
+    /// Specify the maximum number of queued transactions from a single fee payer (validator only)
+    #[clap(default_value = "64", long = "mempool-per-sender")]
+    pub mempool_per_sender: usize,
+    /// Specify the maximum number of queued transactions with a private fee, whose fee payer is not known (validator only)
+    #[clap(default_value = "256", long = "mempool-private-fee")]
+    pub mempool_private_fee: usize,
 
     /// Specify the IP address and port for the REST server
     #[clap(default_value = "0.0.0.0:3030", long = "rest")]
@@ -124,6 +207,18 @@ pub struct Start {
     /// Specify the path to the file where logs will be stored
     #[clap(default_value_os_t = std::env::temp_dir().join("snarkos.log"), long = "logfile")]
     pub logfile: PathBuf,
+    /// Specify the format of the logs [options: text, json]
+    #[clap(default_value = "text", long = "log-format")]
+    pub log_format: LogFormat,
+    /// Specify the maximum size of the logfile in MiB, before it is rotated
+    #[clap(long = "log-max-size")]
+    pub log_max_size: Option<u64>,
+    /// Specify the maximum age of the logfile in hours, before it is rotated
+    #[clap(long = "log-max-age")]
+    pub log_max_age: Option<u64>,
+    /// Specify the number of rotated logfiles to retain
+    #[clap(default_value = "5", long = "log-retention")]
+    pub log_retention: usize,
     /// Enables the metrics exporter
     #[clap(default_value = "false", long = "metrics")]
     pub metrics: bool,
@@ -131,7 +226,7 @@ pub struct Start {
     /// Specify the path to a directory containing the storage database for the ledger
     #[clap(long = "storage")]
     pub storage: Option<PathBuf>,
-    /// Enables the node to prefetch initial blocks from a CDN
+    /// Enables the node to prefetch initial blocks from a CDN, given as a URL, a `file://` directory, or comma-separated mirrors
     #[clap(default_value = "https://s3.us-west-1.amazonaws.com/testnet3.blocks/phase3", long = "cdn")]
     pub cdn: String,
     /// If the flag is set, the node will not prefetch from a CDN
@@ -156,11 +251,17 @@ impl Start {
     /// Starts the snarkOS node.
     pub fn parse(self) -> Result<String> {
         // Initialize the logger.
-        let log_receiver = crate::helpers::initialize_logger(self.verbosity, self.nodisplay, self.logfile.clone());
+        let log_receiver = crate::helpers::initialize_logger(
+            self.verbosity,
+            self.nodisplay,
+            self.logfile.clone(),
+            self.log_format,
+            self.parse_log_rotation(),
+        );
         // Initialize the runtime.
         Self::runtime().block_on(async move {
-            // Clone the configurations.
-            let mut cli = self.clone();
+            // Take ownership of the configurations.
+            let mut cli = self;
             // Parse the network.
             match cli.network {
                 0 => {
@@ -220,6 +321,38 @@ impl Start {
         }
     }
 
+    /// Returns the limits on inbound requests, from the given configurations.
+    pub(crate) fn parse_inbound_limits(&self) -> Result<InboundLimits> {
+        /// The types of requests with a limit.
+        const REQUEST_TYPES: [&str; 5] = ["BlockRequest", "PeerRequest", "Ping", "PuzzleRequest", "ValidatorsRequest"];
+
+        let mut requests = BTreeMap::new();
+        for entry in self.request_rate_limits.split(',').filter(|entry| !entry.is_empty()) {
+            let Some((request_type, limit)) = entry.split_once('=') else {
+                bail!("The limit supplied to '--request-rate-limits' ('{entry}') must be of the form '<TYPE>=<LIMIT>'");
+            };
+            ensure!(
+                REQUEST_TYPES.contains(&request_type),
+                "Unknown request type '{request_type}' in '--request-rate-limits' [options: {}]",
+                REQUEST_TYPES.join(", ")
+            );
+            requests.insert(request_type.to_string(), parse_rate_limit("request-rate-limits", limit)?);
+        }
+        Ok(InboundLimits {
+            connection: parse_rate_limit("peer-rate-limit", &self.peer_rate_limit)?,
+            global: parse_rate_limit("global-rate-limit", &self.global_rate_limit)?,
+            requests,
+        })
+    }
+
+    /// Returns the limits on outbound messages, from the given configurations.
+    pub(crate) fn parse_outbound_limits(&self) -> Result<OutboundLimits> {
+        Ok(OutboundLimits {
+            connection: parse_rate_limit("peer-outbound-rate-limit", &self.peer_outbound_rate_limit)?,
+            global: parse_rate_limit("global-outbound-rate-limit", &self.global_outbound_rate_limit)?,
+        })
+    }
+
     /// Returns the CDN to prefetch initial blocks from, from the given configurations.
     fn parse_cdn(&self) -> Option<String> {
         // Determine if the node type is not declared.
@@ -243,23 +376,30 @@ impl Start {
     /// returning the Aleo account.
     fn parse_private_key<N: Network>(&self) -> Result<Account<N>> {
         match self.dev {
-            None => match (&self.private_key, &self.private_key_file) {
+            None => match (&self.private_key, &self.private_key_file, &self.keystore) {
                 // Parse the private key directly.
-                (Some(private_key), None) => Account::from_str(private_key.trim()),
+                (Some(private_key), None, None) => Account::from_str(private_key.trim()),
                 // Parse the private key from a file.
-                (None, Some(path)) => {
+                (None, Some(path), None) => {
                     check_permissions(path)?;
-                    Account::from_str(std::fs::read_to_string(path)?.trim())
+                    let private_key = Zeroizing::new(std::fs::read_to_string(path)?);
+                    Account::from_str(private_key.trim())
+                }
+                // Decrypt the private key from a keystore.
+                (None, None, Some(path)) => {
+                    let keystore = Keystore::from_str(&std::fs::read_to_string(path)?)?;
+                    let password = read_keystore_password(self.keystore_password_file.as_deref(), false)?;
+                    keystore.decrypt(&password)
                 }
                 // Ensure the private key is provided to the CLI, except for clients or nodes in development mode.
-                (None, None) => match self.client {
+                (None, None, None) => match self.client {
                     true => Account::new(&mut rand::thread_rng()),
-                    false => bail!("Missing the '--private-key' or '--private-key-file' argument"),
+                    false => bail!("Missing the '--private-key', '--private-key-file', or '--keystore' argument"),
                 },
                 // Ensure only one private key flag is provided to the CLI.
-                (Some(_), Some(_)) => {
-                    bail!("Cannot use '--private-key' and '--private-key-file' simultaneously, please use only one")
-                }
+                _ => bail!(
+                    "Cannot use '--private-key', '--private-key-file', and '--keystore' simultaneously, please use only one"
+                ),
             },
             Some(dev) => {
                 // Sample the private key of this node.
@@ -278,6 +418,25 @@ impl Start {
         }
     }
 
+    /// Returns the signer of the node, delegating to the signer process if one is specified,
+    /// and otherwise signing with the account of the given private key.
+    fn parse_signer<N: Network>(&self) -> Result<Signer<N>> {
+        match &self.signer {
+            None => Ok(Signer::from(self.parse_private_key::<N>()?)),
+            #[cfg(target_family = "unix")]
+            Some(path) => {
+                ensure!(self.validator, "The '--signer' flag is only supported for validators");
+                ensure!(
+                    self.private_key.is_none() && self.private_key_file.is_none() && self.keystore.is_none(),
+                    "Cannot use '--signer' with a private key, as the signer process holds the private key"
+                );
+                Ok(Signer::Remote(snarkos_account::RemoteSigner::connect(path)?))
+            }
+            #[cfg(not(target_family = "unix"))]
+            Some(_) => bail!("The '--signer' flag is only supported on Unix platforms"),
+        }
+    }
+
     /// Updates the configurations if the node is in development mode.
     fn parse_development(
         &mut self,
@@ -288,11 +447,16 @@ impl Start {
         // and add each of them to the trusted peers. In addition, set the node IP to `4130 + dev`,
         // and the REST IP to `3030 + dev`.
         if let Some(dev) = self.dev {
+            // Use the loopback and unspecified IPs of the preferred IP family, which defaults to IPv4.
+            let (loopback_ip, unspecified_ip) = match self.prefer_ip_family {
+                Some(IpFamily::Ipv6) => (IpAddr::V6(Ipv6Addr::LOCALHOST), IpAddr::V6(Ipv6Addr::UNSPECIFIED)),
+                _ => (IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+            };
             // Add the dev nodes to the trusted peers.
             if trusted_peers.is_empty() {
                 for i in 0..dev {
                     if i != dev {
-                        trusted_peers.push(SocketAddr::from_str(&format!("127.0.0.1:{}", 4130 + i))?);
+                        trusted_peers.push(SocketAddr::new(loopback_ip, 4130 + i));
                     }
                 }
             }
@@ -301,7 +465,7 @@ impl Start {
                 // To avoid ambiguity, we define the first few nodes to be the trusted validators to connect to.
                 for i in 0..2 {
                     if i != dev {
-                        trusted_validators.push(SocketAddr::from_str(&format!("127.0.0.1:{}", MEMORY_POOL_PORT + i))?);
+                        trusted_validators.push(SocketAddr::new(loopback_ip, MEMORY_POOL_PORT + i));
                     }
                 }
             }
@@ -309,14 +473,14 @@ impl Start {
             //
             // Note: the `node` flag is an option to detect remote devnet testing.
             if self.node.is_none() {
-                self.node = Some(SocketAddr::from_str(&format!("0.0.0.0:{}", 4130 + dev))?);
+                self.node = Some(SocketAddr::new(unspecified_ip, 4130 + dev));
             }
             // If the `norest` flag is not set, and the `bft` flag was not overridden,
             // then set the REST IP to `3030 + dev`.
             //
             // Note: the `bft` flag is an option to detect remote devnet testing.
             if !self.norest && self.bft.is_none() {
-                self.rest = SocketAddr::from_str(&format!("0.0.0.0:{}", 3030 + dev))?;
+                self.rest = SocketAddr::new(unspecified_ip, 3030 + dev);
             }
         }
         Ok(())
@@ -453,6 +617,15 @@ impl Start {
         }
     }
 
+    /// Returns the rotation policy of the logfile, from the given configurations.
+    fn parse_log_rotation(&self) -> LogRotation {
+        LogRotation {
+            max_size: self.log_max_size.map(|max_size| max_size.saturating_mul(1024 * 1024)),
+            max_age: self.log_max_age.map(|max_age| Duration::from_secs(max_age.saturating_mul(3600))),
+            retention: self.log_retention,
+        }
+    }
+
     /// Returns the node type, from the given configurations.
     const fn parse_node_type(&self) -> NodeType {
         if self.validator {
@@ -482,20 +655,27 @@ impl Start {
 
         // Parse the genesis block.
         let genesis = self.parse_genesis::<N>()?;
-        // Parse the private key of the node.
-        let account = self.parse_private_key::<N>()?;
+        // Parse the signer of the node, which holds the private key unless it is a signer process.
+        let signer = self.parse_signer::<N>()?;
+        // Zeroize the private key, as it is no longer needed.
+        self.private_key.zeroize();
         // Parse the node type.
         let node_type = self.parse_node_type();
 
         // Parse the node IP.
         let node_ip = match self.node {
             Some(node_ip) => node_ip,
-            None => SocketAddr::from_str("0.0.0.0:4130").unwrap(),
+            // Listen on both IPv4 and IPv6, if available.
+            None => SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 4130),
         };
         // Parse the BFT IP.
-        let bft_ip = match self.dev.is_some() {
-            true => self.bft,
-            false => None
+        let bft_ip = match self.dev {
+            // In development mode over IPv6, listen on the IPv6 loopback, which the other development nodes dial.
+            Some(dev) if self.bft.is_none() && self.prefer_ip_family == Some(IpFamily::Ipv6) => {
+                Some(SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), MEMORY_POOL_PORT + dev))
+            }
+            Some(_) => self.bft,
+            None => None,
         };
         // Parse the REST IP.
         let rest_ip = match self.norest {
@@ -506,7 +686,7 @@ impl Start {
         // If the display is not enabled, render the welcome message.
         if self.nodisplay {
             // Print the Aleo address.
-            println!("👛 Your Aleo address is {}.\n", account.address().to_string().bold());
+            println!("👛 Your Aleo address is {}.\n", signer.address().to_string().bold());
             // Print the node type and network.
             println!(
                 "🧭 Starting {} on {} at {}.\n",
@@ -516,11 +696,11 @@ impl Start {
             );
 
             // If the node is running a REST server, print the REST IP and JWT.
-            if node_type.is_validator() {
+            if node_type.is_validator() || node_type.is_prover() {
                 if let Some(rest_ip) = rest_ip {
                     println!("🌐 Starting the REST server at {}.\n", rest_ip.to_string().bold());
 
-                    if let Ok(jwt_token) = snarkos_node_rest::Claims::new(account.address()).to_jwt_string() {
+                    if let Ok(jwt_token) = snarkos_node_rest::Claims::new(signer.address()).to_jwt_string() {
                         println!("🔑 Your one-time JWT token is {}\n", jwt_token.dimmed());
                     }
                 }
@@ -558,11 +738,33 @@ impl Start {
             }
         };
 
+        // Initialize the memory pool configurations.
+        let memory_pool = MemoryPoolConfig {
+            max_deployments: self.mempool_deployments,
+            max_executions: self.mempool_executions,
+            max_transactions_per_sender: self.mempool_per_sender,
+            max_private_fee_transactions: self.mempool_private_fee,
+        };
+        // Initialize the limits on inbound requests.
+        let inbound_limits = self.parse_inbound_limits()?;
+        // Initialize the limits on outbound messages.
+        let outbound_limits = self.parse_outbound_limits()?;
+        // Initialize the pool configuration.
+        let pool = match (self.pool, &self.pool_workers) {
+            (Some(pool_ip), Some(path)) => {
+                check_permissions(path)?;
+                Some(PoolConfig::load(pool_ip, path)?)
+            }
+            _ => None,
+        };
+
         // Initialize the node.
-        match node_type {
-            NodeType::Validator => Node::new_validator(node_ip, bft_ip, rest_ip, self.rest_rps, account, &trusted_peers, &trusted_validators, genesis, cdn, storage_mode, self.allow_external_peers, dev_txs).await,
-            NodeType::Prover => Node::new_prover(node_ip, account, &trusted_peers, genesis, storage_mode).await,
-            NodeType::Client => Node::new_client(node_ip, rest_ip, self.rest_rps, account, &trusted_peers, genesis, cdn, storage_mode).await,
+        match (node_type, signer) {
+            (NodeType::Validator, signer) => Node::new_validator(node_ip, bft_ip, rest_ip, self.rest_rps, signer, &trusted_peers, &trusted_validators, genesis, cdn, storage_mode, self.allow_external_peers, dev_txs, memory_pool, self.prefer_ip_family, inbound_limits, outbound_limits).await,
+            (NodeType::Prover, Signer::Local(account)) => Node::new_prover(node_ip, rest_ip, self.rest_rps, account, &trusted_peers, genesis, storage_mode, pool, self.prefer_ip_family, inbound_limits, outbound_limits).await,
+            (NodeType::Client, Signer::Local(account)) => Node::new_client(node_ip, rest_ip, self.rest_rps, account, &trusted_peers, genesis, cdn, storage_mode, self.prefer_ip_family, inbound_limits, outbound_limits).await,
+            #[cfg(target_family = "unix")]
+            (_, Signer::Remote(..)) => bail!("The '--signer' flag is only supported for validators"),
         }
     }
 
@@ -595,7 +797,7 @@ impl Start {
     }
 }
 
-fn check_permissions(path: &PathBuf) -> Result<(), snarkvm::prelude::Error> {
+pub(crate) fn check_permissions(path: &PathBuf) -> Result<(), snarkvm::prelude::Error> {
     #[cfg(target_family = "unix")]
     {
         use std::os::unix::fs::PermissionsExt;
@@ -730,6 +932,59 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_parse_inbound_limits() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        let limits = config.parse_inbound_limits().unwrap();
+        assert_eq!(limits.connection, RateLimit::new(Some(200), None));
+        assert!(limits.global.is_unlimited());
+        assert!(limits.requests.is_empty());
+
+        let config = Start::try_parse_from(
+            [
+                "snarkos",
+                "--peer-rate-limit",
+                "100:1048576",
+                "--global-rate-limit",
+                "1000",
+                "--request-rate-limits",
+                "BlockRequest=20,PeerRequest=0",
+            ]
+            .iter(),
+        )
+        .unwrap();
+        let limits = config.parse_inbound_limits().unwrap();
+        assert_eq!(limits.connection, RateLimit::new(Some(100), Some(1_048_576)));
+        assert_eq!(limits.global, RateLimit::new(Some(1000), None));
+        assert_eq!(limits.request_limit("BlockRequest", RateLimit::default()), RateLimit::new(Some(20), None));
+        assert!(limits.request_limit("PeerRequest", RateLimit::new(Some(1), None)).is_unlimited());
+        assert_eq!(limits.request_limit("Ping", RateLimit::new(Some(1), None)), RateLimit::new(Some(1), None));
+
+        for limits in ["BlockRequest", "BlockRequest=", "UnknownRequest=1", "BlockRequest=1:2:3"] {
+            let config = Start::try_parse_from(["snarkos", "--request-rate-limits", limits].iter()).unwrap();
+            assert!(config.parse_inbound_limits().is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_outbound_limits() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        let limits = config.parse_outbound_limits().unwrap();
+        assert!(limits.connection.is_unlimited());
+        assert!(limits.global.is_unlimited());
+
+        let config = Start::try_parse_from(
+            ["snarkos", "--peer-outbound-rate-limit", "50", "--global-outbound-rate-limit", "500:10485760"].iter(),
+        )
+        .unwrap();
+        let limits = config.parse_outbound_limits().unwrap();
+        assert_eq!(limits.connection, RateLimit::new(Some(50), None));
+        assert_eq!(limits.global, RateLimit::new(Some(500), Some(10_485_760)));
+
+        let config = Start::try_parse_from(["snarkos", "--peer-outbound-rate-limit", "fast"].iter()).unwrap();
+        assert!(config.parse_outbound_limits().is_err());
+    }
+
     #[test]
     fn test_parse_cdn() {
         // Validator (Prod)
@@ -900,6 +1155,21 @@ mod tests {
         assert_eq!(genesis, expected_genesis);
     }
 
+    #[test]
+    fn test_parse_development_ipv6() {
+        let mut trusted_peers = vec![];
+        let mut trusted_validators = vec![];
+        let mut config = Start::try_parse_from(["snarkos", "--dev", "1", "--prefer-ip-family", "ipv6"].iter()).unwrap();
+        config.parse_development(&mut trusted_peers, &mut trusted_validators).unwrap();
+        assert_eq!(config.node, Some(SocketAddr::from_str("[::]:4131").unwrap()));
+        assert_eq!(config.rest, SocketAddr::from_str("[::]:3031").unwrap());
+        assert_eq!(trusted_peers, vec![SocketAddr::from_str("[::1]:4130").unwrap()]);
+        assert_eq!(trusted_validators, vec![SocketAddr::from_str(&format!("[::1]:{MEMORY_POOL_PORT}")).unwrap()]);
+
+        // Ensure an invalid IP family is rejected.
+        Start::try_parse_from(["snarkos", "--prefer-ip-family", "ipv5"].iter()).unwrap_err();
+    }
+
     #[test]
     fn clap_snarkos_start() {
         let arg_vec = vec![
@@ -919,6 +1189,12 @@ mod tests {
             "IP1,IP2,IP3",
             "--rest",
             "127.0.0.1:3030",
+            "--log-format",
+            "json",
+            "--log-max-size",
+            "100",
+            "--log-max-age",
+            "24",
         ];
         let cli = CLI::parse_from(arg_vec);
 
@@ -932,6 +1208,12 @@ mod tests {
             assert_eq!(start.network, 0);
             assert_eq!(start.peers, "IP1,IP2,IP3");
             assert_eq!(start.validators, "IP1,IP2,IP3");
+            assert_eq!(start.log_format, LogFormat::Json);
+            assert_eq!(start.parse_log_rotation(), LogRotation {
+                max_size: Some(100 * 1024 * 1024),
+                max_age: Some(Duration::from_secs(24 * 3600)),
+                retention: 5,
+            });
         } else {
             panic!("Unexpected result of clap parsing!");
         }
@@ -0,0 +1,123 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_account::Keystore;
+
+use anyhow::{bail, ensure, Result};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+    tty::IsTty,
+};
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+use zeroize::Zeroizing;
+
+/// The environment variable that may hold the keystore password.
+pub const KEYSTORE_PASSWORD_ENV: &str = "SNARKOS_KEYSTORE_PASSWORD";
+/// The maximum length of a secret read from a prompt, in bytes.
+const MAX_SECRET_LENGTH: usize = 1024;
+
+/// Returns the keystore password from the given file, the environment, or a prompt, in that order.
+/// If `confirm` is set, the prompt asks for the password twice.
+pub fn read_keystore_password(password_file: Option<&Path>, confirm: bool) -> Result<Zeroizing<String>> {
+    // Read the password from the file, ignoring the trailing newline.
+    if let Some(path) = password_file {
+        let contents = Zeroizing::new(std::fs::read_to_string(path)?);
+        return Ok(Zeroizing::new(contents.trim_end_matches(['\r', '\n']).to_string()));
+    }
+    // Read the password from the environment.
+    if let Ok(password) = std::env::var(KEYSTORE_PASSWORD_ENV) {
+        return Ok(Zeroizing::new(password));
+    }
+    // Prompt for the password.
+    ensure!(
+        io::stdin().is_tty(),
+        "Missing the keystore password, please provide a password file or set '{KEYSTORE_PASSWORD_ENV}'"
+    );
+    let password = prompt_secret("Enter the keystore password: ")?;
+    if confirm {
+        let confirmation = prompt_secret("Confirm the keystore password: ")?;
+        ensure!(password == confirmation, "The keystore passwords do not match");
+    }
+    Ok(password)
+}
+
+/// Prompts for a secret on the terminal, without echoing it.
+pub fn prompt_secret(prompt: &str) -> Result<Zeroizing<String>> {
+    eprint!("{prompt}");
+    io::stderr().flush()?;
+
+    terminal::enable_raw_mode()?;
+    let secret = read_secret();
+    terminal::disable_raw_mode()?;
+    eprintln!();
+    secret
+}
+
+/// Reads a secret from the terminal in raw mode, until the enter key is pressed.
+/// Note: The buffer is preallocated and never grows, so that no copy of the secret is left unzeroized.
+fn read_secret() -> Result<Zeroizing<String>> {
+    let mut secret = Zeroizing::new(String::with_capacity(MAX_SECRET_LENGTH));
+    loop {
+        if let Event::Key(KeyEvent { code, modifiers, kind: KeyEventKind::Press, .. }) = event::read()? {
+            match code {
+                KeyCode::Enter => return Ok(secret),
+                KeyCode::Backspace => {
+                    secret.pop();
+                }
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => bail!("Interrupted"),
+                KeyCode::Char(character) => {
+                    ensure!(secret.len() + character.len_utf8() <= MAX_SECRET_LENGTH, "The secret is too long");
+                    secret.push(character)
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Writes the keystore to the given path, readable only by the owner.
+pub fn write_keystore(keystore: &Keystore, path: &Path) -> Result<()> {
+    ensure!(!path.exists(), "The keystore file '{}' already exists", path.display());
+
+    let mut options = File::options();
+    options.write(true).create_new(true);
+    #[cfg(target_family = "unix")]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(keystore.to_string().as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_keystore_password_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("password");
+        std::fs::write(&path, "password\n").unwrap();
+        // The file takes precedence, and the trailing newline is ignored.
+        let password = read_keystore_password(Some(&path), true).unwrap();
+        assert_eq!(password.as_str(), "password");
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parking_lot::Mutex;
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+/// The policy for rotating the log file.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogRotation {
+    /// The maximum size of the log file in bytes, before it is rotated.
+    pub max_size: Option<u64>,
+    /// The maximum age of the log file, before it is rotated.
+    pub max_age: Option<Duration>,
+    /// The number of rotated log files to retain.
+    pub retention: usize,
+}
+
+impl LogRotation {
+    /// Returns `true` if the log file is never rotated.
+    pub const fn is_disabled(&self) -> bool {
+        self.max_size.is_none() && self.max_age.is_none()
+    }
+}
+
+/// A log file that is rotated once it exceeds the size or age of its rotation policy.
+///
+/// Rotated files are renamed to `<logfile>.1`, `<logfile>.2`, etc., where `<logfile>.1` is the most recent,
+/// and only the configured number of rotated files is retained.
+#[derive(Clone)]
+pub struct RotatingFile(Arc<Mutex<RotatingFileInner>>);
+
+struct RotatingFileInner {
+    /// The path of the active log file.
+    path: PathBuf,
+    /// The rotation policy.
+    policy: LogRotation,
+    /// The active log file.
+    file: File,
+    /// The size of the active log file in bytes.
+    size: u64,
+    /// The time at which the active log file was started.
+    started_at: SystemTime,
+}
+
+impl RotatingFile {
+    /// Opens (or creates) the log file at the given path, appending to it until it is rotated.
+    pub fn open<P: AsRef<Path>>(path: P, policy: LogRotation) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::options().append(true).create(true).open(&path)?;
+        let metadata = file.metadata()?;
+        let started_at = metadata.created().unwrap_or_else(|_| SystemTime::now());
+        Ok(Self(Arc::new(Mutex::new(RotatingFileInner { path, policy, file, size: metadata.len(), started_at }))))
+    }
+}
+
+impl RotatingFileInner {
+    /// Returns `true` if the active log file should be rotated before writing the given number of bytes.
+    fn should_rotate(&self, num_bytes: usize) -> bool {
+        // Never rotate an empty file, so that a single large entry cannot cause a rotation loop.
+        if self.size == 0 {
+            return false;
+        }
+        let exceeds_size = self.policy.max_size.is_some_and(|max_size| self.size + num_bytes as u64 > max_size);
+        let exceeds_age = self
+            .policy
+            .max_age
+            .is_some_and(|max_age| self.started_at.elapsed().is_ok_and(|elapsed| elapsed >= max_age));
+        exceeds_size || exceeds_age
+    }
+
+    /// Returns the path of the rotated log file with the given index.
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    /// Rotates the active log file, and removes the rotated files beyond the retention.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        // Remove the oldest rotated file, if it would exceed the retention.
+        let oldest = self.rotated_path(self.policy.retention.max(1));
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        // Shift the remaining rotated files by one.
+        for index in (1..self.policy.retention).rev() {
+            let source = self.rotated_path(index);
+            if source.exists() {
+                fs::rename(&source, self.rotated_path(index + 1))?;
+            }
+        }
+        // Move the active log file out of the way, unless no rotated files are retained.
+        match self.policy.retention {
+            0 => fs::remove_file(&self.path)?,
+            _ => fs::rename(&self.path, self.rotated_path(1))?,
+        }
+        // Start a new log file.
+        self.file = File::options().append(true).create(true).open(&self.path)?;
+        self.size = 0;
+        self.started_at = SystemTime::now();
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut inner = self.0.lock();
+        // Note: The formatter writes each log entry at once, so entries are never split across files.
+        if inner.should_rotate(buf.len()) {
+            inner.rotate()?;
+        }
+        let num_bytes = inner.file.write(buf)?;
+        inner.size += num_bytes as u64;
+        Ok(num_bytes)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snarkos.log");
+        let policy = LogRotation { max_size: Some(10), max_age: None, retention: 2 };
+        let mut file = RotatingFile::open(&path, policy).unwrap();
+
+        for entry in ["aaaaaaaa\n", "bbbbbbbb\n", "cccccccc\n", "dddddddd\n"] {
+            file.write_all(entry.as_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+
+        // Only the two most recent rotated files are retained.
+        assert_eq!(fs::read_to_string(&path).unwrap(), "dddddddd\n");
+        assert_eq!(fs::read_to_string(dir.path().join("snarkos.log.1")).unwrap(), "cccccccc\n");
+        assert_eq!(fs::read_to_string(dir.path().join("snarkos.log.2")).unwrap(), "bbbbbbbb\n");
+        assert!(!dir.path().join("snarkos.log.3").exists());
+    }
+
+    #[test]
+    fn test_rotate_by_age() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snarkos.log");
+        let policy = LogRotation { max_size: None, max_age: Some(Duration::ZERO), retention: 1 };
+        let mut file = RotatingFile::open(&path, policy).unwrap();
+
+        // The first entry is written to the empty file, and every subsequent entry starts a new file.
+        file.write_all(b"first\n").unwrap();
+        file.write_all(b"second\n").unwrap();
+        file.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+        assert_eq!(fs::read_to_string(dir.path().join("snarkos.log.1")).unwrap(), "first\n");
+    }
+
+    #[test]
+    fn test_no_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snarkos.log");
+        let mut file = RotatingFile::open(&path, LogRotation::default()).unwrap();
+
+        file.write_all(b"first\n").unwrap();
+        file.write_all(b"second\n").unwrap();
+        file.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\nsecond\n");
+        assert!(!dir.path().join("snarkos.log.1").exists());
+    }
+}
@@ -12,17 +12,53 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::LogWriter;
+use crate::helpers::{LogRotation, LogWriter, RotatingFile};
+use snarkos_node::tcp::CONTEXT_TARGET;
+use snarkos_node_rest::LogVerbosity;
 
+use anyhow::{ensure, Result};
+use clap::ValueEnum;
 use crossterm::tty::IsTty;
-use std::{fs::File, io, path::Path};
+use serde::Serialize;
+use std::{
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicU8, Ordering},
+        Arc,
+    },
+};
 use tokio::sync::mpsc;
+use tracing::{Event, Subscriber};
 use tracing_subscriber::{
+    fmt::{
+        self,
+        format::{self, FormatEvent, FormatFields},
+        writer::BoxMakeWriter,
+        FmtContext,
+    },
     layer::{Layer, SubscriberExt},
+    registry::LookupSpan,
+    reload,
     util::SubscriberInitExt,
     EnvFilter,
+    Registry,
 };
 
+/// The maximum verbosity of the logger.
+pub const MAX_VERBOSITY: u8 = 6;
+
+/// The format of the logs.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// One JSON object per line, with the target and span context as fields.
+    Json,
+}
+
 /// Initializes the logger.
 ///
 /// ```ignore
@@ -34,56 +70,15 @@ use tracing_subscriber::{
 /// 5 => info, debug, trace, snarkos_node_router=trace
 /// 6 => info, debug, trace, snarkos_node_tcp=trace
 /// ```
-pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile: P) -> mpsc::Receiver<Vec<u8>> {
-    match verbosity {
-        0 => std::env::set_var("RUST_LOG", "info"),
-        1 => std::env::set_var("RUST_LOG", "debug"),
-        2.. => std::env::set_var("RUST_LOG", "trace"),
-    };
-
-    // Filter out undesirable logs. (unfortunately EnvFilter cannot be cloned)
-    let [filter, filter2] = std::array::from_fn(|_| {
-        let filter = EnvFilter::from_default_env()
-            .add_directive("mio=off".parse().unwrap())
-            .add_directive("tokio_util=off".parse().unwrap())
-            .add_directive("hyper=off".parse().unwrap())
-            .add_directive("reqwest=off".parse().unwrap())
-            .add_directive("want=off".parse().unwrap())
-            .add_directive("warp=off".parse().unwrap());
-
-        let filter = if verbosity >= 2 {
-            filter.add_directive("snarkos_node_sync=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_sync=debug".parse().unwrap())
-        };
-
-        let filter = if verbosity >= 3 {
-            filter
-                .add_directive("snarkos_node_bft=trace".parse().unwrap())
-                .add_directive("snarkos_node_bft::gateway=debug".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_bft=debug".parse().unwrap())
-        };
-
-        let filter = if verbosity >= 4 {
-            filter.add_directive("snarkos_node_bft::gateway=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_bft::gateway=debug".parse().unwrap())
-        };
-
-        let filter = if verbosity >= 5 {
-            filter.add_directive("snarkos_node_router=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_router=debug".parse().unwrap())
-        };
-
-        if verbosity >= 6 {
-            filter.add_directive("snarkos_node_tcp=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_tcp=off".parse().unwrap())
-        }
-    });
-
+///
+/// The verbosity can be adjusted at runtime via the REST API.
+pub fn initialize_logger<P: AsRef<Path>>(
+    verbosity: u8,
+    nodisplay: bool,
+    logfile: P,
+    format: LogFormat,
+    rotation: LogRotation,
+) -> mpsc::Receiver<Vec<u8>> {
     // Create the directories tree for a logfile if it doesn't exist.
     let logfile_dir = logfile.as_ref().parent().expect("Root directory passed as a logfile");
     if !logfile_dir.exists() {
@@ -91,8 +86,7 @@ pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile
             .expect("Failed to create a directories: '{logfile_dir}', please check if user has permissions");
     }
     // Create a file to write logs to.
-    let logfile =
-        File::options().append(true).create(true).open(logfile).expect("Failed to open the file for writing logs");
+    let logfile = RotatingFile::open(logfile, rotation).expect("Failed to open the file for writing logs");
 
     // Initialize the log channel.
     let (log_sender, log_receiver) = mpsc::channel(1024);
@@ -103,29 +97,170 @@ pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile
         false => Some(log_sender),
     };
 
+    // Note: The display renders text, so the JSON format only applies to stdout if the display is disabled.
+    let is_stdout_json = format == LogFormat::Json && log_sender.is_none();
+    let is_stdout_ansi = log_sender.is_none() && io::stdout().is_tty();
+    let is_logfile_json = format == LogFormat::Json;
+
+    // Determine whether the text format renders the event targets, which depends on the verbosity.
+    let with_target = Arc::new(AtomicBool::new(verbosity > 2));
+
+    // Add a layer using LogWriter for stdout / terminal.
+    let stdout = BoxMakeWriter::new(move || LogWriter::new(&log_sender));
+    let (stdout_layer, stdout_handle) =
+        log_layer(stdout, verbosity, is_stdout_json, is_stdout_ansi, with_target.clone());
+    // Add a layer redirecting logs to the file.
+    let logfile = BoxMakeWriter::new(move || logfile.clone());
+    let (logfile_layer, logfile_handle) = log_layer(logfile, verbosity, is_logfile_json, false, with_target.clone());
+
     // Initialize tracing.
-    let _ = tracing_subscriber::registry()
-        .with(
-            // Add layer using LogWriter for stdout / terminal
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(log_sender.is_none() && io::stdout().is_tty())
-                .with_writer(move || LogWriter::new(&log_sender))
-                .with_target(verbosity > 2)
-                .with_filter(filter),
-        )
-        .with(
-            // Add layer redirecting logs to the file
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(false)
-                .with_writer(logfile)
-                .with_target(verbosity > 2)
-                .with_filter(filter2),
-        )
-        .try_init();
+    if tracing_subscriber::registry().with(vec![stdout_layer, logfile_layer]).try_init().is_ok() {
+        // Allow the verbosity to be adjusted at runtime.
+        snarkos_node_rest::register_logger(Logger {
+            verbosity: AtomicU8::new(verbosity),
+            with_target,
+            filters: vec![(stdout_handle, is_stdout_json), (logfile_handle, is_logfile_json)],
+        });
+    }
 
     log_receiver
 }
 
+/// The logger, whose verbosity can be adjusted at runtime.
+struct Logger {
+    /// The current verbosity.
+    verbosity: AtomicU8,
+    /// Whether the text format renders the event targets.
+    with_target: Arc<AtomicBool>,
+    /// The handles of the reloadable filters, along with whether their layer is in the JSON format.
+    filters: Vec<(reload::Handle<EnvFilter, Registry>, bool)>,
+}
+
+impl LogVerbosity for Logger {
+    /// Returns the current verbosity of the logger.
+    fn verbosity(&self) -> u8 {
+        self.verbosity.load(Ordering::SeqCst)
+    }
+
+    /// Applies the given verbosity to the filters and the text format of the logger.
+    fn set_verbosity(&self, verbosity: u8) -> Result<()> {
+        ensure!(verbosity <= MAX_VERBOSITY, "Invalid verbosity {verbosity} (maximum is {MAX_VERBOSITY})");
+        for (handle, is_json) in &self.filters {
+            handle.reload(log_filter(verbosity, *is_json))?;
+        }
+        self.with_target.store(verbosity > 2, Ordering::Relaxed);
+        self.verbosity.store(verbosity, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// The text format of the logs, which renders the event targets only if the flag is set.
+/// Note: The flag follows the verbosity, which may be adjusted at runtime.
+struct TextFormat {
+    /// Whether to render the event targets.
+    with_target: Arc<AtomicBool>,
+}
+
+impl<S, N> FormatEvent<S, N> for TextFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        writer: format::Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        fmt::format().with_target(self.with_target.load(Ordering::Relaxed)).format_event(ctx, writer, event)
+    }
+}
+
+/// Returns a log layer for the given writer, with a filter that can be reloaded at runtime.
+fn log_layer(
+    writer: BoxMakeWriter,
+    verbosity: u8,
+    is_json: bool,
+    is_ansi: bool,
+    with_target: Arc<AtomicBool>,
+) -> (Box<dyn Layer<Registry> + Send + Sync>, reload::Handle<EnvFilter, Registry>) {
+    let (filter, handle) = reload::Layer::new(log_filter(verbosity, is_json));
+    let layer = match is_json {
+        true => fmt::Layer::default()
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_ansi(false)
+            .with_writer(writer)
+            .with_filter(filter)
+            .boxed(),
+        false => fmt::Layer::default()
+            .with_ansi(is_ansi)
+            .with_writer(writer)
+            .event_format(TextFormat { with_target })
+            .with_filter(filter)
+            .boxed(),
+    };
+    (layer, handle)
+}
+
+/// Returns the log filter for the given verbosity.
+fn log_filter(verbosity: u8, is_json: bool) -> EnvFilter {
+    let filter = match verbosity {
+        0 => EnvFilter::new("info"),
+        1 => EnvFilter::new("debug"),
+        2.. => EnvFilter::new("trace"),
+    };
+
+    // Filter out undesirable logs.
+    let filter = filter
+        .add_directive("mio=off".parse().unwrap())
+        .add_directive("tokio_util=off".parse().unwrap())
+        .add_directive("hyper=off".parse().unwrap())
+        .add_directive("reqwest=off".parse().unwrap())
+        .add_directive("want=off".parse().unwrap())
+        .add_directive("warp=off".parse().unwrap());
+
+    let filter = if verbosity >= 2 {
+        filter.add_directive("snarkos_node_sync=trace".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_sync=debug".parse().unwrap())
+    };
+
+    let filter = if verbosity >= 3 {
+        filter
+            .add_directive("snarkos_node_bft=trace".parse().unwrap())
+            .add_directive("snarkos_node_bft::gateway=debug".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_bft=debug".parse().unwrap())
+    };
+
+    let filter = if verbosity >= 4 {
+        filter.add_directive("snarkos_node_bft::gateway=trace".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_bft::gateway=debug".parse().unwrap())
+    };
+
+    let filter = if verbosity >= 5 {
+        filter.add_directive("snarkos_node_router=trace".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_router=debug".parse().unwrap())
+    };
+
+    let filter = if verbosity >= 6 {
+        filter.add_directive("snarkos_node_tcp=trace".parse().unwrap())
+    } else {
+        filter.add_directive("snarkos_node_tcp=off".parse().unwrap())
+    };
+
+    // Only the JSON format records the context spans.
+    match is_json {
+        true => filter.add_directive(format!("{CONTEXT_TARGET}=info").parse().unwrap()),
+        false => filter.add_directive(format!("{CONTEXT_TARGET}=off").parse().unwrap()),
+    }
+}
+
 /// Returns the welcome message as a string.
 pub fn welcome_message() -> String {
     use colored::Colorize;
@@ -151,3 +286,38 @@ pub fn welcome_message() -> String {
     output += &"👋 Welcome to Aleo! We thank you for running a node and supporting privacy.\n".bold();
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_filter() {
+        for verbosity in 0..=MAX_VERBOSITY {
+            // Ensure every directive is valid, in both formats.
+            let text = log_filter(verbosity, false).to_string().to_lowercase();
+            let json = log_filter(verbosity, true).to_string().to_lowercase();
+            assert!(text.contains(&format!("{CONTEXT_TARGET}=off")));
+            assert!(json.contains(&format!("{CONTEXT_TARGET}=info")));
+        }
+    }
+
+    #[test]
+    fn test_set_verbosity() {
+        let (_filter, handle) = reload::Layer::<_, Registry>::new(log_filter(0, false));
+        let logger =
+            Logger { verbosity: AtomicU8::new(0), with_target: Default::default(), filters: vec![(handle, false)] };
+
+        // Ensure the event targets are rendered above a verbosity of 2 only.
+        logger.set_verbosity(3).unwrap();
+        assert_eq!(logger.verbosity(), 3);
+        assert!(logger.with_target.load(Ordering::Relaxed));
+        logger.set_verbosity(1).unwrap();
+        assert_eq!(logger.verbosity(), 1);
+        assert!(!logger.with_target.load(Ordering::Relaxed));
+
+        // Ensure an invalid verbosity is not applied.
+        assert!(logger.set_verbosity(MAX_VERBOSITY + 1).is_err());
+        assert_eq!(logger.verbosity(), 1);
+    }
+}
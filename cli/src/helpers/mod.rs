@@ -15,6 +15,12 @@
 mod bech32m;
 pub use bech32m::*;
 
+mod keystore;
+pub use keystore::*;
+
+mod log_rotation;
+pub use log_rotation::*;
+
 mod log_writer;
 use log_writer::*;
 
@@ -52,6 +52,8 @@ pub struct Display<N: Network> {
     tick_rate: Duration,
     /// The state of the tabs.
     tabs: Tabs,
+    /// The blocks tab.
+    blocks: Blocks,
     /// The logs tab.
     logs: Logs,
 }
@@ -70,7 +72,8 @@ impl<N: Network> Display<N> {
         let mut display = Self {
             node,
             tick_rate: Duration::from_secs(1),
-            tabs: Tabs::new(PAGES.to_vec()),
+            tabs: Tabs::new(Page::ALL.iter().map(Page::title).collect()),
+            blocks: Blocks::new(),
             logs: Logs::new(log_receiver),
         };
 
@@ -103,6 +106,7 @@ impl<N: Network> Display<N> {
 
             if event::poll(timeout)? {
                 if let Event::Key(key) = event::read()? {
+                    let page = Page::from_index(self.tabs.index);
                     match key.code {
                         KeyCode::Esc => {
                             // // TODO (howardwu): @ljedrz to implement a wrapping scope for Display within Node/Server.
@@ -114,6 +118,12 @@ impl<N: Network> Display<N> {
                         }
                         KeyCode::Left => self.tabs.previous(),
                         KeyCode::Right => self.tabs.next(),
+                        // Scroll the blocks tab.
+                        KeyCode::Up if page == Page::Blocks => self.blocks.scroll_up(1),
+                        KeyCode::Down if page == Page::Blocks => self.blocks.scroll_down(1),
+                        KeyCode::PageUp if page == Page::Blocks => self.blocks.scroll_up(PAGE_SIZE),
+                        KeyCode::PageDown if page == Page::Blocks => self.blocks.scroll_down(PAGE_SIZE),
+                        KeyCode::Home if page == Page::Blocks => self.blocks.scroll_to_latest(),
                         _ => {}
                     }
                 }
@@ -169,10 +179,11 @@ impl<N: Network> Display<N> {
         /* Pages */
 
         // Initialize the page.
-        match self.tabs.index {
-            0 => Overview.draw(f, chunks[1], &self.node),
-            1 => self.logs.draw(f, chunks[1]),
-            _ => unreachable!(),
+        match Page::from_index(self.tabs.index) {
+            Page::Overview => Overview.draw(f, chunks[1], &self.node),
+            Page::Peers => Peers.draw(f, chunks[1], &self.node),
+            Page::Blocks => self.blocks.draw(f, chunks[1], &self.node),
+            Page::Logs => self.logs.draw(f, chunks[1]),
         };
     }
 }
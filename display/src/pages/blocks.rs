@@ -0,0 +1,98 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node::Node;
+use snarkvm::prelude::Network;
+
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Paragraph, Row, Table},
+    Frame,
+};
+
+/// The number of blocks to scroll by a page.
+pub(crate) const PAGE_SIZE: u32 = 10;
+
+pub(crate) struct Blocks {
+    /// The number of blocks between the latest block and the first block shown.
+    offset: u32,
+}
+
+impl Blocks {
+    pub(crate) fn new() -> Self {
+        Self { offset: 0 }
+    }
+
+    /// Scrolls towards the latest block.
+    pub(crate) fn scroll_up(&mut self, num_blocks: u32) {
+        self.offset = self.offset.saturating_sub(num_blocks);
+    }
+
+    /// Scrolls towards the genesis block.
+    pub(crate) fn scroll_down(&mut self, num_blocks: u32) {
+        self.offset = self.offset.saturating_add(num_blocks);
+    }
+
+    /// Scrolls back to the latest block.
+    pub(crate) fn scroll_to_latest(&mut self) {
+        self.offset = 0;
+    }
+
+    pub(crate) fn draw<N: Network>(&mut self, f: &mut Frame, area: Rect, node: &Node<N>) {
+        let Some(ledger) = node.ledger() else {
+            let paragraph = Paragraph::new("The block list is not available on provers, as they do not keep a ledger.")
+                .block(Block::default().borders(Borders::ALL).title("Blocks"));
+            f.render_widget(paragraph, area);
+            return;
+        };
+
+        // Determine the range of blocks to show, leaving room for the borders and the header.
+        let latest_height = ledger.latest_height();
+        self.offset = self.offset.min(latest_height);
+        let end_height = latest_height - self.offset;
+        let num_rows = u32::from(area.height.saturating_sub(3)).max(1);
+        let start_height = end_height.saturating_sub(num_rows - 1);
+
+        let rows = (start_height..=end_height)
+            .rev()
+            .filter_map(|height| {
+                let hash = ledger.get_hash(height).ok()?;
+                let header = ledger.get_header(height).ok()?;
+                Some(Row::new(vec![
+                    height.to_string(),
+                    hash.to_string(),
+                    header.round().to_string(),
+                    header.timestamp().to_string(),
+                    header.coinbase_target().to_string(),
+                    header.proof_target().to_string(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+
+        let widths = [
+            Constraint::Length(10),
+            Constraint::Length(62),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(20),
+            Constraint::Length(20),
+        ];
+        let header = Row::new(vec!["Height", "Hash", "Round", "Timestamp", "Coinbase Target", "Proof Target"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let title = format!("Blocks ({start_height} to {end_height} of {latest_height})");
+        let table = Table::new(rows, widths).header(header).block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(table, area);
+    }
+}
@@ -12,10 +12,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod blocks;
+pub(crate) use blocks::{Blocks, PAGE_SIZE};
+
 mod logs;
 pub(crate) use logs::Logs;
 
 mod overview;
 pub(crate) use overview::Overview;
 
-pub(crate) const PAGES: [&str; 2] = [" Overview ", " Logs "];
+mod peers;
+pub(crate) use peers::Peers;
+
+/// A page of the display.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Page {
+    Overview,
+    Peers,
+    Blocks,
+    Logs,
+}
+
+impl Page {
+    /// The pages, in the order of their tabs.
+    pub(crate) const ALL: [Self; 4] = [Self::Overview, Self::Peers, Self::Blocks, Self::Logs];
+
+    /// Returns the page of the tab at the given index.
+    pub(crate) const fn from_index(index: usize) -> Self {
+        Self::ALL[index]
+    }
+
+    /// Returns the title of the page's tab.
+    pub(crate) const fn title(&self) -> &'static str {
+        match self {
+            Self::Overview => " Overview ",
+            Self::Peers => " Peers ",
+            Self::Blocks => " Blocks ",
+            Self::Logs => " Logs ",
+        }
+    }
+}
@@ -12,40 +12,168 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use snarkos_node::Node;
+use snarkos_node::{consensus::Consensus, Node};
 use snarkvm::prelude::Network;
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    text::Span,
-    widgets::{canvas::Canvas, Block, Borders},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table},
     Frame,
 };
 
 pub(crate) struct Overview;
 
 impl Overview {
-    pub(crate) fn draw<N: Network>(&self, f: &mut Frame, area: Rect, _node: &Node<N>) {
+    pub(crate) fn draw<N: Network>(&self, f: &mut Frame, area: Rect, node: &Node<N>) {
         // Initialize the layout of the page.
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(10), Constraint::Percentage(70), Constraint::Max(2)].as_ref())
+            .constraints([Constraint::Length(9), Constraint::Min(0), Constraint::Max(2)].as_ref())
             .split(area);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(30), Constraint::Percentage(30)].as_ref())
+            .split(chunks[0]);
 
-        let canvas = Canvas::default().block(Block::default().borders(Borders::ALL).title("Block")).paint(|_ctx| {
-            // ctx.draw(&ball);
-        });
-        f.render_widget(canvas, chunks[0]);
+        f.render_widget(Self::node(node), top[0]);
+        f.render_widget(Self::sync(node), top[1]);
+        f.render_widget(Self::peers(node), top[2]);
 
-        let canvas = Canvas::default().block(Block::default().borders(Borders::ALL).title("Peers")).paint(|_ctx| {
-            // ctx.draw(&ball);
-        });
-        f.render_widget(canvas, chunks[1]);
+        // Render the consensus state, for validators.
+        match node.consensus() {
+            Some(consensus) => {
+                let middle = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+                    .split(chunks[1]);
+                f.render_widget(Self::consensus(consensus), middle[0]);
+                f.render_widget(Self::committee(consensus), middle[1]);
+            }
+            None => {
+                let paragraph = Paragraph::new("The consensus state is only available on validators.")
+                    .block(Block::default().borders(Borders::ALL).title("Consensus"));
+                f.render_widget(paragraph, chunks[1]);
+            }
+        }
 
-        let canvas = Canvas::default().block(Block::default().borders(Borders::ALL).title("Help")).paint(|ctx| {
-            ctx.print(0f64, 0f64, Span::styled("Press ESC to quit", Style::default().fg(Color::White)));
-        });
-        f.render_widget(canvas, chunks[2]);
+        let help = Paragraph::new(Span::styled(
+            "Press ESC to quit, ←/→ to switch tabs, and ↑/↓ to scroll",
+            Style::default().fg(Color::White),
+        ))
+        .block(Block::default().borders(Borders::TOP).title("Help"));
+        f.render_widget(help, chunks[2]);
     }
+
+    /// Returns the identity of the node.
+    fn node<N: Network>(node: &Node<N>) -> Paragraph<'static> {
+        let lines = vec![
+            field("Type", node.node_type().to_string()),
+            field("Network", N::NAME.to_string()),
+            field("Address", node.address().to_string()),
+            field("Listening", node.router().local_ip().to_string()),
+            field("Development", if node.is_dev() { "yes" } else { "no" }.to_string()),
+        ];
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Node"))
+    }
+
+    /// Returns the sync status of the node.
+    fn sync<N: Network>(node: &Node<N>) -> Paragraph<'static> {
+        let status = node.block_sync().status();
+        let (synced, color) = match status.is_synced {
+            true => ("synced", Color::Green),
+            false => ("syncing", Color::Yellow),
+        };
+        let eta = match status.eta_in_secs {
+            Some(secs) => format!("{secs}s"),
+            None => "unknown".to_string(),
+        };
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("Status: ", Style::default().fg(Color::Gray)),
+                Span::styled(synced, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            ]),
+            field("Height", status.local_height.to_string()),
+            field("Peer height", status.network_height.to_string()),
+            field("Behind", format!("{} blocks", status.blocks_behind)),
+            field("Requests", status.num_outstanding_requests.to_string()),
+            field("ETA", eta),
+        ];
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Sync"))
+    }
+
+    /// Returns the connected peers of the node, by node type.
+    fn peers<N: Network>(node: &Node<N>) -> Paragraph<'static> {
+        let router = node.router();
+        let lines = vec![
+            field("Connected", format!("{} / {}", router.number_of_connected_peers(), router.max_connected_peers())),
+            field("Validators", router.number_of_connected_validators().to_string()),
+            field("Provers", router.number_of_connected_provers().to_string()),
+            field("Clients", router.number_of_connected_clients().to_string()),
+            field("Candidates", router.number_of_candidate_peers().to_string()),
+            field("Restricted", router.number_of_restricted_peers().to_string()),
+        ];
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Peers"))
+    }
+
+    /// Returns the BFT and memory pool state of the validator.
+    fn consensus<N: Network>(consensus: &Consensus<N>) -> Paragraph<'static> {
+        let bft = consensus.bft();
+        let leader = match bft.leader() {
+            Some(leader) => leader.to_string(),
+            None => "unknown".to_string(),
+        };
+        let lines = vec![
+            field("Round", bft.primary().current_round().to_string()),
+            field("Leader", leader),
+            Line::default(),
+            field("Unconfirmed transactions", consensus.num_unconfirmed_transactions().to_string()),
+            field("Unconfirmed solutions", consensus.num_unconfirmed_solutions().to_string()),
+            field("Unconfirmed transmissions", consensus.num_unconfirmed_transmissions().to_string()),
+        ];
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Consensus"))
+    }
+
+    /// Returns the committee of the current BFT round, highlighting the current leader.
+    fn committee<N: Network>(consensus: &Consensus<N>) -> Table<'static> {
+        let bft = consensus.bft();
+        let leader = bft.leader();
+        let current_round = bft.primary().current_round();
+        // Retrieve the committee lookback, which is the committee that the BFT uses for the current round.
+        let (title, rows) = match bft.ledger().get_committee_lookback_for_round(current_round) {
+            Ok(committee) => {
+                let title = format!(
+                    "Committee (Round {current_round}, {} members, {} stake)",
+                    committee.num_members(),
+                    committee.total_stake()
+                );
+                let rows = committee
+                    .members()
+                    .iter()
+                    .map(|(address, (stake, is_open))| {
+                        let style = match Some(*address) == leader {
+                            true => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                            false => Style::default(),
+                        };
+                        Row::new(vec![address.to_string(), stake.to_string(), is_open.to_string()]).style(style)
+                    })
+                    .collect::<Vec<_>>();
+                (title, rows)
+            }
+            Err(error) => (format!("Committee (unavailable - {error})"), vec![]),
+        };
+        let widths = [Constraint::Length(64), Constraint::Length(20), Constraint::Length(6)];
+        Table::new(rows, widths)
+            .header(Row::new(vec!["Address", "Stake", "Open"]).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(Block::default().borders(Borders::ALL).title(title))
+    }
+}
+
+/// Returns a line with the given field name and value.
+fn field(name: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{name}: "), Style::default().fg(Color::Gray)),
+        Span::styled(value, Style::default().fg(Color::White)),
+    ])
 }
@@ -0,0 +1,84 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node::Node;
+use snarkvm::prelude::Network;
+
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Row, Table},
+    Frame,
+};
+use std::collections::HashMap;
+
+pub(crate) struct Peers;
+
+impl Peers {
+    pub(crate) fn draw<N: Network>(&self, f: &mut Frame, area: Rect, node: &Node<N>) {
+        let router = node.router();
+        // Retrieve the peer heights and scores.
+        let heights = node.block_sync().get_peer_heights();
+        let scores = router
+            .connected_metrics()
+            .into_iter()
+            .map(|(peer_ip, _, metrics)| (peer_ip, metrics))
+            .collect::<HashMap<_, _>>();
+
+        // Sort the connected peers by node type, and then by IP.
+        let mut peers = router.get_connected_peers();
+        peers.sort_by_key(|peer| (peer.node_type().to_string(), peer.ip()));
+
+        let rows = peers
+            .iter()
+            .map(|peer| {
+                let height = heights.get(&peer.ip()).map(|height| height.to_string()).unwrap_or_default();
+                let (score, latency) = match scores.get(&peer.ip()) {
+                    Some(metrics) => (
+                        format!("{:.1}", metrics.score),
+                        metrics.latency_ms.map(|latency| format!("{latency}ms")).unwrap_or_default(),
+                    ),
+                    None => Default::default(),
+                };
+                Row::new(vec![
+                    peer.ip().to_string(),
+                    peer.node_type().to_string(),
+                    peer.address().to_string(),
+                    peer.version().to_string(),
+                    height,
+                    score,
+                    latency,
+                    format!("{}s ago", peer.last_seen().elapsed().as_secs()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let widths = [
+            Constraint::Length(22),
+            Constraint::Length(10),
+            Constraint::Length(64),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ];
+        let header = Row::new(vec!["IP", "Type", "Address", "Version", "Height", "Score", "Latency", "Last Seen"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(format!("Connected Peers ({})", peers.len())));
+        f.render_widget(table, area);
+    }
+}
@@ -27,6 +27,22 @@ impl<N: Network> ChallengeRequest<N> {
     pub fn new(listener_port: u16, address: Address<N>, nonce: u64) -> Self {
         Self { version: Event::<N>::VERSION, listener_port, address, nonce }
     }
+
+    /// Advertises support for compressed frames, in the feature bits of the version.
+    pub fn with_compression(mut self) -> Self {
+        self.version |= COMPRESSION_FEATURE;
+        self
+    }
+
+    /// Returns the event protocol version, without the feature bits.
+    pub fn protocol_version(&self) -> u32 {
+        self.version & PROTOCOL_VERSION_MASK
+    }
+
+    /// Returns `true` if the sender supports compressed frames.
+    pub fn supports_compression(&self) -> bool {
+        self.version & COMPRESSION_FEATURE != 0
+    }
 }
 
 impl<N: Network> EventTrait for ChallengeRequest<N> {
@@ -60,7 +76,7 @@ impl<N: Network> FromBytes for ChallengeRequest<N> {
 
 #[cfg(test)]
 pub mod prop_tests {
-    use crate::ChallengeRequest;
+    use crate::{ChallengeRequest, Event};
     use snarkvm::{
         console::prelude::{FromBytes, ToBytes},
         prelude::{Address, TestRng, Uniform},
@@ -96,4 +112,18 @@ pub mod prop_tests {
             ChallengeRequest::read_le(buf.into_inner().reader()).unwrap();
         assert_eq!(original, deserialized);
     }
+
+    #[test]
+    fn test_compression_feature() {
+        let address = Address::rand(&mut TestRng::default());
+
+        let request = ChallengeRequest::<CurrentNetwork>::new(4130, address, 0);
+        assert!(!request.supports_compression());
+
+        let request = request.with_compression();
+        assert!(request.supports_compression());
+        assert_eq!(request.protocol_version(), Event::<CurrentNetwork>::VERSION);
+        // Older nodes, which only compare the raw version, still accept the request.
+        assert!(request.version >= Event::<CurrentNetwork>::VERSION);
+    }
 }
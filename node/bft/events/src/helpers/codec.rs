@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::Event;
+use snarkos_node_tcp::{compress_frame, decompress_frame, COMPRESSION_THRESHOLD};
 use snarkvm::prelude::{FromBytes, Network, ToBytes};
 
 use bytes::{Buf, BufMut, BytesMut};
@@ -31,6 +32,8 @@ pub const NOISE_HANDSHAKE_TYPE: &str = "Noise_XX_25519_ChaChaPoly_BLAKE2s";
 /// The codec used to decode and encode network `Event`s.
 pub struct EventCodec<N: Network> {
     codec: LengthDelimitedCodec,
+    /// If `true`, each frame carries a compression header, as negotiated during the handshake.
+    compression: bool,
     _phantom: PhantomData<N>,
 }
 
@@ -40,12 +43,19 @@ impl<N: Network> EventCodec<N> {
         codec.codec.set_max_frame_length(MAX_HANDSHAKE_SIZE);
         codec
     }
+
+    /// Initializes a codec that compresses large block and certificate responses.
+    /// Note: This codec must only be used with peers that advertised compression during the handshake.
+    pub fn compressed() -> Self {
+        Self { compression: true, ..Default::default() }
+    }
 }
 
 impl<N: Network> Default for EventCodec<N> {
     fn default() -> Self {
         Self {
             codec: LengthDelimitedCodec::builder().max_frame_length(MAX_EVENT_SIZE).little_endian().new_codec(),
+            compression: false,
             _phantom: Default::default(),
         }
     }
@@ -55,6 +65,11 @@ impl<N: Network> Encoder<Event<N>> for EventCodec<N> {
     type Error = std::io::Error;
 
     fn encode(&mut self, event: Event<N>, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        #[cfg(feature = "metrics")]
+        let name = event.name();
+        // Determine if the event may be compressed.
+        let is_compressible = matches!(event, Event::BlockResponse(..) | Event::CertificateResponse(..));
+
         // Serialize the payload directly into dst.
         event
             .write_le(&mut dst.writer())
@@ -63,7 +78,23 @@ impl<N: Network> Encoder<Event<N>> for EventCodec<N> {
 
         let serialized_event = dst.split_to(dst.len()).freeze();
 
-        self.codec.encode(serialized_event, dst)
+        // If compression was negotiated, frame the event, compressing it if it is large enough.
+        let frame = match self.compression {
+            true => {
+                let compress = is_compressible && serialized_event.len() >= COMPRESSION_THRESHOLD;
+                compress_frame(&serialized_event, compress)?
+            }
+            false => serialized_event,
+        };
+
+        // Note: The metrics record the size of the frame, which is the size of the event on the wire.
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter_with_labels(metrics::bft::EVENT_BYTES_SENT, frame.len() as u64, &[(
+            "event",
+            name.to_string(),
+        )]);
+
+        self.codec.encode(frame, dst)
     }
 }
 
@@ -78,10 +109,26 @@ impl<N: Network> Decoder for EventCodec<N> {
             None => return Ok(None),
         };
 
+        #[cfg(feature = "metrics")]
+        let num_bytes = bytes.len() as u64;
+
+        // If compression was negotiated, decode the payload from the frame.
+        let bytes = match self.compression {
+            true => decompress_frame(bytes, MAX_EVENT_SIZE)?,
+            false => bytes.freeze(),
+        };
+
         // Convert the bytes to an event, or fail if it is not valid.
         let reader = bytes.reader();
         match Event::read_le(reader) {
-            Ok(event) => Ok(Some(event)),
+            Ok(event) => {
+                #[cfg(feature = "metrics")]
+                metrics::increment_counter_with_labels(metrics::bft::EVENT_BYTES_RECEIVED, num_bytes, &[(
+                    "event",
+                    event.name().to_string(),
+                )]);
+                Ok(Some(event))
+            }
             Err(error) => {
                 error!("Failed to deserialize an event: {}", error);
                 Err(std::io::ErrorKind::InvalidData.into())
@@ -99,12 +146,13 @@ mod tests {
     type CurrentNetwork = snarkvm::prelude::MainnetV0;
 
     fn assert_roundtrip(msg: Event<CurrentNetwork>) {
-        let mut codec: EventCodec<CurrentNetwork> = Default::default();
-        let mut encoded_event = BytesMut::new();
+        for mut codec in [EventCodec::<CurrentNetwork>::default(), EventCodec::compressed()] {
+            let mut encoded_event = BytesMut::new();
 
-        assert!(codec.encode(msg.clone(), &mut encoded_event).is_ok());
-        let decoded = codec.decode(&mut encoded_event).unwrap().unwrap();
-        assert_eq!(decoded.to_bytes_le().unwrap(), msg.to_bytes_le().unwrap());
+            assert!(codec.encode(msg.clone(), &mut encoded_event).is_ok());
+            let decoded = codec.decode(&mut encoded_event).unwrap().unwrap();
+            assert_eq!(decoded.to_bytes_le().unwrap(), msg.to_bytes_le().unwrap());
+        }
     }
 
     #[proptest]
@@ -66,6 +66,7 @@ mod worker_ping;
 pub use worker_ping::WorkerPing;
 
 use snarkos_node_sync_locators::BlockLocators;
+use snarkos_node_tcp::{COMPRESSION_FEATURE, PROTOCOL_VERSION_MASK};
 use snarkvm::{
     console::prelude::{error, FromBytes, Network, Read, ToBytes, Write},
     ledger::{
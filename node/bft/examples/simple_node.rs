@@ -17,7 +17,7 @@ extern crate tracing;
 
 use snarkos_account::Account;
 use snarkos_node_bft::{
-    helpers::{init_consensus_channels, init_primary_channels, ConsensusReceiver, PrimarySender, Storage},
+    helpers::{init_consensus_channels, init_primary_channels, ConsensusReceiver, PrimarySender, Priority, Storage},
     Primary,
     BFT,
     MEMORY_POOL_PORT,
@@ -139,7 +139,16 @@ pub async fn start_bft(
     // Initialize the consensus receiver handler.
     consensus_handler(consensus_receiver);
     // Initialize the BFT instance.
-    let mut bft = BFT::<CurrentNetwork>::new(account, storage, ledger, ip, &trusted_validators, dev)?;
+    let mut bft = BFT::<CurrentNetwork>::new(
+        account,
+        storage,
+        ledger,
+        ip,
+        &trusted_validators,
+        dev,
+        Default::default(),
+        Default::default(),
+    )?;
     // Run the BFT instance.
     bft.run(Some(consensus_sender), sender.clone(), receiver).await?;
     // Retrieve the BFT's primary.
@@ -176,7 +185,16 @@ pub async fn start_primary(
     // Initialize the trusted validators.
     let trusted_validators = trusted_validators(node_id, num_nodes, peers);
     // Initialize the primary instance.
-    let mut primary = Primary::<CurrentNetwork>::new(account, storage, ledger, ip, &trusted_validators, dev)?;
+    let mut primary = Primary::<CurrentNetwork>::new(
+        account,
+        storage,
+        ledger,
+        ip,
+        &trusted_validators,
+        dev,
+        Default::default(),
+        Default::default(),
+    )?;
     // Run the primary instance.
     primary.run(None, sender.clone(), receiver).await?;
     // Handle OS signals.
@@ -416,8 +434,8 @@ fn fire_unconfirmed_transactions(sender: &PrimarySender<CurrentNetwork>, node_id
             let (id, transaction) = if counter % 2 == 0 { sample(&mut shared_rng) } else { sample(&mut unique_rng) };
             // Initialize a callback sender and receiver.
             let (callback, callback_receiver) = oneshot::channel();
-            // Send the fake transaction.
-            if let Err(e) = tx_unconfirmed_transaction.send((id, transaction, callback)).await {
+            // Send the fake transaction, with the lowest priority.
+            if let Err(e) = tx_unconfirmed_transaction.send((id, transaction, Priority::default(), callback)).await {
                 error!("Failed to send unconfirmed transaction: {e}");
             }
             let _ = callback_receiver.await;
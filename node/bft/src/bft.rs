@@ -27,8 +27,9 @@ use crate::{
     Primary,
     MAX_LEADER_CERTIFICATE_DELAY_IN_SECS,
 };
-use snarkos_account::Account;
+use snarkos_account::Signer;
 use snarkos_node_bft_ledger_service::LedgerService;
+use snarkos_node_tcp::{InboundLimits, OutboundLimits};
 use snarkvm::{
     console::account::Address,
     ledger::{
@@ -78,15 +79,26 @@ pub struct BFT<N: Network> {
 impl<N: Network> BFT<N> {
     /// Initializes a new instance of the BFT.
     pub fn new(
-        account: Account<N>,
+        signer: impl Into<Signer<N>>,
         storage: Storage<N>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
         Ok(Self {
-            primary: Primary::new(account, storage, ledger, ip, trusted_validators, dev)?,
+            primary: Primary::new(
+                signer,
+                storage,
+                ledger,
+                ip,
+                trusted_validators,
+                dev,
+                inbound_limits,
+                outbound_limits,
+            )?,
             dag: Default::default(),
             leader_certificate: Default::default(),
             leader_certificate_timer: Default::default(),
@@ -104,6 +116,8 @@ impl<N: Network> BFT<N> {
         primary_receiver: PrimaryReceiver<N>,
     ) -> Result<()> {
         info!("Starting the BFT instance...");
+        #[cfg(feature = "metrics")]
+        metrics::gauge(metrics::bft::LEADER_CERTIFICATE_MAX_DELAY, MAX_LEADER_CERTIFICATE_DELAY_IN_SECS as f64);
         // Initialize the BFT channels.
         let (bft_sender, bft_receiver) = init_bft_channels::<N>();
         // First, start the BFT handlers.
@@ -218,6 +232,10 @@ impl<N: Network> BFT<N> {
                 let elapsed = std::time::Duration::from_secs((end - start) as u64);
                 metrics::histogram(metrics::bft::COMMIT_ROUNDS_LATENCY, elapsed.as_secs_f64());
             }
+            // Update the leader metrics, before the timer for the leader certificate is reset.
+            if is_ready && current_round % 2 == 0 {
+                self.update_leader_metrics(current_round);
+            }
         }
 
         // Log whether the round is going to update.
@@ -348,12 +366,47 @@ impl<N: Network> BFT<N> {
         // If the timer has expired, and we can achieve quorum threshold (2f + 1) without the leader, return 'true'.
         if self.is_timer_expired() {
             debug!("BFT (timer expired) - Advancing from round {current_round} to the next round (without the leader)");
+            #[cfg(feature = "metrics")]
+            metrics::increment_counter(metrics::bft::LEADER_CERTIFICATE_TIMEOUTS);
             return true;
         }
         // Otherwise, return 'false'.
         false
     }
 
+    /// Updates the metrics for the leader of the given even round, as the BFT is ready to advance from it.
+    #[cfg(feature = "metrics")]
+    fn update_leader_metrics(&self, even_round: u64) {
+        // Record the time spent waiting for the leader certificate, which is bounded by `MAX_LEADER_CERTIFICATE_DELAY_IN_SECS`.
+        let start = self.leader_certificate_timer.load(Ordering::SeqCst);
+        // Only record if the timer was set, otherwise we get a time difference since the EPOCH.
+        if start > 0 {
+            metrics::histogram(metrics::bft::LEADER_CERTIFICATE_WAIT, now().saturating_sub(start) as f64);
+        }
+        // If the leader was absent from the round, count the missed round against the leader.
+        let has_leader_certificate =
+            self.leader_certificate.read().as_ref().map_or(false, |certificate| certificate.round() == even_round);
+        if !has_leader_certificate {
+            if let Some((_, leader)) = self.ledger().latest_leader().filter(|(round, _)| *round == even_round) {
+                metrics::increment_counter_with_labels(metrics::bft::MISSED_LEADER_ROUNDS, 1, &[(
+                    "leader",
+                    leader.to_string(),
+                )]);
+            }
+        }
+    }
+
+    /// Updates the metrics for the size of the DAG.
+    #[cfg(feature = "metrics")]
+    fn update_dag_metrics(&self) {
+        let dag = self.dag.read();
+        metrics::gauge(metrics::bft::DAG_ROUNDS, dag.graph().len() as f64);
+        metrics::gauge(
+            metrics::bft::DAG_CERTIFICATES,
+            dag.graph().values().map(|round| round.len()).sum::<usize>() as f64,
+        );
+    }
+
     /// Returns `true` if the timer for the leader certificate has expired.
     fn is_timer_expired(&self) -> bool {
         self.leader_certificate_timer.load(Ordering::SeqCst) + MAX_LEADER_CERTIFICATE_DELAY_IN_SECS <= now()
@@ -453,6 +506,8 @@ impl<N: Network> BFT<N> {
         let certificate_round = certificate.round();
         // Insert the certificate into the DAG.
         self.dag.write().insert(certificate);
+        #[cfg(feature = "metrics")]
+        self.update_dag_metrics();
 
         // Construct the commit round.
         let commit_round = certificate_round.saturating_sub(1);
@@ -663,6 +718,8 @@ impl<N: Network> BFT<N> {
 
         // Perform garbage collection based on the latest committed leader round.
         self.storage().garbage_collect_certificates(latest_leader_round);
+        #[cfg(feature = "metrics")]
+        self.update_dag_metrics();
 
         Ok(())
     }
@@ -920,7 +977,16 @@ mod tests {
         // Initialize the account.
         let account = Account::new(rng)?;
         // Initialize the BFT.
-        let bft = BFT::new(account.clone(), storage.clone(), ledger.clone(), None, &[], None)?;
+        let bft = BFT::new(
+            account.clone(),
+            storage.clone(),
+            ledger.clone(),
+            None,
+            &[],
+            None,
+            Default::default(),
+            Default::default(),
+        )?;
         assert!(bft.is_timer_expired());
         // Ensure this call succeeds on an odd round.
         let result = bft.is_leader_quorum_or_nonleaders_available(1);
@@ -955,7 +1021,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, &[], None, Default::default(), Default::default())?;
         assert!(bft.is_timer_expired()); // 0 + 5 < now()
 
         // Store is at round 1, and we are checking for round 2.
@@ -977,7 +1043,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, &[], None, Default::default(), Default::default())?;
         assert!(bft.is_timer_expired()); // 0 + 5 < now()
 
         // Ensure this call fails on an even round.
@@ -1017,7 +1083,16 @@ mod tests {
         // Initialize the account.
         let account = Account::new(rng)?;
         // Initialize the BFT.
-        let bft = BFT::new(account.clone(), storage.clone(), ledger.clone(), None, &[], None)?;
+        let bft = BFT::new(
+            account.clone(),
+            storage.clone(),
+            ledger.clone(),
+            None,
+            &[],
+            None,
+            Default::default(),
+            Default::default(),
+        )?;
         // Set the leader certificate.
         let leader_certificate = sample_batch_certificate_for_round(2, rng);
         *bft.leader_certificate.write() = Some(leader_certificate);
@@ -1029,7 +1104,16 @@ mod tests {
         assert!(result);
 
         // Initialize a new BFT.
-        let bft_timer = BFT::new(account.clone(), storage.clone(), ledger.clone(), None, &[], None)?;
+        let bft_timer = BFT::new(
+            account.clone(),
+            storage.clone(),
+            ledger.clone(),
+            None,
+            &[],
+            None,
+            Default::default(),
+            Default::default(),
+        )?;
         // If the leader certificate is not set and the timer has not expired, we are not ready for the next round.
         let result = bft_timer.is_even_round_ready_for_next_round(certificates.clone(), committee.clone(), 2);
         if !bft_timer.is_timer_expired() {
@@ -1060,7 +1144,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, &[], None, Default::default(), Default::default())?;
 
         // Ensure this call fails on an odd round.
         let result = bft.update_leader_certificate_to_even_round(1);
@@ -1078,7 +1162,7 @@ mod tests {
         assert_eq!(storage.max_gc_rounds(), 10);
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, &[], None, Default::default(), Default::default())?;
 
         // Ensure this call succeeds on an even round.
         let result = bft.update_leader_certificate_to_even_round(6);
@@ -1130,7 +1214,7 @@ mod tests {
 
         // Initialize the BFT.
         let account = Account::new(rng)?;
-        let bft = BFT::new(account, storage.clone(), ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage.clone(), ledger, None, &[], None, Default::default(), Default::default())?;
 
         // Set the leader certificate.
         *bft.leader_certificate.write() = Some(leader_certificate);
@@ -1168,7 +1252,16 @@ mod tests {
             // Initialize the storage.
             let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
             // Initialize the BFT.
-            let bft = BFT::new(account.clone(), storage, ledger.clone(), None, &[], None)?;
+            let bft = BFT::new(
+                account.clone(),
+                storage,
+                ledger.clone(),
+                None,
+                &[],
+                None,
+                Default::default(),
+                Default::default(),
+            )?;
 
             // Insert a mock DAG in the BFT.
             *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(3);
@@ -1198,7 +1291,7 @@ mod tests {
             // Initialize the storage.
             let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 1);
             // Initialize the BFT.
-            let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+            let bft = BFT::new(account, storage, ledger, None, &[], None, Default::default(), Default::default())?;
 
             // Insert a mock DAG in the BFT.
             *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(2);
@@ -1256,7 +1349,7 @@ mod tests {
         /* Test missing previous certificate. */
 
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage, ledger, None, &[], None, Default::default(), Default::default())?;
 
         // The expected error message.
         let error_msg = format!(
@@ -1317,7 +1410,7 @@ mod tests {
 
         // Initialize the BFT.
         let account = Account::new(rng)?;
-        let bft = BFT::new(account, storage.clone(), ledger, None, &[], None)?;
+        let bft = BFT::new(account, storage.clone(), ledger, None, &[], None, Default::default(), Default::default())?;
         // Insert a mock DAG in the BFT.
         *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(commit_round);
 
@@ -1383,7 +1476,16 @@ mod tests {
 
         // Initialize the BFT.
         let account = Account::new(rng)?;
-        let bft = BFT::new(account.clone(), storage, ledger.clone(), None, &[], None)?;
+        let bft = BFT::new(
+            account.clone(),
+            storage,
+            ledger.clone(),
+            None,
+            &[],
+            None,
+            Default::default(),
+            Default::default(),
+        )?;
 
         // Insert a mock DAG in the BFT.
         *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(commit_round);
@@ -1401,7 +1503,7 @@ mod tests {
         // Initialize a new instance of storage.
         let storage_2 = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), max_gc_rounds);
         // Initialize a new instance of BFT.
-        let bootup_bft = BFT::new(account, storage_2, ledger, None, &[], None)?;
+        let bootup_bft = BFT::new(account, storage_2, ledger, None, &[], None, Default::default(), Default::default())?;
 
         // Sync the BFT DAG at bootup.
         bootup_bft.sync_bft_dag_at_bootup(certificates.clone()).await;
@@ -1555,7 +1657,16 @@ mod tests {
 
         // Initialize the BFT without bootup.
         let account = Account::new(rng)?;
-        let bft = BFT::new(account.clone(), storage, ledger.clone(), None, &[], None)?;
+        let bft = BFT::new(
+            account.clone(),
+            storage,
+            ledger.clone(),
+            None,
+            &[],
+            None,
+            Default::default(),
+            Default::default(),
+        )?;
 
         // Insert a mock DAG in the BFT without bootup.
         *bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(0);
@@ -1580,7 +1691,16 @@ mod tests {
         let bootup_storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), max_gc_rounds);
 
         // Initialize a new instance of BFT with bootup.
-        let bootup_bft = BFT::new(account, bootup_storage.clone(), ledger.clone(), None, &[], None)?;
+        let bootup_bft = BFT::new(
+            account,
+            bootup_storage.clone(),
+            ledger.clone(),
+            None,
+            &[],
+            None,
+            Default::default(),
+            Default::default(),
+        )?;
 
         // Sync the BFT DAG at bootup.
         bootup_bft.sync_bft_dag_at_bootup(pre_shutdown_certificates.clone()).await;
@@ -1758,7 +1878,16 @@ mod tests {
         }
         // Initialize the bootup BFT.
         let account = Account::new(rng)?;
-        let bootup_bft = BFT::new(account.clone(), storage.clone(), ledger.clone(), None, &[], None)?;
+        let bootup_bft = BFT::new(
+            account.clone(),
+            storage.clone(),
+            ledger.clone(),
+            None,
+            &[],
+            None,
+            Default::default(),
+            Default::default(),
+        )?;
         // Insert a mock DAG in the BFT without bootup.
         *bootup_bft.dag.write() = crate::helpers::dag::test_helpers::mock_dag_with_modified_last_committed_round(0);
         // Sync the BFT DAG at bootup.
@@ -21,7 +21,7 @@ use crate::{
     MAX_BATCH_DELAY_IN_MS,
     MEMORY_POOL_PORT,
 };
-use snarkos_account::Account;
+use snarkos_account::Signer;
 use snarkos_node_bft_events::{
     BlockRequest,
     BlockResponse,
@@ -39,7 +39,7 @@ use snarkos_node_bft_events::{
     ValidatorsResponse,
 };
 use snarkos_node_bft_ledger_service::LedgerService;
-use snarkos_node_sync::{communication_service::CommunicationService, MAX_BLOCKS_BEHIND};
+use snarkos_node_sync::{communication_service::CommunicationService, MAX_BLOCKS_BEHIND, MAX_BLOCK_REQUESTS};
 use snarkos_node_tcp::{
     is_bogon_ip,
     is_unspecified_or_broadcast_ip,
@@ -47,7 +47,12 @@ use snarkos_node_tcp::{
     Config,
     Connection,
     ConnectionSide,
+    InboundLimits,
+    OutboundLimits,
+    RateLimit,
+    RateLimitViolation,
     Tcp,
+    CONTEXT_TARGET,
     P2P,
 };
 use snarkvm::{
@@ -72,6 +77,7 @@ use tokio::{
 };
 use tokio_stream::StreamExt;
 use tokio_util::codec::Framed;
+use tracing::Instrument;
 
 /// The maximum interval of events to cache.
 const CACHE_EVENTS_INTERVAL: i64 = (MAX_BATCH_DELAY_IN_MS / 1000) as i64; // seconds
@@ -88,6 +94,16 @@ const MIN_CONNECTED_VALIDATORS: usize = 175;
 /// The maximum number of validators to send in a validators response event.
 const MAX_VALIDATORS_TO_SEND: usize = 200;
 
+/// The default limit on block requests per second from a single validator, which leaves room for
+/// twice the number of blocks a syncing validator requests at once.
+const BLOCK_REQUEST_LIMIT: RateLimit = RateLimit::new(Some(2 * MAX_BLOCK_REQUESTS as u32), None);
+/// The default limit on validators requests per second from a single validator.
+const VALIDATORS_REQUEST_LIMIT: RateLimit = RateLimit::new(Some(1), None);
+/// The maximum number of rate limit violations by a validator in an interval, before disconnecting from it.
+const MAX_RATE_LIMIT_VIOLATIONS: usize = 100;
+/// The interval of rate limit violations to cache.
+const RATE_LIMIT_VIOLATIONS_INTERVAL: i64 = 10; // seconds
+
 /// Part of the Gateway API that deals with networking.
 /// This is a separate trait to allow for easier testing/mocking.
 #[async_trait]
@@ -98,8 +114,8 @@ pub trait Transport<N: Network>: Send + Sync {
 
 #[derive(Clone)]
 pub struct Gateway<N: Network> {
-    /// The account of the node.
-    account: Account<N>,
+    /// The signer of the node.
+    signer: Signer<N>,
     /// The storage.
     storage: Storage<N>,
     /// The ledger service.
@@ -111,7 +127,7 @@ pub struct Gateway<N: Network> {
     /// The resolver.
     resolver: Arc<Resolver<N>>,
     /// The set of trusted validators.
-    trusted_validators: IndexSet<SocketAddr>,
+    trusted_validators: Arc<RwLock<IndexSet<SocketAddr>>>,
     /// The map of connected peer IPs to their peer handlers.
     connected_peers: Arc<RwLock<IndexSet<SocketAddr>>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
@@ -119,6 +135,8 @@ pub struct Gateway<N: Network> {
     /// prevent simultaneous "two-way" connections between two peers (i.e. both nodes simultaneously
     /// attempt to connect to each other). This set is used to prevent this from happening.
     connecting_peers: Arc<Mutex<IndexSet<SocketAddr>>>,
+    /// The set of connected peer IPs that negotiated compression during the handshake.
+    compressed_peers: Arc<RwLock<HashSet<SocketAddr>>>,
     /// The primary sender.
     primary_sender: Arc<OnceCell<PrimarySender<N>>>,
     /// The worker senders.
@@ -127,6 +145,11 @@ pub struct Gateway<N: Network> {
     sync_sender: Arc<OnceCell<SyncSender<N>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// The number of bytes received from and sent to each connected peer, as of the last traffic metrics update.
+    #[cfg(feature = "metrics")]
+    reported_traffic: Arc<Mutex<std::collections::HashMap<SocketAddr, (u64, u64)>>>,
+    /// The limits on inbound requests.
+    inbound_limits: Arc<InboundLimits>,
     /// The development mode.
     dev: Option<u16>,
 }
@@ -134,36 +157,48 @@ pub struct Gateway<N: Network> {
 impl<N: Network> Gateway<N> {
     /// Initializes a new gateway.
     pub fn new(
-        account: Account<N>,
+        signer: impl Into<Signer<N>>,
         storage: Storage<N>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
         // Initialize the gateway IP.
         let ip = match (ip, dev) {
             (None, Some(dev)) => SocketAddr::from_str(&format!("127.0.0.1:{}", MEMORY_POOL_PORT + dev))?,
-            (None, None) => SocketAddr::from_str(&format!("0.0.0.0:{}", MEMORY_POOL_PORT))?,
+            (None, None) => SocketAddr::from_str(&format!("[::]:{}", MEMORY_POOL_PORT))?,
             (Some(ip), _) => ip,
         };
         // Initialize the TCP stack.
-        let tcp = Tcp::new(Config::new(ip, Committee::<N>::MAX_COMMITTEE_SIZE));
+        let tcp = Tcp::new(Config {
+            inbound_limit: inbound_limits.connection,
+            global_inbound_limit: inbound_limits.global,
+            outbound_limit: outbound_limits.connection,
+            global_outbound_limit: outbound_limits.global,
+            ..Config::new(ip, Committee::<N>::MAX_COMMITTEE_SIZE)
+        });
         // Return the gateway.
         Ok(Self {
-            account,
+            signer: signer.into(),
             storage,
             ledger,
             tcp,
             cache: Default::default(),
             resolver: Default::default(),
-            trusted_validators: trusted_validators.iter().copied().collect(),
+            trusted_validators: Arc::new(RwLock::new(trusted_validators.iter().copied().collect())),
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
+            compressed_peers: Default::default(),
             primary_sender: Default::default(),
             worker_senders: Default::default(),
             sync_sender: Default::default(),
             handles: Default::default(),
+            #[cfg(feature = "metrics")]
+            reported_traffic: Default::default(),
+            inbound_limits: Arc::new(inbound_limits),
             dev,
         })
     }
@@ -256,9 +291,9 @@ impl<N: Network> CommunicationService for Gateway<N> {
 }
 
 impl<N: Network> Gateway<N> {
-    /// Returns the account of the node.
-    pub const fn account(&self) -> &Account<N> {
-        &self.account
+    /// Returns the signer of the node.
+    pub const fn signer(&self) -> &Signer<N> {
+        &self.signer
     }
 
     /// Returns the IP address of this node.
@@ -318,10 +353,15 @@ impl<N: Network> Gateway<N> {
         self.connecting_peers.lock().contains(&ip)
     }
 
+    /// Returns `true` if compression was negotiated with the given peer IP.
+    pub fn is_compressed_ip(&self, ip: SocketAddr) -> bool {
+        self.compressed_peers.read().contains(&ip)
+    }
+
     /// Returns `true` if the given peer IP is an authorized validator.
     pub fn is_authorized_validator_ip(&self, ip: SocketAddr) -> bool {
         // If the peer IP is in the trusted validators, return early.
-        if self.trusted_validators.contains(&ip) {
+        if self.trusted_validators.read().contains(&ip) {
             return true;
         }
         // Retrieve the Aleo address of the peer IP.
@@ -388,6 +428,23 @@ impl<N: Network> Gateway<N> {
         &self.connected_peers
     }
 
+    /// Returns the list of trusted validators.
+    pub fn trusted_validators(&self) -> IndexSet<SocketAddr> {
+        self.trusted_validators.read().clone()
+    }
+
+    /// Inserts the given peer IP into the trusted validators.
+    /// Returns `true` if the peer IP was not already trusted.
+    pub fn insert_trusted_validator(&self, peer_ip: SocketAddr) -> bool {
+        self.trusted_validators.write().insert(peer_ip)
+    }
+
+    /// Removes the given peer IP from the trusted validators.
+    /// Returns `true` if the peer IP was trusted.
+    pub fn remove_trusted_validator(&self, peer_ip: SocketAddr) -> bool {
+        self.trusted_validators.write().shift_remove(&peer_ip)
+    }
+
     /// Attempts to connect to the given peer IP.
     pub fn connect(&self, peer_ip: SocketAddr) -> Option<JoinHandle<()>> {
         // Return early if the attempt is against the protocol rules.
@@ -460,6 +517,27 @@ impl<N: Network> Gateway<N> {
         metrics::gauge(metrics::bft::CONNECTING, self.connecting_peers.lock().len() as f64);
     }
 
+    /// Updates the traffic metrics of the connected validators in aggregate, from the statistics of their connections.
+    #[cfg(feature = "metrics")]
+    fn update_traffic_metrics(&self) {
+        let mut reported_traffic = self.reported_traffic.lock();
+        let (mut bytes_received, mut bytes_sent) = (0u64, 0u64);
+        for peer_ip in self.connected_peers.read().iter() {
+            // Retrieve the statistics of the connection with the validator.
+            let Some(stats) = self.resolver.get_ambiguous(*peer_ip).and_then(|addr| self.tcp.known_peers().get(addr))
+            else {
+                continue;
+            };
+            let (received, sent) = (stats.received().1, stats.sent().1);
+            // Report the traffic since the last update, as the connections come and go.
+            let (last_received, last_sent) = reported_traffic.insert(*peer_ip, (received, sent)).unwrap_or_default();
+            bytes_received = bytes_received.saturating_add(received.saturating_sub(last_received));
+            bytes_sent = bytes_sent.saturating_add(sent.saturating_sub(last_sent));
+        }
+        metrics::increment_counter_with_labels(metrics::bft::PEER_BYTES_RECEIVED, bytes_received, &[]);
+        metrics::increment_counter_with_labels(metrics::bft::PEER_BYTES_SENT, bytes_sent, &[]);
+    }
+
     /// Inserts the given peer into the connected peers.
     #[cfg(not(test))]
     fn insert_connected_peer(&self, peer_ip: SocketAddr, peer_addr: SocketAddr, address: Address<N>) {
@@ -496,6 +574,10 @@ impl<N: Network> Gateway<N> {
         self.resolver.remove_peer(peer_ip);
         // Remove this peer from the connected peers, if it exists.
         self.connected_peers.write().shift_remove(&peer_ip);
+        self.compressed_peers.write().remove(&peer_ip);
+        // Forget the traffic reported for the peer, as its next connection starts from zero.
+        #[cfg(feature = "metrics")]
+        self.reported_traffic.lock().remove(&peer_ip);
         #[cfg(feature = "metrics")]
         self.update_metrics();
     }
@@ -513,6 +595,8 @@ impl<N: Network> Gateway<N> {
         };
         // Retrieve the event name.
         let name = event.name();
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter_with_labels(metrics::bft::EVENTS_SENT, 1, &[("event", name.to_string())]);
         // Send the event to the peer.
         trace!("{CONTEXT} Sending '{name}' to '{peer_ip}'");
         let result = self.unicast(peer_addr, event);
@@ -568,6 +652,14 @@ impl<N: Network> Gateway<N> {
         }
         trace!("{CONTEXT} Received '{}' from '{peer_ip}'", event.name());
 
+        // Count the event, and time its handler until this function returns.
+        #[cfg(feature = "metrics")]
+        let _timer = {
+            let labels = vec![("event", event.name().to_string())];
+            metrics::increment_counter_with_labels(metrics::bft::EVENTS_RECEIVED, 1, &labels);
+            metrics::HistogramTimer::new(metrics::bft::EVENT_HANDLER_LATENCY, labels)
+        };
+
         // This match statement handles the inbound event by deserializing the event,
         // checking the event is valid, and then calling the appropriate (trait) handler.
         match event {
@@ -768,7 +860,7 @@ impl<N: Network> Gateway<N> {
                             }
 
                             // Ensure the validator address is not this node.
-                            if self_.account.address() == validator_address {
+                            if self_.signer.address() == validator_address {
                                 continue;
                             }
                             // Ensure the validator IP is not already connected or connecting.
@@ -870,6 +962,9 @@ impl<N: Network> Gateway<N> {
         self.handle_unauthorized_validators();
         // If the number of connected validators is less than the minimum, send a `ValidatorsRequest`.
         self.handle_min_connected_validators();
+        // Update the traffic metrics of the connected validators.
+        #[cfg(feature = "metrics")]
+        self.update_traffic_metrics();
     }
 
     /// Logs the connected validators.
@@ -896,14 +991,14 @@ impl<N: Network> Gateway<N> {
     /// This function attempts to connect to any disconnected trusted validators.
     fn handle_trusted_validators(&self) {
         // Ensure that the trusted nodes are connected.
-        for validator_ip in &self.trusted_validators {
+        for validator_ip in self.trusted_validators() {
             // If the trusted_validator is not connected, attempt to connect to it.
-            if !self.is_local_ip(*validator_ip)
-                && !self.is_connecting_ip(*validator_ip)
-                && !self.is_connected_ip(*validator_ip)
+            if !self.is_local_ip(validator_ip)
+                && !self.is_connecting_ip(validator_ip)
+                && !self.is_connected_ip(validator_ip)
             {
                 // Attempt to connect to the trusted validator.
-                self.connect(*validator_ip);
+                self.connect(validator_ip);
             }
         }
     }
@@ -1014,6 +1109,16 @@ impl<N: Network> Transport<N> for Gateway<N> {
     }
 }
 
+impl<N: Network> Gateway<N> {
+    /// Returns the codec for the given peer address, which compresses large events if negotiated during the handshake.
+    fn event_codec(&self, peer_addr: SocketAddr) -> EventCodec<N> {
+        match self.resolver.get_listener(peer_addr).map_or(false, |peer_ip| self.is_compressed_ip(peer_ip)) {
+            true => EventCodec::compressed(),
+            false => EventCodec::default(),
+        }
+    }
+}
+
 impl<N: Network> P2P for Gateway<N> {
     /// Returns a reference to the TCP instance.
     fn tcp(&self) -> &Tcp {
@@ -1034,14 +1139,17 @@ impl<N: Network> Reading for Gateway<N> {
 
     /// Creates a [`Decoder`] used to interpret messages from the network.
     /// The `side` param indicates the connection side **from the node's perspective**.
-    fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.event_codec(peer_addr)
     }
 
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
+        // Process the message within the context of the peer, for structured logging.
+        let peer_ip = self.resolver.get_listener(peer_addr).unwrap_or(peer_addr);
+        let span = info_span!(target: CONTEXT_TARGET, "peer", peer_ip = %peer_ip);
         // Process the message. Disconnect if the peer violated the protocol.
-        if let Err(error) = self.inbound(peer_addr, message).await {
+        if let Err(error) = self.inbound(peer_addr, message).instrument(span).await {
             if let Some(peer_ip) = self.resolver.get_listener(peer_addr) {
                 warn!("{CONTEXT} Disconnecting from '{peer_ip}' - {error}");
                 let self_ = self.clone();
@@ -1054,6 +1162,41 @@ impl<N: Network> Reading for Gateway<N> {
         }
         Ok(())
     }
+
+    /// Returns the name of the type of the given inbound event and its per-peer rate limit, if the type is limited.
+    fn message_limit(&self, event: &Self::Message) -> Option<(&'static str, RateLimit)> {
+        let (name, default) = match event {
+            Event::BlockRequest(..) => ("BlockRequest", BLOCK_REQUEST_LIMIT),
+            Event::ValidatorsRequest(..) => ("ValidatorsRequest", VALIDATORS_REQUEST_LIMIT),
+            _ => return None,
+        };
+        Some((name, self.inbound_limits.request_limit(name, default)))
+    }
+
+    /// Disconnects from the peer at the given address once it exceeds the rate limits too often.
+    /// Violations of the global limit are not attributed to any single peer, and are ignored.
+    fn rate_limit_exceeded(&self, peer_addr: SocketAddr, violation: RateLimitViolation) {
+        if violation == RateLimitViolation::Global {
+            return;
+        }
+        if let Some(peer_ip) = self.resolver.get_listener(peer_addr) {
+            // Disconnect on every `MAX_RATE_LIMIT_VIOLATIONS`-th violation only, so that a burst of dropped
+            // events results in a single disconnect.
+            let num_violations =
+                self.cache.insert_inbound_rate_limit_violation(peer_ip, RATE_LIMIT_VIOLATIONS_INTERVAL);
+            if num_violations % MAX_RATE_LIMIT_VIOLATIONS != 0 {
+                debug!("{CONTEXT} Dropped an event from '{peer_ip}', as it exceeds {violation}");
+                return;
+            }
+            warn!("{CONTEXT} Disconnecting from '{peer_ip}' - exceeded {violation} too often");
+            let self_ = self.clone();
+            tokio::spawn(async move {
+                Transport::send(&self_, peer_ip, DisconnectReason::ProtocolViolation.into()).await;
+                // Disconnect from this peer.
+                self_.disconnect(peer_ip);
+            });
+        }
+    }
 }
 
 #[async_trait]
@@ -1069,8 +1212,8 @@ impl<N: Network> Writing for Gateway<N> {
 
     /// Creates an [`Encoder`] used to write the outbound messages to the target stream.
     /// The `side` parameter indicates the connection side **from the node's perspective**.
-    fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.event_codec(peer_addr)
     }
 }
 
@@ -1194,7 +1337,8 @@ impl<N: Network> Gateway<N> {
         // Sample a random nonce.
         let our_nonce = rng.gen();
         // Send a challenge request to the peer.
-        let our_request = ChallengeRequest::new(self.local_ip().port(), self.account.address(), our_nonce);
+        let our_request =
+            ChallengeRequest::new(self.local_ip().port(), self.signer.address(), our_nonce).with_compression();
         send_event(&mut framed, peer_addr, Event::ChallengeRequest(our_request)).await?;
 
         /* Step 2: Receive the peer's challenge response followed by the challenge request. */
@@ -1221,8 +1365,9 @@ impl<N: Network> Gateway<N> {
 
         // Sign the counterparty nonce.
         let response_nonce: u64 = rng.gen();
-        let data = [peer_request.nonce.to_le_bytes(), response_nonce.to_le_bytes()].concat();
-        let Ok(our_signature) = self.account.sign_bytes(&data, rng) else {
+        let (signer, nonce) = (self.signer.clone(), peer_request.nonce);
+        let signature = spawn_blocking!(signer.sign_challenge(nonce, response_nonce, &mut rand::rngs::OsRng));
+        let Ok(our_signature) = signature else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
         // Send the challenge response.
@@ -1231,6 +1376,10 @@ impl<N: Network> Gateway<N> {
 
         // Add the peer to the gateway.
         self.insert_connected_peer(peer_ip, peer_addr, peer_request.address);
+        // If the peer supports compression, compress the large events sent to and from it.
+        if peer_request.supports_compression() {
+            self.compressed_peers.write().insert(peer_ip);
+        }
 
         Ok((peer_ip, framed))
     }
@@ -1251,7 +1400,7 @@ impl<N: Network> Gateway<N> {
         let peer_request = expect_event!(Event::ChallengeRequest, framed, peer_addr);
 
         // Ensure the address is not the same as this node.
-        if self.account.address() == peer_request.address {
+        if self.signer.address() == peer_request.address {
             return Err(error("Skipping request to connect to self".to_string()));
         }
 
@@ -1276,8 +1425,9 @@ impl<N: Network> Gateway<N> {
 
         // Sign the counterparty nonce.
         let response_nonce: u64 = rng.gen();
-        let data = [peer_request.nonce.to_le_bytes(), response_nonce.to_le_bytes()].concat();
-        let Ok(our_signature) = self.account.sign_bytes(&data, rng) else {
+        let (signer, nonce) = (self.signer.clone(), peer_request.nonce);
+        let signature = spawn_blocking!(signer.sign_challenge(nonce, response_nonce, &mut rand::rngs::OsRng));
+        let Ok(our_signature) = signature else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
         // Send the challenge response.
@@ -1287,7 +1437,8 @@ impl<N: Network> Gateway<N> {
         // Sample a random nonce.
         let our_nonce = rng.gen();
         // Send the challenge request.
-        let our_request = ChallengeRequest::new(self.local_ip().port(), self.account.address(), our_nonce);
+        let our_request =
+            ChallengeRequest::new(self.local_ip().port(), self.signer.address(), our_nonce).with_compression();
         send_event(&mut framed, peer_addr, Event::ChallengeRequest(our_request)).await?;
 
         /* Step 3: Receive the challenge response. */
@@ -1303,6 +1454,10 @@ impl<N: Network> Gateway<N> {
         }
         // Add the peer to the gateway.
         self.insert_connected_peer(peer_ip, peer_addr, peer_request.address);
+        // If the peer supports compression, compress the large events sent to and from it.
+        if peer_request.supports_compression() {
+            self.compressed_peers.write().insert(peer_ip);
+        }
 
         Ok((peer_ip, framed))
     }
@@ -1310,8 +1465,9 @@ impl<N: Network> Gateway<N> {
     /// Verifies the given challenge request. Returns a disconnect reason if the request is invalid.
     fn verify_challenge_request(&self, peer_addr: SocketAddr, event: &ChallengeRequest<N>) -> Option<DisconnectReason> {
         // Retrieve the components of the challenge request.
-        let &ChallengeRequest { version, listener_port: _, address, nonce: _ } = event;
+        let &ChallengeRequest { version: _, listener_port: _, address, nonce: _ } = event;
         // Ensure the event protocol version is not outdated.
+        let version = event.protocol_version();
         if version < Event::<N>::VERSION {
             warn!("{CONTEXT} Gateway is dropping '{peer_addr}' on version {version} (outdated)");
             return Some(DisconnectReason::OutdatedClientVersion);
@@ -1397,7 +1553,7 @@ mod prop_tests {
     impl Debug for Gateway<CurrentNetwork> {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
             // TODO implement Debug properly and move it over to production code
-            f.debug_tuple("Gateway").field(&self.account.address()).field(&self.tcp.config()).finish()
+            f.debug_tuple("Gateway").field(&self.signer.address()).field(&self.tcp.config()).finish()
         }
     }
 
@@ -1437,6 +1593,8 @@ mod prop_tests {
                         address.ip(),
                         &[],
                         address.port(),
+                        Default::default(),
+                        Default::default(),
                     )
                     .unwrap()
                 })
@@ -1481,16 +1639,24 @@ mod prop_tests {
         let (storage, _, private_key, dev) = input;
         let account = Account::try_from(private_key).unwrap();
 
-        let gateway =
-            Gateway::new(account.clone(), storage.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port())
-                .unwrap();
+        let gateway = Gateway::new(
+            account.clone(),
+            storage.clone(),
+            storage.ledger().clone(),
+            dev.ip(),
+            &[],
+            dev.port(),
+            Default::default(),
+            Default::default(),
+        )
+        .unwrap();
         let tcp_config = gateway.tcp().config();
         assert_eq!(tcp_config.listener_ip, Some(IpAddr::V4(Ipv4Addr::LOCALHOST)));
         assert_eq!(tcp_config.desired_listening_port, Some(MEMORY_POOL_PORT + dev.port().unwrap()));
 
         let tcp_config = gateway.tcp().config();
         assert_eq!(tcp_config.max_connections, Committee::<CurrentNetwork>::MAX_COMMITTEE_SIZE);
-        assert_eq!(gateway.account().address(), account.address());
+        assert_eq!(gateway.signer().address(), account.address());
     }
 
     #[proptest]
@@ -1498,9 +1664,17 @@ mod prop_tests {
         let (storage, _, private_key, dev) = input;
         let account = Account::try_from(private_key).unwrap();
 
-        let gateway =
-            Gateway::new(account.clone(), storage.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port())
-                .unwrap();
+        let gateway = Gateway::new(
+            account.clone(),
+            storage.clone(),
+            storage.ledger().clone(),
+            dev.ip(),
+            &[],
+            dev.port(),
+            Default::default(),
+            Default::default(),
+        )
+        .unwrap();
         let tcp_config = gateway.tcp().config();
         if let Some(socket_addr) = dev.ip() {
             assert_eq!(tcp_config.listener_ip, Some(socket_addr.ip()));
@@ -1512,7 +1686,7 @@ mod prop_tests {
 
         let tcp_config = gateway.tcp().config();
         assert_eq!(tcp_config.max_connections, Committee::<CurrentNetwork>::MAX_COMMITTEE_SIZE);
-        assert_eq!(gateway.account().address(), account.address());
+        assert_eq!(gateway.signer().address(), account.address());
     }
 
     #[proptest(async = "tokio")]
@@ -1525,8 +1699,17 @@ mod prop_tests {
         let worker_storage = storage.clone();
         let account = Account::try_from(private_key).unwrap();
 
-        let gateway =
-            Gateway::new(account, storage.clone(), storage.ledger().clone(), dev.ip(), &[], dev.port()).unwrap();
+        let gateway = Gateway::new(
+            account,
+            storage.clone(),
+            storage.ledger().clone(),
+            dev.ip(),
+            &[],
+            dev.port(),
+            Default::default(),
+            Default::default(),
+        )
+        .unwrap();
 
         let (primary_sender, _) = init_primary_channels();
 
@@ -1590,8 +1773,17 @@ mod prop_tests {
         // Initialize the storage.
         let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), max_gc_rounds);
         // Initialize the gateway.
-        let gateway =
-            Gateway::new(account.clone(), storage.clone(), ledger.clone(), dev.ip(), &[], dev.port()).unwrap();
+        let gateway = Gateway::new(
+            account.clone(),
+            storage.clone(),
+            ledger.clone(),
+            dev.ip(),
+            &[],
+            dev.port(),
+            Default::default(),
+            Default::default(),
+        )
+        .unwrap();
         // Insert certificate to the storage.
         for certificate in certificates.iter() {
             storage.testing_only_insert_certificate_testing_only(certificate.clone());
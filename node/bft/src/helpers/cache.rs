@@ -32,6 +32,8 @@ pub struct Cache<N: Network> {
     seen_inbound_certificates: RwLock<BTreeMap<i64, HashMap<Field<N>, u32>>>,
     /// The ordered timestamp map of transmission IDs and cache hits.
     seen_inbound_transmissions: RwLock<BTreeMap<i64, HashMap<TransmissionID<N>, u32>>>,
+    /// The ordered timestamp map of peer IPs and their rate limit violations.
+    seen_inbound_rate_limit_violations: RwLock<BTreeMap<i64, HashMap<SocketAddr, u32>>>,
     /// The ordered timestamp map of peer IPs and their cache hits on outbound events.
     seen_outbound_events: RwLock<BTreeMap<i64, HashMap<SocketAddr, u32>>>,
     /// The ordered timestamp map of peer IPs and their cache hits on certificate requests.
@@ -57,6 +59,7 @@ impl<N: Network> Cache<N> {
             seen_inbound_events: Default::default(),
             seen_inbound_certificates: Default::default(),
             seen_inbound_transmissions: Default::default(),
+            seen_inbound_rate_limit_violations: Default::default(),
             seen_outbound_events: Default::default(),
             seen_outbound_certificates: Default::default(),
             seen_outbound_transmissions: Default::default(),
@@ -85,6 +88,11 @@ impl<N: Network> Cache<N> {
     pub fn insert_inbound_transmission(&self, key: TransmissionID<N>, interval_in_secs: i64) -> usize {
         Self::retain_and_insert(&self.seen_inbound_transmissions, key, interval_in_secs)
     }
+
+    /// Inserts a new timestamp for the given peer, returning the number of recent rate limit violations.
+    pub fn insert_inbound_rate_limit_violation(&self, peer_ip: SocketAddr, interval_in_secs: i64) -> usize {
+        Self::retain_and_insert(&self.seen_inbound_rate_limit_violations, peer_ip, interval_in_secs)
+    }
 }
 
 impl<N: Network> Cache<N> {
@@ -289,6 +297,7 @@ mod tests {
        inbound_event,
        inbound_certificate,
        inbound_transmission,
+       inbound_rate_limit_violation,
        outbound_event,
        outbound_certificate,
        outbound_transmission
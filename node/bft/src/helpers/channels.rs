@@ -12,13 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::events::{
-    BatchPropose,
-    BatchSignature,
-    CertificateRequest,
-    CertificateResponse,
-    TransmissionRequest,
-    TransmissionResponse,
+use crate::{
+    events::{
+        BatchPropose,
+        BatchSignature,
+        CertificateRequest,
+        CertificateResponse,
+        TransmissionRequest,
+        TransmissionResponse,
+    },
+    helpers::Priority,
 };
 use snarkos_node_sync::locators::BlockLocators;
 use snarkvm::{
@@ -127,7 +130,8 @@ pub struct PrimarySender<N: Network> {
     pub tx_batch_certified: mpsc::Sender<(SocketAddr, Data<BatchCertificate<N>>)>,
     pub tx_primary_ping: mpsc::Sender<(SocketAddr, Data<BatchCertificate<N>>)>,
     pub tx_unconfirmed_solution: mpsc::Sender<(SolutionID<N>, Data<Solution<N>>, oneshot::Sender<Result<()>>)>,
-    pub tx_unconfirmed_transaction: mpsc::Sender<(N::TransactionID, Data<Transaction<N>>, oneshot::Sender<Result<()>>)>,
+    pub tx_unconfirmed_transaction:
+        mpsc::Sender<(N::TransactionID, Data<Transaction<N>>, Priority, oneshot::Sender<Result<()>>)>,
 }
 
 impl<N: Network> PrimarySender<N> {
@@ -145,16 +149,17 @@ impl<N: Network> PrimarySender<N> {
         callback_receiver.await?
     }
 
-    /// Sends the unconfirmed transaction to the primary.
+    /// Sends the unconfirmed transaction, with its priority, to the primary.
     pub async fn send_unconfirmed_transaction(
         &self,
         transaction_id: N::TransactionID,
         transaction: Data<Transaction<N>>,
+        priority: Priority,
     ) -> Result<()> {
         // Initialize a callback sender and receiver.
         let (callback_sender, callback_receiver) = oneshot::channel();
         // Send the unconfirmed transaction to the primary.
-        self.tx_unconfirmed_transaction.send((transaction_id, transaction, callback_sender)).await?;
+        self.tx_unconfirmed_transaction.send((transaction_id, transaction, priority, callback_sender)).await?;
         // Await the callback to continue.
         callback_receiver.await?
     }
@@ -168,7 +173,7 @@ pub struct PrimaryReceiver<N: Network> {
     pub rx_primary_ping: mpsc::Receiver<(SocketAddr, Data<BatchCertificate<N>>)>,
     pub rx_unconfirmed_solution: mpsc::Receiver<(SolutionID<N>, Data<Solution<N>>, oneshot::Sender<Result<()>>)>,
     pub rx_unconfirmed_transaction:
-        mpsc::Receiver<(N::TransactionID, Data<Transaction<N>>, oneshot::Sender<Result<()>>)>,
+        mpsc::Receiver<(N::TransactionID, Data<Transaction<N>>, Priority, oneshot::Sender<Result<()>>)>,
 }
 
 /// Initializes the primary channels.
@@ -27,6 +27,9 @@ pub use partition::*;
 pub mod pending;
 pub use pending::*;
 
+pub mod priority;
+pub use priority::*;
+
 pub mod proposal;
 pub use proposal::*;
 
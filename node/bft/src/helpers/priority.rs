@@ -0,0 +1,124 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    console::prelude::*,
+    ledger::{
+        block::Transaction,
+        narwhal::{Data, Transmission},
+    },
+};
+
+use std::cmp::Ordering;
+
+/// The priority of a transaction, measured as its priority fee per byte.
+#[derive(Copy, Clone, Debug)]
+pub struct Priority {
+    /// The priority fee, in microcredits.
+    fee: u64,
+    /// The size of the transaction, in bytes.
+    size: u64,
+}
+
+impl Default for Priority {
+    /// Initializes the lowest priority.
+    fn default() -> Self {
+        Self::new(0, 1)
+    }
+}
+
+impl Priority {
+    /// Initializes a new priority, from the given priority fee and transaction size.
+    pub fn new(fee: u64, size: u64) -> Self {
+        // Note: The size is bounded below by one byte, so that the fee per byte is always defined.
+        Self { fee, size: size.max(1) }
+    }
+
+    /// Returns the priority of the given transaction.
+    pub fn from_transaction<N: Network>(transaction: &Transaction<N>) -> Result<Self> {
+        let fee = *transaction.priority_fee_amount()?;
+        let size = transaction.to_bytes_le()?.len() as u64;
+        Ok(Self::new(fee, size))
+    }
+
+    /// Returns the priority of the given transmission.
+    /// Note: Solutions, ratifications, and malformed transactions have the lowest priority.
+    /// Note: A transaction in a buffer is deserialized, so this method should only be used off the hot path,
+    /// e.g. to reinsert transmissions, as the priority of a new transaction is determined by consensus.
+    pub fn from_transmission<N: Network>(transmission: &Transmission<N>) -> Self {
+        match transmission {
+            Transmission::Transaction(Data::Object(transaction)) => {
+                Self::from_transaction(transaction).unwrap_or_default()
+            }
+            Transmission::Transaction(Data::Buffer(bytes)) => Transaction::<N>::read_le(&bytes[..])
+                .and_then(|transaction| transaction.priority_fee_amount())
+                .map(|fee| Self::new(*fee, bytes.len() as u64))
+                .unwrap_or_default(),
+            _ => Self::default(),
+        }
+    }
+
+    /// Returns the priority fee, in microcredits.
+    pub const fn fee(&self) -> u64 {
+        self.fee
+    }
+
+    /// Returns the size of the transaction, in bytes.
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+impl Ord for Priority {
+    /// Compares the fees per byte, without any loss of precision.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let lhs = self.fee as u128 * other.size as u128;
+        let rhs = other.fee as u128 * self.size as u128;
+        lhs.cmp(&rhs)
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Priority {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Priority {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_ordering() {
+        // The priority is the fee per byte.
+        assert!(Priority::new(200, 100) > Priority::new(100, 100));
+        assert!(Priority::new(100, 50) > Priority::new(100, 100));
+        assert_eq!(Priority::new(100, 100), Priority::new(200, 200));
+        // A transaction without a priority fee has the lowest priority.
+        assert_eq!(Priority::new(0, 100), Priority::default());
+        assert!(Priority::new(1, u64::MAX) > Priority::default());
+        // The comparison does not overflow.
+        assert!(Priority::new(u64::MAX, 1) > Priority::new(u64::MAX - 1, 1));
+        // The size is bounded below by one byte.
+        assert_eq!(Priority::new(100, 0).size(), 1);
+    }
+}
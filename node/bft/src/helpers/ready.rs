@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::helpers::Priority;
 use snarkvm::{
     console::prelude::*,
     ledger::{
@@ -23,12 +24,36 @@ use snarkvm::{
 
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// The ordering key of a transmission, which sorts solutions first, then transactions by decreasing priority,
+/// and then by insertion order.
+type OrderKey = (bool, Reverse<Priority>, u64);
+
+#[derive(Debug)]
+struct Transmissions<N: Network> {
+    /// The map of `(transmission ID, (transmission, ordering key))` entries.
+    entries: HashMap<TransmissionID<N>, (Transmission<N>, OrderKey)>,
+    /// The transmission IDs, in the order in which they are drained.
+    order: BTreeMap<OrderKey, TransmissionID<N>>,
+    /// The insertion counter, which breaks ties between transmissions of equal priority.
+    sequence: u64,
+}
+
+impl<N: Network> Default for Transmissions<N> {
+    fn default() -> Self {
+        Self { entries: Default::default(), order: Default::default(), sequence: 0 }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Ready<N: Network> {
-    /// The current map of `(transmission ID, transmission)` entries.
-    transmissions: Arc<RwLock<IndexMap<TransmissionID<N>, Transmission<N>>>>,
+    /// The current transmissions, ordered by priority.
+    transmissions: Arc<RwLock<Transmissions<N>>>,
 }
 
 impl<N: Network> Default for Ready<N> {
@@ -46,42 +71,43 @@ impl<N: Network> Ready<N> {
 
     /// Returns `true` if the ready queue is empty.
     pub fn is_empty(&self) -> bool {
-        self.transmissions.read().is_empty()
+        self.transmissions.read().entries.is_empty()
     }
 
     /// Returns the number of transmissions in the ready queue.
     pub fn num_transmissions(&self) -> usize {
-        self.transmissions.read().len()
+        self.transmissions.read().entries.len()
     }
 
     /// Returns the number of ratifications in the ready queue.
     pub fn num_ratifications(&self) -> usize {
-        self.transmissions.read().keys().filter(|id| matches!(id, TransmissionID::Ratification)).count()
+        self.transmissions.read().entries.keys().filter(|id| matches!(id, TransmissionID::Ratification)).count()
     }
 
     /// Returns the number of solutions in the ready queue.
     pub fn num_solutions(&self) -> usize {
-        self.transmissions.read().keys().filter(|id| matches!(id, TransmissionID::Solution(..))).count()
+        self.transmissions.read().entries.keys().filter(|id| matches!(id, TransmissionID::Solution(..))).count()
     }
 
     /// Returns the number of transactions in the ready queue.
     pub fn num_transactions(&self) -> usize {
-        self.transmissions.read().keys().filter(|id| matches!(id, TransmissionID::Transaction(..))).count()
+        self.transmissions.read().entries.keys().filter(|id| matches!(id, TransmissionID::Transaction(..))).count()
     }
 
-    /// Returns the transmission IDs in the ready queue.
+    /// Returns the transmission IDs in the ready queue, in the order in which they are drained.
     pub fn transmission_ids(&self) -> IndexSet<TransmissionID<N>> {
-        self.transmissions.read().keys().copied().collect()
+        self.transmissions.read().order.values().copied().collect()
     }
 
-    /// Returns the transmissions in the ready queue.
+    /// Returns the transmissions in the ready queue, in the order in which they are drained.
     pub fn transmissions(&self) -> IndexMap<TransmissionID<N>, Transmission<N>> {
-        self.transmissions.read().clone()
+        let transmissions = self.transmissions.read();
+        transmissions.order.values().map(|id| (*id, transmissions.entries[id].0.clone())).collect()
     }
 
     /// Returns the solutions in the ready queue.
     pub fn solutions(&self) -> impl '_ + Iterator<Item = (SolutionID<N>, Data<Solution<N>>)> {
-        self.transmissions.read().clone().into_iter().filter_map(|(id, transmission)| match (id, transmission) {
+        self.transmissions().into_iter().filter_map(|(id, transmission)| match (id, transmission) {
             (TransmissionID::Solution(id), Transmission::Solution(solution)) => Some((id, solution)),
             _ => None,
         })
@@ -89,7 +115,7 @@ impl<N: Network> Ready<N> {
 
     /// Returns the transactions in the ready queue.
     pub fn transactions(&self) -> impl '_ + Iterator<Item = (N::TransactionID, Data<Transaction<N>>)> {
-        self.transmissions.read().clone().into_iter().filter_map(|(id, transmission)| match (id, transmission) {
+        self.transmissions().into_iter().filter_map(|(id, transmission)| match (id, transmission) {
             (TransmissionID::Transaction(id), Transmission::Transaction(tx)) => Some((id, tx)),
             _ => None,
         })
@@ -99,32 +125,61 @@ impl<N: Network> Ready<N> {
 impl<N: Network> Ready<N> {
     /// Returns `true` if the ready queue contains the specified `transmission ID`.
     pub fn contains(&self, transmission_id: impl Into<TransmissionID<N>>) -> bool {
-        self.transmissions.read().contains_key(&transmission_id.into())
+        self.transmissions.read().entries.contains_key(&transmission_id.into())
     }
 
     /// Returns the transmission, given the specified `transmission ID`.
     pub fn get(&self, transmission_id: impl Into<TransmissionID<N>>) -> Option<Transmission<N>> {
-        self.transmissions.read().get(&transmission_id.into()).cloned()
+        self.transmissions.read().entries.get(&transmission_id.into()).map(|(transmission, _)| transmission.clone())
     }
 
-    /// Inserts the specified (`transmission ID`, `transmission`) to the ready queue.
+    /// Inserts the specified (`transmission ID`, `transmission`) to the ready queue, with the lowest priority.
     /// Returns `true` if the transmission is new, and was added to the ready queue.
     pub fn insert(&self, transmission_id: impl Into<TransmissionID<N>>, transmission: Transmission<N>) -> bool {
+        self.insert_with_priority(transmission_id, transmission, Priority::default())
+    }
+
+    /// Inserts the specified (`transmission ID`, `transmission`) to the ready queue, with the given priority.
+    /// Returns `true` if the transmission is new, and was added to the ready queue.
+    pub fn insert_with_priority(
+        &self,
+        transmission_id: impl Into<TransmissionID<N>>,
+        transmission: Transmission<N>,
+        priority: Priority,
+    ) -> bool {
         let transmission_id = transmission_id.into();
-        // Insert the transmission ID.
-        let is_new = self.transmissions.write().insert(transmission_id, transmission).is_none();
-        // Return whether the transmission is new.
-        is_new
+        // Acquire the write lock.
+        let mut transmissions = self.transmissions.write();
+        // Ensure the transmission is new.
+        if transmissions.entries.contains_key(&transmission_id) {
+            return false;
+        }
+        // Determine the ordering key of the transmission.
+        let is_transaction = matches!(transmission_id, TransmissionID::Transaction(..));
+        let key = (is_transaction, Reverse(priority), transmissions.sequence);
+        transmissions.sequence += 1;
+        // Insert the transmission.
+        transmissions.order.insert(key, transmission_id);
+        transmissions.entries.insert(transmission_id, (transmission, key));
+        true
     }
 
     /// Removes up to the specified number of transmissions and returns them.
+    ///
+    /// Solutions are drained first, followed by the transactions in decreasing order of priority fee per byte.
+    /// Ties are broken by insertion order, so the drained transmissions are deterministic for a given ready queue.
     pub fn drain(&self, num_transmissions: usize) -> IndexMap<TransmissionID<N>, Transmission<N>> {
         // Acquire the write lock.
         let mut transmissions = self.transmissions.write();
-        // Determine the number of transmissions to drain.
-        let range = 0..transmissions.len().min(num_transmissions);
-        // Drain the transmission IDs.
-        transmissions.drain(range).collect::<IndexMap<_, _>>()
+        // Drain the transmissions with the highest priority.
+        let mut drained = IndexMap::with_capacity(num_transmissions.min(transmissions.entries.len()));
+        while drained.len() < num_transmissions {
+            let Some((_, transmission_id)) = transmissions.order.pop_first() else { break };
+            if let Some((transmission, _)) = transmissions.entries.remove(&transmission_id) {
+                drained.insert(transmission_id, transmission);
+            }
+        }
+        drained
     }
 }
 
@@ -197,6 +252,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ready_drain_order() {
+        let rng = &mut TestRng::default();
+
+        // Sample random fake bytes.
+        let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+
+        // Initialize the ready queue.
+        let ready = Ready::<CurrentNetwork>::new();
+
+        // Initialize the transmission IDs.
+        let transaction_id_1 = TransmissionID::Transaction(Field::<CurrentNetwork>::rand(rng).into());
+        let solution_id_1 = TransmissionID::Solution(rng.gen::<u64>().into());
+        let transaction_id_2 = TransmissionID::Transaction(Field::<CurrentNetwork>::rand(rng).into());
+        let solution_id_2 = TransmissionID::Solution(rng.gen::<u64>().into());
+
+        // Insert the transmissions, interleaving transactions and solutions.
+        // Note: The transactions are inserted without a priority, so they share the lowest priority.
+        assert!(ready.insert(transaction_id_1, Transmission::Transaction(data(rng))));
+        assert!(ready.insert(solution_id_1, Transmission::Solution(data(rng))));
+        assert!(ready.insert(transaction_id_2, Transmission::Transaction(data(rng))));
+        assert!(ready.insert(solution_id_2, Transmission::Solution(data(rng))));
+
+        // Check that the solutions are drained first, and that ties are broken by insertion order.
+        assert_eq!(ready.drain(3).into_keys().collect::<Vec<_>>(), vec![
+            solution_id_1,
+            solution_id_2,
+            transaction_id_1
+        ]);
+        assert_eq!(ready.drain(3).into_keys().collect::<Vec<_>>(), vec![transaction_id_2]);
+        assert!(ready.is_empty());
+    }
+
+    #[test]
+    fn test_ready_drain_priority() {
+        let rng = &mut TestRng::default();
+
+        // Sample random fake bytes.
+        let data = |rng: &mut TestRng| Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>()));
+
+        // Initialize the ready queue.
+        let ready = Ready::<CurrentNetwork>::new();
+
+        // Initialize the transmission IDs.
+        let [low, high, medium, also_high] =
+            [(); 4].map(|_| TransmissionID::Transaction(Field::<CurrentNetwork>::rand(rng).into()));
+        let solution_id = TransmissionID::Solution(rng.gen::<u64>().into());
+
+        // Insert the transactions with different priority fees per byte.
+        assert!(ready.insert_with_priority(low, Transmission::Transaction(data(rng)), Priority::new(1, 100)));
+        assert!(ready.insert_with_priority(high, Transmission::Transaction(data(rng)), Priority::new(100, 100)));
+        assert!(ready.insert_with_priority(medium, Transmission::Transaction(data(rng)), Priority::new(10, 100)));
+        assert!(ready.insert_with_priority(also_high, Transmission::Transaction(data(rng)), Priority::new(50, 50)));
+        assert!(ready.insert(solution_id, Transmission::Solution(data(rng))));
+        // Ensure a duplicate does not change the priority of the transmission.
+        assert!(!ready.insert_with_priority(low, Transmission::Transaction(data(rng)), Priority::new(1000, 1)));
+
+        // Check that the transmission IDs are listed in the order in which they are drained.
+        let expected = vec![solution_id, high, also_high, medium, low];
+        assert_eq!(ready.transmission_ids().into_iter().collect::<Vec<_>>(), expected);
+        // Check that the solution is drained first, and then the transactions by decreasing priority.
+        assert_eq!(ready.drain(2).into_keys().collect::<Vec<_>>(), expected[..2]);
+        assert_eq!(ready.drain(5).into_keys().collect::<Vec<_>>(), expected[2..]);
+        assert!(ready.is_empty());
+    }
+
     #[test]
     fn test_ready_duplicate() {
         use rand::RngCore;
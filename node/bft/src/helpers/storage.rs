@@ -12,9 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::{check_timestamp_for_liveness, fmt_id};
+use crate::helpers::{check_timestamp_for_liveness, fmt_id, is_proposal_expired, now};
 use snarkos_node_bft_ledger_service::LedgerService;
-use snarkos_node_bft_storage_service::StorageService;
+use snarkos_node_bft_storage_service::{SignedBatch, StorageService};
 use snarkvm::{
     ledger::{
         block::{Block, Transaction},
@@ -225,6 +225,8 @@ impl<N: Network> Storage<N> {
             }
             // Update the GC round.
             self.gc_round.store(next_gc_round, Ordering::SeqCst);
+            #[cfg(feature = "metrics")]
+            metrics::gauge(metrics::bft::GC_ROUND, next_gc_round as f64);
         }
     }
 }
@@ -264,6 +266,32 @@ impl<N: Network> Storage<N> {
         self.transmissions.get_transmission(transmission_id.into())
     }
 
+    /// Returns the batch signed by this validator for the given author in the given round, if any.
+    pub fn get_signed_batch(&self, author: Address<N>, round: u64) -> Option<SignedBatch<N>> {
+        self.transmissions.get_signed_batch(author, round)
+    }
+
+    /// Records the given batch header in the signing history, before it is signed by this validator.
+    ///
+    /// Returns an error if a different batch from the same author was already signed for the same round,
+    /// and that batch has not yet expired. The record persists across restarts for persistent storage.
+    pub fn record_signed_batch(&self, batch_header: &BatchHeader<N>) -> Result<()> {
+        let (author, round, batch_id) = (batch_header.author(), batch_header.round(), batch_header.batch_id());
+        if let Some(signed_batch) = self.transmissions.get_signed_batch(author, round) {
+            // If this batch was already signed, there is nothing to record.
+            if signed_batch.batch_id == batch_id {
+                return Ok(());
+            }
+            // Ensure the previously-signed batch has expired.
+            ensure!(
+                is_proposal_expired(now(), signed_batch.timestamp),
+                "Refusing to sign a second batch from '{author}' in round {round} prior to expiration"
+            );
+        }
+        let signed_batch = SignedBatch { batch_id, timestamp: batch_header.timestamp() };
+        self.transmissions.insert_signed_batch(author, round, signed_batch)
+    }
+
     /// Returns the round for the given `certificate ID`.
     /// If the certificate ID does not exist in storage, `None` is returned.
     pub fn get_round_for_certificate(&self, certificate_id: Field<N>) -> Option<u64> {
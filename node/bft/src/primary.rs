@@ -39,9 +39,10 @@ use crate::{
     PRIMARY_PING_IN_MS,
     WORKER_PING_IN_MS,
 };
-use snarkos_account::Account;
+use snarkos_account::Signer;
 use snarkos_node_bft_events::PrimaryPing;
 use snarkos_node_bft_ledger_service::LedgerService;
+use snarkos_node_tcp::{InboundLimits, OutboundLimits, CONTEXT_TARGET};
 use snarkvm::{
     console::{
         account::Signature,
@@ -66,12 +67,13 @@ use std::{
     future::Future,
     net::SocketAddr,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::{
     sync::{Mutex as TMutex, OnceCell},
     task::JoinHandle,
 };
+use tracing::Instrument;
 
 /// A helper type for an optional proposed batch.
 pub type ProposedBatch<N> = RwLock<Option<Proposal<N>>>;
@@ -94,6 +96,8 @@ pub struct Primary<N: Network> {
     proposed_batch: Arc<ProposedBatch<N>>,
     /// The timestamp of the most recent proposed batch.
     latest_proposed_batch_timestamp: Arc<RwLock<i64>>,
+    /// The instant at which the most recent batch was proposed, to measure the time to reach quorum.
+    latest_proposed_batch_instant: Arc<RwLock<Option<Instant>>>,
     /// The recently-signed batch proposals (a map from the address to the round, timestamp, batch ID, and signature).
     signed_proposals: Arc<RwLock<HashMap<Address<N>, (u64, i64, Field<N>, Signature<N>)>>>,
     /// The spawned handles.
@@ -108,15 +112,26 @@ impl<N: Network> Primary<N> {
 
     /// Initializes a new primary instance.
     pub fn new(
-        account: Account<N>,
+        signer: impl Into<Signer<N>>,
         storage: Storage<N>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
         dev: Option<u16>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
         // Initialize the gateway.
-        let gateway = Gateway::new(account, storage.clone(), ledger.clone(), ip, trusted_validators, dev)?;
+        let gateway = Gateway::new(
+            signer,
+            storage.clone(),
+            ledger.clone(),
+            ip,
+            trusted_validators,
+            dev,
+            inbound_limits,
+            outbound_limits,
+        )?;
         // Initialize the sync module.
         let sync = Sync::new(gateway.clone(), storage.clone(), ledger.clone());
         // Initialize the primary instance.
@@ -129,6 +144,7 @@ impl<N: Network> Primary<N> {
             bft_sender: Default::default(),
             proposed_batch: Default::default(),
             latest_proposed_batch_timestamp: Default::default(),
+            latest_proposed_batch_instant: Default::default(),
             signed_proposals: Default::default(),
             handles: Default::default(),
             propose_lock: Default::default(),
@@ -204,6 +220,11 @@ impl<N: Network> Primary<N> {
         &self.gateway
     }
 
+    /// Returns the sync module.
+    pub const fn sync(&self) -> &Sync<N> {
+        &self.sync
+    }
+
     /// Returns the storage.
     pub const fn storage(&self) -> &Storage<N> {
         &self.storage
@@ -329,13 +350,13 @@ impl<N: Network> Primary<N> {
         metrics::gauge(metrics::bft::PROPOSAL_ROUND, round as f64);
 
         // Ensure that the primary does not create a new proposal too quickly.
-        if let Err(e) = self.check_proposal_timestamp(previous_round, self.gateway.account().address(), now()) {
+        if let Err(e) = self.check_proposal_timestamp(previous_round, self.gateway.signer().address(), now()) {
             debug!("Primary is safely skipping a batch proposal - {}", format!("{e}").dimmed());
             return Ok(());
         }
 
         // Ensure the primary has not proposed a batch for this round before.
-        if self.storage.contains_certificate_in_round_from(round, self.gateway.account().address()) {
+        if self.storage.contains_certificate_in_round_from(round, self.gateway.signer().address()) {
             // If a BFT sender was provided, attempt to advance the current round.
             if let Some(bft_sender) = self.bft_sender.get() {
                 match bft_sender.send_primary_round_to_bft(self.current_round()).await {
@@ -360,7 +381,7 @@ impl<N: Network> Primary<N> {
             // Retrieve the connected validator addresses.
             let mut connected_validators = self.gateway.connected_addresses();
             // Append the primary to the set.
-            connected_validators.insert(self.gateway.account().address());
+            connected_validators.insert(self.gateway.signer().address());
             // If quorum threshold is not reached, return early.
             if !committee_lookback.is_quorum_threshold_reached(&connected_validators) {
                 debug!(
@@ -491,8 +512,8 @@ impl<N: Network> Primary<N> {
         /* Proceeding to sign & propose the batch. */
         info!("Proposing a batch with {} transmissions for round {round}...", transmissions.len());
 
-        // Retrieve the private key.
-        let private_key = *self.gateway.account().private_key();
+        // Retrieve the signer.
+        let signer = self.gateway.signer().clone();
         // Retrieve the committee ID.
         let committee_id = committee_lookback.id();
         // Prepare the transmission IDs.
@@ -500,8 +521,7 @@ impl<N: Network> Primary<N> {
         // Prepare the previous batch certificate IDs.
         let previous_certificate_ids = previous_certificates.into_iter().map(|c| c.id()).collect();
         // Sign the batch header.
-        let batch_header = spawn_blocking!(BatchHeader::new(
-            &private_key,
+        let batch_header = spawn_blocking!(signer.sign_batch_header(
             round,
             current_timestamp,
             committee_id,
@@ -511,10 +531,18 @@ impl<N: Network> Primary<N> {
         ))?;
         // Construct the proposal.
         let proposal = Proposal::new(committee_lookback, batch_header.clone(), transmissions)?;
+        // Record the batch in the signing history, before it is broadcast.
+        let (storage, header) = (self.storage.clone(), batch_header.clone());
+        if let Err(e) = spawn_blocking!(storage.record_signed_batch(&header)) {
+            // Reinsert the transmissions back into the ready queue for the next proposal.
+            self.reinsert_transmissions_into_workers(proposal)?;
+            return Err(e);
+        }
         // Broadcast the batch to all validators for signing.
         self.gateway.broadcast(Event::BatchPropose(batch_header.into()));
         // Set the timestamp of the latest proposed batch.
         *self.latest_proposed_batch_timestamp.write() = proposal.timestamp();
+        *self.latest_proposed_batch_instant.write() = Some(Instant::now());
         // Set the proposed batch.
         *self.proposed_batch.write() = Some(proposal);
         Ok(())
@@ -563,7 +591,7 @@ impl<N: Network> Primary<N> {
             bail!("Malicious peer - proposed batch from a non-committee member ({batch_author})");
         }
         // Ensure the batch proposal is not from the current primary.
-        if self.gateway.account().address() == batch_author {
+        if self.gateway.signer().address() == batch_author {
             bail!("Invalid peer - proposed batch from myself ({batch_author})");
         }
 
@@ -657,9 +685,12 @@ impl<N: Network> Primary<N> {
         let batch_id = batch_header.batch_id();
         // Retrieve the proposal timestamp.
         let timestamp = batch_header.timestamp();
+        // Record the batch in the signing history, refusing to sign a conflicting batch.
+        let (storage, header) = (self.storage.clone(), batch_header.clone());
+        spawn_blocking!(storage.record_signed_batch(&header))?;
         // Sign the batch ID.
-        let account = self.gateway.account().clone();
-        let signature = spawn_blocking!(account.sign(&[batch_id], &mut rand::thread_rng()))?;
+        let (signer, header) = (self.gateway.signer().clone(), batch_header.clone());
+        let signature = spawn_blocking!(signer.sign_batch(&header, &mut rand::thread_rng()))?;
 
         // Ensure the proposal has not already been signed.
         //
@@ -726,7 +757,7 @@ impl<N: Network> Primary<N> {
             bail!("Malicious peer - batch signature is from a different validator ({signer})");
         }
         // Ensure the batch signature is not from the current primary.
-        if self.gateway.account().address() == signer {
+        if self.gateway.signer().address() == signer {
             bail!("Invalid peer - received a batch signature from myself ({signer})");
         }
 
@@ -791,7 +822,17 @@ impl<N: Network> Primary<N> {
         }
 
         #[cfg(feature = "metrics")]
-        metrics::increment_gauge(metrics::bft::CERTIFIED_BATCHES, 1.0);
+        {
+            metrics::increment_gauge(metrics::bft::CERTIFIED_BATCHES, 1.0);
+            // Note: The author of the batch is not a nonsigner, but does not send a batch signature.
+            let num_nonsigners = proposal.nonsigners(&committee_lookback).len();
+            let num_signatures = committee_lookback.num_members().saturating_sub(num_nonsigners + 1);
+            metrics::gauge(metrics::bft::PROPOSAL_NONSIGNERS, num_nonsigners as f64);
+            metrics::histogram(metrics::bft::PROPOSAL_SIGNATURES, num_signatures as f64);
+            if let Some(proposed_at) = *self.latest_proposed_batch_instant.read() {
+                metrics::histogram(metrics::bft::PROPOSAL_QUORUM_LATENCY, proposed_at.elapsed().as_secs_f64());
+            }
+        }
         Ok(())
     }
 
@@ -825,7 +866,7 @@ impl<N: Network> Primary<N> {
             bail!("Malicious peer - Received a batch certificate from an unauthorized validator IP ({peer_ip})");
         }
         // Ensure the batch certificate is not from the current primary.
-        if self.gateway.account().address() == author {
+        if self.gateway.signer().address() == author {
             bail!("Received a batch certificate for myself ({author})");
         }
 
@@ -907,7 +948,7 @@ impl<N: Network> Primary<N> {
                     // Retrieve the latest certificate of the primary.
                     let primary_certificate = {
                         // Retrieve the primary address.
-                        let primary_address = self_.gateway.account().address();
+                        let primary_address = self_.gateway.signer().address();
 
                         // Iterate backwards from the latest round to find the primary certificate.
                         let mut certificate = None;
@@ -1012,7 +1053,8 @@ impl<N: Network> Primary<N> {
                 // If there is no proposed batch, attempt to propose a batch.
                 // Note: Do NOT spawn a task around this function call. Proposing a batch is a critical path,
                 // and only one batch needs be proposed at a time.
-                if let Err(e) = self_.propose_batch().await {
+                let span = info_span!(target: CONTEXT_TARGET, "round", round = self_.current_round());
+                if let Err(e) = self_.propose_batch().instrument(span).await {
                     warn!("Cannot propose a batch - {e}");
                 }
             }
@@ -1107,7 +1149,8 @@ impl<N: Network> Primary<N> {
         // Process the unconfirmed transactions.
         let self_ = self.clone();
         self.spawn(async move {
-            while let Some((transaction_id, transaction, callback)) = rx_unconfirmed_transaction.recv().await {
+            while let Some((transaction_id, transaction, priority, callback)) = rx_unconfirmed_transaction.recv().await
+            {
                 trace!("Primary - Received an unconfirmed transaction '{}'", fmt_id(transaction_id));
                 // Compute the worker ID.
                 let Ok(worker_id) = assign_to_worker::<N>(&transaction_id, self_.num_workers()) else {
@@ -1119,7 +1162,7 @@ impl<N: Network> Primary<N> {
                     // Retrieve the worker.
                     let worker = &self_.workers[worker_id as usize];
                     // Process the unconfirmed transaction.
-                    let result = worker.process_unconfirmed_transaction(transaction_id, transaction).await;
+                    let result = worker.process_unconfirmed_transaction(transaction_id, transaction, priority).await;
                     // Send the result to the callback.
                     callback.send(result).ok();
                 });
@@ -1147,6 +1190,14 @@ impl<N: Network> Primary<N> {
             let proposal = self.proposed_batch.write().take();
             if let Some(proposal) = proposal {
                 debug!("Cleared expired proposal for round {}", proposal.round());
+                #[cfg(feature = "metrics")]
+                {
+                    metrics::increment_counter(metrics::bft::PROPOSALS_EXPIRED);
+                    if let Ok(committee_lookback) = self.ledger.get_committee_lookback_for_round(proposal.round()) {
+                        let num_nonsigners = proposal.nonsigners(&committee_lookback).len();
+                        metrics::gauge(metrics::bft::PROPOSAL_NONSIGNERS, num_nonsigners as f64);
+                    }
+                }
                 self.reinsert_transmissions_into_workers(proposal)?;
             }
         }
@@ -1197,7 +1248,8 @@ impl<N: Network> Primary<N> {
 
             // If the node is ready, propose a batch for the next round.
             if is_ready {
-                self.propose_batch().await?;
+                let span = info_span!(target: CONTEXT_TARGET, "round", round = self.current_round());
+                self.propose_batch().instrument(span).await?;
             }
         }
         Ok(())
@@ -1235,7 +1287,7 @@ impl<N: Network> Primary<N> {
         let previous_timestamp = match self.storage.get_certificate_for_round_with_author(previous_round, author) {
             // Ensure that the previous certificate was created at least `MIN_BATCH_DELAY_IN_MS` seconds ago.
             Some(certificate) => certificate.timestamp(),
-            None => match self.gateway.account().address() == author {
+            None => match self.gateway.signer().address() == author {
                 // If we are the author, then ensure the previous proposal was created at least `MIN_BATCH_DELAY_IN_MS` seconds ago.
                 true => *self.latest_proposed_batch_timestamp.read(),
                 // If we do not see a previous certificate for the author, then proceed optimistically.
@@ -1557,7 +1609,8 @@ impl<N: Network> Primary<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::PROPOSAL_EXPIRATION_IN_SECS;
+    use crate::{helpers::Priority, PROPOSAL_EXPIRATION_IN_SECS};
+    use snarkos_account::Account;
     use snarkos_node_bft_ledger_service::MockLedgerService;
     use snarkos_node_bft_storage_service::BFTMemoryService;
     use snarkvm::{
@@ -1596,7 +1649,8 @@ mod tests {
         let storage = Storage::new(ledger.clone(), Arc::new(BFTMemoryService::new()), 10);
 
         // Initialize the primary.
-        let mut primary = Primary::new(account, storage, ledger, None, &[], None).unwrap();
+        let mut primary =
+            Primary::new(account, storage, ledger, None, &[], None, Default::default(), Default::default()).unwrap();
 
         // Construct a worker instance.
         primary.workers = Arc::from([Worker::new(
@@ -1690,7 +1744,7 @@ mod tests {
         // Each committee member signs the batch.
         let mut signatures = Vec::with_capacity(accounts.len() - 1);
         for (socket_addr, account) in accounts {
-            if account.address() == primary.gateway.account().address() {
+            if account.address() == primary.gateway.signer().address() {
                 continue;
             }
             let batch_id = primary.proposed_batch.read().as_ref().unwrap().batch_id();
@@ -1816,7 +1870,10 @@ mod tests {
 
         // Store it on one of the workers.
         primary.workers[0].process_unconfirmed_solution(solution_id, solution).await.unwrap();
-        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+        primary.workers[0]
+            .process_unconfirmed_transaction(transaction_id, transaction, Priority::default())
+            .await
+            .unwrap();
 
         // Try to propose a batch again. This time, it should succeed.
         assert!(primary.propose_batch().await.is_ok());
@@ -1846,7 +1903,10 @@ mod tests {
 
         // Store it on one of the workers.
         primary.workers[0].process_unconfirmed_solution(solution_id, solution).await.unwrap();
-        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+        primary.workers[0]
+            .process_unconfirmed_transaction(transaction_id, transaction, Priority::default())
+            .await
+            .unwrap();
 
         // Propose a batch again. This time, it should succeed.
         assert!(primary.propose_batch().await.is_ok());
@@ -1878,7 +1938,10 @@ mod tests {
 
         // Store it on one of the workers.
         primary.workers[0].process_unconfirmed_solution(solution_commitment, solution).await.unwrap();
-        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+        primary.workers[0]
+            .process_unconfirmed_transaction(transaction_id, transaction, Priority::default())
+            .await
+            .unwrap();
 
         // Check that the worker has 2 transmissions.
         assert_eq!(primary.workers[0].num_transmissions(), 2);
@@ -1945,7 +2008,10 @@ mod tests {
 
         // Store it on one of the workers.
         primary.workers[0].process_unconfirmed_solution(solution_id, solution).await.unwrap();
-        primary.workers[0].process_unconfirmed_transaction(transaction_id, transaction).await.unwrap();
+        primary.workers[0]
+            .process_unconfirmed_transaction(transaction_id, transaction, Priority::default())
+            .await
+            .unwrap();
 
         // Propose a batch again. This time, it should succeed.
         assert!(primary.propose_batch().await.is_ok());
@@ -2179,6 +2245,70 @@ mod tests {
         assert_eq!(batch_id, original_batch_id);
     }
 
+    #[tokio::test]
+    async fn test_batch_propose_from_peer_conflict_after_restart() {
+        let round = 2;
+        let mut rng = TestRng::default();
+        let (primary, accounts) = primary_without_handlers(&mut rng).await;
+
+        // Generate certificates.
+        let previous_certificates = store_certificate_chain(&primary, &accounts, round, &mut rng);
+
+        // Sleep for a while to ensure the primary is ready to propose the next round.
+        tokio::time::sleep(Duration::from_secs(MIN_BATCH_DELAY_IN_SECS)).await;
+
+        // Create a valid proposal with an author that isn't the primary.
+        let peer_account = &accounts[1];
+        let peer_ip = peer_account.0;
+        let proposal = create_test_proposal(
+            &peer_account.1,
+            primary.ledger.current_committee().unwrap(),
+            round,
+            previous_certificates.clone(),
+            now(),
+            &mut rng,
+        );
+
+        // Make sure the primary is aware of the transmissions in the proposal.
+        for (transmission_id, transmission) in proposal.transmissions() {
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+        }
+
+        // The author must be known to resolver to pass propose checks.
+        primary.gateway.resolver().insert_peer(peer_ip, peer_ip, peer_account.1.address());
+
+        // Try to process the batch proposal from the peer, should succeed.
+        primary.process_batch_propose_from_peer(peer_ip, (*proposal.batch_header()).clone().into()).await.unwrap();
+        // Ensure the batch was recorded in the signing history.
+        let signed_batch = primary.storage.get_signed_batch(peer_account.1.address(), round).unwrap();
+        assert_eq!(signed_batch.batch_id, proposal.batch_id());
+
+        // Simulate a restart, which clears the cached signatures.
+        primary.signed_proposals.write().clear();
+
+        // Construct a conflicting proposal for the same round.
+        let new_proposal = create_test_proposal(
+            &peer_account.1,
+            primary.ledger.current_committee().unwrap(),
+            round,
+            previous_certificates,
+            now(),
+            &mut rng,
+        );
+        for (transmission_id, transmission) in new_proposal.transmissions() {
+            primary.workers[0].process_transmission_from_peer(peer_ip, *transmission_id, transmission.clone())
+        }
+
+        // Try to process the conflicting proposal, should be refused by the signing history.
+        assert!(
+            primary
+                .process_batch_propose_from_peer(peer_ip, (*new_proposal.batch_header()).clone().into())
+                .await
+                .is_err()
+        );
+        assert!(primary.signed_proposals.read().get(&peer_account.1.address()).is_none());
+    }
+
     #[tokio::test]
     async fn test_batch_propose_from_peer_after_expiration() {
         let round = 2;
@@ -2330,7 +2460,7 @@ mod tests {
         let round = 1;
         let timestamp = now() + MIN_BATCH_DELAY_IN_SECS as i64;
         let proposal = create_test_proposal(
-            primary.gateway.account(),
+            primary.gateway.signer().account().unwrap(),
             primary.ledger.current_committee().unwrap(),
             round,
             Default::default(),
@@ -2353,7 +2483,7 @@ mod tests {
         }
 
         // Check the certificate was created and stored by the primary.
-        assert!(primary.storage.contains_certificate_in_round_from(round, primary.gateway.account().address()));
+        assert!(primary.storage.contains_certificate_in_round_from(round, primary.gateway.signer().address()));
         // Check the round was incremented.
         assert_eq!(primary.current_round(), round + 1);
     }
@@ -2371,7 +2501,7 @@ mod tests {
         // Create a valid proposal.
         let timestamp = now();
         let proposal = create_test_proposal(
-            primary.gateway.account(),
+            primary.gateway.signer().account().unwrap(),
             primary.ledger.current_committee().unwrap(),
             round,
             previous_certificates,
@@ -2394,7 +2524,7 @@ mod tests {
         }
 
         // Check the certificate was created and stored by the primary.
-        assert!(primary.storage.contains_certificate_in_round_from(round, primary.gateway.account().address()));
+        assert!(primary.storage.contains_certificate_in_round_from(round, primary.gateway.signer().address()));
         // Check the round was incremented.
         assert_eq!(primary.current_round(), round + 1);
     }
@@ -2409,7 +2539,7 @@ mod tests {
         let round = 1;
         let timestamp = now() + MIN_BATCH_DELAY_IN_SECS as i64;
         let proposal = create_test_proposal(
-            primary.gateway.account(),
+            primary.gateway.signer().account().unwrap(),
             primary.ledger.current_committee().unwrap(),
             round,
             Default::default(),
@@ -2431,7 +2561,7 @@ mod tests {
         primary.process_batch_signature_from_peer(*socket_addr, *signature).await.unwrap();
 
         // Check the certificate was not created and stored by the primary.
-        assert!(!primary.storage.contains_certificate_in_round_from(round, primary.gateway.account().address()));
+        assert!(!primary.storage.contains_certificate_in_round_from(round, primary.gateway.signer().address()));
         // Check the round was incremented.
         assert_eq!(primary.current_round(), round);
     }
@@ -2449,7 +2579,7 @@ mod tests {
         // Create a valid proposal.
         let timestamp = now() + MIN_BATCH_DELAY_IN_SECS as i64;
         let proposal = create_test_proposal(
-            primary.gateway.account(),
+            primary.gateway.signer().account().unwrap(),
             primary.ledger.current_committee().unwrap(),
             round,
             previous_certificates,
@@ -2471,7 +2601,7 @@ mod tests {
         primary.process_batch_signature_from_peer(*socket_addr, *signature).await.unwrap();
 
         // Check the certificate was not created and stored by the primary.
-        assert!(!primary.storage.contains_certificate_in_round_from(round, primary.gateway.account().address()));
+        assert!(!primary.storage.contains_certificate_in_round_from(round, primary.gateway.signer().address()));
         // Check the round was incremented.
         assert_eq!(primary.current_round(), round);
     }
@@ -379,6 +379,11 @@ impl<N: Network> Sync<N> {
         self.block_sync.is_block_synced()
     }
 
+    /// Returns the block sync module.
+    pub const fn block_sync(&self) -> &BlockSync<N> {
+        &self.block_sync
+    }
+
     /// Returns `true` if the node is in gateway mode.
     pub const fn is_gateway_mode(&self) -> bool {
         self.block_sync.mode().is_gateway()
@@ -14,7 +14,7 @@
 
 use crate::{
     events::{Event, TransmissionRequest, TransmissionResponse},
-    helpers::{fmt_id, max_redundant_requests, Pending, Ready, Storage, WorkerReceiver},
+    helpers::{fmt_id, max_redundant_requests, Pending, Priority, Ready, Storage, WorkerReceiver},
     spawn_blocking,
     ProposedBatch,
     Transport,
@@ -151,6 +151,11 @@ impl<N: Network> Worker<N> {
 }
 
 impl<N: Network> Worker<N> {
+    /// Returns `true` if the transmission ID exists in the ready queue.
+    pub fn is_ready(&self, transmission_id: impl Into<TransmissionID<N>>) -> bool {
+        self.ready.contains(transmission_id)
+    }
+
     /// Returns `true` if the transmission ID exists in the ready queue, proposed batch, storage, or ledger.
     pub fn contains_transmission(&self, transmission_id: impl Into<TransmissionID<N>>) -> bool {
         let transmission_id = transmission_id.into();
@@ -210,8 +215,10 @@ impl<N: Network> Worker<N> {
     pub(crate) fn reinsert(&self, transmission_id: TransmissionID<N>, transmission: Transmission<N>) -> bool {
         // Check if the transmission ID exists.
         if !self.contains_transmission(transmission_id) {
+            // Determine the priority of the transmission.
+            let priority = Priority::from_transmission(&transmission);
             // Insert the transmission into the ready queue.
-            return self.ready.insert(transmission_id, transmission);
+            return self.ready.insert_with_priority(transmission_id, transmission, priority);
         }
         false
     }
@@ -294,6 +301,8 @@ impl<N: Network> Worker<N> {
             _ => false,
         };
         // If the transmission ID and transmission type matches, then insert the transmission into the ready queue.
+        // Note: The transmission is not deserialized, so it is inserted with the lowest priority,
+        // as the priority of a transaction is only determined by the consensus of the validator that received it.
         if is_well_formed && self.ready.insert(transmission_id, transmission) {
             trace!("Worker {} - Added transmission '{}' from '{peer_ip}'", self.id, fmt_id(transmission_id));
         }
@@ -323,11 +332,12 @@ impl<N: Network> Worker<N> {
         Ok(())
     }
 
-    /// Handles the incoming unconfirmed transaction.
+    /// Handles the incoming unconfirmed transaction, with the priority determined by consensus.
     pub(crate) async fn process_unconfirmed_transaction(
         &self,
         transaction_id: N::TransactionID,
         transaction: Data<Transaction<N>>,
+        priority: Priority,
     ) -> Result<()> {
         // Construct the transmission.
         let transmission = Transmission::Transaction(transaction.clone());
@@ -340,7 +350,7 @@ impl<N: Network> Worker<N> {
         // Check that the transaction is well-formed and unique.
         self.ledger.check_transaction_basic(transaction_id, transaction).await?;
         // Adds the transaction to the ready queue.
-        if self.ready.insert(&transaction_id, transmission) {
+        if self.ready.insert_with_priority(&transaction_id, transmission, priority) {
             trace!("Worker {} - Added unconfirmed transaction '{}'", self.id, fmt_id(transaction_id));
         }
         Ok(())
@@ -754,6 +764,7 @@ mod tests {
             .process_unconfirmed_transaction(
                 transaction_id,
                 Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>())),
+                Priority::default(),
             )
             .await;
         assert!(result.is_ok());
@@ -794,6 +805,7 @@ mod tests {
             .process_unconfirmed_transaction(
                 transaction_id,
                 Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>())),
+                Priority::default(),
             )
             .await;
         assert!(result.is_err());
@@ -870,6 +882,7 @@ mod tests {
             .process_unconfirmed_transaction(
                 transaction_id,
                 Data::Buffer(Bytes::from((0..512).map(|_| rng.gen::<u8>()).collect::<Vec<_>>())),
+                Priority::default(),
             )
             .await;
         assert!(result.is_ok());
@@ -25,5 +25,8 @@ pub mod persistent;
 #[cfg(feature = "persistent")]
 pub use persistent::*;
 
+pub mod signing_history;
+pub use signing_history::*;
+
 pub mod traits;
 pub use traits::*;
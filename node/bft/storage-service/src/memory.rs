@@ -12,10 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::StorageService;
+use crate::{SignedBatch, SigningHistory, StorageService};
 use snarkvm::{
     ledger::narwhal::{BatchHeader, Transmission, TransmissionID},
-    prelude::{bail, Field, Network, Result},
+    prelude::{bail, Address, Field, Network, Result},
 };
 
 use indexmap::{indexset, map::Entry, IndexMap, IndexSet};
@@ -28,6 +28,8 @@ use tracing::error;
 pub struct BFTMemoryService<N: Network> {
     /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
     transmissions: RwLock<IndexMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)>>,
+    /// The signing history.
+    signing_history: SigningHistory<N>,
 }
 
 impl<N: Network> Default for BFTMemoryService<N> {
@@ -40,7 +42,7 @@ impl<N: Network> Default for BFTMemoryService<N> {
 impl<N: Network> BFTMemoryService<N> {
     /// Initializes a new BFT in-memory storage service.
     pub fn new() -> Self {
-        Self { transmissions: Default::default() }
+        Self { transmissions: Default::default(), signing_history: Default::default() }
     }
 }
 
@@ -143,6 +145,16 @@ impl<N: Network> StorageService<N> for BFTMemoryService<N> {
         }
     }
 
+    /// Returns the batch signed by this validator for the given author in the given round, if any.
+    fn get_signed_batch(&self, author: Address<N>, round: u64) -> Option<SignedBatch<N>> {
+        self.signing_history.get(author, round)
+    }
+
+    /// Records the batch signed by this validator for the given author in the given round.
+    fn insert_signed_batch(&self, author: Address<N>, round: u64, signed_batch: SignedBatch<N>) -> Result<()> {
+        self.signing_history.insert(author, round, signed_batch)
+    }
+
     /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)> {
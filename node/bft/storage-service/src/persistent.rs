@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::StorageService;
+use crate::{SignedBatch, SigningHistory, StorageService};
 use snarkvm::{
     ledger::{
         narwhal::{BatchHeader, Transmission, TransmissionID},
@@ -28,7 +28,7 @@ use snarkvm::{
             },
         },
     },
-    prelude::{bail, Field, Network, Result},
+    prelude::{bail, Address, Field, Network, Result},
 };
 
 use aleo_std::StorageMode;
@@ -41,19 +41,27 @@ use tracing::error;
 pub struct BFTPersistentStorage<N: Network> {
     /// The map of `transmission ID` to `(transmission, certificate IDs)` entries.
     transmissions: DataMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)>,
+    /// The signing history, which is kept in a file alongside the BFT database.
+    signing_history: SigningHistory<N>,
 }
 
 impl<N: Network> BFTPersistentStorage<N> {
     /// Initializes a new BFT persistent storage service.
     pub fn open(storage_mode: StorageMode) -> Result<Self> {
-        Ok(Self { transmissions: internal::RocksDB::open_map(N::ID, storage_mode, MapID::BFT(BFTMap::Transmissions))? })
+        let history_path = aleo_std::aleo_ledger_dir(N::ID, storage_mode.clone()).join(SigningHistory::<N>::FILE_NAME);
+        Ok(Self {
+            transmissions: internal::RocksDB::open_map(N::ID, storage_mode, MapID::BFT(BFTMap::Transmissions))?,
+            signing_history: SigningHistory::open(history_path)?,
+        })
     }
 
     /// Initializes a new BFT persistent storage service.
     #[cfg(any(test, feature = "test"))]
     pub fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
+        let history_path = temp_dir.join(SigningHistory::<N>::FILE_NAME);
         Ok(Self {
             transmissions: internal::RocksDB::open_map_testing(temp_dir, dev, MapID::BFT(BFTMap::Transmissions))?,
+            signing_history: SigningHistory::open(history_path)?,
         })
     }
 }
@@ -194,6 +202,16 @@ impl<N: Network> StorageService<N> for BFTPersistentStorage<N> {
         }
     }
 
+    /// Returns the batch signed by this validator for the given author in the given round, if any.
+    fn get_signed_batch(&self, author: Address<N>, round: u64) -> Option<SignedBatch<N>> {
+        self.signing_history.get(author, round)
+    }
+
+    /// Records the batch signed by this validator for the given author in the given round.
+    fn insert_signed_batch(&self, author: Address<N>, round: u64, signed_batch: SignedBatch<N>) -> Result<()> {
+        self.signing_history.insert(author, round, signed_batch)
+    }
+
     /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)> {
@@ -0,0 +1,321 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    ledger::narwhal::BatchHeader,
+    prelude::{ensure, Address, Field, FromBytes, Network, Result, ToBytes},
+};
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+/// The minimum number of stale records in the signing history file before it is compacted.
+const MIN_STALE_RECORDS_FOR_COMPACTION: usize = 1024;
+
+/// A batch signed by this validator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SignedBatch<N: Network> {
+    /// The batch ID.
+    pub batch_id: Field<N>,
+    /// The timestamp of the batch proposal.
+    pub timestamp: i64,
+}
+
+/// The signing history of a validator, which records the batch signed for each author in each round.
+///
+/// If the history is backed by a file, each record is appended and synced to disk before the
+/// insertion returns, so that the history survives restarts and crashes.
+#[derive(Debug)]
+pub struct SigningHistory<N: Network> {
+    /// The path to the signing history file, if it is persisted.
+    path: Option<PathBuf>,
+    /// The signing records.
+    records: Mutex<SigningRecords<N>>,
+}
+
+#[derive(Debug)]
+struct SigningRecords<N: Network> {
+    /// The map of `round` to `author` to signed batch.
+    batches: BTreeMap<u64, HashMap<Address<N>, SignedBatch<N>>>,
+    /// The number of records signed, across all rounds.
+    num_batches: usize,
+    /// The signing history file, opened for appending.
+    file: Option<File>,
+    /// The number of records in the signing history file.
+    num_persisted: usize,
+}
+
+impl<N: Network> Default for SigningHistory<N> {
+    /// Initializes a new in-memory signing history.
+    fn default() -> Self {
+        Self {
+            path: None,
+            records: Mutex::new(SigningRecords {
+                batches: Default::default(),
+                num_batches: 0,
+                file: None,
+                num_persisted: 0,
+            }),
+        }
+    }
+}
+
+impl<N: Network> SigningHistory<N> {
+    /// The file name of the signing history.
+    pub const FILE_NAME: &'static str = "signing-history";
+    /// The number of rounds (below the latest signed round) for which signed batches are remembered.
+    pub const NUM_ROUNDS: u64 = 2 * BatchHeader::<N>::MAX_GC_ROUNDS as u64;
+
+    /// Opens the signing history at the given path, creating it if it does not exist.
+    ///
+    /// A partially-written record at the end of the file (left behind by a crash) is discarded.
+    pub fn open(path: PathBuf) -> Result<Self> {
+        // Ensure the parent directory exists.
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let history = Self { path: Some(path.clone()), ..Default::default() };
+        let mut records = history.records();
+
+        // Load the records, stopping at the first record that cannot be read in full.
+        let bytes = match path.exists() {
+            true => fs::read(&path)?,
+            false => Vec::new(),
+        };
+        let mut remaining = &bytes[..];
+        while !remaining.is_empty() {
+            let mut reader = remaining;
+            let Ok((author, round, signed_batch)) = read_record::<N>(&mut reader) else {
+                break;
+            };
+            records.insert(author, round, signed_batch);
+            records.num_persisted += 1;
+            remaining = reader;
+        }
+
+        // Truncate the partially-written record, if any, and open the file for appending.
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        file.set_len((bytes.len() - remaining.len()) as u64)?;
+        file.sync_all()?;
+        records.file = Some(file);
+        drop(records);
+
+        Ok(history)
+    }
+
+    /// Returns the number of signed batches in the history.
+    pub fn len(&self) -> usize {
+        self.records().num_batches
+    }
+
+    /// Returns `true` if the history is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the lowest round that is still remembered.
+    pub fn min_round(&self) -> u64 {
+        self.records().min_round()
+    }
+
+    /// Returns the batch signed for the given author in the given round, if any.
+    pub fn get(&self, author: Address<N>, round: u64) -> Option<SignedBatch<N>> {
+        self.records().batches.get(&round).and_then(|batches| batches.get(&author)).copied()
+    }
+
+    /// Records the batch signed for the given author in the given round, replacing any previous record.
+    /// If the history is persisted, the record is synced to disk before this method returns.
+    pub fn insert(&self, author: Address<N>, round: u64, signed_batch: SignedBatch<N>) -> Result<()> {
+        let mut guard = self.records();
+        let records = &mut *guard;
+        let min_round = records.min_round();
+        ensure!(round >= min_round, "Cannot record a signed batch for round {round} (below round {min_round})");
+
+        // Persist the record first, so that it is never signed without being recorded.
+        if let Some(file) = &mut records.file {
+            let mut bytes = Vec::new();
+            write_record(&mut bytes, author, round, signed_batch)?;
+            file.write_all(&bytes)?;
+            file.sync_data()?;
+            records.num_persisted += 1;
+        }
+        records.insert(author, round, signed_batch);
+
+        // Compact the file once most of its records have been pruned.
+        let num_stale = records.num_persisted.saturating_sub(records.num_batches);
+        if num_stale >= MIN_STALE_RECORDS_FOR_COMPACTION && num_stale > records.num_batches {
+            if let Some(path) = &self.path {
+                records.compact(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the signing records.
+    fn records(&self) -> MutexGuard<'_, SigningRecords<N>> {
+        // A poisoned lock holds consistent records, as each record is inserted before any panic can occur.
+        self.records.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl<N: Network> SigningRecords<N> {
+    /// Returns the lowest round that is still remembered.
+    fn min_round(&self) -> u64 {
+        let latest_round = self.batches.keys().next_back().copied().unwrap_or_default();
+        latest_round.saturating_sub(SigningHistory::<N>::NUM_ROUNDS)
+    }
+
+    /// Inserts the given record in memory, pruning the rounds that are no longer remembered.
+    fn insert(&mut self, author: Address<N>, round: u64, signed_batch: SignedBatch<N>) {
+        if self.batches.entry(round).or_default().insert(author, signed_batch).is_none() {
+            self.num_batches += 1;
+        }
+        let min_round = self.min_round();
+        while let Some(entry) = self.batches.first_entry() {
+            if *entry.key() >= min_round {
+                break;
+            }
+            self.num_batches -= entry.remove().len();
+        }
+    }
+
+    /// Rewrites the signing history file with only the remembered records.
+    fn compact(&mut self, path: &Path) -> Result<()> {
+        let mut bytes = Vec::new();
+        for (round, batches) in &self.batches {
+            for (author, signed_batch) in batches {
+                write_record(&mut bytes, *author, *round, *signed_batch)?;
+            }
+        }
+        // Write to a temporary file first, so that a crash does not leave a partially-written history.
+        let temp_path = path.with_extension("tmp");
+        let mut temp_file = File::create(&temp_path)?;
+        temp_file.write_all(&bytes)?;
+        temp_file.sync_all()?;
+        fs::rename(temp_path, path)?;
+
+        self.file = Some(OpenOptions::new().append(true).open(path)?);
+        self.num_persisted = self.num_batches;
+        Ok(())
+    }
+}
+
+/// Writes the given record as `(round, author, batch ID, timestamp)`.
+fn write_record<N: Network>(
+    bytes: &mut Vec<u8>,
+    author: Address<N>,
+    round: u64,
+    signed_batch: SignedBatch<N>,
+) -> Result<()> {
+    round.write_le(&mut *bytes)?;
+    author.write_le(&mut *bytes)?;
+    signed_batch.batch_id.write_le(&mut *bytes)?;
+    signed_batch.timestamp.write_le(&mut *bytes)?;
+    Ok(())
+}
+
+/// Reads a record written by `write_record`.
+fn read_record<N: Network>(reader: &mut &[u8]) -> Result<(Address<N>, u64, SignedBatch<N>)> {
+    let round = u64::read_le(&mut *reader)?;
+    let author = Address::read_le(&mut *reader)?;
+    let batch_id = Field::read_le(&mut *reader)?;
+    let timestamp = i64::read_le(&mut *reader)?;
+    Ok((author, round, SignedBatch { batch_id, timestamp }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        console::prelude::Rng,
+        prelude::{MainnetV0, PrivateKey, TestRng, Uniform},
+    };
+
+    type CurrentNetwork = MainnetV0;
+
+    fn sample_signed_batch(rng: &mut TestRng) -> SignedBatch<CurrentNetwork> {
+        SignedBatch { batch_id: Field::rand(rng), timestamp: rng.gen() }
+    }
+
+    #[test]
+    fn test_signing_history_persistence() {
+        let rng = &mut TestRng::default();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signing-history");
+        let author = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let (batch_a, batch_b) = (sample_signed_batch(rng), sample_signed_batch(rng));
+
+        let history = SigningHistory::open(path.clone()).unwrap();
+        assert!(history.is_empty());
+        history.insert(author, 5, batch_a).unwrap();
+        history.insert(author, 6, batch_b).unwrap();
+        drop(history);
+
+        // Ensure the records survive a restart.
+        let history = SigningHistory::<CurrentNetwork>::open(path.clone()).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.get(author, 5), Some(batch_a));
+        assert_eq!(history.get(author, 6), Some(batch_b));
+        assert_eq!(history.get(author, 7), None);
+        drop(history);
+
+        // Simulate a crash in the middle of writing a record.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&7u64.to_le_bytes()).unwrap();
+        drop(file);
+
+        // Ensure the partial record is discarded, and new records can still be appended.
+        let history = SigningHistory::<CurrentNetwork>::open(path.clone()).unwrap();
+        assert_eq!(history.len(), 2);
+        history.insert(author, 7, batch_a).unwrap();
+        drop(history);
+        let history = SigningHistory::<CurrentNetwork>::open(path.clone()).unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.get(author, 7), Some(batch_a));
+    }
+
+    #[test]
+    fn test_signing_history_pruning() {
+        let rng = &mut TestRng::default();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signing-history");
+        let author = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let num_rounds = SigningHistory::<CurrentNetwork>::NUM_ROUNDS;
+
+        let history = SigningHistory::open(path.clone()).unwrap();
+        // Insert enough rounds to trigger a compaction.
+        let latest_round = (MIN_STALE_RECORDS_FOR_COMPACTION as u64) * 2 + num_rounds;
+        for round in 1..=latest_round {
+            history.insert(author, round, sample_signed_batch(rng)).unwrap();
+        }
+        assert_eq!(history.min_round(), latest_round - num_rounds);
+        assert_eq!(history.len(), num_rounds as usize + 1);
+        // Ensure rounds below the history cannot be recorded.
+        assert!(history.insert(author, history.min_round() - 1, sample_signed_batch(rng)).is_err());
+        // Ensure the file was compacted.
+        assert!(history.records().num_persisted < latest_round as usize);
+        drop(history);
+
+        // Ensure the compacted history is restored.
+        let history = SigningHistory::<CurrentNetwork>::open(path.clone()).unwrap();
+        assert_eq!(history.min_round(), latest_round - num_rounds);
+        assert_eq!(history.len(), num_rounds as usize + 1);
+    }
+}
@@ -12,9 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::SignedBatch;
 use snarkvm::{
     ledger::narwhal::{BatchHeader, Transmission, TransmissionID},
-    prelude::{Field, Network, Result},
+    prelude::{Address, Field, Network, Result},
 };
 
 use indexmap::IndexSet;
@@ -48,6 +49,14 @@ pub trait StorageService<N: Network>: Debug + Send + Sync {
     /// If the transmission no longer references any certificate IDs, the entry is removed from storage.
     fn remove_transmissions(&self, certificate_id: &Field<N>, transmission_ids: &IndexSet<TransmissionID<N>>);
 
+    /// Returns the batch signed by this validator for the given author in the given round, if any.
+    fn get_signed_batch(&self, author: Address<N>, round: u64) -> Option<SignedBatch<N>>;
+
+    /// Records the batch signed by this validator for the given author in the given round.
+    ///
+    /// The record must be durable once this method returns, as it guards against double-signing.
+    fn insert_signed_batch(&self, author: Address<N>, round: u64, signed_batch: SignedBatch<N>) -> Result<()>;
+
     /// Returns a HashMap over the `(transmission ID, (transmission, certificate IDs))` entries.
     #[cfg(any(test, feature = "test"))]
     fn as_hashmap(&self) -> HashMap<TransmissionID<N>, (Transmission<N>, IndexSet<Field<N>>)>;
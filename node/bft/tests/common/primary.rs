@@ -159,11 +159,30 @@ impl TestNetwork {
             );
 
             let (primary, bft) = if config.bft {
-                let bft = BFT::<CurrentNetwork>::new(account, storage, ledger, None, &[], Some(id as u16)).unwrap();
+                let bft = BFT::<CurrentNetwork>::new(
+                    account,
+                    storage,
+                    ledger,
+                    None,
+                    &[],
+                    Some(id as u16),
+                    Default::default(),
+                    Default::default(),
+                )
+                .unwrap();
                 (bft.primary().clone(), Some(bft))
             } else {
-                let primary =
-                    Primary::<CurrentNetwork>::new(account, storage, ledger, None, &[], Some(id as u16)).unwrap();
+                let primary = Primary::<CurrentNetwork>::new(
+                    account,
+                    storage,
+                    ledger,
+                    None,
+                    &[],
+                    Some(id as u16),
+                    Default::default(),
+                    Default::default(),
+                )
+                .unwrap();
                 (primary, None)
             };
 
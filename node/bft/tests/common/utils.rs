@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::common::CurrentNetwork;
-use snarkos_node_bft::helpers::PrimarySender;
+use snarkos_node_bft::helpers::{PrimarySender, Priority};
 use snarkvm::{
     ledger::narwhal::Data,
     prelude::{
@@ -152,8 +152,8 @@ pub fn fire_unconfirmed_transactions(
             let (id, transaction) = if counter % 2 == 0 { sample(&mut shared_rng) } else { sample(&mut unique_rng) };
             // Initialize a callback sender and receiver.
             let (callback, callback_receiver) = oneshot::channel();
-            // Send the fake transaction.
-            if let Err(e) = tx_unconfirmed_transaction.send((id, transaction, callback)).await {
+            // Send the fake transaction, with the lowest priority.
+            if let Err(e) = tx_unconfirmed_transaction.send((id, transaction, Priority::default(), callback)).await {
                 error!("Failed to send unconfirmed transaction: {e}");
             }
             let _ = callback_receiver.await;
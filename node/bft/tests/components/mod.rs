@@ -67,7 +67,7 @@ pub fn sample_gateway<N: Network>(
     let (accounts, _committee) = primary::new_test_committee(num_nodes);
     let account = Account::from_str(&accounts[0].private_key().to_string()).unwrap();
     // Initialize the gateway.
-    Gateway::new(account, storage, ledger, None, &[], None).unwrap()
+    Gateway::new(account, storage, ledger, None, &[], None, Default::default(), Default::default()).unwrap()
 }
 
 /// Samples a new worker with the given ledger.
@@ -26,10 +26,11 @@ use snarkvm::prelude::{
     Serialize,
 };
 
+use crate::{block_source, BlockSource};
+
 use anyhow::{anyhow, bail, Result};
 use colored::Colorize;
 use parking_lot::Mutex;
-use reqwest::Client;
 use std::{
     cmp,
     sync::{
@@ -40,7 +41,7 @@ use std::{
 };
 
 /// The number of blocks per file.
-const BLOCKS_PER_FILE: u32 = 50;
+pub(crate) const BLOCKS_PER_FILE: u32 = 50;
 /// The desired number of concurrent requests to the CDN.
 const CONCURRENT_REQUESTS: u32 = 16;
 /// Maximum number of pending sync blocks.
@@ -49,13 +50,48 @@ const MAXIMUM_PENDING_BLOCKS: u32 = BLOCKS_PER_FILE * CONCURRENT_REQUESTS * 2;
 const MAXIMUM_REQUEST_ATTEMPTS: u8 = 10;
 /// The supported network.
 const NETWORK_ID: u16 = 0;
+/// The file name of the latest state of the bundles.
+pub(crate) const LATEST_FILE_NAME: &str = "latest.json";
+
+/// A representation of the 'latest.json' file object.
+#[derive(Deserialize, Serialize, Debug)]
+pub(crate) struct LatestState {
+    pub(crate) exclusive_height: u32,
+    pub(crate) inclusive_height: u32,
+    pub(crate) hash: String,
+}
+
+impl LatestState {
+    /// Parses the latest state from the contents of a 'latest.json' file.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_str(&bincode::deserialize::<String>(bytes)?)?)
+    }
+
+    /// Returns the contents of a 'latest.json' file for the latest state.
+    pub(crate) fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(&serde_json::to_string(self)?)?)
+    }
+}
+
+/// Returns the file name of the bundle of blocks starting at the given height.
+pub(crate) fn bundle_file_name(start: u32) -> String {
+    format!("{start}.{}.blocks", start + BLOCKS_PER_FILE)
+}
+
+/// Returns the start height of the bundle of blocks with the given file name, if it is a valid bundle file name.
+pub(crate) fn parse_bundle_file_name(file_name: &str) -> Option<u32> {
+    let (start, end) = file_name.strip_suffix(".blocks")?.split_once('.')?;
+    let (start, end) = (start.parse::<u32>().ok()?, end.parse::<u32>().ok()?);
+    (start % BLOCKS_PER_FILE == 0 && end == start.checked_add(BLOCKS_PER_FILE)?).then_some(start)
+}
 
 /// Loads blocks from a CDN into the ledger.
 ///
+/// The location of the CDN is given in any of the forms accepted by [`block_source`].
 /// On success, this function returns the completed block height.
 /// On failure, this function returns the last successful block height (if any), along with the error.
 pub async fn sync_ledger_with_cdn<N: Network, C: ConsensusStorage<N>>(
-    base_url: &str,
+    location: &str,
     ledger: Ledger<N, C>,
     shutdown: Arc<AtomicBool>,
 ) -> Result<u32, (u32, anyhow::Error)> {
@@ -63,7 +99,7 @@ pub async fn sync_ledger_with_cdn<N: Network, C: ConsensusStorage<N>>(
     let start_height = ledger.latest_height() + 1;
     // Load the blocks from the CDN into the ledger.
     let ledger_clone = ledger.clone();
-    let result = load_blocks(base_url, start_height, None, shutdown, move |block: Block<N>| {
+    let result = load_blocks(location, start_height, None, shutdown, move |block: Block<N>| {
         ledger_clone.advance_to_next_block(&block)
     })
     .await;
@@ -98,10 +134,11 @@ pub async fn sync_ledger_with_cdn<N: Network, C: ConsensusStorage<N>>(
 
 /// Loads blocks from a CDN and process them with the given function.
 ///
+/// The location of the CDN is given in any of the forms accepted by [`block_source`].
 /// On success, this function returns the completed block height.
 /// On failure, this function returns the last successful block height (if any), along with the error.
 pub async fn load_blocks<N: Network>(
-    base_url: &str,
+    location: &str,
     start_height: u32,
     end_height: Option<u32>,
     shutdown: Arc<AtomicBool>,
@@ -112,16 +149,14 @@ pub async fn load_blocks<N: Network>(
         return Err((start_height, anyhow!("The network ({}) is not supported", N::ID)));
     }
 
-    // Create a Client to maintain a connection pool throughout the sync.
-    let client = match Client::builder().build() {
-        Ok(client) => client,
-        Err(error) => {
-            return Err((start_height.saturating_sub(1), anyhow!("Failed to create a CDN request client - {error}")));
-        }
+    // Prepare the source of the blocks.
+    let source: Arc<dyn BlockSource> = match block_source(location) {
+        Ok(source) => source.into(),
+        Err(error) => return Err((start_height.saturating_sub(1), error)),
     };
 
     // Fetch the CDN height.
-    let cdn_height = match cdn_height::<BLOCKS_PER_FILE>(&client, base_url).await {
+    let cdn_height = match cdn_height::<BLOCKS_PER_FILE>(&*source).await {
         Ok(cdn_height) => cdn_height,
         Err(error) => return Err((start_height, error)),
     };
@@ -161,10 +196,9 @@ pub async fn load_blocks<N: Network>(
 
     // Spawn a background task responsible for concurrent downloads.
     let pending_blocks_clone = pending_blocks.clone();
-    let base_url = base_url.to_owned();
     let shutdown_clone = shutdown.clone();
     tokio::spawn(async move {
-        download_block_bundles(client, base_url, cdn_start, cdn_end, pending_blocks_clone, shutdown_clone).await;
+        download_block_bundles(source, cdn_start, cdn_end, pending_blocks_clone, shutdown_clone).await;
     });
 
     // A loop for inserting the pending blocks into the ledger.
@@ -237,8 +271,7 @@ pub async fn load_blocks<N: Network>(
 }
 
 async fn download_block_bundles<N: Network>(
-    client: Client,
-    base_url: String,
+    source: Arc<dyn BlockSource>,
     cdn_start: u32,
     cdn_end: u32,
     pending_blocks: Arc<Mutex<Vec<Block<N>>>>,
@@ -280,8 +313,7 @@ async fn download_block_bundles<N: Network>(
                 break;
             }
 
-            let client_clone = client.clone();
-            let base_url_clone = base_url.clone();
+            let source_clone = source.clone();
             let pending_blocks_clone = pending_blocks.clone();
             let active_requests_clone = active_requests.clone();
             let shutdown_clone = shutdown.clone();
@@ -292,8 +324,8 @@ async fn download_block_bundles<N: Network>(
                 let ctx = format!("blocks {start} to {end}");
                 debug!("Requesting {ctx} (of {cdn_end})");
 
-                // Prepare the URL.
-                let blocks_url = format!("{base_url_clone}/{start}.{end}.blocks");
+                // Prepare the file name.
+                let file_name = bundle_file_name(start);
                 let ctx = format!("blocks {start} to {end}");
                 // Download blocks, retrying on failure.
                 let mut attempts = 0;
@@ -301,7 +333,7 @@ async fn download_block_bundles<N: Network>(
 
                 loop {
                     // Fetch the blocks.
-                    match cdn_get(client_clone.clone(), &blocks_url, &ctx).await {
+                    match cdn_get(&*source_clone, &file_name, &ctx).await {
                         Ok::<Vec<Block<N>>, _>(blocks) => {
                             // Keep the collection of pending blocks sorted by the height.
                             let mut pending_blocks = pending_blocks_clone.lock();
@@ -319,7 +351,7 @@ async fn download_block_bundles<N: Network>(
                             // case the maximum number of attempts has been breached.
                             attempts += 1;
                             if attempts > MAXIMUM_REQUEST_ATTEMPTS {
-                                warn!("Maximum number of requests to {blocks_url} reached - shutting down...");
+                                warn!("Maximum number of requests for {file_name} to '{source_clone}' reached - shutting down...");
                                 shutdown_clone.store(true, Ordering::Relaxed);
                                 break;
                             }
@@ -344,39 +376,20 @@ async fn download_block_bundles<N: Network>(
     debug!("Finished network requests to the CDN");
 }
 
-/// Retrieves the CDN height with the given base URL.
+/// Retrieves the CDN height from the given source.
 ///
 /// Note: This function decrements the tip by a few blocks, to ensure the
 /// tip is not on a block that is not yet available on the CDN.
-async fn cdn_height<const BLOCKS_PER_FILE: u32>(client: &Client, base_url: &str) -> Result<u32> {
-    // A representation of the 'latest.json' file object.
-    #[derive(Deserialize, Serialize, Debug)]
-    struct LatestState {
-        exclusive_height: u32,
-        inclusive_height: u32,
-        hash: String,
-    }
-    // Prepare the URL.
-    let latest_json_url = format!("{base_url}/latest.json");
-    // Send the request.
-    let response = match client.get(latest_json_url).send().await {
-        Ok(response) => response,
-        Err(error) => bail!("Failed to fetch the CDN height - {error}"),
-    };
-    // Parse the response.
-    let bytes = match response.bytes().await {
+async fn cdn_height<const BLOCKS_PER_FILE: u32>(source: &dyn BlockSource) -> Result<u32> {
+    // Fetch the latest state.
+    let bytes = match source.fetch(LATEST_FILE_NAME).await {
         Ok(bytes) => bytes,
-        Err(error) => bail!("Failed to parse the CDN height response - {error}"),
+        Err(error) => bail!("Failed to fetch the CDN height from '{source}' - {error}"),
     };
-    // Parse the bytes for the string.
-    let latest_state_string = match bincode::deserialize::<String>(&bytes) {
-        Ok(string) => string,
-        Err(error) => bail!("Failed to deserialize the CDN height response - {error}"),
-    };
-    // Parse the string for the tip.
-    let tip = match serde_json::from_str::<LatestState>(&latest_state_string) {
+    // Parse the latest state for the tip.
+    let tip = match LatestState::from_bytes(&bytes) {
         Ok(latest) => latest.exclusive_height,
-        Err(error) => bail!("Failed to extract the CDN height response - {error}"),
+        Err(error) => bail!("Failed to deserialize the CDN height response - {error}"),
     };
     // Decrement the tip by a few blocks to ensure the CDN is caught up.
     let tip = tip.saturating_sub(10);
@@ -384,17 +397,16 @@ async fn cdn_height<const BLOCKS_PER_FILE: u32>(client: &Client, base_url: &str)
     Ok(tip - (tip % BLOCKS_PER_FILE) + BLOCKS_PER_FILE)
 }
 
-/// Retrieves the objects from the CDN with the given URL.
-async fn cdn_get<T: 'static + DeserializeOwned + Send>(client: Client, url: &str, ctx: &str) -> Result<T> {
-    // Fetch the bytes from the given URL.
-    let response = match client.get(url).send().await {
-        Ok(response) => response,
-        Err(error) => bail!("Failed to fetch {ctx} - {error}"),
-    };
-    // Parse the response.
-    let bytes = match response.bytes().await {
+/// Retrieves the objects in the given file from the source.
+async fn cdn_get<T: 'static + DeserializeOwned + Send>(
+    source: &dyn BlockSource,
+    file_name: &str,
+    ctx: &str,
+) -> Result<T> {
+    // Fetch the bytes of the given file.
+    let bytes = match source.fetch(file_name).await {
         Ok(bytes) => bytes,
-        Err(error) => bail!("Failed to parse {ctx} - {error}"),
+        Err(error) => bail!("Failed to fetch {ctx} from '{source}' - {error}"),
     };
     // Parse the objects.
     match tokio::task::spawn_blocking(move || bincode::deserialize::<T>(&bytes)).await {
@@ -437,6 +449,7 @@ mod tests {
     use crate::{
         blocks::{cdn_get, cdn_height, log_progress, BLOCKS_PER_FILE},
         load_blocks,
+        HttpSource,
     };
     use snarkvm::prelude::{block::Block, MainnetV0};
 
@@ -500,9 +513,9 @@ mod tests {
     #[test]
     fn test_cdn_height() {
         let rt = tokio::runtime::Runtime::new().unwrap();
-        let client = reqwest::Client::builder().build().unwrap();
+        let source = HttpSource::new(TEST_BASE_URL).unwrap();
         rt.block_on(async {
-            let height = cdn_height::<BLOCKS_PER_FILE>(&client, TEST_BASE_URL).await.unwrap();
+            let height = cdn_height::<BLOCKS_PER_FILE>(&source).await.unwrap();
             assert!(height > 0);
         });
     }
@@ -511,9 +524,8 @@ mod tests {
     fn test_cdn_get() {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async {
-            let client = reqwest::Client::new();
-            let height =
-                cdn_get::<u32>(client, &format!("{TEST_BASE_URL}/mainnet/latest/height"), "height").await.unwrap();
+            let source = HttpSource::new(TEST_BASE_URL).unwrap();
+            let height = cdn_get::<u32>(&source, "mainnet/latest/height", "height").await.unwrap();
             assert!(height > 0);
         });
     }
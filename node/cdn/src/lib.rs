@@ -19,3 +19,12 @@ extern crate tracing;
 
 mod blocks;
 pub use blocks::{load_blocks, sync_ledger_with_cdn};
+
+mod serve;
+pub use serve::{read_cdn_file, serve_blocks};
+
+mod snapshot;
+pub use snapshot::{export_blocks, import_blocks};
+
+mod source;
+pub use source::*;
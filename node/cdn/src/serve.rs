@@ -0,0 +1,87 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::blocks::{parse_bundle_file_name, LatestState, BLOCKS_PER_FILE, LATEST_FILE_NAME};
+use snarkvm::prelude::{store::ConsensusStorage, Ledger, Network};
+
+use anyhow::Result;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+
+/// Serves the blocks of the ledger over HTTP on the given address, in the same bundle format as the CDN.
+///
+/// Only complete bundles of `BLOCKS_PER_FILE` blocks are served. The ledger storage is locked by its owner,
+/// so this serves a ledger that no running node uses; a running node serves its blocks over REST instead.
+pub async fn serve_blocks<N: Network, C: ConsensusStorage<N>>(
+    ledger: Ledger<N, C>,
+    listener_ip: SocketAddr,
+) -> Result<()> {
+    let router = Router::new().route("/:file_name", get(get_file::<N, C>)).with_state(ledger);
+    let listener = TcpListener::bind(listener_ip).await?;
+    axum::serve(listener, router.into_make_service()).await?;
+    Ok(())
+}
+
+// GET /latest.json
+// GET /{start}.{end}.blocks
+async fn get_file<N: Network, C: ConsensusStorage<N>>(
+    State(ledger): State<Ledger<N, C>>,
+    Path(file_name): Path<String>,
+) -> Result<Vec<u8>, (StatusCode, String)> {
+    let internal_error = |error: String| (StatusCode::INTERNAL_SERVER_ERROR, error);
+    let contents = tokio::task::spawn_blocking(move || (read_cdn_file(&ledger, &file_name), file_name))
+        .await
+        .map_err(|error| internal_error(error.to_string()))?;
+    match contents {
+        (Ok(Some(bytes)), _) => Ok(bytes),
+        (Ok(None), file_name) => Err((StatusCode::NOT_FOUND, format!("'{file_name}' was not found"))),
+        (Err(error), _) => Err(internal_error(error.to_string())),
+    }
+}
+
+/// Returns the contents of the given file as laid out on the CDN, or `None` if the ledger does not have it yet.
+pub fn read_cdn_file<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    file_name: &str,
+) -> Result<Option<Vec<u8>>> {
+    // Compute the (exclusive) end height of the last complete bundle.
+    let end_height = ledger.latest_height().saturating_add(1);
+    let end_height = end_height - (end_height % BLOCKS_PER_FILE);
+
+    // Serve the latest state, once there is a complete bundle.
+    if file_name == LATEST_FILE_NAME {
+        if end_height == 0 {
+            return Ok(None);
+        }
+        let inclusive_height = end_height - 1;
+        let hash = ledger.get_hash(inclusive_height)?;
+        let latest = LatestState { exclusive_height: end_height, inclusive_height, hash: hash.to_string() };
+        return latest.to_bytes().map(Some);
+    }
+
+    // Serve the bundle of blocks, if it is complete.
+    match parse_bundle_file_name(file_name) {
+        Some(start) if start + BLOCKS_PER_FILE <= end_height => {
+            let blocks = ledger.get_blocks(start..start + BLOCKS_PER_FILE)?;
+            Ok(Some(bincode::serialize(&blocks)?))
+        }
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::blocks::{bundle_file_name, LatestState, BLOCKS_PER_FILE, LATEST_FILE_NAME};
+use snarkvm::prelude::{block::Block, store::ConsensusStorage, Ledger, Network};
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use std::{cmp, fs, path::Path};
+
+/// Exports the blocks of the ledger into the given directory, in the same bundle format as the CDN.
+///
+/// Only complete bundles of `BLOCKS_PER_FILE` blocks are exported, starting from the bundle containing the start height.
+/// The `progress` function is called with the latest exported block height after each bundle.
+/// On success, this function returns the (exclusive) end height of the exported blocks.
+pub fn export_blocks<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    directory: &Path,
+    start_height: u32,
+    end_height: Option<u32>,
+    mut progress: impl FnMut(u32),
+) -> Result<u32> {
+    // Compute the start height, rounded down to the start of its bundle.
+    let start_height = start_height - (start_height % BLOCKS_PER_FILE);
+    // Compute the (exclusive) end height, rounded down to the end of the last complete bundle.
+    let end_height = cmp::min(end_height.unwrap_or(u32::MAX), ledger.latest_height().saturating_add(1));
+    let end_height = end_height - (end_height % BLOCKS_PER_FILE);
+    // Ensure there is at least one complete bundle to export.
+    ensure!(
+        start_height < end_height,
+        "The ledger does not contain a complete bundle of {BLOCKS_PER_FILE} blocks from block {start_height}"
+    );
+
+    // Create the directory, if it does not exist.
+    fs::create_dir_all(directory)?;
+
+    // Write each bundle of blocks.
+    for bundle_start in (start_height..end_height).step_by(BLOCKS_PER_FILE as usize) {
+        let blocks = ledger.get_blocks(bundle_start..bundle_start + BLOCKS_PER_FILE)?;
+        write_file(&directory.join(bundle_file_name(bundle_start)), &bincode::serialize(&blocks)?)?;
+        progress(bundle_start + BLOCKS_PER_FILE - 1);
+    }
+
+    // Write the latest state, which is only updated once all of the bundles are written.
+    let inclusive_height = end_height - 1;
+    let latest = LatestState {
+        exclusive_height: end_height,
+        inclusive_height,
+        hash: ledger.get_hash(inclusive_height)?.to_string(),
+    };
+    write_file(&directory.join(LATEST_FILE_NAME), &latest.to_bytes()?)?;
+
+    Ok(end_height)
+}
+
+/// Imports the blocks from the given directory of bundles into the ledger, verifying each block before it is added.
+///
+/// The directory must follow the same bundle format as the CDN, such as one written by `export_blocks`.
+/// The `progress` function is called with the height of each block after it is added to the ledger.
+/// On success, this function returns the latest height of the ledger.
+pub fn import_blocks<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    directory: &Path,
+    mut progress: impl FnMut(u32),
+) -> Result<u32> {
+    // Read the latest state of the bundles.
+    let latest = read_latest_state(directory)?;
+
+    // Determine the range of blocks to import.
+    // If the ledger is already at the latest block, its hash is still checked below.
+    let start_height = ledger.latest_height() + 1;
+    let end_height = latest.exclusive_height;
+    if start_height > end_height {
+        return Ok(ledger.latest_height());
+    }
+
+    // Import each bundle of blocks, starting from the bundle containing the start height.
+    let bundle_start_height = start_height - (start_height % BLOCKS_PER_FILE);
+    for bundle_start in (bundle_start_height..end_height).step_by(BLOCKS_PER_FILE as usize) {
+        // Read the bundle of blocks.
+        let path = directory.join(bundle_file_name(bundle_start));
+        let bytes = fs::read(&path).with_context(|| format!("Failed to read '{}'", path.display()))?;
+        let blocks: Vec<Block<N>> =
+            bincode::deserialize(&bytes).with_context(|| format!("Failed to deserialize '{}'", path.display()))?;
+
+        // Add each block that the ledger does not have yet.
+        for block in blocks.into_iter().filter(|block| (start_height..end_height).contains(&block.height())) {
+            let height = block.height();
+            // Ensure the block is the next block in the ledger.
+            let expected_height = ledger.latest_height() + 1;
+            if height != expected_height {
+                bail!("Expected block {expected_height} in '{}', found block {height}", path.display());
+            }
+            // Verify the block, and add it to the ledger.
+            ledger
+                .check_next_block(&block, &mut rand::thread_rng())
+                .map_err(|error| anyhow!("Block {height} is invalid - {error}"))?;
+            ledger.advance_to_next_block(&block)?;
+            progress(height);
+        }
+    }
+
+    // Ensure the latest block matches the latest state of the bundles.
+    let latest_height = ledger.latest_height();
+    if latest_height == latest.inclusive_height {
+        let latest_hash = ledger.latest_hash().to_string();
+        ensure!(latest_hash == latest.hash, "Block {latest_height} has hash {latest_hash}, expected {}", latest.hash);
+    }
+
+    Ok(latest_height)
+}
+
+/// Reads the latest state from the given directory of bundles.
+fn read_latest_state(directory: &Path) -> Result<LatestState> {
+    let path = directory.join(LATEST_FILE_NAME);
+    let bytes = fs::read(&path).with_context(|| format!("Failed to read '{}'", path.display()))?;
+    LatestState::from_bytes(&bytes)
+}
+
+/// Writes the given bytes to the given path, via a temporary file so that a partially-written file is never observed.
+fn write_file(path: &Path, bytes: &[u8]) -> Result<()> {
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, bytes)?;
+    fs::rename(temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::parse_bundle_file_name;
+    use snarkvm::prelude::{
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        FromBytes,
+        MainnetV0,
+        PrivateKey,
+        TestRng,
+        VM,
+    };
+
+    use aleo_std::StorageMode;
+
+    #[test]
+    fn test_latest_state_roundtrip() {
+        let directory = tempfile::tempdir().unwrap();
+
+        // Write the latest state in the CDN format.
+        let latest = LatestState { exclusive_height: 100, inclusive_height: 99, hash: "ab1hash".to_string() };
+        write_file(&directory.path().join(LATEST_FILE_NAME), &latest.to_bytes().unwrap()).unwrap();
+
+        // Check that the latest state is read back.
+        let candidate = read_latest_state(directory.path()).unwrap();
+        assert_eq!(candidate.exclusive_height, 100);
+        assert_eq!(candidate.inclusive_height, 99);
+        assert_eq!(candidate.hash, "ab1hash");
+    }
+
+    #[test]
+    fn test_import_blocks() {
+        type CurrentNetwork = MainnetV0;
+
+        let rng = &mut TestRng::default();
+        let directory = tempfile::tempdir().unwrap();
+
+        // Initialize a ledger with a new genesis block, and advance it by one block.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap();
+        let genesis = VM::from(store).unwrap().genesis_beacon(&private_key, rng).unwrap();
+        let source =
+            Ledger::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::load(genesis.clone(), StorageMode::Production)
+                .unwrap();
+        let block = source.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+        source.advance_to_next_block(&block).unwrap();
+
+        // Write a bundle containing both blocks, and the latest state pointing at the second one.
+        let bundle = bincode::serialize(&vec![genesis.clone(), block.clone()]).unwrap();
+        write_file(&directory.path().join(bundle_file_name(0)), &bundle).unwrap();
+        let latest = LatestState { exclusive_height: 2, inclusive_height: 1, hash: block.hash().to_string() };
+        write_file(&directory.path().join(LATEST_FILE_NAME), &latest.to_bytes().unwrap()).unwrap();
+
+        // Import the bundle into a ledger with the same genesis block.
+        let ledger =
+            Ledger::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::load(genesis, StorageMode::Production).unwrap();
+        let mut imported = Vec::new();
+        assert_eq!(import_blocks(&ledger, directory.path(), |height| imported.push(height)).unwrap(), 1);
+        assert_eq!(imported, vec![1]);
+        assert_eq!(ledger.latest_height(), 1);
+        assert_eq!(ledger.latest_hash(), block.hash());
+
+        // Ensure importing the same bundle again does not add any blocks.
+        assert_eq!(import_blocks(&ledger, directory.path(), |_| unreachable!()).unwrap(), 1);
+
+        // Ensure the import fails if the latest state does not match the ledger.
+        let latest = LatestState { hash: "ab1hash".to_string(), ..latest };
+        write_file(&directory.path().join(LATEST_FILE_NAME), &latest.to_bytes().unwrap()).unwrap();
+        assert!(import_blocks(&ledger, directory.path(), |_| ()).is_err());
+
+        // Ensure the import fails if the blocks do not follow the genesis block of the ledger.
+        let other_genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let other_ledger =
+            Ledger::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::load(other_genesis, StorageMode::Production)
+                .unwrap();
+        assert!(import_blocks(&other_ledger, directory.path(), |_| ()).is_err());
+        assert_eq!(other_ledger.latest_height(), 0);
+    }
+
+    #[test]
+    fn test_bundle_file_name() {
+        assert_eq!(bundle_file_name(0), "0.50.blocks");
+        assert_eq!(bundle_file_name(150), "150.200.blocks");
+
+        assert_eq!(parse_bundle_file_name("0.50.blocks"), Some(0));
+        assert_eq!(parse_bundle_file_name(&bundle_file_name(150)), Some(150));
+        assert_eq!(parse_bundle_file_name("10.60.blocks"), None);
+        assert_eq!(parse_bundle_file_name("0.100.blocks"), None);
+        assert_eq!(parse_bundle_file_name("latest.json"), None);
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::{
+    fmt,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A source of block bundles, laid out as a `latest.json` file alongside `{start}.{end}.blocks` files.
+#[async_trait]
+pub trait BlockSource: fmt::Display + Send + Sync {
+    /// Fetches the contents of the given file from the source.
+    async fn fetch(&self, file_name: &str) -> Result<Vec<u8>>;
+}
+
+/// Returns the block source for the given location.
+///
+/// The location is either an HTTP(S) base URL, a `file://` path to a local directory,
+/// or a comma-separated list of these, which are tried in order as mirrors of one another.
+pub fn block_source(location: &str) -> Result<Box<dyn BlockSource>> {
+    let locations = location.split(',').map(str::trim).filter(|location| !location.is_empty()).collect::<Vec<_>>();
+    match locations.as_slice() {
+        [] => bail!("The CDN location is empty"),
+        [location] => single_block_source(location),
+        locations => Ok(Box::new(MirroredSource::new(
+            locations.iter().map(|location| single_block_source(location)).collect::<Result<_>>()?,
+        ))),
+    }
+}
+
+/// Returns the block source for the given (non-mirrored) location.
+fn single_block_source(location: &str) -> Result<Box<dyn BlockSource>> {
+    if let Some(path) = location.strip_prefix("file://") {
+        Ok(Box::new(DirectorySource::new(path)))
+    } else if location.starts_with("http://") || location.starts_with("https://") {
+        Ok(Box::new(HttpSource::new(location)?))
+    } else {
+        bail!("Unsupported CDN location '{location}' (expected an 'http(s)://' or 'file://' location)")
+    }
+}
+
+/// A block source served over HTTP(S), such as the Aleo CDN or a mirror of it.
+pub struct HttpSource {
+    /// The client, which maintains a connection pool throughout the sync.
+    client: Client,
+    /// The base URL of the block bundles.
+    base_url: String,
+}
+
+impl HttpSource {
+    /// Initializes a new HTTP source with the given base URL.
+    pub fn new(base_url: &str) -> Result<Self> {
+        let client = Client::builder().build().map_err(|error| anyhow!("Failed to create a CDN client - {error}"))?;
+        Ok(Self { client, base_url: base_url.trim_end_matches('/').to_string() })
+    }
+}
+
+impl fmt::Display for HttpSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.base_url)
+    }
+}
+
+#[async_trait]
+impl BlockSource for HttpSource {
+    async fn fetch(&self, file_name: &str) -> Result<Vec<u8>> {
+        let url = format!("{}/{file_name}", self.base_url);
+        // Send the request, treating any unsuccessful status as an error.
+        let response = self.client.get(&url).send().await.and_then(|response| response.error_for_status())?;
+        // Read the response.
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+/// A block source in a local directory, such as one written by `snarkos ledger export`.
+pub struct DirectorySource {
+    /// The directory of the block bundles.
+    directory: PathBuf,
+}
+
+impl DirectorySource {
+    /// Initializes a new directory source with the given path.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self { directory: directory.into() }
+    }
+}
+
+impl fmt::Display for DirectorySource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "file://{}", self.directory.display())
+    }
+}
+
+#[async_trait]
+impl BlockSource for DirectorySource {
+    async fn fetch(&self, file_name: &str) -> Result<Vec<u8>> {
+        let path = self.directory.join(file_name);
+        tokio::task::spawn_blocking(move || {
+            std::fs::read(&path).with_context(|| format!("Failed to read '{}'", path.display()))
+        })
+        .await?
+    }
+}
+
+/// A set of block sources mirroring one another.
+///
+/// Each file is fetched from the last source that succeeded, failing over to the remaining sources in order.
+pub struct MirroredSource {
+    /// The mirrors.
+    sources: Vec<Box<dyn BlockSource>>,
+    /// The index of the mirror that was last fetched from successfully.
+    preferred: AtomicUsize,
+}
+
+impl MirroredSource {
+    /// Initializes a new set of mirrors, preferring the first one.
+    pub fn new(sources: Vec<Box<dyn BlockSource>>) -> Self {
+        Self { sources, preferred: Default::default() }
+    }
+}
+
+impl fmt::Display for MirroredSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sources = self.sources.iter().map(|source| source.to_string()).collect::<Vec<_>>();
+        write!(f, "{}", sources.join(","))
+    }
+}
+
+#[async_trait]
+impl BlockSource for MirroredSource {
+    async fn fetch(&self, file_name: &str) -> Result<Vec<u8>> {
+        let preferred = self.preferred.load(Ordering::Relaxed);
+        let mut last_error = anyhow!("No mirrors are available");
+        // Try each mirror in turn, starting from the preferred one.
+        for index in (0..self.sources.len()).map(|offset| (preferred + offset) % self.sources.len()) {
+            match self.sources[index].fetch(file_name).await {
+                Ok(bytes) => {
+                    self.preferred.store(index, Ordering::Relaxed);
+                    return Ok(bytes);
+                }
+                Err(error) => {
+                    warn!("Failed to fetch '{file_name}' from '{}' - {error}", self.sources[index]);
+                    last_error = error;
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicU32;
+
+    /// A source that fails its first few fetches.
+    struct FlakySource {
+        failures: AtomicU32,
+    }
+
+    impl fmt::Display for FlakySource {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "flaky")
+        }
+    }
+
+    #[async_trait]
+    impl BlockSource for FlakySource {
+        async fn fetch(&self, file_name: &str) -> Result<Vec<u8>> {
+            match self.failures.load(Ordering::Relaxed) {
+                0 => Ok(file_name.as_bytes().to_vec()),
+                _ => {
+                    self.failures.fetch_sub(1, Ordering::Relaxed);
+                    bail!("Unavailable")
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_source() {
+        assert_eq!(block_source("https://example.com/blocks/").unwrap().to_string(), "https://example.com/blocks");
+        assert_eq!(block_source("file:///tmp/blocks").unwrap().to_string(), "file:///tmp/blocks");
+        assert_eq!(
+            block_source("http://a.example.com, file:///tmp/blocks").unwrap().to_string(),
+            "http://a.example.com,file:///tmp/blocks"
+        );
+        assert!(block_source("").is_err());
+        assert!(block_source("/tmp/blocks").is_err());
+    }
+
+    #[test]
+    fn test_mirrored_source_failover() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let mirrors = MirroredSource::new(vec![
+                Box::new(FlakySource { failures: AtomicU32::new(u32::MAX) }),
+                Box::new(FlakySource { failures: AtomicU32::new(1) }),
+                Box::new(FlakySource { failures: AtomicU32::new(0) }),
+            ]);
+            // The first two mirrors fail, so the third mirror is used and preferred from now on.
+            assert_eq!(mirrors.fetch("latest.json").await.unwrap(), b"latest.json");
+            assert_eq!(mirrors.preferred.load(Ordering::Relaxed), 2);
+            assert_eq!(mirrors.fetch("0.50.blocks").await.unwrap(), b"0.50.blocks");
+            assert_eq!(mirrors.preferred.load(Ordering::Relaxed), 2);
+        });
+    }
+
+    #[test]
+    fn test_directory_source() {
+        let directory = tempfile::tempdir().unwrap();
+        std::fs::write(directory.path().join("latest.json"), b"latest").unwrap();
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let source = block_source(&format!("file://{}", directory.path().display())).unwrap();
+            assert_eq!(source.fetch("latest.json").await.unwrap(), b"latest");
+            assert!(source.fetch("0.50.blocks").await.is_err());
+        });
+    }
+}
@@ -17,7 +17,11 @@
 #[macro_use]
 extern crate tracing;
 
-use snarkos_account::Account;
+mod transactions_queue;
+pub use transactions_queue::MemoryPoolConfig;
+use transactions_queue::TransactionsQueue;
+
+use snarkos_account::Signer;
 use snarkos_node_bft::{
     helpers::{
         fmt_id,
@@ -25,6 +29,7 @@ use snarkos_node_bft::{
         ConsensusReceiver,
         PrimaryReceiver,
         PrimarySender,
+        Priority,
         Storage as NarwhalStorage,
     },
     spawn_blocking,
@@ -33,6 +38,7 @@ use snarkos_node_bft::{
 };
 use snarkos_node_bft_ledger_service::LedgerService;
 use snarkos_node_bft_storage_service::BFTPersistentStorage;
+use snarkos_node_tcp::{InboundLimits, OutboundLimits, CONTEXT_TARGET};
 use snarkvm::{
     ledger::{
         block::Transaction,
@@ -48,38 +54,61 @@ use colored::Colorize;
 use indexmap::IndexMap;
 use lru::LruCache;
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 use std::{future::Future, net::SocketAddr, num::NonZeroUsize, sync::Arc};
 use tokio::{
-    sync::{oneshot, OnceCell},
+    sync::{broadcast, oneshot, OnceCell},
     task::JoinHandle,
 };
 
-/// The capacity of the queue reserved for deployments.
-/// Note: This is an inbound queue capacity, not a Narwhal-enforced capacity.
-const CAPACITY_FOR_DEPLOYMENTS: usize = 1 << 10;
-/// The capacity of the queue reserved for executions.
-/// Note: This is an inbound queue capacity, not a Narwhal-enforced capacity.
-const CAPACITY_FOR_EXECUTIONS: usize = 1 << 10;
 /// The capacity of the queue reserved for solutions.
 /// Note: This is an inbound queue capacity, not a Narwhal-enforced capacity.
 const CAPACITY_FOR_SOLUTIONS: usize = 1 << 10;
+/// The capacity of the channel reserved for memory pool events.
+/// Note: Subscribers that fall behind by more than this capacity will miss events.
+const CAPACITY_FOR_MEMORY_POOL_EVENTS: usize = 1 << 12;
+/// The capacity of the cache of recently-rejected transactions.
+const CAPACITY_FOR_REJECTED_TRANSACTIONS: usize = 1 << 12;
 /// The **suggested** maximum number of deployments in each interval.
 /// Note: This is an inbound queue limit, not a Narwhal-enforced limit.
 const MAX_DEPLOYMENTS_PER_INTERVAL: usize = 1;
 
-/// Helper struct to track incoming transactions.
-struct TransactionsQueue<N: Network> {
-    pub deployments: LruCache<N::TransactionID, Transaction<N>>,
-    pub executions: LruCache<N::TransactionID, Transaction<N>>,
+/// An event emitted when an unconfirmed transmission enters or leaves the consensus queues.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MemoryPoolEvent<N: Network> {
+    /// The transmission entered the consensus queues.
+    Queued(TransmissionID<N>),
+    /// The transmission left the consensus queues, either to the primary or by eviction.
+    Dequeued(TransmissionID<N>),
 }
 
-impl<N: Network> Default for TransactionsQueue<N> {
-    fn default() -> Self {
-        Self {
-            deployments: LruCache::new(NonZeroUsize::new(CAPACITY_FOR_DEPLOYMENTS).unwrap()),
-            executions: LruCache::new(NonZeroUsize::new(CAPACITY_FOR_EXECUTIONS).unwrap()),
-        }
-    }
+/// The status of a transaction, as seen by this node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case", bound = "")]
+pub enum TransactionStatus<N: Network> {
+    /// The transaction is in the consensus queue, waiting to be sent to the primary.
+    Queued,
+    /// The transaction is in the ready queue of the given worker, waiting to be proposed.
+    Ready { worker: u8 },
+    /// The transaction is in the batch proposal of this node for the given round.
+    Proposed { round: u64 },
+    /// The transaction is in a certified batch for the given round, waiting to be committed.
+    Certified { round: u64, certificate_id: Field<N> },
+    /// The transaction was accepted in the block at the given height.
+    Confirmed { height: u32, block_hash: N::BlockHash },
+    /// The transaction was rejected, either in the block at the given height or by the memory pool of this node.
+    Rejected {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        height: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block_hash: Option<N::BlockHash>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// The transaction was aborted in the block at the given height.
+    Aborted { height: u32, block_hash: N::BlockHash },
+    /// The transaction is not known to this node.
+    Unknown,
 }
 
 #[derive(Clone)]
@@ -98,6 +127,10 @@ pub struct Consensus<N: Network> {
     seen_solutions: Arc<Mutex<LruCache<SolutionID<N>, ()>>>,
     /// The recently-seen unconfirmed transactions.
     seen_transactions: Arc<Mutex<LruCache<N::TransactionID, ()>>>,
+    /// The recently-rejected unconfirmed transactions, along with the rejection reason.
+    rejected_transactions: Arc<Mutex<LruCache<N::TransactionID, String>>>,
+    /// The memory pool event sender.
+    memory_pool_events: broadcast::Sender<MemoryPoolEvent<N>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -105,11 +138,14 @@ pub struct Consensus<N: Network> {
 impl<N: Network> Consensus<N> {
     /// Initializes a new instance of consensus.
     pub fn new(
-        account: Account<N>,
+        signer: impl Into<Signer<N>>,
         ledger: Arc<dyn LedgerService<N>>,
         ip: Option<SocketAddr>,
         trusted_validators: &[SocketAddr],
         storage_mode: StorageMode,
+        memory_pool: MemoryPoolConfig,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
         // Recover the development ID, if it is present.
         let dev = match storage_mode {
@@ -121,16 +157,21 @@ impl<N: Network> Consensus<N> {
         // Initialize the Narwhal storage.
         let storage = NarwhalStorage::new(ledger.clone(), transmissions, BatchHeader::<N>::MAX_GC_ROUNDS as u64);
         // Initialize the BFT.
-        let bft = BFT::new(account, storage, ledger.clone(), ip, trusted_validators, dev)?;
+        let bft =
+            BFT::new(signer, storage, ledger.clone(), ip, trusted_validators, dev, inbound_limits, outbound_limits)?;
         // Return the consensus.
         Ok(Self {
             ledger,
             bft,
             primary_sender: Default::default(),
             solutions_queue: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(CAPACITY_FOR_SOLUTIONS).unwrap()))),
-            transactions_queue: Default::default(),
+            transactions_queue: Arc::new(Mutex::new(TransactionsQueue::new(memory_pool))),
             seen_solutions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
             seen_transactions: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(1 << 16).unwrap()))),
+            rejected_transactions: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(CAPACITY_FOR_REJECTED_TRANSACTIONS).unwrap(),
+            ))),
+            memory_pool_events: broadcast::channel(CAPACITY_FOR_MEMORY_POOL_EVENTS).0,
             handles: Default::default(),
         })
     }
@@ -164,6 +205,11 @@ impl<N: Network> Consensus<N> {
     pub fn primary_sender(&self) -> &PrimarySender<N> {
         self.primary_sender.get().expect("Primary sender not set")
     }
+
+    /// Returns a receiver for the events of transmissions entering and leaving the consensus queues.
+    pub fn subscribe_memory_pool(&self) -> broadcast::Receiver<MemoryPoolEvent<N>> {
+        self.memory_pool_events.subscribe()
+    }
 }
 
 impl<N: Network> Consensus<N> {
@@ -233,9 +279,15 @@ impl<N: Network> Consensus<N> {
             }
             // Add the solution to the memory pool.
             trace!("Received unconfirmed solution '{}' in the queue", fmt_id(solution_id));
-            if self.solutions_queue.lock().put(solution_id, solution).is_some() {
-                bail!("Solution '{}' exists in the memory pool", fmt_id(solution_id));
+            match self.solutions_queue.lock().push(solution_id, solution) {
+                Some((id, _)) if id == solution_id => {
+                    bail!("Solution '{}' exists in the memory pool", fmt_id(solution_id))
+                }
+                // If the queue is full, the least recently used solution is evicted.
+                Some((id, _)) => self.notify_memory_pool(MemoryPoolEvent::Dequeued(TransmissionID::from(id))),
+                None => (),
             }
+            self.notify_memory_pool(MemoryPoolEvent::Queued(TransmissionID::from(solution_id)));
         }
 
         // If the memory pool of this node is full, return early.
@@ -260,6 +312,7 @@ impl<N: Network> Consensus<N> {
         // Iterate over the solutions.
         for solution in solutions.into_iter() {
             let solution_id = solution.id();
+            self.notify_memory_pool(MemoryPoolEvent::Dequeued(TransmissionID::from(solution_id)));
             trace!("Adding unconfirmed solution '{}' to the memory pool...", fmt_id(solution_id));
             // Send the unconfirmed solution to the primary.
             if let Err(e) = self.primary_sender().send_unconfirmed_solution(solution_id, Data::Object(solution)).await {
@@ -291,7 +344,9 @@ impl<N: Network> Consensus<N> {
                 bail!("Transaction '{}' is a fee transaction {}", fmt_id(transaction_id), "(skipping)".dimmed());
             }
             // Check if the transaction was recently seen.
-            if self.seen_transactions.lock().put(transaction_id, ()).is_some() {
+            // Note: A transaction is only marked as seen once it is admitted to the memory pool, so that a
+            // transaction rejected by the memory pool policy may be submitted again once there is room for it.
+            if self.seen_transactions.lock().contains(&transaction_id) {
                 // If the transaction was recently seen, return early.
                 return Ok(());
             }
@@ -301,13 +356,30 @@ impl<N: Network> Consensus<N> {
             }
             // Add the transaction to the memory pool.
             trace!("Received unconfirmed transaction '{}' in the queue", fmt_id(transaction_id));
-            if transaction.is_deploy() {
-                if self.transactions_queue.lock().deployments.put(transaction_id, transaction).is_some() {
+            let evicted = {
+                let mut tx_queue = self.transactions_queue.lock();
+                // Check if the transaction already exists in the memory pool.
+                if tx_queue.contains(&transaction_id) {
                     bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id));
                 }
-            } else if self.transactions_queue.lock().executions.put(transaction_id, transaction).is_some() {
-                bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id));
+                match tx_queue.insert(transaction) {
+                    Ok(evicted) => evicted,
+                    Err(error) => {
+                        // Record the rejection by the memory pool policy, so that it can be reported by the status.
+                        self.rejected_transactions.lock().put(transaction_id, error.to_string());
+                        return Err(error);
+                    }
+                }
+            };
+            // Mark the transaction as seen, now that it was admitted to the memory pool.
+            self.seen_transactions.lock().put(transaction_id, ());
+            // If the queue is full, the lowest-priority transaction is evicted.
+            for id in evicted {
+                self.notify_memory_pool(MemoryPoolEvent::Dequeued(TransmissionID::from(&id)));
             }
+            self.notify_memory_pool(MemoryPoolEvent::Queued(TransmissionID::from(&transaction_id)));
+            // Clear any earlier rejection of the transaction.
+            self.rejected_transactions.lock().pop(&transaction_id);
         }
 
         // If the memory pool of this node is full, return early.
@@ -322,30 +394,30 @@ impl<N: Network> Consensus<N> {
             // Acquire the lock on the transactions queue.
             let mut tx_queue = self.transactions_queue.lock();
             // Determine the number of deployments to send.
-            let num_deployments = tx_queue.deployments.len().min(capacity).min(MAX_DEPLOYMENTS_PER_INTERVAL);
+            let num_deployments = tx_queue.num_deployments().min(capacity).min(MAX_DEPLOYMENTS_PER_INTERVAL);
             // Determine the number of executions to send.
-            let num_executions = tx_queue.executions.len().min(capacity.saturating_sub(num_deployments));
+            let num_executions = tx_queue.num_executions().min(capacity.saturating_sub(num_deployments));
             // Create an iterator which will select interleaved deployments and executions within the capacity.
             // Note: interleaving ensures we will never have consecutive invalid deployments blocking the queue.
             let selector_iter = (0..num_deployments).map(|_| true).interleave((0..num_executions).map(|_| false));
-            // Drain the transactions from the queue, interleaving deployments and executions.
+            // Drain the highest-priority transactions from the queue, interleaving deployments and executions.
             selector_iter
-                .filter_map(|select_deployment| {
-                    if select_deployment {
-                        tx_queue.deployments.pop_lru().map(|(_, tx)| tx)
-                    } else {
-                        tx_queue.executions.pop_lru().map(|(_, tx)| tx)
-                    }
+                .filter_map(|select_deployment| match select_deployment {
+                    true => tx_queue.pop_deployment(),
+                    false => tx_queue.pop_execution(),
                 })
                 .collect_vec()
         };
         // Iterate over the transactions.
-        for transaction in transactions.into_iter() {
+        for (transaction, priority) in transactions.into_iter() {
             let transaction_id = transaction.id();
+            self.notify_memory_pool(MemoryPoolEvent::Dequeued(TransmissionID::from(&transaction_id)));
             trace!("Adding unconfirmed transaction '{}' to the memory pool...", fmt_id(transaction_id));
             // Send the unconfirmed transaction to the primary.
-            if let Err(e) =
-                self.primary_sender().send_unconfirmed_transaction(transaction_id, Data::Object(transaction)).await
+            if let Err(e) = self
+                .primary_sender()
+                .send_unconfirmed_transaction(transaction_id, Data::Object(transaction), priority)
+                .await
             {
                 // If the BFT is synced, then log the warning.
                 if self.bft.is_synced() {
@@ -354,12 +426,63 @@ impl<N: Network> Consensus<N> {
                         fmt_id(transaction_id)
                     );
                 }
+                // Record the rejection, so that it can be reported by the transaction status.
+                // Note: A transaction that the primary already has (e.g. from a peer) is not a rejection.
+                let is_known =
+                    self.bft.primary().workers().iter().any(|worker| worker.contains_transmission(&transaction_id));
+                if !is_known {
+                    self.rejected_transactions.lock().put(transaction_id, e.to_string());
+                }
             }
         }
         Ok(())
     }
 }
 
+impl<N: Network> Consensus<N> {
+    /// Returns the status of the given transaction in the memory pool of this node, if it is known.
+    ///
+    /// Note: This method does not consult the ledger, so the caller should first check if the
+    /// transaction is already in a block, as certified transactions remain in storage after commit.
+    pub fn unconfirmed_transaction_status(&self, transaction_id: &N::TransactionID) -> Option<TransactionStatus<N>> {
+        // Check if the transaction is in the consensus queue.
+        if self.transactions_queue.lock().contains(transaction_id) {
+            return Some(TransactionStatus::Queued);
+        }
+
+        let transmission_id = TransmissionID::from(transaction_id);
+        let primary = self.bft.primary();
+        // Check if the transaction is in the ready queue of a worker.
+        if let Some(worker) = primary.workers().iter().find(|worker| worker.is_ready(transmission_id)) {
+            return Some(TransactionStatus::Ready { worker: worker.id() });
+        }
+        // Check if the transaction is in the batch proposal of this node.
+        if let Some(proposal) = primary.proposed_batch().read().as_ref() {
+            if proposal.contains_transmission(transmission_id) {
+                return Some(TransactionStatus::Proposed { round: proposal.round() });
+            }
+        }
+        // Check if the transaction is in a certified batch.
+        let storage = self.bft.storage();
+        if storage.contains_transmission(transmission_id) {
+            // Search the rounds in storage, starting from the most recent one.
+            for round in (storage.gc_round()..=storage.current_round()).rev() {
+                let certificates = storage.get_certificates_for_round(round);
+                if let Some(certificate) =
+                    certificates.iter().find(|certificate| certificate.transmission_ids().contains(&transmission_id))
+                {
+                    return Some(TransactionStatus::Certified { round, certificate_id: certificate.id() });
+                }
+            }
+        }
+        // Check if the transaction was recently rejected by the memory pool.
+        if let Some(reason) = self.rejected_transactions.lock().peek(transaction_id) {
+            return Some(TransactionStatus::Rejected { height: None, block_hash: None, reason: Some(reason.clone()) });
+        }
+        None
+    }
+}
+
 impl<N: Network> Consensus<N> {
     /// Starts the consensus handlers.
     fn start_handlers(&self, consensus_receiver: ConsensusReceiver<N>) {
@@ -403,6 +526,15 @@ impl<N: Network> Consensus<N> {
         subdag: Subdag<N>,
         transmissions: IndexMap<TransmissionID<N>, Transmission<N>>,
     ) -> Result<()> {
+        // Advance the ledger within the context of the next block, for structured logging.
+        let _span = info_span!(
+            target: CONTEXT_TARGET,
+            "block",
+            height = self.ledger.latest_height() + 1,
+            round = subdag.anchor_round()
+        )
+        .entered();
+
         #[cfg(feature = "metrics")]
         let start = subdag.leader_certificate().batch_header().timestamp();
         #[cfg(feature = "metrics")]
@@ -463,6 +595,8 @@ impl<N: Network> Consensus<N> {
     ) -> Result<()> {
         // Initialize a callback sender and receiver.
         let (callback, callback_receiver) = oneshot::channel();
+        // Determine the priority of the transmission.
+        let priority = Priority::from_transmission(&transmission);
         // Send the transmission to the primary.
         match (transmission_id, transmission) {
             (TransmissionID::Ratification, Transmission::Ratification) => return Ok(()),
@@ -472,7 +606,10 @@ impl<N: Network> Consensus<N> {
             }
             (TransmissionID::Transaction(transaction_id), Transmission::Transaction(transaction)) => {
                 // Send the transaction to the primary.
-                self.primary_sender().tx_unconfirmed_transaction.send((transaction_id, transaction, callback)).await?;
+                self.primary_sender()
+                    .tx_unconfirmed_transaction
+                    .send((transaction_id, transaction, priority, callback))
+                    .await?;
             }
             _ => bail!("Mismatching `(transmission_id, transmission)` pair in consensus"),
         }
@@ -480,6 +617,12 @@ impl<N: Network> Consensus<N> {
         callback_receiver.await?
     }
 
+    /// Notifies the memory pool subscribers of the given event.
+    fn notify_memory_pool(&self, event: MemoryPoolEvent<N>) {
+        // Note: An error is only returned if there are no subscribers, in which case the event is dropped.
+        let _ = self.memory_pool_events.send(event);
+    }
+
     /// Spawns a task with the given future; it should only be used for long-running tasks.
     fn spawn<T: Future<Output = ()> + Send + 'static>(&self, future: T) {
         self.handles.lock().push(tokio::spawn(future));
@@ -494,3 +637,106 @@ impl<N: Network> Consensus<N> {
         self.handles.lock().iter().for_each(|handle| handle.abort());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkos_account::Account;
+    use snarkos_node_bft::helpers::{now, Proposal};
+    use snarkos_node_bft_ledger_service::MockLedgerService;
+    use snarkvm::ledger::{
+        committee::{Committee, MIN_VALIDATOR_STAKE},
+        ledger_test_helpers::sample_execution_transaction_with_fee,
+    };
+
+    use std::path::Path;
+
+    type CurrentNetwork = snarkvm::prelude::MainnetV0;
+
+    // Returns a consensus instance with the given memory pool configurations, along with its account and committee.
+    fn sample_consensus(
+        directory: &Path,
+        memory_pool: MemoryPoolConfig,
+        rng: &mut TestRng,
+    ) -> (Consensus<CurrentNetwork>, Account<CurrentNetwork>, Committee<CurrentNetwork>) {
+        // Create a committee containing the account of this node.
+        let accounts = (0..4).map(|_| Account::new(rng).unwrap()).collect::<Vec<_>>();
+        let members = accounts.iter().map(|account| (account.address(), (MIN_VALIDATOR_STAKE, true))).collect();
+        let committee = Committee::<CurrentNetwork>::new(1, members).unwrap();
+
+        let account = accounts[0].clone();
+        let ledger = Arc::new(MockLedgerService::new(committee.clone()));
+        let storage_mode = StorageMode::Custom(directory.to_path_buf());
+        let consensus = Consensus::new(
+            account.clone(),
+            ledger,
+            None,
+            &[],
+            storage_mode,
+            memory_pool,
+            Default::default(),
+            Default::default(),
+        )
+        .unwrap();
+        (consensus, account, committee)
+    }
+
+    #[tokio::test]
+    async fn test_unconfirmed_transaction_status() {
+        let rng = &mut TestRng::default();
+        let directory = tempfile::tempdir().unwrap();
+        let (consensus, account, committee) = sample_consensus(directory.path(), Default::default(), rng);
+
+        // A transaction that was never received is not known.
+        let transaction = sample_execution_transaction_with_fee(true, rng);
+        let transaction_id = transaction.id();
+        assert_eq!(consensus.unconfirmed_transaction_status(&transaction_id), None);
+
+        // A transaction in the consensus queue is queued.
+        consensus.transactions_queue.lock().insert(transaction.clone()).unwrap();
+        assert_eq!(consensus.unconfirmed_transaction_status(&transaction_id), Some(TransactionStatus::Queued));
+
+        // A transaction in the batch proposal of this node is proposed.
+        assert!(consensus.transactions_queue.lock().pop_execution().is_some());
+        let transmission_id = TransmissionID::from(&transaction_id);
+        let transmissions = IndexMap::from([(transmission_id, Transmission::Transaction(Data::Object(transaction)))]);
+        let batch_header = BatchHeader::new(
+            account.private_key(),
+            1,
+            now(),
+            committee.id(),
+            transmissions.keys().copied().collect(),
+            Default::default(),
+            rng,
+        )
+        .unwrap();
+        let proposal = Proposal::new(committee, batch_header, transmissions).unwrap();
+        *consensus.bft.primary().proposed_batch().write() = Some(proposal);
+        let status = consensus.unconfirmed_transaction_status(&transaction_id);
+        assert_eq!(status, Some(TransactionStatus::Proposed { round: 1 }));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_transaction_status() {
+        let rng = &mut TestRng::default();
+        let directory = tempfile::tempdir().unwrap();
+        // Reject every transaction with a public fee.
+        let memory_pool = MemoryPoolConfig { max_transactions_per_sender: 0, ..Default::default() };
+        let (consensus, _, _) = sample_consensus(directory.path(), memory_pool, rng);
+
+        // A transaction that is rejected by the memory pool policy is reported with the reason.
+        let transaction = sample_execution_transaction_with_fee(false, rng);
+        let transaction_id = transaction.id();
+        assert!(consensus.add_unconfirmed_transaction(transaction.clone()).await.is_err());
+        match consensus.unconfirmed_transaction_status(&transaction_id) {
+            Some(TransactionStatus::Rejected { height: None, block_hash: None, reason: Some(reason) }) => {
+                assert!(reason.contains("exceeds the limit"))
+            }
+            status => panic!("Unexpected status {status:?}"),
+        }
+
+        // The rejected transaction is not marked as seen, so it is checked again when it is received again.
+        assert!(!consensus.seen_transactions.lock().contains(&transaction_id));
+        assert!(consensus.add_unconfirmed_transaction(transaction).await.is_err());
+    }
+}
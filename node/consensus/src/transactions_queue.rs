@@ -0,0 +1,439 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_bft::helpers::{fmt_id, Priority};
+use snarkvm::{ledger::block::Transaction, prelude::*};
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+};
+
+/// The default capacity of the queue reserved for deployments.
+/// Note: This is an inbound queue capacity, not a Narwhal-enforced capacity.
+const CAPACITY_FOR_DEPLOYMENTS: usize = 1 << 10;
+/// The default capacity of the queue reserved for executions.
+/// Note: This is an inbound queue capacity, not a Narwhal-enforced capacity.
+const CAPACITY_FOR_EXECUTIONS: usize = 1 << 10;
+/// The default maximum number of queued transactions from a single fee payer.
+const MAX_TRANSACTIONS_PER_SENDER: usize = 1 << 6;
+/// The default maximum number of queued transactions with a private fee, whose fee payer is not known.
+const MAX_PRIVATE_FEE_TRANSACTIONS: usize = 1 << 8;
+
+/// The configurations of the memory pool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MemoryPoolConfig {
+    /// The maximum number of deployments in the queue.
+    pub max_deployments: usize,
+    /// The maximum number of executions in the queue.
+    pub max_executions: usize,
+    /// The maximum number of queued transactions from a single fee payer.
+    pub max_transactions_per_sender: usize,
+    /// The maximum number of queued transactions with a private fee.
+    /// Note: The fee payer of a private fee is not known, so these transactions share a single limit.
+    pub max_private_fee_transactions: usize,
+}
+
+impl Default for MemoryPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_deployments: CAPACITY_FOR_DEPLOYMENTS,
+            max_executions: CAPACITY_FOR_EXECUTIONS,
+            max_transactions_per_sender: MAX_TRANSACTIONS_PER_SENDER,
+            max_private_fee_transactions: MAX_PRIVATE_FEE_TRANSACTIONS,
+        }
+    }
+}
+
+/// The ordering key of a queued transaction, which sorts by decreasing priority, and then by arrival.
+type OrderKey = (Reverse<Priority>, u64);
+
+/// A queued transaction.
+struct Entry<N: Network> {
+    /// The transaction.
+    transaction: Transaction<N>,
+    /// The ordering key of the transaction.
+    key: OrderKey,
+    /// The fee payer of the transaction, if the fee is public.
+    sender: Option<Address<N>>,
+}
+
+/// A bounded queue of transactions, ordered by priority fee per byte.
+struct PriorityQueue<N: Network> {
+    /// The maximum number of transactions in the queue.
+    capacity: usize,
+    /// The map of `(transaction ID, entry)` entries.
+    entries: HashMap<N::TransactionID, Entry<N>>,
+    /// The transaction IDs, from the highest to the lowest priority.
+    order: BTreeMap<OrderKey, N::TransactionID>,
+}
+
+impl<N: Network> PriorityQueue<N> {
+    /// Initializes a new queue with the given capacity.
+    fn new(capacity: usize) -> Self {
+        Self { capacity, entries: Default::default(), order: Default::default() }
+    }
+
+    /// Returns `true` if the queue is full.
+    fn is_full(&self) -> bool {
+        self.entries.len() >= self.capacity
+    }
+
+    /// Returns the ordering key and ID of the lowest-priority transaction.
+    fn lowest(&self) -> Option<(OrderKey, N::TransactionID)> {
+        self.order.last_key_value().map(|(key, id)| (*key, *id))
+    }
+
+    /// Inserts the given entry into the queue.
+    fn insert(&mut self, transaction_id: N::TransactionID, entry: Entry<N>) {
+        self.order.insert(entry.key, transaction_id);
+        self.entries.insert(transaction_id, entry);
+    }
+
+    /// Removes the given transaction from the queue.
+    fn remove(&mut self, transaction_id: &N::TransactionID) -> Option<Entry<N>> {
+        let entry = self.entries.remove(transaction_id)?;
+        self.order.remove(&entry.key);
+        Some(entry)
+    }
+
+    /// Removes the highest-priority transaction from the queue.
+    fn pop(&mut self) -> Option<Entry<N>> {
+        let (_, transaction_id) = self.order.pop_first()?;
+        self.entries.remove(&transaction_id)
+    }
+}
+
+/// The number of queued transactions for each fee payer.
+struct Senders<N: Network> {
+    /// The number of queued transactions with a public fee, for each fee payer.
+    public: HashMap<Address<N>, usize>,
+    /// The number of queued transactions with a private fee.
+    private: usize,
+}
+
+impl<N: Network> Senders<N> {
+    /// Returns the number of queued transactions for the given fee payer, where `None` denotes a private fee.
+    fn get(&self, sender: Option<Address<N>>) -> usize {
+        match sender {
+            Some(sender) => self.public.get(&sender).copied().unwrap_or_default(),
+            None => self.private,
+        }
+    }
+
+    /// Increments the number of queued transactions for the given fee payer.
+    fn increment(&mut self, sender: Option<Address<N>>) {
+        match sender {
+            Some(sender) => *self.public.entry(sender).or_default() += 1,
+            None => self.private += 1,
+        }
+    }
+
+    /// Decrements the number of queued transactions for the given fee payer.
+    fn decrement(&mut self, sender: Option<Address<N>>) {
+        match sender {
+            Some(sender) => {
+                if let Some(num_queued) = self.public.get_mut(&sender) {
+                    *num_queued = num_queued.saturating_sub(1);
+                    if *num_queued == 0 {
+                        self.public.remove(&sender);
+                    }
+                }
+            }
+            None => self.private = self.private.saturating_sub(1),
+        }
+    }
+}
+
+/// Helper struct to track incoming transactions, by priority fee per byte.
+pub(crate) struct TransactionsQueue<N: Network> {
+    /// The configurations of the memory pool.
+    config: MemoryPoolConfig,
+    /// The queue of deployments.
+    deployments: PriorityQueue<N>,
+    /// The queue of executions.
+    executions: PriorityQueue<N>,
+    /// The number of queued transactions for each fee payer.
+    senders: Senders<N>,
+    /// The arrival counter, which breaks ties between transactions of equal priority.
+    sequence: u64,
+}
+
+impl<N: Network> TransactionsQueue<N> {
+    /// Initializes a new queue with the given configurations.
+    pub(crate) fn new(config: MemoryPoolConfig) -> Self {
+        Self {
+            config,
+            deployments: PriorityQueue::new(config.max_deployments),
+            executions: PriorityQueue::new(config.max_executions),
+            senders: Senders { public: Default::default(), private: 0 },
+            sequence: 0,
+        }
+    }
+
+    /// Returns the number of deployments in the queue.
+    pub(crate) fn num_deployments(&self) -> usize {
+        self.deployments.entries.len()
+    }
+
+    /// Returns the number of executions in the queue.
+    pub(crate) fn num_executions(&self) -> usize {
+        self.executions.entries.len()
+    }
+
+    /// Returns `true` if the queue contains the given transaction ID.
+    pub(crate) fn contains(&self, transaction_id: &N::TransactionID) -> bool {
+        self.deployments.entries.contains_key(transaction_id) || self.executions.entries.contains_key(transaction_id)
+    }
+
+    /// Inserts the given transaction into the queue, and returns the IDs of the evicted transactions.
+    ///
+    /// If the queue is full, the lowest-priority transaction is evicted, provided that the given
+    /// transaction has a strictly higher priority. Otherwise, the given transaction is rejected.
+    pub(crate) fn insert(&mut self, transaction: Transaction<N>) -> Result<Vec<N::TransactionID>> {
+        // Determine the priority of the transaction.
+        let priority = Priority::from_transaction(&transaction)?;
+        self.insert_with_priority(transaction, priority)
+    }
+
+    /// Inserts the given transaction into the queue with the given priority, and returns the IDs of the evicted
+    /// transactions.
+    fn insert_with_priority(
+        &mut self,
+        transaction: Transaction<N>,
+        priority: Priority,
+    ) -> Result<Vec<N::TransactionID>> {
+        let transaction_id = transaction.id();
+        // Ensure the transaction is not already in the queue.
+        if self.contains(&transaction_id) {
+            bail!("Transaction '{}' exists in the memory pool", fmt_id(transaction_id))
+        }
+        // Determine the fee payer of the transaction.
+        // Note: The fee payer of a private fee is not known, so these transactions share a single limit.
+        let sender = transaction.fee_transition().and_then(|fee| fee.payer());
+        // Ensure the fee payer has not reached the limit of queued transactions.
+        let num_queued = self.senders.get(sender);
+        match sender {
+            Some(sender) if num_queued >= self.config.max_transactions_per_sender => bail!(
+                "Transaction '{}' exceeds the limit of {} queued transactions for '{sender}'",
+                fmt_id(transaction_id),
+                self.config.max_transactions_per_sender
+            ),
+            None if num_queued >= self.config.max_private_fee_transactions => bail!(
+                "Transaction '{}' exceeds the limit of {} queued transactions with a private fee",
+                fmt_id(transaction_id),
+                self.config.max_private_fee_transactions
+            ),
+            _ => (),
+        }
+
+        // Determine the ordering key of the transaction.
+        let key = (Reverse(priority), self.sequence);
+        let queue = if transaction.is_deploy() { &mut self.deployments } else { &mut self.executions };
+        // If the queue is full, evict the lowest-priority transaction, if it has a lower priority.
+        let mut evicted = Vec::new();
+        if queue.is_full() {
+            match queue.lowest() {
+                Some((lowest_key, lowest_id)) if lowest_key.0 > key.0 => {
+                    if let Some(entry) = queue.remove(&lowest_id) {
+                        self.senders.decrement(entry.sender);
+                    }
+                    evicted.push(lowest_id);
+                }
+                _ => bail!(
+                    "Transaction '{}' has too low a priority fee for the full memory pool",
+                    fmt_id(transaction_id)
+                ),
+            }
+        }
+        // Insert the transaction.
+        queue.insert(transaction_id, Entry { transaction, key, sender });
+        self.senders.increment(sender);
+        self.sequence += 1;
+        Ok(evicted)
+    }
+
+    /// Removes and returns the highest-priority deployment, along with its priority.
+    pub(crate) fn pop_deployment(&mut self) -> Option<(Transaction<N>, Priority)> {
+        let entry = self.deployments.pop()?;
+        Some(self.release(entry))
+    }
+
+    /// Removes and returns the highest-priority execution, along with its priority.
+    pub(crate) fn pop_execution(&mut self) -> Option<(Transaction<N>, Priority)> {
+        let entry = self.executions.pop()?;
+        Some(self.release(entry))
+    }
+
+    /// Releases the given entry from the per-sender limits, and returns its transaction and priority.
+    fn release(&mut self, entry: Entry<N>) -> (Transaction<N>, Priority) {
+        self.senders.decrement(entry.sender);
+        let (Reverse(priority), _) = entry.key;
+        (entry.transaction, priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::ledger::ledger_test_helpers::sample_execution_transaction_with_fee;
+
+    type CurrentNetwork = snarkvm::prelude::MainnetV0;
+
+    /// Samples an execution with a public fee, which is paid by the same fee payer in every sample.
+    fn sample_public_fee_execution(rng: &mut TestRng) -> Transaction<CurrentNetwork> {
+        sample_execution_transaction_with_fee(false, rng)
+    }
+
+    /// Samples an execution with a private fee.
+    fn sample_private_fee_execution(rng: &mut TestRng) -> Transaction<CurrentNetwork> {
+        sample_execution_transaction_with_fee(true, rng)
+    }
+
+    /// Returns the fee payer of the given transaction.
+    fn sender(transaction: &Transaction<CurrentNetwork>) -> Option<Address<CurrentNetwork>> {
+        transaction.fee_transition().and_then(|fee| fee.payer())
+    }
+
+    #[test]
+    fn test_insert_and_pop() {
+        let rng = &mut TestRng::default();
+
+        let mut queue = TransactionsQueue::<CurrentNetwork>::new(MemoryPoolConfig::default());
+        let transaction = sample_public_fee_execution(rng);
+        let transaction_id = transaction.id();
+        let sender = sender(&transaction);
+        assert!(sender.is_some());
+
+        // Insert the transaction.
+        assert_eq!(queue.insert(transaction.clone()).unwrap(), vec![]);
+        assert!(queue.contains(&transaction_id));
+        assert_eq!(queue.num_executions(), 1);
+        assert_eq!(queue.num_deployments(), 0);
+        assert_eq!(queue.senders.get(sender), 1);
+
+        // Ensure a duplicate is rejected, and does not count towards the fee payer.
+        assert!(queue.insert(transaction.clone()).is_err());
+        assert_eq!(queue.num_executions(), 1);
+        assert_eq!(queue.senders.get(sender), 1);
+
+        // Pop the transaction, and ensure the fee payer is released.
+        assert!(queue.pop_deployment().is_none());
+        let (popped, priority) = queue.pop_execution().unwrap();
+        assert_eq!(popped.id(), transaction_id);
+        assert_eq!(priority, Priority::from_transaction(&transaction).unwrap());
+        assert!(!queue.contains(&transaction_id));
+        assert_eq!(queue.senders.get(sender), 0);
+        assert!(queue.senders.public.is_empty());
+        assert!(queue.pop_execution().is_none());
+    }
+
+    #[test]
+    fn test_pop_by_fee_per_byte() {
+        let rng = &mut TestRng::default();
+
+        let mut queue = TransactionsQueue::<CurrentNetwork>::new(MemoryPoolConfig::default());
+        // Sample transactions of different sizes, so that they have different fees per byte.
+        let mut transactions = vec![
+            sample_private_fee_execution(rng),
+            sample_public_fee_execution(rng),
+            sample_private_fee_execution(rng),
+            sample_public_fee_execution(rng),
+        ];
+        for transaction in &transactions {
+            queue.insert(transaction.clone()).unwrap();
+        }
+
+        // Ensure the transactions are popped in decreasing order of fee per byte, and then in arrival order.
+        // Note: The sort is stable, which preserves the arrival order of transactions with equal priority.
+        transactions.sort_by_key(|transaction| Reverse(Priority::from_transaction(transaction).unwrap()));
+        let popped = std::iter::from_fn(|| queue.pop_execution()).collect::<Vec<_>>();
+        assert_eq!(popped.len(), transactions.len());
+        for ((transaction, priority), expected) in popped.into_iter().zip_eq(transactions) {
+            assert_eq!(transaction.id(), expected.id());
+            assert_eq!(priority, Priority::from_transaction(&expected).unwrap());
+        }
+        assert!(queue.senders.public.is_empty());
+        assert_eq!(queue.senders.private, 0);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let rng = &mut TestRng::default();
+
+        let config = MemoryPoolConfig { max_executions: 2, ..Default::default() };
+        let mut queue = TransactionsQueue::<CurrentNetwork>::new(config);
+        let [low, high, equal, higher] = [(); 4].map(|_| sample_public_fee_execution(rng));
+        let sender = sender(&low);
+
+        // Fill the queue.
+        assert_eq!(queue.insert_with_priority(low.clone(), Priority::new(10, 1)).unwrap(), vec![]);
+        assert_eq!(queue.insert_with_priority(high.clone(), Priority::new(20, 1)).unwrap(), vec![]);
+        assert_eq!(queue.senders.get(sender), 2);
+
+        // Ensure a transaction with the same priority as the lowest one is rejected.
+        assert!(queue.insert_with_priority(equal.clone(), Priority::new(20, 2)).is_err());
+        assert!(!queue.contains(&equal.id()));
+        assert!(queue.contains(&low.id()));
+
+        // Ensure a transaction with a strictly higher priority evicts the lowest one.
+        assert_eq!(queue.insert_with_priority(higher.clone(), Priority::new(11, 1)).unwrap(), vec![low.id()]);
+        assert!(!queue.contains(&low.id()));
+        assert_eq!(queue.num_executions(), 2);
+        assert_eq!(queue.senders.get(sender), 2);
+
+        // Ensure the remaining transactions are popped in decreasing order of priority.
+        assert_eq!(queue.pop_execution().unwrap().0.id(), high.id());
+        assert_eq!(queue.pop_execution().unwrap().0.id(), higher.id());
+        assert_eq!(queue.senders.get(sender), 0);
+    }
+
+    #[test]
+    fn test_sender_limits() {
+        let rng = &mut TestRng::default();
+
+        let config =
+            MemoryPoolConfig { max_transactions_per_sender: 2, max_private_fee_transactions: 1, ..Default::default() };
+        let mut queue = TransactionsQueue::<CurrentNetwork>::new(config);
+
+        // Ensure the fee payer may not exceed its limit.
+        let [first, second, third] = [(); 3].map(|_| sample_public_fee_execution(rng));
+        queue.insert(first.clone()).unwrap();
+        queue.insert(second).unwrap();
+        assert!(queue.insert(third.clone()).is_err());
+        assert!(!queue.contains(&third.id()));
+
+        // Ensure the limit is released once a transaction is popped.
+        queue.pop_execution().unwrap();
+        queue.insert(third).unwrap();
+        assert_eq!(queue.senders.get(sender(&first)), 2);
+
+        // Ensure the transactions with a private fee share a single limit.
+        let [private_first, private_second] = [(); 2].map(|_| sample_private_fee_execution(rng));
+        assert!(sender(&private_first).is_none());
+        queue.insert(private_first).unwrap();
+        assert!(queue.insert(private_second.clone()).is_err());
+        assert_eq!(queue.senders.private, 1);
+
+        // Ensure the limit is released once the transaction is evicted.
+        let config = MemoryPoolConfig { max_executions: 1, max_private_fee_transactions: 1, ..Default::default() };
+        let mut queue = TransactionsQueue::<CurrentNetwork>::new(config);
+        queue.insert_with_priority(private_second.clone(), Priority::new(1, 1)).unwrap();
+        assert_eq!(queue.senders.private, 1);
+        let public = sample_public_fee_execution(rng);
+        assert_eq!(queue.insert_with_priority(public.clone(), Priority::new(2, 1)).unwrap(), vec![private_second.id()]);
+        assert_eq!(queue.senders.private, 0);
+        assert_eq!(queue.senders.get(sender(&public)), 1);
+    }
+}
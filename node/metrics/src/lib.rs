@@ -19,6 +19,8 @@ pub use names::*;
 // Re-export the snarkVM metrics.
 pub use snarkvm::metrics::*;
 
+use std::time::Instant;
+
 /// Initializes the metrics and returns a handle to the task running the metrics exporter.
 pub fn initialize_metrics() {
     // Build the Prometheus exporter.
@@ -38,3 +40,38 @@ pub fn initialize_metrics() {
         register_histogram(name);
     }
 }
+
+/// Increments the counter with the given name and labels by the given value.
+pub fn increment_counter_with_labels(name: &'static str, value: u64, labels: &[(&'static str, String)]) {
+    ::metrics::counter!(name, labels).increment(value);
+}
+
+/// Sets the counter with the given name and labels to the given (cumulative) value.
+pub fn absolute_counter_with_labels(name: &'static str, value: u64, labels: &[(&'static str, String)]) {
+    ::metrics::counter!(name, labels).absolute(value);
+}
+
+/// Records the given value in the histogram with the given name and labels.
+pub fn histogram_with_labels(name: &'static str, value: f64, labels: &[(&'static str, String)]) {
+    ::metrics::histogram!(name, labels).record(value);
+}
+
+/// A timer that records the elapsed time (in seconds) in the histogram with the given name and labels, once dropped.
+pub struct HistogramTimer {
+    name: &'static str,
+    labels: Vec<(&'static str, String)>,
+    start: Instant,
+}
+
+impl HistogramTimer {
+    /// Starts a new timer for the histogram with the given name and labels.
+    pub fn new(name: &'static str, labels: Vec<(&'static str, String)>) -> Self {
+        Self { name, labels, start: Instant::now() }
+    }
+}
+
+impl Drop for HistogramTimer {
+    fn drop(&mut self) {
+        histogram_with_labels(self.name, self.start.elapsed().as_secs_f64(), &self.labels);
+    }
+}
@@ -12,14 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub(super) const COUNTER_NAMES: [&str; 1] = [bft::LEADERS_ELECTED];
+pub(super) const COUNTER_NAMES: [&str; 5] = [
+    bft::LEADERS_ELECTED,
+    bft::LEADER_CERTIFICATE_TIMEOUTS,
+    bft::PROPOSALS_EXPIRED,
+    prover::ATTEMPTS,
+    prover::SOLUTIONS,
+];
 
-pub(super) const GAUGE_NAMES: [&str; 21] = [
+pub(super) const GAUGE_NAMES: [&str; 31] = [
     bft::CONNECTED,
     bft::CONNECTING,
     bft::LAST_STORED_ROUND,
     bft::PROPOSAL_ROUND,
     bft::CERTIFIED_BATCHES,
+    bft::PROPOSAL_NONSIGNERS,
+    bft::LEADER_CERTIFICATE_MAX_DELAY,
+    bft::DAG_ROUNDS,
+    bft::DAG_CERTIFICATES,
+    bft::GC_ROUND,
     blocks::HEIGHT,
     blocks::SOLUTIONS,
     blocks::TRANSACTIONS,
@@ -32,14 +43,22 @@ pub(super) const GAUGE_NAMES: [&str; 21] = [
     consensus::UNCONFIRMED_SOLUTIONS,
     consensus::UNCONFIRMED_TRANSACTIONS,
     consensus::UNCONFIRMED_TRANSMISSIONS,
+    prover::ATTEMPTS_PER_SEC,
+    prover::EPOCH_SOLUTIONS,
+    prover::BEST_PROOF_TARGET,
+    prover::LAST_FOUND_SOLUTION_AGE,
+    prover::PUZZLE_INSTANCES,
     router::CONNECTED,
     router::CANDIDATE,
     router::RESTRICTED,
     tcp::TCP_TASKS,
 ];
 
-pub(super) const HISTOGRAM_NAMES: [&str; 7] = [
+pub(super) const HISTOGRAM_NAMES: [&str; 10] = [
     bft::COMMIT_ROUNDS_LATENCY,
+    bft::PROPOSAL_QUORUM_LATENCY,
+    bft::PROPOSAL_SIGNATURES,
+    bft::LEADER_CERTIFICATE_WAIT,
     consensus::CERTIFICATE_COMMIT_LATENCY,
     consensus::BLOCK_LATENCY,
     tcp::NOISE_CODEC_ENCRYPTION_TIME,
@@ -52,10 +71,29 @@ pub mod bft {
     pub const COMMIT_ROUNDS_LATENCY: &str = "snarkos_bft_commit_rounds_latency_secs"; // <-- This one doesn't even make sense.
     pub const CONNECTED: &str = "snarkos_bft_connected_total";
     pub const CONNECTING: &str = "snarkos_bft_connecting_total";
+    // The following metrics are labeled, and are therefore not registered on init.
+    pub const EVENTS_RECEIVED: &str = "snarkos_bft_events_received_total";
+    pub const EVENTS_SENT: &str = "snarkos_bft_events_sent_total";
+    pub const EVENT_BYTES_RECEIVED: &str = "snarkos_bft_event_bytes_received_total";
+    pub const EVENT_BYTES_SENT: &str = "snarkos_bft_event_bytes_sent_total";
+    pub const EVENT_HANDLER_LATENCY: &str = "snarkos_bft_event_handler_latency_secs";
+    pub const MISSED_LEADER_ROUNDS: &str = "snarkos_bft_missed_leader_rounds_total";
+    pub const PEER_BYTES_RECEIVED: &str = "snarkos_bft_peer_bytes_received_total";
+    pub const PEER_BYTES_SENT: &str = "snarkos_bft_peer_bytes_sent_total";
     pub const LAST_STORED_ROUND: &str = "snarkos_bft_last_stored_round";
     pub const LEADERS_ELECTED: &str = "snarkos_bft_leaders_elected_total";
     pub const PROPOSAL_ROUND: &str = "snarkos_bft_primary_proposal_round";
     pub const CERTIFIED_BATCHES: &str = "snarkos_bft_primary_certified_batches";
+    pub const PROPOSAL_QUORUM_LATENCY: &str = "snarkos_bft_primary_proposal_quorum_latency_secs";
+    pub const PROPOSAL_SIGNATURES: &str = "snarkos_bft_primary_proposal_signatures";
+    pub const PROPOSAL_NONSIGNERS: &str = "snarkos_bft_primary_proposal_nonsigners";
+    pub const PROPOSALS_EXPIRED: &str = "snarkos_bft_primary_proposals_expired_total";
+    pub const LEADER_CERTIFICATE_WAIT: &str = "snarkos_bft_leader_certificate_wait_secs";
+    pub const LEADER_CERTIFICATE_MAX_DELAY: &str = "snarkos_bft_leader_certificate_max_delay_secs";
+    pub const LEADER_CERTIFICATE_TIMEOUTS: &str = "snarkos_bft_leader_certificate_timeouts_total";
+    pub const DAG_ROUNDS: &str = "snarkos_bft_dag_rounds";
+    pub const DAG_CERTIFICATES: &str = "snarkos_bft_dag_certificates";
+    pub const GC_ROUND: &str = "snarkos_bft_gc_round";
 }
 
 pub mod blocks {
@@ -78,10 +116,30 @@ pub mod consensus {
     pub const UNCONFIRMED_SOLUTIONS: &str = "snarkos_consensus_unconfirmed_solutions_total";
 }
 
+pub mod prover {
+    pub const ATTEMPTS: &str = "snarkos_prover_attempts_total";
+    pub const ATTEMPTS_PER_SEC: &str = "snarkos_prover_attempts_per_sec";
+    pub const SOLUTIONS: &str = "snarkos_prover_solutions_total";
+    pub const EPOCH_SOLUTIONS: &str = "snarkos_prover_epoch_solutions";
+    pub const BEST_PROOF_TARGET: &str = "snarkos_prover_best_proof_target";
+    pub const LAST_FOUND_SOLUTION_AGE: &str = "snarkos_prover_last_found_solution_age_secs";
+    pub const PUZZLE_INSTANCES: &str = "snarkos_prover_puzzle_instances";
+    // The following metrics are labeled, and are therefore not registered on init.
+    pub const POOL_SHARES: &str = "snarkos_prover_pool_shares_total";
+}
+
 pub mod router {
     pub const CONNECTED: &str = "snarkos_router_connected_total";
     pub const CANDIDATE: &str = "snarkos_router_candidate_total";
     pub const RESTRICTED: &str = "snarkos_router_restricted_total";
+    // The following metrics are labeled, and are therefore not registered on init.
+    pub const MESSAGES_RECEIVED: &str = "snarkos_router_messages_received_total";
+    pub const MESSAGES_SENT: &str = "snarkos_router_messages_sent_total";
+    pub const MESSAGE_BYTES_RECEIVED: &str = "snarkos_router_message_bytes_received_total";
+    pub const MESSAGE_BYTES_SENT: &str = "snarkos_router_message_bytes_sent_total";
+    pub const MESSAGE_HANDLER_LATENCY: &str = "snarkos_router_message_handler_latency_secs";
+    pub const PEER_BYTES_RECEIVED: &str = "snarkos_router_peer_bytes_received_total";
+    pub const PEER_BYTES_SENT: &str = "snarkos_router_peer_bytes_sent_total";
 }
 
 pub mod tcp {
@@ -0,0 +1,137 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{
+    block::{Block, Transaction},
+    store::ConsensusStorage,
+    Identifier,
+    Ledger,
+    Network,
+    ProgramID,
+};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+/// The representation of each block returned by the block range routes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockFormat {
+    /// The full block.
+    #[default]
+    Full,
+    /// The block height, hash, and header.
+    Header,
+    /// The block height, hash, and transaction IDs.
+    TransactionIds,
+}
+
+impl BlockFormat {
+    /// Returns the maximum number of blocks that may be returned in one page, for this format.
+    pub const fn max_page_size(&self) -> u32 {
+        match self {
+            Self::Full => 50,
+            Self::Header | Self::TransactionIds => 1000,
+        }
+    }
+}
+
+/// A filter on the transactions of a block, by program ID and (optionally) function name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionFilter<N: Network> {
+    /// The program ID to match.
+    program_id: ProgramID<N>,
+    /// The function name to match, if any.
+    function_name: Option<Identifier<N>>,
+}
+
+impl<N: Network> TransactionFilter<N> {
+    /// Parses the filter from the given query parameters, returning `None` if no filter was requested.
+    pub fn parse(program_id: Option<&str>, function_name: Option<&str>) -> Result<Option<Self>> {
+        match (program_id, function_name) {
+            (None, None) => Ok(None),
+            (None, Some(_)) => Err(anyhow!("A function filter requires a program filter")),
+            (Some(program_id), function_name) => Ok(Some(Self {
+                program_id: ProgramID::from_str(program_id)?,
+                function_name: function_name.map(Identifier::from_str).transpose()?,
+            })),
+        }
+    }
+
+    /// Returns `true` if any transition in the given transaction matches the filter.
+    pub fn matches(&self, transaction: &Transaction<N>) -> bool {
+        transaction.transitions().any(|transition| {
+            transition.program_id() == &self.program_id
+                && self.function_name.as_ref().map_or(true, |function_name| transition.function_name() == function_name)
+        })
+    }
+}
+
+/// Returns the block at the given height in the requested format,
+/// or `None` if a filter is given and no transaction in the block matches it.
+pub fn render_block<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    height: u32,
+    format: BlockFormat,
+    filter: Option<&TransactionFilter<N>>,
+) -> Result<Option<Value>> {
+    // If there is no filter, a header can be rendered without loading the full block.
+    if filter.is_none() && format == BlockFormat::Header {
+        return Ok(Some(json!({
+            "height": height,
+            "hash": ledger.get_hash(height)?,
+            "header": ledger.get_header(height)?,
+        })));
+    }
+
+    // Retrieve the block.
+    let block = ledger.get_block(height)?;
+    // Determine the IDs of the matching transactions.
+    let transaction_ids = matching_transaction_ids(&block, filter);
+    // If the filter did not match any transaction, skip the block.
+    if filter.is_some() && transaction_ids.is_empty() {
+        return Ok(None);
+    }
+
+    let value = match format {
+        BlockFormat::Full => serde_json::to_value(&block)?,
+        BlockFormat::Header => json!({
+            "height": height,
+            "hash": block.hash(),
+            "header": block.header(),
+        }),
+        BlockFormat::TransactionIds => json!({
+            "height": height,
+            "hash": block.hash(),
+            "transactionIds": transaction_ids,
+        }),
+    };
+    Ok(Some(value))
+}
+
+/// Returns the IDs of the transactions in the given block that match the filter.
+/// If no filter is given, all transaction IDs are returned.
+fn matching_transaction_ids<N: Network>(
+    block: &Block<N>,
+    filter: Option<&TransactionFilter<N>>,
+) -> Vec<N::TransactionID> {
+    block
+        .transactions()
+        .iter()
+        .filter(|confirmed| filter.map_or(true, |filter| filter.matches(confirmed.transaction())))
+        .map(|confirmed| confirmed.id())
+        .collect()
+}
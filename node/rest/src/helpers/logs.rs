@@ -0,0 +1,45 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::OnceCell;
+
+/// A logger whose verbosity can be adjusted at runtime, via the REST API.
+pub trait LogVerbosity: Send + Sync {
+    /// Returns the current verbosity of the logger.
+    fn verbosity(&self) -> u8;
+
+    /// Applies the given verbosity to the logger.
+    fn set_verbosity(&self, verbosity: u8) -> Result<()>;
+}
+
+/// The logger registered by the node, if any.
+static LOGGER: OnceCell<Box<dyn LogVerbosity>> = OnceCell::new();
+
+/// Registers the logger whose verbosity can be adjusted at runtime, via the REST API.
+/// Note: The logger is global, hence only the first registered logger is retained.
+pub fn register_logger<L: LogVerbosity + 'static>(logger: L) {
+    let _ = LOGGER.set(Box::new(logger));
+}
+
+/// Returns the current verbosity of the logger, if one was registered.
+pub fn log_verbosity() -> Option<u8> {
+    LOGGER.get().map(|logger| logger.verbosity())
+}
+
+/// Applies the given verbosity to the logger.
+pub fn set_log_verbosity(verbosity: u8) -> Result<()> {
+    let logger = LOGGER.get().ok_or_else(|| anyhow!("The logger does not support runtime verbosity"))?;
+    logger.set_verbosity(verbosity)
+}
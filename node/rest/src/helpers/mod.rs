@@ -15,5 +15,11 @@
 mod auth;
 pub use auth::*;
 
+mod blocks;
+pub use blocks::*;
+
 mod error;
 pub use error::*;
+
+mod logs;
+pub use logs::*;
@@ -22,11 +22,14 @@ pub use helpers::*;
 
 mod routes;
 
+mod subscriptions;
+
 use snarkos_node_consensus::Consensus;
 use snarkos_node_router::{
     messages::{Message, UnconfirmedTransaction},
     Routing,
 };
+use snarkos_node_sync::BlockSync;
 use snarkvm::{
     console::{program::ProgramID, types::Field},
     ledger::narwhal::Data,
@@ -46,8 +49,8 @@ use axum::{
 };
 use axum_extra::response::ErasedJson;
 use parking_lot::Mutex;
-use std::{net::SocketAddr, sync::Arc};
-use tokio::{net::TcpListener, task::JoinHandle};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{net::TcpListener, sync::watch, task::JoinHandle};
 use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
 use tower_http::{
     cors::{Any, CorsLayer},
@@ -63,6 +66,10 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
     ledger: Ledger<N, C>,
     /// The node (routing).
     routing: Arc<R>,
+    /// The block sync module.
+    sync: BlockSync<N>,
+    /// The latest block height, used to notify the subscribers.
+    latest_height: watch::Receiver<u32>,
     /// The server handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -75,9 +82,14 @@ impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R>
         consensus: Option<Consensus<N>>,
         ledger: Ledger<N, C>,
         routing: Arc<R>,
+        sync: BlockSync<N>,
     ) -> Result<Self> {
+        // Initialize the latest height notifier.
+        let (height_sender, latest_height) = watch::channel(ledger.latest_height());
         // Initialize the server.
-        let mut server = Self { consensus, ledger, routing, handles: Default::default() };
+        let mut server = Self { consensus, ledger, routing, sync, latest_height, handles: Default::default() };
+        // Spawn the latest height notifier.
+        server.spawn_height_notifier(height_sender);
         // Spawn the server.
         server.spawn_server(rest_ip, rest_rps).await;
         // Return the server.
@@ -95,33 +107,42 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub const fn handles(&self) -> &Arc<Mutex<Vec<JoinHandle<()>>>> {
         &self.handles
     }
+
+    /// Returns the consensus module, or an error if the node does not run consensus.
+    fn require_consensus(&self) -> Result<&Consensus<N>, RestError> {
+        self.consensus.as_ref().ok_or_else(|| RestError("Route isn't available for this node type".to_string()))
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     async fn spawn_server(&mut self, rest_ip: SocketAddr, rest_rps: u32) {
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-            .allow_headers([CONTENT_TYPE]);
-
-        // Log the REST rate limit per IP.
-        debug!("REST rate limit per IP - {rest_rps} RPS");
-
-        // Prepare the rate limiting setup.
-        let governor_config = Box::new(
-            GovernorConfigBuilder::default()
-                .per_second(1)
-                .burst_size(rest_rps)
-                .error_handler(|error| Response::new(error.to_string().into()))
-                .finish()
-                .expect("Couldn't set up rate limiting for the REST server!"),
-        );
-
         let router = {
             axum::Router::new()
 
             // All the endpoints before the call to `route_layer` are protected with JWT auth.
             .route("/mainnet/node/address", get(Self::get_node_address))
+
+            // GET, POST, and DELETE ../admin/.. (runtime peer management)
+            .route("/mainnet/admin/peers/trusted", get(Self::get_trusted_peers))
+            .route(
+                "/mainnet/admin/peers/trusted/:ip",
+                post(Self::insert_trusted_peer).delete(Self::remove_trusted_peer),
+            )
+            .route("/mainnet/admin/peers/restricted", get(Self::get_restricted_peers))
+            .route("/mainnet/admin/peers/restricted/:ip", post(Self::restrict_peer).delete(Self::unrestrict_peer))
+            .route("/mainnet/admin/peers/connected/:ip", post(Self::connect_peer).delete(Self::disconnect_peer))
+            .route("/mainnet/admin/validators/connected", get(Self::get_connected_validators))
+            .route(
+                "/mainnet/admin/validators/connected/:ip",
+                post(Self::connect_validator).delete(Self::disconnect_validator),
+            )
+            .route("/mainnet/admin/validators/trusted", get(Self::get_trusted_validators))
+            .route(
+                "/mainnet/admin/validators/trusted/:ip",
+                post(Self::insert_trusted_validator).delete(Self::remove_trusted_validator),
+            )
+            .route("/mainnet/admin/logs/verbosity", get(Self::get_log_verbosity))
+            .route("/mainnet/admin/logs/verbosity/:verbosity", post(Self::set_log_verbosity))
             .route_layer(middleware::from_fn(auth_middleware))
 
             // ----------------- DEPRECATED ROUTES -----------------
@@ -151,6 +172,7 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
             // GET and POST ../transaction/..
             .route("/mainnet/transaction/:id", get(Self::get_transaction))
+            .route("/mainnet/transaction/:id/status", get(Self::get_transaction_status))
             .route("/mainnet/transaction/confirmed/:id", get(Self::get_confirmed_transaction))
             .route("/mainnet/transaction/broadcast", post(Self::transaction_broadcast))
 
@@ -175,6 +197,8 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
             // GET misc endpoints.
             .route("/mainnet/blocks", get(Self::get_blocks))
+            .route("/mainnet/blocks/page", get(Self::get_blocks_page))
+            .route("/mainnet/blocks/stream", get(Self::stream_blocks))
             .route("/mainnet/height/:hash", get(Self::get_height))
             .route("/mainnet/memoryPool/transmissions", get(Self::get_memory_pool_transmissions))
             .route("/mainnet/memoryPool/solutions", get(Self::get_memory_pool_solutions))
@@ -182,23 +206,23 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             .route("/mainnet/statePath/:commitment", get(Self::get_state_path_for_commitment))
             .route("/mainnet/stateRoot/latest", get(Self::get_state_root_latest))
             .route("/mainnet/committee/latest", get(Self::get_committee_latest))
+            .route("/mainnet/node/sync", get(Self::get_node_sync))
+            .route("/mainnet/cdn/:file_name", get(Self::get_cdn_file))
+
+            // GET ../subscribe/.. (server-sent events)
+            .route("/mainnet/subscribe/blocks", get(Self::subscribe_blocks))
+            .route("/mainnet/subscribe/transactions", get(Self::subscribe_transactions))
+            .route("/mainnet/subscribe/memoryPool", get(Self::subscribe_memory_pool))
 
             // Pass in `Rest` to make things convenient.
             .with_state(self.clone())
-            // Enable tower-http tracing.
-            .layer(TraceLayer::new_for_http())
-            // Custom logging.
-            .layer(middleware::from_fn(log_middleware))
-            // Enable CORS.
-            .layer(cors)
-            // Cap body size at 10MB.
-            .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
-            .layer(GovernorLayer {
-                // We can leak this because it is created only once and it persists.
-                config: Box::leak(governor_config),
-            })
         };
 
+        let router = with_middleware(router, rest_rps)
+            // GET health checks (load balancers and orchestrators), which are not rate limited.
+            .route("/health", get(Self::get_health))
+            .route("/ready", get(Self::get_ready).with_state(self.clone()));
+
         let rest_listener = TcpListener::bind(rest_ip).await.unwrap();
         self.handles.lock().push(tokio::spawn(async move {
             axum::serve(rest_listener, router.into_make_service_with_connect_info::<SocketAddr>())
@@ -208,6 +232,67 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     }
 }
 
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// The interval at which the latest block height is checked for the subscribers.
+    const HEIGHT_NOTIFIER_INTERVAL_IN_MS: u64 = 500;
+
+    /// Spawns a task that notifies the subscribers whenever the ledger advances.
+    fn spawn_height_notifier(&self, height_sender: watch::Sender<u32>) {
+        let ledger = self.ledger.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(Self::HEIGHT_NOTIFIER_INTERVAL_IN_MS)).await;
+                // Retrieve the latest height.
+                let latest_height = ledger.latest_height();
+                // Notify the subscribers, only if the latest height has changed.
+                height_sender.send_if_modified(|height| {
+                    let is_modified = *height != latest_height;
+                    *height = latest_height;
+                    is_modified
+                });
+            }
+        }))
+    }
+}
+
+/// Applies the middleware of the REST server to the given router, namely the tracing, logging, CORS,
+/// body size limit, and the rate limiting of `rest_rps` requests per IP.
+///
+/// Note: The router must be served with `into_make_service_with_connect_info::<SocketAddr>`.
+pub fn with_middleware(router: axum::Router, rest_rps: u32) -> axum::Router {
+    let cors = CorsLayer::new()
+        .allow_origin(Any)
+        .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+        .allow_headers([CONTENT_TYPE]);
+
+    // Log the REST rate limit per IP.
+    debug!("REST rate limit per IP - {rest_rps} RPS");
+
+    // Prepare the rate limiting setup.
+    let governor_config = Box::new(
+        GovernorConfigBuilder::default()
+            .per_second(1)
+            .burst_size(rest_rps)
+            .error_handler(|error| Response::new(error.to_string().into()))
+            .finish()
+            .expect("Couldn't set up rate limiting for the REST server!"),
+    );
+
+    router
+        // Enable tower-http tracing.
+        .layer(TraceLayer::new_for_http())
+        // Custom logging.
+        .layer(middleware::from_fn(log_middleware))
+        // Enable CORS.
+        .layer(cors)
+        // Cap body size at 10MB.
+        .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
+        .layer(GovernorLayer {
+            // We can leak this because it is created only once per server and it persists.
+            config: Box::leak(governor_config),
+        })
+}
+
 async fn log_middleware(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     request: Request<Body>,
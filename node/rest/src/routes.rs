@@ -13,12 +13,21 @@
 // limitations under the License.
 
 use super::*;
-use snarkos_node_router::messages::UnconfirmedSolution;
+use snarkos_node_consensus::{MemoryPoolEvent, TransactionStatus};
+use snarkos_node_router::messages::{DisconnectReason, UnconfirmedSolution};
 use snarkvm::{
     ledger::puzzle::Solution,
     prelude::{block::Transaction, Identifier, Plaintext},
 };
 
+use axum::{
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+};
+use futures_util::{stream, Stream, StreamExt};
 use indexmap::IndexMap;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -33,6 +42,52 @@ pub(crate) struct BlockRange {
     end: u32,
 }
 
+/// The `get_blocks_page` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct BlockPage {
+    /// The starting block height (inclusive), which defaults to the genesis block.
+    cursor: Option<u32>,
+    /// The maximum number of blocks to return.
+    limit: Option<u32>,
+    /// The format of each block.
+    #[serde(default)]
+    format: BlockFormat,
+    /// The program ID to filter the transactions by.
+    program: Option<String>,
+    /// The function name to filter the transactions by.
+    function: Option<String>,
+}
+
+/// The `stream_blocks` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct BlockStream {
+    /// The starting block height (inclusive).
+    start: u32,
+    /// The ending block height (exclusive).
+    end: u32,
+    /// The format of each block.
+    #[serde(default)]
+    format: BlockFormat,
+    /// The program ID to filter the transactions by.
+    program: Option<String>,
+    /// The function name to filter the transactions by.
+    function: Option<String>,
+}
+
+/// The `subscribe_blocks` and `subscribe_transactions` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct Cursor {
+    /// The block height to resume from (inclusive), if any.
+    from: Option<u32>,
+}
+
+/// The `restrict_peer` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct RestrictionReason {
+    /// The reason for the restriction.
+    reason: Option<String>,
+}
+
 /// The `get_mapping_value` query object.
 #[derive(Deserialize, Serialize)]
 pub(crate) struct Metadata {
@@ -142,6 +197,91 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(blocks))
     }
 
+    // GET /mainnet/blocks/page?cursor={start_height}&limit={limit}&format={format}&program={programID}&function={functionName}
+    pub(crate) async fn get_blocks_page(
+        State(rest): State<Self>,
+        Query(page): Query<BlockPage>,
+    ) -> Result<ErasedJson, RestError> {
+        // The maximum number of blocks scanned per call.
+        const MAX_BLOCKS_SCANNED: u32 = 1000;
+        // The maximum size of the returned blocks, in bytes.
+        const MAX_RESPONSE_SIZE_IN_BYTES: usize = 8 * 1024 * 1024;
+
+        // Parse the transaction filter.
+        let filter = TransactionFilter::parse(page.program.as_deref(), page.function.as_deref())?;
+
+        // Ensure the page limit is bounded.
+        let max_page_size = page.format.max_page_size();
+        let limit = page.limit.unwrap_or(max_page_size);
+        if limit == 0 || limit > max_page_size {
+            return Err(RestError(format!("The page limit must be between 1 and {max_page_size} (requested {limit})")));
+        }
+
+        // Determine the range of blocks to scan.
+        let latest_height = rest.ledger.latest_height();
+        let mut cursor = page.cursor.unwrap_or(0);
+        let end = cursor.saturating_add(MAX_BLOCKS_SCANNED).min(latest_height.saturating_add(1));
+
+        let mut blocks = Vec::new();
+        let mut response_size = 0;
+        while cursor < end && blocks.len() < limit as usize {
+            if let Some(block) = render_block(&rest.ledger, cursor, page.format, filter.as_ref())? {
+                // Ensure the response size is bounded, while always returning at least one block.
+                let block_size = block.to_string().len();
+                if !blocks.is_empty() && response_size + block_size > MAX_RESPONSE_SIZE_IN_BYTES {
+                    break;
+                }
+                response_size += block_size;
+                blocks.push(block);
+            }
+            cursor += 1;
+        }
+
+        // Return the blocks, along with the cursor of the next page, if there are more blocks.
+        Ok(ErasedJson::pretty(json!({
+            "blocks": blocks,
+            "next": (cursor <= latest_height).then_some(cursor),
+        })))
+    }
+
+    // GET /mainnet/blocks/stream?start={start_height}&end={end_height}&format={format}&program={programID}&function={functionName}
+    pub(crate) async fn stream_blocks(
+        State(rest): State<Self>,
+        Query(range): Query<BlockStream>,
+    ) -> Result<Response, RestError> {
+        const MAX_BLOCK_STREAM_RANGE: u32 = 10_000;
+
+        // Ensure the end height is greater than the start height.
+        if range.start > range.end {
+            return Err(RestError("Invalid block range".to_string()));
+        }
+
+        // Ensure the block range is bounded.
+        if range.end - range.start > MAX_BLOCK_STREAM_RANGE {
+            return Err(RestError(format!(
+                "Cannot stream more than {MAX_BLOCK_STREAM_RANGE} blocks per call (requested {})",
+                range.end - range.start
+            )));
+        }
+
+        // Parse the transaction filter.
+        let filter = TransactionFilter::parse(range.program.as_deref(), range.function.as_deref())?;
+
+        // Stream the blocks as newline-delimited JSON, rendering each block only when it is polled.
+        let format = range.format;
+        let end_height = range.end.min(rest.ledger.latest_height().saturating_add(1));
+        let lines = stream::iter(range.start..end_height).filter_map(move |height| {
+            let line = render_block(&rest.ledger, height, format, filter.as_ref())
+                .map(|block| block.map(|block| format!("{block}\n")));
+            async move { line.transpose() }
+        });
+
+        Response::builder()
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .body(Body::from_stream(lines))
+            .map_err(|error| RestError(error.to_string()))
+    }
+
     // GET /mainnet/height/{blockHash}
     pub(crate) async fn get_height(
         State(rest): State<Self>,
@@ -174,6 +314,14 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         Ok(ErasedJson::pretty(rest.ledger.get_confirmed_transaction(tx_id)?))
     }
 
+    // GET /mainnet/transaction/{transactionID}/status
+    pub(crate) async fn get_transaction_status(
+        State(rest): State<Self>,
+        Path(tx_id): Path<N::TransactionID>,
+    ) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(transaction_status(&rest.ledger, rest.consensus.as_ref(), &tx_id)?))
+    }
+
     // GET /mainnet/memoryPool/transmissions
     pub(crate) async fn get_memory_pool_transmissions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
         match rest.consensus {
@@ -276,6 +424,55 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         ErasedJson::pretty(rest.routing.router().address())
     }
 
+    // GET /mainnet/node/sync
+    pub(crate) async fn get_node_sync(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.sync.status())
+    }
+
+    // GET /health
+    pub(crate) async fn get_health() -> StatusCode {
+        StatusCode::OK
+    }
+
+    // GET /ready
+    pub(crate) async fn get_ready(State(rest): State<Self>) -> (StatusCode, ErasedJson) {
+        // The node is ready once it is synced and connected to at least one peer.
+        // Validators sync from, and are connected to, the other validators through the gateway.
+        let status = rest.sync.status();
+        let num_peers = match &rest.consensus {
+            Some(consensus) => consensus.bft().primary().gateway().number_of_connected_peers(),
+            None => rest.routing.router().number_of_connected_peers(),
+        };
+        let is_ready = status.is_synced && num_peers > 0;
+        let code = if is_ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+        (
+            code,
+            ErasedJson::pretty(json!({
+                "ready": is_ready,
+                "is_synced": status.is_synced,
+                "local_height": status.local_height,
+                "network_height": status.network_height,
+                "connected_peers": num_peers,
+            })),
+        )
+    }
+
+    // GET /mainnet/cdn/latest.json
+    // GET /mainnet/cdn/{start}.{end}.blocks
+    pub(crate) async fn get_cdn_file(
+        State(rest): State<Self>,
+        Path(file_name): Path<String>,
+    ) -> Result<Response, RestError> {
+        let (contents, file_name) =
+            tokio::task::spawn_blocking(move || (snarkos_node_cdn::read_cdn_file(&rest.ledger, &file_name), file_name))
+                .await
+                .map_err(|error| RestError(error.to_string()))?;
+        match contents? {
+            Some(bytes) => Ok(bytes.into_response()),
+            None => Ok((StatusCode::NOT_FOUND, format!("'{file_name}' was not found")).into_response()),
+        }
+    }
+
     // GET /mainnet/find/blockHash/{transactionID}
     pub(crate) async fn find_block_hash(
         State(rest): State<Self>,
@@ -304,8 +501,8 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub(crate) async fn find_transition_id(
         State(rest): State<Self>,
         Path(input_or_output_id): Path<Field<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transition_id(&input_or_output_id)?))
+    ) -> Result<Response, RestError> {
+        find_transition_id(&rest.ledger, &input_or_output_id)
     }
 
     // POST /mainnet/transaction/broadcast
@@ -353,4 +550,337 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 
         Ok(ErasedJson::pretty(solution_id))
     }
+
+    // GET /mainnet/admin/peers/trusted
+    pub(crate) async fn get_trusted_peers(State(rest): State<Self>) -> ErasedJson {
+        ErasedJson::pretty(rest.routing.router().trusted_peers())
+    }
+
+    // POST /mainnet/admin/peers/trusted/{ip}
+    pub(crate) async fn insert_trusted_peer(State(rest): State<Self>, Path(peer_ip): Path<SocketAddr>) -> ErasedJson {
+        info!("Adding '{peer_ip}' to the trusted peers (via the REST API)");
+        let router = rest.routing.router();
+        let is_inserted = router.insert_trusted_peer(peer_ip);
+        // Connect to the trusted peer, instead of waiting for the next heartbeat.
+        if !router.is_connected(&peer_ip) {
+            router.connect(peer_ip);
+        }
+        ErasedJson::pretty(is_inserted)
+    }
+
+    // DELETE /mainnet/admin/peers/trusted/{ip}
+    pub(crate) async fn remove_trusted_peer(State(rest): State<Self>, Path(peer_ip): Path<SocketAddr>) -> ErasedJson {
+        info!("Removing '{peer_ip}' from the trusted peers (via the REST API)");
+        ErasedJson::pretty(rest.routing.router().remove_trusted_peer(peer_ip))
+    }
+
+    // GET /mainnet/admin/peers/restricted
+    pub(crate) async fn get_restricted_peers(State(rest): State<Self>) -> ErasedJson {
+        let router = rest.routing.router();
+        let restricted_peers = router
+            .restricted_peers()
+            .into_iter()
+            .filter(|peer_ip| router.is_restricted(peer_ip))
+            .map(|peer_ip| (peer_ip, router.peer_book().get(&peer_ip).and_then(|record| record.restriction)))
+            .collect::<IndexMap<_, _>>();
+        ErasedJson::pretty(restricted_peers)
+    }
+
+    // POST /mainnet/admin/peers/restricted/{ip}
+    // POST /mainnet/admin/peers/restricted/{ip}?reason={reason}
+    pub(crate) async fn restrict_peer(
+        State(rest): State<Self>,
+        Path(peer_ip): Path<SocketAddr>,
+        Query(restriction): Query<RestrictionReason>,
+    ) -> Result<ErasedJson, RestError> {
+        let router = rest.routing.router();
+        // Ensure the peer is not trusted.
+        if router.is_trusted(&peer_ip) {
+            return Err(RestError(format!("'{peer_ip}' is a trusted peer, remove it from the trusted peers first")));
+        }
+        let reason = restriction.reason.unwrap_or_else(|| "restricted via the REST API".to_string());
+        info!("Restricting '{peer_ip}' (via the REST API)");
+        // Restrict the peer first, so that disconnecting does not make it a candidate peer again.
+        router.insert_restricted_peer(peer_ip, &reason);
+        // Disconnect from the peer, if it is connected.
+        if router.is_connected(&peer_ip) {
+            rest.routing.send(peer_ip, Message::Disconnect(DisconnectReason::NoReasonGiven.into()));
+            router.disconnect(peer_ip);
+        }
+        Ok(ErasedJson::pretty(true))
+    }
+
+    // DELETE /mainnet/admin/peers/restricted/{ip}
+    pub(crate) async fn unrestrict_peer(State(rest): State<Self>, Path(peer_ip): Path<SocketAddr>) -> ErasedJson {
+        info!("Lifting the restriction of '{peer_ip}' (via the REST API)");
+        ErasedJson::pretty(rest.routing.router().remove_restricted_peer(peer_ip))
+    }
+
+    // POST /mainnet/admin/peers/connected/{ip}
+    pub(crate) async fn connect_peer(
+        State(rest): State<Self>,
+        Path(peer_ip): Path<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        info!("Connecting to '{peer_ip}' (via the REST API)");
+        match rest.routing.router().connect(peer_ip) {
+            Some(handle) => Ok(ErasedJson::pretty(handle.await.map_err(|error| RestError(error.to_string()))?)),
+            None => Err(RestError(format!("Unable to connect to '{peer_ip}' (see the node logs)"))),
+        }
+    }
+
+    // DELETE /mainnet/admin/peers/connected/{ip}
+    pub(crate) async fn disconnect_peer(
+        State(rest): State<Self>,
+        Path(peer_ip): Path<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        let router = rest.routing.router();
+        // Ensure the peer is connected.
+        if !router.is_connected(&peer_ip) {
+            return Err(RestError(format!("'{peer_ip}' is not connected")));
+        }
+        info!("Disconnecting from '{peer_ip}' (via the REST API)");
+        rest.routing.send(peer_ip, Message::Disconnect(DisconnectReason::NoReasonGiven.into()));
+        let is_disconnected = router.disconnect(peer_ip).await.map_err(|error| RestError(error.to_string()))?;
+        Ok(ErasedJson::pretty(is_disconnected))
+    }
+
+    // GET /mainnet/admin/validators/connected
+    pub(crate) async fn get_connected_validators(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        let gateway = rest.require_consensus()?.bft().primary().gateway();
+        Ok(ErasedJson::pretty(gateway.connected_peers().read().clone()))
+    }
+
+    // POST /mainnet/admin/validators/connected/{ip}
+    pub(crate) async fn connect_validator(
+        State(rest): State<Self>,
+        Path(peer_ip): Path<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        let gateway = rest.require_consensus()?.bft().primary().gateway();
+        info!("Connecting to validator '{peer_ip}' (via the REST API)");
+        match gateway.connect(peer_ip) {
+            Some(handle) => {
+                handle.await.map_err(|error| RestError(error.to_string()))?;
+                Ok(ErasedJson::pretty(gateway.is_connected_ip(peer_ip)))
+            }
+            None => Err(RestError(format!("Unable to connect to validator '{peer_ip}' (see the node logs)"))),
+        }
+    }
+
+    // DELETE /mainnet/admin/validators/connected/{ip}
+    pub(crate) async fn disconnect_validator(
+        State(rest): State<Self>,
+        Path(peer_ip): Path<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        let gateway = rest.require_consensus()?.bft().primary().gateway();
+        // Ensure the validator is connected.
+        if !gateway.is_connected_ip(peer_ip) {
+            return Err(RestError(format!("Validator '{peer_ip}' is not connected")));
+        }
+        info!("Disconnecting from validator '{peer_ip}' (via the REST API)");
+        gateway.disconnect(peer_ip).await.map_err(|error| RestError(error.to_string()))?;
+        Ok(ErasedJson::pretty(true))
+    }
+
+    // GET /mainnet/admin/validators/trusted
+    pub(crate) async fn get_trusted_validators(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+        Ok(ErasedJson::pretty(rest.require_consensus()?.bft().primary().gateway().trusted_validators()))
+    }
+
+    // POST /mainnet/admin/validators/trusted/{ip}
+    pub(crate) async fn insert_trusted_validator(
+        State(rest): State<Self>,
+        Path(peer_ip): Path<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        let gateway = rest.require_consensus()?.bft().primary().gateway();
+        info!("Adding validator '{peer_ip}' to the trusted validators (via the REST API)");
+        Ok(ErasedJson::pretty(gateway.insert_trusted_validator(peer_ip)))
+    }
+
+    // DELETE /mainnet/admin/validators/trusted/{ip}
+    pub(crate) async fn remove_trusted_validator(
+        State(rest): State<Self>,
+        Path(peer_ip): Path<SocketAddr>,
+    ) -> Result<ErasedJson, RestError> {
+        let gateway = rest.require_consensus()?.bft().primary().gateway();
+        info!("Removing validator '{peer_ip}' from the trusted validators (via the REST API)");
+        Ok(ErasedJson::pretty(gateway.remove_trusted_validator(peer_ip)))
+    }
+
+    // GET /mainnet/admin/logs/verbosity
+    pub(crate) async fn get_log_verbosity() -> Result<ErasedJson, RestError> {
+        match log_verbosity() {
+            Some(verbosity) => Ok(ErasedJson::pretty(verbosity)),
+            None => Err(RestError("The logger does not support runtime verbosity".to_string())),
+        }
+    }
+
+    // POST /mainnet/admin/logs/verbosity/{verbosity}
+    pub(crate) async fn set_log_verbosity(Path(verbosity): Path<u8>) -> Result<ErasedJson, RestError> {
+        info!("Setting the log verbosity to {verbosity} (via the REST API)");
+        crate::set_log_verbosity(verbosity)?;
+        Ok(ErasedJson::pretty(verbosity))
+    }
+
+    // GET /mainnet/subscribe/blocks
+    // GET /mainnet/subscribe/blocks?from={height}
+    pub(crate) async fn subscribe_blocks(
+        State(rest): State<Self>,
+        Query(cursor): Query<Cursor>,
+        headers: HeaderMap,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, RestError> {
+        // If the client is reconnecting, resume after the last block it received.
+        let from = match last_event_id(&headers)? {
+            Some(event_id) => Some(parse_block_event_id(event_id)?.saturating_add(1)),
+            None => cursor.from,
+        };
+        let events = rest.block_stream(from).map(|block| {
+            // Note: The event ID is the block height, to allow a reconnecting client to resume from it.
+            Event::default().event("block").id(block.height().to_string()).json_data(block)
+        });
+        Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+    }
+
+    // GET /mainnet/subscribe/transactions
+    // GET /mainnet/subscribe/transactions?from={height}
+    pub(crate) async fn subscribe_transactions(
+        State(rest): State<Self>,
+        Query(cursor): Query<Cursor>,
+        headers: HeaderMap,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, RestError> {
+        // If the client is reconnecting, resume after the last transaction it received.
+        let last_event = last_event_id(&headers)?.map(parse_transaction_event_id).transpose()?;
+        let from = match last_event {
+            Some((height, _)) => Some(height),
+            None => cursor.from,
+        };
+        let events = rest.block_stream(from).flat_map(move |block| {
+            let height = block.height();
+            let events = block
+                .transactions()
+                .iter()
+                .enumerate()
+                // Skip the transactions the client received before reconnecting.
+                .filter(|(index, _)| match last_event {
+                    Some((last_height, last_index)) => height != last_height || *index > last_index,
+                    None => true,
+                })
+                .map(|(index, transaction)| {
+                    // Note: The event ID is unique to each transaction, to allow a reconnecting client to resume from it.
+                    Event::default()
+                        .event("transaction")
+                        .id(transaction_event_id(height, index))
+                        .json_data(json!({ "height": height, "transaction": transaction }))
+                })
+                .collect::<Vec<_>>();
+            stream::iter(events)
+        });
+        Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+    }
+
+    // GET /mainnet/subscribe/memoryPool
+    pub(crate) async fn subscribe_memory_pool(
+        State(rest): State<Self>,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, RestError> {
+        let events = rest.memory_pool_stream()?.map(|event| match event {
+            MemoryPoolEvent::Queued(transmission_id) => Event::default().event("queued").json_data(transmission_id),
+            MemoryPoolEvent::Dequeued(transmission_id) => Event::default().event("dequeued").json_data(transmission_id),
+        });
+        Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+    }
+}
+
+/// Returns the status of the given transaction, from the ledger if it is in a block,
+/// and otherwise from the memory pool of the given consensus module.
+fn transaction_status<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    consensus: Option<&Consensus<N>>,
+    tx_id: &N::TransactionID,
+) -> Result<TransactionStatus<N>> {
+    // Check if the transaction is in a block.
+    if let Some(block_hash) = ledger.find_block_hash(tx_id)? {
+        let height = ledger.get_height(&block_hash)?;
+        let block = ledger.get_block(height)?;
+        // Determine how the transaction was included in the block.
+        let status = if block.aborted_transaction_ids().contains(tx_id) {
+            TransactionStatus::Aborted { height, block_hash }
+        } else {
+            match block.transactions().get(tx_id) {
+                Some(confirmed) if confirmed.is_accepted() => TransactionStatus::Confirmed { height, block_hash },
+                // The ID is either the fee transaction ID or the unconfirmed ID of a rejected transaction.
+                _ => TransactionStatus::Rejected { height: Some(height), block_hash: Some(block_hash), reason: None },
+            }
+        };
+        return Ok(status);
+    }
+    // Otherwise, check if the transaction is in the memory pool.
+    Ok(consensus
+        .and_then(|consensus| consensus.unconfirmed_transaction_status(tx_id))
+        .unwrap_or(TransactionStatus::Unknown))
+}
+
+/// Returns the ID of the transition with the given input or output ID,
+/// or `404 Not Found` if the ledger does not contain the input or output ID.
+fn find_transition_id<N: Network, C: ConsensusStorage<N>>(
+    ledger: &Ledger<N, C>,
+    input_or_output_id: &Field<N>,
+) -> Result<Response, RestError> {
+    if !ledger.contains_input_id(input_or_output_id)? && !ledger.contains_output_id(input_or_output_id)? {
+        return Ok((StatusCode::NOT_FOUND, format!("'{input_or_output_id}' was not found")).into_response());
+    }
+    Ok(ErasedJson::pretty(ledger.find_transition_id(input_or_output_id)?).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{
+        block::Block,
+        store::helpers::memory::ConsensusMemory,
+        FromBytes,
+        MainnetV0,
+        TestRng,
+        Uniform,
+    };
+
+    use aleo_std::StorageMode;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_transaction_status() {
+        let rng = &mut TestRng::default();
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let ledger =
+            Ledger::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::load(genesis.clone(), StorageMode::Production)
+                .unwrap();
+
+        // A transaction in a block is confirmed.
+        let transaction_id = genesis.transactions().transaction_ids().next().unwrap();
+        let status = transaction_status(&ledger, None, transaction_id).unwrap();
+        assert_eq!(status, TransactionStatus::Confirmed { height: 0, block_hash: genesis.hash() });
+
+        // A transaction that is neither in the ledger nor in the memory pool is not known.
+        let transaction_id = Field::<CurrentNetwork>::rand(rng).into();
+        assert_eq!(transaction_status(&ledger, None, &transaction_id).unwrap(), TransactionStatus::Unknown);
+    }
+
+    #[test]
+    fn test_find_transition_id() {
+        let rng = &mut TestRng::default();
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let ledger =
+            Ledger::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::load(genesis.clone(), StorageMode::Production)
+                .unwrap();
+
+        // The inputs and outputs of a transition in a block are found.
+        let transition = genesis.transitions().next().unwrap();
+        let input_id = transition.inputs().first().unwrap().id();
+        assert_eq!(find_transition_id(&ledger, input_id).unwrap().status(), StatusCode::OK);
+        let output_id = transition.outputs().first().unwrap().id();
+        assert_eq!(find_transition_id(&ledger, output_id).unwrap().status(), StatusCode::OK);
+
+        // An ID that is not in the ledger is not found, e.g. the serial number of an unspent record.
+        let unknown_id = Field::<CurrentNetwork>::rand(rng);
+        assert_eq!(find_transition_id(&ledger, &unknown_id).unwrap().status(), StatusCode::NOT_FOUND);
+    }
 }
@@ -0,0 +1,128 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkos_node_consensus::MemoryPoolEvent;
+use snarkvm::prelude::block::Block;
+
+use axum::http::HeaderMap;
+use futures_util::{stream, Stream};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Returns the `Last-Event-ID` header, which a reconnecting subscriber sends to resume its stream.
+pub(crate) fn last_event_id(headers: &HeaderMap) -> Result<Option<&str>, RestError> {
+    match headers.get("last-event-id") {
+        Some(value) => Ok(Some(value.to_str().map_err(|_| RestError("Invalid 'Last-Event-ID' header".to_string()))?)),
+        None => Ok(None),
+    }
+}
+
+/// Returns the block height of the given block event ID.
+pub(crate) fn parse_block_event_id(event_id: &str) -> Result<u32, RestError> {
+    event_id.parse().map_err(|_| RestError(format!("Invalid block event ID '{event_id}'")))
+}
+
+/// Returns the event ID of the transaction at the given index in the block at the given height.
+pub(crate) fn transaction_event_id(height: u32, index: usize) -> String {
+    format!("{height}:{index}")
+}
+
+/// Returns the block height and transaction index of the given transaction event ID.
+pub(crate) fn parse_transaction_event_id(event_id: &str) -> Result<(u32, usize), RestError> {
+    let error = || RestError(format!("Invalid transaction event ID '{event_id}'"));
+    let (height, index) = event_id.split_once(':').ok_or_else(error)?;
+    Ok((height.parse().map_err(|_| error())?, index.parse().map_err(|_| error())?))
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Returns a stream of the blocks in the ledger, starting from the given height (inclusive).
+    /// If no height is given, the stream starts from the next block to be added to the ledger.
+    ///
+    /// The stream first catches up on the blocks that are already in the ledger,
+    /// and then yields each new block as soon as the ledger advances.
+    pub(crate) fn block_stream(&self, from: Option<u32>) -> impl Stream<Item = Block<N>> {
+        // Determine the starting height.
+        let cursor = from.unwrap_or_else(|| self.ledger.latest_height().saturating_add(1));
+        // Initialize the stream.
+        stream::unfold(
+            (self.ledger.clone(), cursor, self.latest_height.clone()),
+            |(ledger, cursor, mut latest_height)| async move {
+                loop {
+                    // If the block is in the ledger, return it.
+                    if cursor <= ledger.latest_height() {
+                        match ledger.get_block(cursor) {
+                            Ok(block) => return Some((block, (ledger, cursor.saturating_add(1), latest_height))),
+                            Err(error) => {
+                                warn!("Failed to retrieve block {cursor} for a subscriber - {error}");
+                                return None;
+                            }
+                        }
+                    }
+                    // Otherwise, wait for the ledger to advance.
+                    if latest_height.changed().await.is_err() {
+                        return None;
+                    }
+                }
+            },
+        )
+    }
+
+    /// Returns a stream of the transmissions entering and leaving the consensus queues.
+    pub(crate) fn memory_pool_stream(&self) -> Result<impl Stream<Item = MemoryPoolEvent<N>>, RestError> {
+        // Subscribe to the memory pool events.
+        let receiver = match &self.consensus {
+            Some(consensus) => consensus.subscribe_memory_pool(),
+            None => return Err(RestError("Route isn't available for this node type".to_string())),
+        };
+        // Initialize the stream.
+        Ok(stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    // If the subscriber fell behind, skip the missed events.
+                    Err(RecvError::Lagged(num_missed)) => {
+                        warn!("A memory pool subscriber fell behind by {num_missed} events");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_event_id() {
+        let mut headers = HeaderMap::new();
+        assert!(last_event_id(&headers).ok().unwrap().is_none());
+
+        headers.insert("Last-Event-ID", "10:2".parse().unwrap());
+        assert_eq!(last_event_id(&headers).ok().unwrap(), Some("10:2"));
+    }
+
+    #[test]
+    fn test_event_ids() {
+        assert_eq!(parse_block_event_id("10").ok(), Some(10));
+        assert!(parse_block_event_id("10:2").is_err());
+
+        // Each transaction in a block has its own event ID.
+        assert_ne!(transaction_event_id(10, 0), transaction_event_id(10, 1));
+        assert_eq!(parse_transaction_event_id(&transaction_event_id(10, 2)).ok(), Some((10, 2)));
+        assert!(parse_transaction_event_id("10").is_err());
+        assert!(parse_transaction_event_id("10:x").is_err());
+    }
+}
@@ -14,6 +14,7 @@
 
 use super::*;
 
+use snarkos_node_tcp::{COMPRESSION_FEATURE, PROTOCOL_VERSION_MASK};
 use snarkvm::prelude::{FromBytes, ToBytes};
 
 use std::borrow::Cow;
@@ -62,6 +63,22 @@ impl<N: Network> ChallengeRequest<N> {
     pub fn new(listener_port: u16, node_type: NodeType, address: Address<N>, nonce: u64) -> Self {
         Self { version: Message::<N>::VERSION, listener_port, node_type, address, nonce }
     }
+
+    /// Advertises support for compressed frames, in the feature bits of the version.
+    pub fn with_compression(mut self) -> Self {
+        self.version |= COMPRESSION_FEATURE;
+        self
+    }
+
+    /// Returns the message protocol version, without the feature bits.
+    pub fn protocol_version(&self) -> u32 {
+        self.version & PROTOCOL_VERSION_MASK
+    }
+
+    /// Returns `true` if the sender supports compressed frames.
+    pub fn supports_compression(&self) -> bool {
+        self.version & COMPRESSION_FEATURE != 0
+    }
 }
 
 #[cfg(test)]
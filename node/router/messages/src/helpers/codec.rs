@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::Message;
+use snarkos_node_tcp::{compress_frame, decompress_frame, COMPRESSION_THRESHOLD};
 use snarkvm::prelude::{FromBytes, Network, ToBytes};
 
 use ::bytes::{Buf, BufMut, BytesMut};
@@ -28,6 +29,8 @@ pub(crate) const MAXIMUM_MESSAGE_SIZE: usize = 128 * 1024 * 1024; // 128 MiB
 /// The codec used to decode and encode network `Message`s.
 pub struct MessageCodec<N: Network> {
     codec: LengthDelimitedCodec,
+    /// If `true`, each frame carries a compression header, as negotiated during the handshake.
+    compression: bool,
     _phantom: PhantomData<N>,
 }
 
@@ -37,12 +40,19 @@ impl<N: Network> MessageCodec<N> {
         codec.codec.set_max_frame_length(MAXIMUM_HANDSHAKE_MESSAGE_SIZE);
         codec
     }
+
+    /// Initializes a codec that compresses large block responses.
+    /// Note: This codec must only be used with peers that advertised compression during the handshake.
+    pub fn compressed() -> Self {
+        Self { compression: true, ..Default::default() }
+    }
 }
 
 impl<N: Network> Default for MessageCodec<N> {
     fn default() -> Self {
         Self {
             codec: LengthDelimitedCodec::builder().max_frame_length(MAXIMUM_MESSAGE_SIZE).little_endian().new_codec(),
+            compression: false,
             _phantom: Default::default(),
         }
     }
@@ -52,6 +62,11 @@ impl<N: Network> Encoder<Message<N>> for MessageCodec<N> {
     type Error = std::io::Error;
 
     fn encode(&mut self, message: Message<N>, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        #[cfg(feature = "metrics")]
+        let name = message.name();
+        // Determine if the message may be compressed.
+        let is_compressible = matches!(message, Message::BlockResponse(..));
+
         // Serialize the payload directly into dst.
         message
             .write_le(&mut dst.writer())
@@ -60,7 +75,23 @@ impl<N: Network> Encoder<Message<N>> for MessageCodec<N> {
 
         let serialized_message = dst.split_to(dst.len()).freeze();
 
-        self.codec.encode(serialized_message, dst)
+        // If compression was negotiated, frame the message, compressing it if it is large enough.
+        let frame = match self.compression {
+            true => {
+                let compress = is_compressible && serialized_message.len() >= COMPRESSION_THRESHOLD;
+                compress_frame(&serialized_message, compress)?
+            }
+            false => serialized_message,
+        };
+
+        // Note: The metrics record the size of the frame, which is the size of the message on the wire.
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter_with_labels(metrics::router::MESSAGE_BYTES_SENT, frame.len() as u64, &[(
+            "message",
+            name.to_string(),
+        )]);
+
+        self.codec.encode(frame, dst)
     }
 }
 
@@ -75,10 +106,26 @@ impl<N: Network> Decoder for MessageCodec<N> {
             None => return Ok(None),
         };
 
+        #[cfg(feature = "metrics")]
+        let num_bytes = bytes.len() as u64;
+
+        // If compression was negotiated, decode the payload from the frame.
+        let bytes = match self.compression {
+            true => decompress_frame(bytes, MAXIMUM_MESSAGE_SIZE)?,
+            false => bytes.freeze(),
+        };
+
         // Convert the bytes to a message, or fail if it is not valid.
         let reader = bytes.reader();
         match Message::read_le(reader) {
-            Ok(message) => Ok(Some(message)),
+            Ok(message) => {
+                #[cfg(feature = "metrics")]
+                metrics::increment_counter_with_labels(metrics::router::MESSAGE_BYTES_RECEIVED, num_bytes, &[(
+                    "message",
+                    message.name().to_string(),
+                )]);
+                Ok(Some(message))
+            }
             Err(error) => {
                 warn!("Failed to deserialize a message - {}", error);
                 Err(std::io::ErrorKind::InvalidData.into())
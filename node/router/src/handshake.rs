@@ -20,7 +20,7 @@ use crate::{
 use snarkos_node_tcp::{ConnectionSide, Tcp, P2P};
 use snarkvm::{
     ledger::narwhal::Data,
-    prelude::{block::Header, error, Address, Network},
+    prelude::{block::Header, error, Address, Network, Signature},
 };
 
 use anyhow::{bail, Result};
@@ -139,7 +139,8 @@ impl<N: Network> Router<N> {
         // Sample a random nonce.
         let our_nonce = rng.gen();
         // Send a challenge request to the peer.
-        let our_request = ChallengeRequest::new(self.local_ip().port(), self.node_type, self.address(), our_nonce);
+        let our_request =
+            ChallengeRequest::new(self.local_ip().port(), self.node_type, self.address(), our_nonce).with_compression();
         send(&mut framed, peer_addr, Message::ChallengeRequest(our_request)).await?;
 
         /* Step 2: Receive the peer's challenge response followed by the challenge request. */
@@ -164,10 +165,9 @@ impl<N: Network> Router<N> {
         }
         /* Step 3: Send the challenge response. */
 
-        let response_nonce: u64 = rng.gen();
-        let data = [peer_request.nonce.to_le_bytes(), response_nonce.to_le_bytes()].concat();
         // Sign the counterparty nonce.
-        let Ok(our_signature) = self.account.sign_bytes(&data, rng) else {
+        let response_nonce: u64 = rng.gen();
+        let Ok(our_signature) = self.sign_challenge(peer_request.nonce, response_nonce).await else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
         // Send the challenge response.
@@ -175,6 +175,12 @@ impl<N: Network> Router<N> {
             ChallengeResponse { genesis_header, signature: Data::Object(our_signature), nonce: response_nonce };
         send(&mut framed, peer_addr, Message::ChallengeResponse(our_response)).await?;
 
+        // Ensure the peer is not already connected by its address in the other IP family.
+        if self.is_connected_over_other_family(&peer_ip, &peer_request.address) {
+            // Remove the address from the candidate peers, so it is not dialed again.
+            self.remove_candidate_peer(peer_ip);
+            return Err(error(format!("Dropped '{peer_addr}' (already connected over the other IP family)")));
+        }
         // Add the peer to the router.
         self.insert_connected_peer(Peer::new(peer_ip, &peer_request), peer_addr);
 
@@ -217,8 +223,7 @@ impl<N: Network> Router<N> {
 
         // Sign the counterparty nonce.
         let response_nonce: u64 = rng.gen();
-        let data = [peer_request.nonce.to_le_bytes(), response_nonce.to_le_bytes()].concat();
-        let Ok(our_signature) = self.account.sign_bytes(&data, rng) else {
+        let Ok(our_signature) = self.sign_challenge(peer_request.nonce, response_nonce).await else {
             return Err(error(format!("Failed to sign the challenge request nonce from '{peer_addr}'")));
         };
         // Send the challenge response.
@@ -229,7 +234,8 @@ impl<N: Network> Router<N> {
         // Sample a random nonce.
         let our_nonce = rng.gen();
         // Send the challenge request.
-        let our_request = ChallengeRequest::new(self.local_ip().port(), self.node_type, self.address(), our_nonce);
+        let our_request =
+            ChallengeRequest::new(self.local_ip().port(), self.node_type, self.address(), our_nonce).with_compression();
         send(&mut framed, peer_addr, Message::ChallengeRequest(our_request)).await?;
 
         /* Step 3: Receive the challenge response. */
@@ -244,6 +250,10 @@ impl<N: Network> Router<N> {
             send(&mut framed, peer_addr, reason.into()).await?;
             return Err(error(format!("Dropped '{peer_addr}' for reason: {reason:?}")));
         }
+        // Ensure the peer is not already connected by its address in the other IP family.
+        if self.is_connected_over_other_family(&peer_ip, &peer_request.address) {
+            return Err(error(format!("Dropped '{peer_addr}' (already connected over the other IP family)")));
+        }
         // Add the peer to the router.
         self.insert_connected_peer(Peer::new(peer_ip, &peer_request), peer_addr);
 
@@ -279,13 +289,20 @@ impl<N: Network> Router<N> {
             // Ensure the connecting peer has not surpassed the connection attempt limit.
             if num_attempts > Self::MAXIMUM_CONNECTION_FAILURES {
                 // Restrict the peer.
-                self.insert_restricted_peer(peer_ip);
+                self.insert_restricted_peer(peer_ip, "too many connection attempts");
                 bail!("Dropping connection request from '{peer_ip}' (tried {num_attempts} times)")
             }
         }
         Ok(())
     }
 
+    /// Signs the counterparty nonce and the given response nonce with the signer of the node,
+    /// which may be an external signer process.
+    async fn sign_challenge(&self, nonce: u64, response_nonce: u64) -> Result<Signature<N>> {
+        let signer = self.signer.clone();
+        tokio::task::spawn_blocking(move || signer.sign_challenge(nonce, response_nonce, &mut OsRng)).await?
+    }
+
     /// Verifies the given challenge request. Returns a disconnect reason if the request is invalid.
     fn verify_challenge_request(
         &self,
@@ -293,9 +310,10 @@ impl<N: Network> Router<N> {
         message: &ChallengeRequest<N>,
     ) -> Option<DisconnectReason> {
         // Retrieve the components of the challenge request.
-        let &ChallengeRequest { version, listener_port: _, node_type: _, address: _, nonce: _ } = message;
+        let &ChallengeRequest { version: _, listener_port: _, node_type: _, address: _, nonce: _ } = message;
 
         // Ensure the message protocol version is not outdated.
+        let version = message.protocol_version();
         if version < Message::<N>::VERSION {
             warn!("Dropping '{peer_addr}' on version {version} (outdated)");
             return Some(DisconnectReason::OutdatedClientVersion);
@@ -20,7 +20,11 @@ use crate::{
 use snarkvm::prelude::Network;
 
 use colored::Colorize;
-use rand::{prelude::IteratorRandom, rngs::OsRng};
+use rand::{
+    prelude::{IteratorRandom, SliceRandom},
+    rngs::OsRng,
+};
+use std::cmp::Reverse;
 
 /// A helper function to compute the maximum of two numbers.
 /// See Rust issue 92391: https://github.com/rust-lang/rust/issues/92391.
@@ -60,6 +64,13 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         self.handle_trusted_peers();
         // Keep the puzzle request up to date.
         self.handle_puzzle_request();
+        // Prune the negligible scores of disconnected peers.
+        self.router().peer_scores().prune();
+        // Persist the peer book.
+        self.router().save_peer_book();
+        // Update the traffic metrics of the connected peers.
+        #[cfg(feature = "metrics")]
+        self.router().update_traffic_metrics();
     }
 
     /// TODO (howardwu): Consider checking minimum number of validators, to exclude clients and provers.
@@ -165,18 +176,20 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
             // Initialize an RNG.
             let rng = &mut OsRng;
 
-            // Determine the provers to disconnect from.
-            let prover_ips_to_disconnect = self
+            // Determine the provers to disconnect from, starting from the lowest-scoring ones.
+            let mut prover_ips_to_disconnect = self
                 .router()
                 .connected_provers()
                 .into_iter()
                 .filter(|peer_ip| !trusted.contains(peer_ip) && !bootstrap.contains(peer_ip))
-                .choose_multiple(rng, num_surplus_provers);
+                .collect::<Vec<_>>();
+            prover_ips_to_disconnect.shuffle(rng);
+            self.router().peer_scores().sort_by_score(&mut prover_ips_to_disconnect);
+            prover_ips_to_disconnect.truncate(num_surplus_provers);
 
             // TODO (howardwu): As a validator, prioritize disconnecting from clients.
-            //  Remove RNG, pick the `n` oldest nodes.
-            // Determine the clients and validators to disconnect from.
-            let peer_ips_to_disconnect = self
+            // Determine the clients and validators to disconnect from, starting from the lowest-scoring ones.
+            let mut peer_ips_to_disconnect = self
                 .router()
                 .get_connected_peers()
                 .into_iter()
@@ -188,7 +201,10 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
                         None
                     }
                 })
-                .choose_multiple(rng, num_surplus_clients_validators);
+                .collect::<Vec<_>>();
+            peer_ips_to_disconnect.shuffle(rng);
+            self.router().peer_scores().sort_by_score(&mut peer_ips_to_disconnect);
+            peer_ips_to_disconnect.truncate(num_surplus_clients_validators);
 
             // Proceed to send disconnect requests to these peers.
             for peer_ip in peer_ips_to_disconnect.into_iter().chain(prover_ips_to_disconnect) {
@@ -217,8 +233,17 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
             // Initialize an RNG.
             let rng = &mut OsRng;
 
+            // Shuffle the candidate peers, and then prefer the ones that have historically been reliable.
+            let mut candidate_peers = self.router().candidate_peers().into_iter().collect::<Vec<_>>();
+            candidate_peers.shuffle(rng);
+            candidate_peers.sort_by_key(|peer_ip| Reverse(self.router().peer_book().reliability(peer_ip)));
+            // Prefer the candidate peers of the preferred IP family, if one is configured.
+            if let Some(ip_family) = self.router().preferred_ip_family() {
+                candidate_peers.sort_by_key(|peer_ip| !ip_family.contains(peer_ip));
+            }
+
             // Attempt to connect to more peers.
-            for peer_ip in self.router().candidate_peers().into_iter().choose_multiple(rng, num_deficient) {
+            for peer_ip in candidate_peers.into_iter().take(num_deficient) {
                 self.router().connect(peer_ip);
             }
             if self.router().allow_external_peers() {
@@ -270,9 +295,9 @@ pub trait Heartbeat<N: Network>: Outbound<N> {
         // Ensure that the trusted nodes are connected.
         for peer_ip in self.router().trusted_peers() {
             // If the peer is not connected, attempt to connect to it.
-            if !self.router().is_connected(peer_ip) {
+            if !self.router().is_connected(&peer_ip) {
                 // Attempt to connect to the trusted peer.
-                self.router().connect(*peer_ip);
+                self.router().connect(peer_ip);
             }
         }
     }
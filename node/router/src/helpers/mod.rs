@@ -18,5 +18,11 @@ pub use cache::Cache;
 mod peer;
 pub use peer::*;
 
+mod peer_book;
+pub use peer_book::*;
+
+mod peer_score;
+pub use peer_score::*;
+
 mod resolver;
 pub use resolver::*;
@@ -28,6 +28,8 @@ pub struct Peer<N: Network> {
     node_type: NodeType,
     /// The message version of the peer.
     version: u32,
+    /// Whether compression was negotiated with the peer during the handshake.
+    is_compressed: bool,
     /// The timestamp of the first message received from the peer.
     first_seen: Instant,
     /// The timestamp of the last message received from this peer.
@@ -41,7 +43,8 @@ impl<N: Network> Peer<N> {
             peer_ip: listening_ip,
             address: challenge_request.address,
             node_type: challenge_request.node_type,
-            version: challenge_request.version,
+            version: challenge_request.protocol_version(),
+            is_compressed: challenge_request.supports_compression(),
             first_seen: Instant::now(),
             last_seen: Instant::now(),
         }
@@ -82,6 +85,11 @@ impl<N: Network> Peer<N> {
         self.version
     }
 
+    /// Returns `true` if compression was negotiated with the peer.
+    pub const fn is_compressed(&self) -> bool {
+        self.is_compressed
+    }
+
     /// Returns the first seen timestamp of the peer.
     pub fn first_seen(&self) -> Instant {
         self.first_seen
@@ -0,0 +1,297 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::messages::NodeType;
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+use time::OffsetDateTime;
+
+/// The maximum number of peers to store in the peer book.
+const MAX_PEER_BOOK_SIZE: usize = 10_000;
+
+/// The reason and time that a peer was restricted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Restriction {
+    /// The reason the peer was restricted.
+    pub reason: String,
+    /// The UTC timestamp (in seconds) of the restriction.
+    pub timestamp: i64,
+    /// The duration (in seconds) of the restriction.
+    #[serde(default)]
+    pub duration: u64,
+}
+
+/// The historical record of a peer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    /// The UTC timestamp (in seconds) of the last time the peer was seen.
+    pub last_seen: Option<i64>,
+    /// The node type of the peer, if known.
+    pub node_type: Option<NodeType>,
+    /// The message version of the peer, if known.
+    pub version: Option<u32>,
+    /// The number of successful connections with the peer.
+    pub num_connections: u32,
+    /// The number of consecutive connection failures with the peer.
+    pub connection_failures: u32,
+    /// The most recent restriction of the peer, if any.
+    pub restriction: Option<Restriction>,
+    /// The number of times the peer has been restricted.
+    #[serde(default)]
+    pub num_restrictions: u32,
+}
+
+impl PeerRecord {
+    /// Returns the reliability of the peer, where a higher value is more reliable.
+    /// Peers without any history have a reliability of zero.
+    pub fn reliability(&self) -> i64 {
+        let restriction_penalty = if self.restriction.is_some() { 10 } else { 0 };
+        self.num_connections as i64 - 2 * self.connection_failures as i64 - restriction_penalty
+    }
+}
+
+/// A peer book, which records the history of peers and persists it across restarts.
+#[derive(Debug)]
+pub struct PeerBook {
+    /// The path to the peer book file, if it is persisted.
+    path: Option<PathBuf>,
+    /// The map of peer IPs to their records.
+    peers: RwLock<HashMap<SocketAddr, PeerRecord>>,
+}
+
+impl Default for PeerBook {
+    /// Initializes a new in-memory peer book.
+    fn default() -> Self {
+        Self { path: None, peers: Default::default() }
+    }
+}
+
+impl PeerBook {
+    /// The file name of the peer book.
+    pub const FILE_NAME: &'static str = "peer-book.json";
+
+    /// Loads the peer book from the given path, or initializes an empty one if the file does not exist.
+    /// If no path is given, the peer book is kept in memory only.
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let peers = match &path {
+            Some(path) if path.exists() => match Self::read(path) {
+                Ok(peers) => peers,
+                Err(error) => {
+                    warn!("Failed to load the peer book from '{}' - {error}", path.display());
+                    Default::default()
+                }
+            },
+            _ => Default::default(),
+        };
+        Self { path, peers: RwLock::new(peers) }
+    }
+
+    /// Reads the peer records from the given path.
+    fn read(path: &Path) -> Result<HashMap<SocketAddr, PeerRecord>> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Persists the peer book, if it has a path.
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        // Ensure the parent directory exists.
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Serialize the peer records.
+        let bytes = serde_json::to_vec(&*self.peers.read())?;
+        // Write to a temporary file first, so that a crash does not leave a partially-written peer book.
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, bytes)?;
+        fs::rename(temp_path, path)?;
+        Ok(())
+    }
+
+    /// Returns the number of peers in the peer book.
+    pub fn len(&self) -> usize {
+        self.peers.read().len()
+    }
+
+    /// Returns `true` if the peer book is empty.
+    pub fn is_empty(&self) -> bool {
+        self.peers.read().is_empty()
+    }
+
+    /// Returns the record of the given peer IP, if it exists.
+    pub fn get(&self, peer_ip: &SocketAddr) -> Option<PeerRecord> {
+        self.peers.read().get(peer_ip).cloned()
+    }
+
+    /// Returns the reliability of the given peer IP, which is zero for unknown peers.
+    pub fn reliability(&self, peer_ip: &SocketAddr) -> i64 {
+        self.peers.read().get(peer_ip).map_or(0, |record| record.reliability())
+    }
+
+    /// Returns the known peer IPs, ordered from the most to the least reliable.
+    pub fn peers_by_reliability(&self) -> Vec<SocketAddr> {
+        let peers = self.peers.read();
+        let mut peer_ips = peers.keys().copied().collect::<Vec<_>>();
+        peer_ips.sort_by_key(|peer_ip| std::cmp::Reverse(peers[peer_ip].reliability()));
+        peer_ips
+    }
+
+    /// Returns the number of times the given peer IP has been restricted.
+    pub fn num_restrictions(&self, peer_ip: &SocketAddr) -> u32 {
+        self.peers.read().get(peer_ip).map_or(0, |record| record.num_restrictions)
+    }
+
+    /// Returns the peer IPs that are still restricted, along with the number of seconds remaining in their restriction.
+    pub fn restricted_peers(&self) -> Vec<(SocketAddr, u64)> {
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        self.peers
+            .read()
+            .iter()
+            .filter_map(|(peer_ip, record)| {
+                let restriction = record.restriction.as_ref()?;
+                let elapsed = u64::try_from(now.saturating_sub(restriction.timestamp)).ok()?;
+                (elapsed < restriction.duration).then_some((*peer_ip, restriction.duration - elapsed))
+            })
+            .collect()
+    }
+
+    /// Records a successful connection with the given peer.
+    pub fn record_connection(&self, peer_ip: SocketAddr, node_type: NodeType, version: u32) {
+        self.update(peer_ip, |record| {
+            record.last_seen = Some(OffsetDateTime::now_utc().unix_timestamp());
+            record.node_type = Some(node_type);
+            record.version = Some(version);
+            record.num_connections = record.num_connections.saturating_add(1);
+            record.connection_failures = 0;
+        });
+    }
+
+    /// Records that the given peer was seen.
+    pub fn record_seen(&self, peer_ip: SocketAddr) {
+        self.update(peer_ip, |record| record.last_seen = Some(OffsetDateTime::now_utc().unix_timestamp()));
+    }
+
+    /// Records a failed connection attempt with the given peer.
+    pub fn record_connection_failure(&self, peer_ip: SocketAddr) {
+        self.update(peer_ip, |record| record.connection_failures = record.connection_failures.saturating_add(1));
+    }
+
+    /// Records the restriction of the given peer, with the given reason and duration (in seconds).
+    pub fn record_restriction(&self, peer_ip: SocketAddr, reason: &str, duration: u64) {
+        self.update(peer_ip, |record| {
+            let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+            record.restriction = Some(Restriction { reason: reason.to_string(), timestamp, duration });
+            record.num_restrictions = record.num_restrictions.saturating_add(1);
+        });
+    }
+
+    /// Clears the restriction of the given peer, if it exists.
+    pub fn clear_restriction(&self, peer_ip: &SocketAddr) {
+        if let Some(record) = self.peers.write().get_mut(peer_ip) {
+            record.restriction = None;
+        }
+    }
+
+    /// Updates the record of the given peer with the given function, inserting a new record if needed.
+    fn update(&self, peer_ip: SocketAddr, update_fn: impl FnOnce(&mut PeerRecord)) {
+        let mut peers = self.peers.write();
+        // If the peer book is full, evict the peer that was seen the longest time ago.
+        if peers.len() >= MAX_PEER_BOOK_SIZE && !peers.contains_key(&peer_ip) {
+            if let Some(oldest) = peers.iter().min_by_key(|(_, record)| record.last_seen).map(|(ip, _)| *ip) {
+                peers.remove(&oldest);
+            }
+        }
+        update_fn(peers.entry(peer_ip).or_default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_reliability() {
+        let peer_book = PeerBook::default();
+        let reliable = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234);
+        let unknown = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 2345);
+        let unreliable = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 3456);
+
+        // Record the history of the peers.
+        peer_book.record_connection(reliable, NodeType::Client, 1);
+        peer_book.record_connection_failure(unreliable);
+        assert_eq!(peer_book.len(), 2);
+
+        // Check the reliability of the peers.
+        assert_eq!(peer_book.reliability(&reliable), 1);
+        assert_eq!(peer_book.reliability(&unknown), 0);
+        assert_eq!(peer_book.reliability(&unreliable), -2);
+        assert_eq!(peer_book.peers_by_reliability(), vec![reliable, unreliable]);
+
+        // Check that a successful connection resets the connection failures.
+        peer_book.record_connection(unreliable, NodeType::Validator, 1);
+        assert_eq!(peer_book.get(&unreliable).unwrap().connection_failures, 0);
+        assert_eq!(peer_book.get(&unreliable).unwrap().node_type, Some(NodeType::Validator));
+    }
+
+    #[test]
+    fn test_restriction() {
+        let peer_book = PeerBook::default();
+        let peer_ip = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234);
+
+        // Restrict the peer.
+        peer_book.record_restriction(peer_ip, "spam", 60);
+        assert_eq!(peer_book.get(&peer_ip).unwrap().restriction.unwrap().reason, "spam");
+        assert_eq!(peer_book.restricted_peers().len(), 1);
+        assert!(peer_book.restricted_peers()[0].1 <= 60);
+        assert_eq!(peer_book.reliability(&peer_ip), -10);
+
+        // Clear the restriction, which retains the number of restrictions.
+        peer_book.clear_restriction(&peer_ip);
+        assert!(peer_book.restricted_peers().is_empty());
+        assert_eq!(peer_book.num_restrictions(&peer_ip), 1);
+
+        // Check that an expired restriction is not restored.
+        peer_book.record_restriction(peer_ip, "spam", 0);
+        assert!(peer_book.restricted_peers().is_empty());
+        assert_eq!(peer_book.num_restrictions(&peer_ip), 2);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(PeerBook::FILE_NAME);
+        let peer_ip = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234);
+
+        // Record a peer and save the peer book.
+        let peer_book = PeerBook::load(Some(path.clone()));
+        assert!(peer_book.is_empty());
+        peer_book.record_connection(peer_ip, NodeType::Prover, 1);
+        peer_book.record_restriction(peer_ip, "spam", 60);
+        peer_book.save().unwrap();
+
+        // Check that the peer book is restored.
+        let restored = PeerBook::load(Some(path.clone()));
+        assert_eq!(restored.get(&peer_ip), peer_book.get(&peer_ip));
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+/// The number of seconds after which a peer score decays to half of its value.
+const SCORE_HALF_LIFE_IN_SECS: f64 = 900.0; // 15 minutes
+/// The maximum score a peer can accumulate.
+const MAXIMUM_SCORE: f64 = 100.0;
+/// The minimum score a peer can accumulate.
+const MINIMUM_SCORE: f64 = -1000.0;
+/// The number of milliseconds of round-trip latency that costs a peer one point.
+const LATENCY_PENALTY_UNIT_IN_MS: f64 = 100.0;
+/// The maximum score penalty for a high round-trip latency.
+const MAXIMUM_LATENCY_PENALTY: f64 = 20.0;
+/// The weight of the newest latency sample in the moving average.
+const LATENCY_SMOOTHING_FACTOR: f64 = 0.25;
+
+/// An event that adjusts the score of a peer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PeerEvent {
+    /// The peer sent a message that violated the protocol.
+    InvalidMessage,
+    /// The peer did not respond to a block request in time.
+    BlockRequestTimeout,
+    /// The peer delivered the given number of useful blocks.
+    UsefulBlocks(usize),
+    /// The peer sent a message that exceeded a rate limit.
+    RateLimited,
+}
+
+impl PeerEvent {
+    /// Returns the score adjustment for the event.
+    fn weight(&self) -> f64 {
+        match self {
+            Self::InvalidMessage => -25.0,
+            Self::BlockRequestTimeout => -10.0,
+            Self::UsefulBlocks(num_blocks) => 0.5 * *num_blocks as f64,
+            Self::RateLimited => -5.0,
+        }
+    }
+}
+
+impl fmt::Display for PeerEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidMessage => write!(f, "invalid message"),
+            Self::BlockRequestTimeout => write!(f, "block request timeout"),
+            Self::UsefulBlocks(num_blocks) => write!(f, "{num_blocks} useful blocks"),
+            Self::RateLimited => write!(f, "exceeded rate limit"),
+        }
+    }
+}
+
+/// The score metrics of a peer.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct PeerScoreMetrics {
+    /// The current score of the peer, including the latency penalty.
+    pub score: f64,
+    /// The average `Ping`/`Pong` round-trip latency of the peer in milliseconds, if known.
+    pub latency_ms: Option<u64>,
+}
+
+/// The score of a single peer.
+#[derive(Copy, Clone, Debug)]
+struct PeerScore {
+    /// The accumulated score, as of `updated_at`.
+    value: f64,
+    /// The time of the last update to `value`.
+    updated_at: Instant,
+    /// The moving average of the round-trip latency, if known.
+    latency: Option<Duration>,
+    /// The time the outstanding `Ping` was sent, if any.
+    ping_sent_at: Option<Instant>,
+}
+
+impl PeerScore {
+    /// Initializes a new neutral score.
+    fn new(now: Instant) -> Self {
+        Self { value: 0.0, updated_at: now, latency: None, ping_sent_at: None }
+    }
+
+    /// Returns the accumulated score, decayed towards zero as of the given time.
+    fn decayed(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.value * 0.5f64.powf(elapsed / SCORE_HALF_LIFE_IN_SECS)
+    }
+
+    /// Returns the score as of the given time, including the latency penalty.
+    fn score(&self, now: Instant) -> f64 {
+        let latency_penalty = self.latency.map_or(0.0, |latency| {
+            (latency.as_millis() as f64 / LATENCY_PENALTY_UNIT_IN_MS).min(MAXIMUM_LATENCY_PENALTY)
+        });
+        self.decayed(now) - latency_penalty
+    }
+
+    /// Applies the given event at the given time.
+    fn apply(&mut self, event: PeerEvent, now: Instant) {
+        self.value = (self.decayed(now) + event.weight()).clamp(MINIMUM_SCORE, MAXIMUM_SCORE);
+        self.updated_at = now;
+    }
+
+    /// Records a latency sample into the moving average.
+    fn record_latency(&mut self, sample: Duration) {
+        self.latency = Some(match self.latency {
+            Some(latency) => latency.mul_f64(1.0 - LATENCY_SMOOTHING_FACTOR) + sample.mul_f64(LATENCY_SMOOTHING_FACTOR),
+            None => sample,
+        });
+    }
+}
+
+/// The scores of peers, which decay over time and drive the disconnect and restriction decisions.
+#[derive(Debug, Default)]
+pub struct PeerScores {
+    /// The map of peer IPs to their scores.
+    scores: RwLock<HashMap<SocketAddr, PeerScore>>,
+}
+
+impl PeerScores {
+    /// The score below which a peer is restricted.
+    pub const RESTRICTION_THRESHOLD: f64 = -100.0;
+
+    /// Returns the current score of the given peer IP, which is zero for unknown peers.
+    pub fn score(&self, peer_ip: &SocketAddr) -> f64 {
+        self.scores.read().get(peer_ip).map_or(0.0, |score| score.score(Instant::now()))
+    }
+
+    /// Returns the score metrics of the given peer IP.
+    pub fn metrics(&self, peer_ip: &SocketAddr) -> PeerScoreMetrics {
+        match self.scores.read().get(peer_ip) {
+            Some(score) => PeerScoreMetrics {
+                score: score.score(Instant::now()),
+                latency_ms: score.latency.map(|latency| latency.as_millis() as u64),
+            },
+            None => PeerScoreMetrics { score: 0.0, latency_ms: None },
+        }
+    }
+
+    /// Records the given event for the given peer IP, and returns the updated score.
+    pub fn record(&self, peer_ip: SocketAddr, event: PeerEvent) -> f64 {
+        let now = Instant::now();
+        let mut scores = self.scores.write();
+        let score = scores.entry(peer_ip).or_insert_with(|| PeerScore::new(now));
+        score.apply(event, now);
+        score.score(now)
+    }
+
+    /// Records that a `Ping` was sent to the given peer IP.
+    pub fn record_ping(&self, peer_ip: SocketAddr) {
+        let now = Instant::now();
+        self.scores.write().entry(peer_ip).or_insert_with(|| PeerScore::new(now)).ping_sent_at = Some(now);
+    }
+
+    /// Records that a `Pong` was received from the given peer IP, and returns the round-trip latency, if a `Ping` was outstanding.
+    pub fn record_pong(&self, peer_ip: SocketAddr) -> Option<Duration> {
+        let mut scores = self.scores.write();
+        let score = scores.get_mut(&peer_ip)?;
+        let latency = score.ping_sent_at.take()?.elapsed();
+        score.record_latency(latency);
+        Some(latency)
+    }
+
+    /// Sorts the given peer IPs from the lowest to the highest score.
+    /// The sort is stable, so peers with equal scores retain their relative order.
+    pub fn sort_by_score(&self, peer_ips: &mut [SocketAddr]) {
+        let now = Instant::now();
+        let scores = self.scores.read();
+        let score = |peer_ip: &SocketAddr| scores.get(peer_ip).map_or(0.0, |score| score.score(now));
+        peer_ips.sort_by(|a, b| score(a).total_cmp(&score(b)));
+    }
+
+    /// Removes the score of the given peer IP.
+    pub fn remove(&self, peer_ip: &SocketAddr) {
+        self.scores.write().remove(peer_ip);
+    }
+
+    /// Handles the disconnection of the given peer IP.
+    /// The latency is forgotten, and the score is only retained if it is negative.
+    pub fn disconnect(&self, peer_ip: &SocketAddr) {
+        let now = Instant::now();
+        let mut scores = self.scores.write();
+        if let Some(score) = scores.get_mut(peer_ip) {
+            match score.decayed(now) < 0.0 {
+                true => {
+                    score.latency = None;
+                    score.ping_sent_at = None;
+                }
+                false => {
+                    scores.remove(peer_ip);
+                }
+            }
+        }
+    }
+
+    /// Removes the scores of disconnected peers that have decayed to a negligible value.
+    pub fn prune(&self) {
+        let now = Instant::now();
+        self.scores
+            .write()
+            .retain(|_, score| score.latency.is_some() || score.ping_sent_at.is_some() || score.decayed(now) <= -1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_events() {
+        let scores = PeerScores::default();
+        let good = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234);
+        let bad = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 2345);
+        let unknown = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 3456);
+
+        // Record the events of the peers.
+        assert!(scores.record(good, PeerEvent::UsefulBlocks(10)) > 4.9);
+        assert!(scores.record(bad, PeerEvent::InvalidMessage) < -24.9);
+        assert!(scores.record(bad, PeerEvent::BlockRequestTimeout) < -34.9);
+        assert_eq!(scores.score(&unknown), 0.0);
+
+        // Check that the peers are sorted from the lowest to the highest score.
+        let mut peer_ips = vec![good, unknown, bad];
+        scores.sort_by_score(&mut peer_ips);
+        assert_eq!(peer_ips, vec![bad, unknown, good]);
+
+        // Check that the score is bounded.
+        for _ in 0..100 {
+            scores.record(bad, PeerEvent::InvalidMessage);
+        }
+        assert!(scores.score(&bad) >= MINIMUM_SCORE);
+        assert!(scores.score(&bad) < PeerScores::RESTRICTION_THRESHOLD);
+    }
+
+    #[test]
+    fn test_decay() {
+        let now = Instant::now();
+        let mut score = PeerScore::new(now);
+        score.apply(PeerEvent::InvalidMessage, now);
+
+        // Check that the score halves after the half-life.
+        let later = now + Duration::from_secs_f64(SCORE_HALF_LIFE_IN_SECS);
+        assert!((score.decayed(later) + 12.5).abs() < 1e-6);
+        // Check that the score converges to zero.
+        let much_later = now + Duration::from_secs_f64(20.0 * SCORE_HALF_LIFE_IN_SECS);
+        assert!(score.decayed(much_later).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_latency() {
+        let scores = PeerScores::default();
+        let peer_ip = SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234);
+
+        // A `Pong` without an outstanding `Ping` is ignored.
+        assert!(scores.record_pong(peer_ip).is_none());
+        scores.record_ping(peer_ip);
+        assert!(scores.record_pong(peer_ip).is_some());
+        assert!(scores.record_pong(peer_ip).is_none());
+        assert!(scores.metrics(&peer_ip).latency_ms.is_some());
+
+        // Check that a high latency is penalized, up to a limit.
+        let mut score = PeerScore::new(Instant::now());
+        score.record_latency(Duration::from_millis(500));
+        assert!((score.score(Instant::now()) + 5.0).abs() < 1e-6);
+        score.record_latency(Duration::from_secs(60));
+        assert_eq!(score.score(Instant::now()), -MAXIMUM_LATENCY_PENALTY);
+
+        // Check that the latency is forgotten on disconnect, and the neutral score is pruned.
+        scores.disconnect(&peer_ip);
+        assert_eq!(scores.metrics(&peer_ip), PeerScoreMetrics { score: 0.0, latency_ms: None });
+        scores.prune();
+        assert!(scores.scores.read().is_empty());
+    }
+}
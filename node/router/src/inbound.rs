@@ -70,6 +70,15 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
 
         trace!("Received '{}' from '{peer_ip}'", message.name());
 
+        // Count the message, and time its handler until this function returns.
+        #[cfg(feature = "metrics")]
+        let _timer = {
+            let labels =
+                vec![("message", message.name().to_string()), ("peer_type", self.router().peer_type_label(&peer_ip))];
+            metrics::increment_counter_with_labels(metrics::router::MESSAGES_RECEIVED, 1, &labels);
+            metrics::HistogramTimer::new(metrics::router::MESSAGE_HANDLER_LATENCY, labels)
+        };
+
         // This match statement handles the inbound message by deserializing the message,
         // checking that the message is valid, and then calling the appropriate (trait) handler.
         match message {
@@ -170,10 +179,16 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
                     false => bail!("Peer '{peer_ip}' sent an invalid ping"),
                 }
             }
-            Message::Pong(message) => match self.pong(peer_ip, message) {
-                true => Ok(()),
-                false => bail!("Peer '{peer_ip}' sent an invalid pong"),
-            },
+            Message::Pong(message) => {
+                // Record the latency of the peer.
+                if let Some(latency) = self.router().peer_scores().record_pong(peer_ip) {
+                    trace!("Measured a latency of {}ms with '{peer_ip}'", latency.as_millis());
+                }
+                match self.pong(peer_ip, message) {
+                    true => Ok(()),
+                    false => bail!("Peer '{peer_ip}' sent an invalid pong"),
+                }
+            }
             Message::PuzzleRequest(..) => {
                 // Insert the puzzle request for the peer, and fetch the recent frequency.
                 let frequency = self.router().cache.insert_inbound_puzzle_request(peer_ip);
@@ -271,7 +286,7 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
         // Retrieve the connected peers.
         let peers = self.router().connected_peers();
         // Filter out invalid addresses.
-        let peers = match self.router().is_dev() {
+        let mut peers: Vec<_> = match self.router().is_dev() {
             // In development mode, relax the validity requirements to make operating devnets more flexible.
             true => {
                 peers.into_iter().filter(|ip| *ip != peer_ip && !is_bogon_ip(ip.ip())).take(MAX_PEERS_TO_SEND).collect()
@@ -283,6 +298,11 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
                 .take(MAX_PEERS_TO_SEND)
                 .collect(),
         };
+        // Advertise the address of the node in the other IP family, so the peer learns of both of its addresses.
+        if let Some(listener) = self.router().other_family_listener(&peer_ip) {
+            peers.truncate(MAX_PEERS_TO_SEND - 1);
+            peers.push(listener);
+        }
         // Send a `PeerResponse` message to the peer.
         self.send(peer_ip, Message::PeerResponse(PeerResponse { peers }));
         true
@@ -38,21 +38,34 @@ pub use outbound::*;
 mod routing;
 pub use routing::*;
 
-use crate::messages::NodeType;
-use snarkos_account::Account;
-use snarkos_node_tcp::{is_bogon_ip, is_unspecified_or_broadcast_ip, Config, Tcp};
+use crate::messages::{Message, MessageCodec, NodeType};
+use snarkos_account::Signer;
+use snarkos_node_tcp::{
+    is_bogon_ip,
+    is_unspecified_or_broadcast_ip,
+    routable_ip,
+    to_canonical_addr,
+    Config,
+    InboundLimits,
+    IpFamily,
+    OutboundLimits,
+    RateLimit,
+    RateLimitViolation,
+    Tcp,
+};
 use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
 
+use aleo_std::StorageMode;
 use anyhow::{bail, Result};
 use parking_lot::{Mutex, RwLock};
 use std::{
     collections::{HashMap, HashSet},
     future::Future,
-    net::SocketAddr,
+    net::{IpAddr, Ipv6Addr, SocketAddr},
     ops::Deref,
     str::FromStr,
     sync::Arc,
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tokio::task::JoinHandle;
 
@@ -72,14 +85,14 @@ pub struct InnerRouter<N: Network> {
     tcp: Tcp,
     /// The node type.
     node_type: NodeType,
-    /// The account of the node.
-    account: Account<N>,
+    /// The signer of the node, which signs the handshake challenges.
+    signer: Signer<N>,
     /// The cache.
     cache: Cache<N>,
     /// The resolver.
     resolver: Resolver,
     /// The set of trusted peers.
-    trusted_peers: HashSet<SocketAddr>,
+    trusted_peers: RwLock<HashSet<SocketAddr>>,
     /// The map of connected peer IPs to their peer handlers.
     connected_peers: RwLock<HashMap<SocketAddr, Peer<N>>>,
     /// The set of handshaking peers. While `Tcp` already recognizes the connecting IP addresses
@@ -89,12 +102,23 @@ pub struct InnerRouter<N: Network> {
     connecting_peers: Mutex<HashSet<SocketAddr>>,
     /// The set of candidate peer IPs.
     candidate_peers: RwLock<HashSet<SocketAddr>>,
-    /// The set of restricted peer IPs.
+    /// The map of restricted peer IPs to the time their restriction expires.
     restricted_peers: RwLock<HashMap<SocketAddr, Instant>>,
+    /// The peer book, which records the history of peers across restarts.
+    peer_book: PeerBook,
+    /// The scores of peers.
+    peer_scores: PeerScores,
     /// The spawned handles.
     handles: Mutex<Vec<JoinHandle<()>>>,
+    /// The number of bytes received from and sent to each connected peer, as of the last traffic metrics update.
+    #[cfg(feature = "metrics")]
+    reported_traffic: Mutex<HashMap<SocketAddr, (u64, u64)>>,
     /// If the flag is set, the node will engage in P2P gossip to request more peers.
     allow_external_peers: bool,
+    /// The IP family preferred for outbound connections, if any.
+    preferred_ip_family: Option<IpFamily>,
+    /// The limits on inbound requests.
+    inbound_limits: InboundLimits,
     /// The boolean flag for the development mode.
     is_dev: bool,
 }
@@ -107,6 +131,17 @@ impl<N: Network> Router<N> {
     /// The duration in seconds after which a connected peer is considered inactive or
     /// disconnected if no message has been received in the meantime.
     const RADIO_SILENCE_IN_SECS: u64 = 150; // 2.5 minutes
+    /// The duration in seconds of the first restriction of a peer, which doubles with each subsequent restriction.
+    const MINIMUM_RESTRICTION_IN_SECS: u64 = 150;
+    /// The maximum duration in seconds of a restriction.
+    const MAXIMUM_RESTRICTION_IN_SECS: u64 = 86_400;
+    /// The default limit on block requests per second from a single peer, which leaves room for
+    /// twice the number of blocks a syncing peer requests at once.
+    const BLOCK_REQUEST_LIMIT: RateLimit = RateLimit::new(Some(100), None);
+    /// The default limit on peer requests per second from a single peer.
+    const PEER_REQUEST_LIMIT: RateLimit = RateLimit::new(Some(1), None);
+    /// The default limit on puzzle requests per second from a single peer.
+    const PUZZLE_REQUEST_LIMIT: RateLimit = RateLimit::new(Some(1), None);
 }
 
 impl<N: Network> Router<N> {
@@ -114,31 +149,70 @@ impl<N: Network> Router<N> {
     pub async fn new(
         node_ip: SocketAddr,
         node_type: NodeType,
-        account: Account<N>,
+        signer: Signer<N>,
         trusted_peers: &[SocketAddr],
         max_peers: u16,
         allow_external_peers: bool,
-        is_dev: bool,
+        storage_mode: StorageMode,
+        preferred_ip_family: Option<IpFamily>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
+        // Determine if the node is in development mode.
+        let is_dev = matches!(storage_mode, StorageMode::Development(_));
         // Initialize the TCP stack.
-        let tcp = Tcp::new(Config::new(node_ip, max_peers));
+        let tcp = Tcp::new(Config {
+            inbound_limit: inbound_limits.connection,
+            global_inbound_limit: inbound_limits.global,
+            outbound_limit: outbound_limits.connection,
+            global_outbound_limit: outbound_limits.global,
+            ..Config::new(node_ip, max_peers)
+        });
+        // Load the peer book, which is stored alongside the ledger.
+        let peer_book = PeerBook::load(Some(aleo_std::aleo_ledger_dir(N::ID, storage_mode).join(PeerBook::FILE_NAME)));
         // Initialize the router.
         Ok(Self(Arc::new(InnerRouter {
             tcp,
             node_type,
-            account,
+            signer,
             cache: Default::default(),
             resolver: Default::default(),
-            trusted_peers: trusted_peers.iter().copied().collect(),
+            trusted_peers: RwLock::new(trusted_peers.iter().copied().collect()),
             connected_peers: Default::default(),
             connecting_peers: Default::default(),
             candidate_peers: Default::default(),
             restricted_peers: Default::default(),
+            peer_book,
+            peer_scores: Default::default(),
             handles: Default::default(),
+            #[cfg(feature = "metrics")]
+            reported_traffic: Default::default(),
             allow_external_peers,
+            preferred_ip_family,
+            inbound_limits,
             is_dev,
         })))
     }
+
+    /// Restores the candidate and restricted peers from the peer book.
+    /// Note: This must be called after the TCP listener is enabled.
+    pub fn restore_from_peer_book(&self) {
+        // Restore the peers that are still restricted, for the remainder of their restriction.
+        for (peer_ip, remaining) in self.peer_book.restricted_peers() {
+            self.restricted_peers.write().insert(peer_ip, Instant::now() + Duration::from_secs(remaining));
+        }
+        // Restore the candidate peers, starting from the most reliable ones.
+        let candidate_peers = self
+            .peer_book
+            .peers_by_reliability()
+            .into_iter()
+            .filter(|peer_ip| self.is_valid_peer_ip(peer_ip) && self.peer_book.reliability(peer_ip) >= 0)
+            .collect::<Vec<_>>();
+        self.insert_candidate_peers(&candidate_peers);
+        if !self.peer_book.is_empty() {
+            debug!("Loaded {} peers from the peer book", self.peer_book.len());
+        }
+    }
 }
 
 impl<N: Network> Router<N> {
@@ -162,6 +236,7 @@ impl<N: Network> Router<N> {
                 // If the connection was not allowed, log the error.
                 Err(error) => {
                     router.connecting_peers.lock().remove(&peer_ip);
+                    router.peer_book.record_connection_failure(peer_ip);
                     warn!("Unable to connect to '{peer_ip}' - {error}");
                     false
                 }
@@ -230,24 +305,44 @@ impl<N: Network> Router<N> {
         !self.is_local_ip(ip) && !is_bogon_ip(ip.ip()) && !is_unspecified_or_broadcast_ip(ip.ip())
     }
 
+    /// Returns the address at which the node can be reached over the other IP family than that of the given peer IP,
+    /// if the node listens on both families and the host has a public address in the other family.
+    pub fn other_family_listener(&self, peer_ip: &SocketAddr) -> Option<SocketAddr> {
+        // Only a listener bound to the unspecified IPv6 address is dual-stack, as it otherwise falls back to IPv4.
+        let local_ip = self.local_ip();
+        if local_ip.ip() != IpAddr::V6(Ipv6Addr::UNSPECIFIED) {
+            return None;
+        }
+        routable_ip(IpFamily::of(peer_ip).other()).map(|ip| SocketAddr::new(ip, local_ip.port()))
+    }
+
+    /// Returns `true` if the peer with the given Aleo address is already connected over the other IP family than
+    /// that of the given peer IP, with the same listener port, as it is then the same node reached by another address.
+    pub fn is_connected_over_other_family(&self, peer_ip: &SocketAddr, address: &Address<N>) -> bool {
+        let family = IpFamily::of(peer_ip);
+        self.connected_peers.read().values().any(|peer| {
+            peer.address() == *address && peer.ip().port() == peer_ip.port() && !family.contains(&peer.ip())
+        })
+    }
+
     /// Returns the node type.
     pub fn node_type(&self) -> NodeType {
         self.node_type
     }
 
-    /// Returns the account private key of the node.
-    pub fn private_key(&self) -> &PrivateKey<N> {
-        self.account.private_key()
+    /// Returns the account private key of the node, unless it is held by an external signer process.
+    pub fn private_key(&self) -> Option<&PrivateKey<N>> {
+        self.signer.account().map(|account| account.private_key())
     }
 
-    /// Returns the account view key of the node.
-    pub fn view_key(&self) -> &ViewKey<N> {
-        self.account.view_key()
+    /// Returns the account view key of the node, unless it is held by an external signer process.
+    pub fn view_key(&self) -> Option<&ViewKey<N>> {
+        self.signer.account().map(|account| account.view_key())
     }
 
     /// Returns the account address of the node.
     pub fn address(&self) -> Address<N> {
-        self.account.address()
+        self.signer.address()
     }
 
     /// Returns `true` if the node is in development mode.
@@ -260,6 +355,11 @@ impl<N: Network> Router<N> {
         self.allow_external_peers
     }
 
+    /// Returns the IP family preferred for outbound connections, if any.
+    pub fn preferred_ip_family(&self) -> Option<IpFamily> {
+        self.preferred_ip_family
+    }
+
     /// Returns the listener IP address from the (ambiguous) peer address.
     pub fn resolve_to_listener(&self, peer_addr: &SocketAddr) -> Option<SocketAddr> {
         self.resolver.get_listener(peer_addr)
@@ -270,6 +370,41 @@ impl<N: Network> Router<N> {
         self.resolver.get_ambiguous(peer_ip)
     }
 
+    /// Returns the codec for the given peer address, which compresses large messages if negotiated during the handshake.
+    pub fn codec(&self, peer_addr: &SocketAddr) -> MessageCodec<N> {
+        let peer = self.resolve_to_listener(peer_addr).and_then(|peer_ip| self.get_connected_peer(&peer_ip));
+        match peer.map_or(false, |peer| peer.is_compressed()) {
+            true => MessageCodec::compressed(),
+            false => MessageCodec::default(),
+        }
+    }
+
+    /// Returns the name of the type of the given inbound message and its per-peer rate limit, if the type is limited.
+    /// Only requests are limited, as responses and gossip are either solicited by the node or deduplicated by the cache.
+    pub fn message_limit(&self, message: &Message<N>) -> Option<(&'static str, RateLimit)> {
+        let (name, default) = match message {
+            Message::BlockRequest(..) => ("BlockRequest", Self::BLOCK_REQUEST_LIMIT),
+            Message::PeerRequest(..) => ("PeerRequest", Self::PEER_REQUEST_LIMIT),
+            Message::PuzzleRequest(..) => ("PuzzleRequest", Self::PUZZLE_REQUEST_LIMIT),
+            // Pings are only limited by the per-peer limit.
+            Message::Ping(..) => ("Ping", RateLimit::default()),
+            _ => return None,
+        };
+        Some((name, self.inbound_limits.request_limit(name, default)))
+    }
+
+    /// Penalizes the peer at the given address for exceeding a rate limit.
+    /// Violations of the global limit are not attributed to any single peer, and are not penalized.
+    pub fn rate_limit_exceeded(&self, peer_addr: SocketAddr, violation: RateLimitViolation) {
+        if violation == RateLimitViolation::Global {
+            return;
+        }
+        if let Some(peer_ip) = self.resolve_to_listener(&peer_addr) {
+            debug!("Dropped a message from '{peer_ip}', as it exceeds {violation}");
+            self.update_peer_score(peer_ip, PeerEvent::RateLimited);
+        }
+    }
+
     /// Returns `true` if the node is connected to the given peer IP.
     pub fn is_connected(&self, ip: &SocketAddr) -> bool {
         self.connected_peers.read().contains_key(ip)
@@ -297,16 +432,12 @@ impl<N: Network> Router<N> {
 
     /// Returns `true` if the given IP is restricted.
     pub fn is_restricted(&self, ip: &SocketAddr) -> bool {
-        self.restricted_peers
-            .read()
-            .get(ip)
-            .map(|time| time.elapsed().as_secs() < Self::RADIO_SILENCE_IN_SECS)
-            .unwrap_or(false)
+        self.restricted_peers.read().get(ip).map(|restricted_until| Instant::now() < *restricted_until).unwrap_or(false)
     }
 
     /// Returns `true` if the given IP is trusted.
     pub fn is_trusted(&self, ip: &SocketAddr) -> bool {
-        self.trusted_peers.contains(ip)
+        self.trusted_peers.read().contains(ip)
     }
 
     /// Returns the maximum number of connected peers.
@@ -384,9 +515,19 @@ impl<N: Network> Router<N> {
         self.restricted_peers.read().keys().copied().collect()
     }
 
+    /// Returns the peer book.
+    pub fn peer_book(&self) -> &PeerBook {
+        &self.peer_book
+    }
+
+    /// Returns the peer scores.
+    pub fn peer_scores(&self) -> &PeerScores {
+        &self.peer_scores
+    }
+
     /// Returns the list of trusted peers.
-    pub fn trusted_peers(&self) -> &HashSet<SocketAddr> {
-        &self.trusted_peers
+    pub fn trusted_peers(&self) -> HashSet<SocketAddr> {
+        self.trusted_peers.read().clone()
     }
 
     /// Returns the list of bootstrap peers.
@@ -404,8 +545,12 @@ impl<N: Network> Router<N> {
     }
 
     /// Returns the list of metrics for the connected peers.
-    pub fn connected_metrics(&self) -> Vec<(SocketAddr, NodeType)> {
-        self.connected_peers.read().iter().map(|(ip, peer)| (*ip, peer.node_type())).collect()
+    pub fn connected_metrics(&self) -> Vec<(SocketAddr, NodeType, PeerScoreMetrics)> {
+        self.connected_peers
+            .read()
+            .iter()
+            .map(|(ip, peer)| (*ip, peer.node_type(), self.peer_scores.metrics(ip)))
+            .collect()
     }
 
     #[cfg(feature = "metrics")]
@@ -415,9 +560,41 @@ impl<N: Network> Router<N> {
         metrics::gauge(metrics::router::RESTRICTED, self.restricted_peers.read().len() as f64);
     }
 
+    /// Returns the metrics label for the node type of the given peer.
+    #[cfg(feature = "metrics")]
+    pub fn peer_type_label(&self, peer_ip: &SocketAddr) -> String {
+        self.connected_peers
+            .read()
+            .get(peer_ip)
+            .map_or_else(|| "Unknown".to_string(), |peer| peer.node_type().to_string())
+    }
+
+    /// Updates the traffic metrics of the connected peers by node type, from the statistics of their connections.
+    #[cfg(feature = "metrics")]
+    pub fn update_traffic_metrics(&self) {
+        let mut reported_traffic = self.reported_traffic.lock();
+        for (peer_ip, peer) in self.connected_peers.read().iter() {
+            // Retrieve the statistics of the connection with the peer.
+            let Some(stats) = self.resolve_to_ambiguous(peer_ip).and_then(|addr| self.tcp.known_peers().get(addr))
+            else {
+                continue;
+            };
+            let (received, sent) = (stats.received().1, stats.sent().1);
+            // Report the traffic since the last update, as the connections come and go.
+            let (last_received, last_sent) = reported_traffic.insert(*peer_ip, (received, sent)).unwrap_or_default();
+            let labels = [("peer_type", peer.node_type().to_string())];
+            let bytes_received = received.saturating_sub(last_received);
+            metrics::increment_counter_with_labels(metrics::router::PEER_BYTES_RECEIVED, bytes_received, &labels);
+            let bytes_sent = sent.saturating_sub(last_sent);
+            metrics::increment_counter_with_labels(metrics::router::PEER_BYTES_SENT, bytes_sent, &labels);
+        }
+    }
+
     /// Inserts the given peer into the connected peers.
     pub fn insert_connected_peer(&self, peer: Peer<N>, peer_addr: SocketAddr) {
         let peer_ip = peer.ip();
+        // Record the connection in the peer book.
+        self.peer_book.record_connection(peer_ip, peer.node_type(), peer.version());
         // Adds a bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.insert_peer(peer_ip, peer_addr);
         // Add an entry for this `Peer` in the connected peers.
@@ -434,12 +611,14 @@ impl<N: Network> Router<N> {
     ///
     /// This method skips adding any given peers if the combined size exceeds the threshold,
     /// as the peer providing this list could be subverting the protocol.
+    /// IPv4-mapped IPv6 addresses are converted to IPv4, so that they match the addresses of the connected peers.
     pub fn insert_candidate_peers(&self, peers: &[SocketAddr]) {
         // Compute the maximum number of candidate peers.
         let max_candidate_peers = Self::MAXIMUM_CANDIDATE_PEERS.saturating_sub(self.number_of_candidate_peers());
         // Ensure the combined number of peers does not surpass the threshold.
         let eligible_peers = peers
             .iter()
+            .map(|peer_ip| to_canonical_addr(*peer_ip))
             .filter(|peer_ip| {
                 // Ensure the peer is not itself, is not already connected, and is not restricted.
                 !self.is_local_ip(peer_ip) && !self.is_connected(peer_ip) && !self.is_restricted(peer_ip)
@@ -452,14 +631,68 @@ impl<N: Network> Router<N> {
         self.update_metrics();
     }
 
-    /// Inserts the given peer into the restricted peers.
-    pub fn insert_restricted_peer(&self, peer_ip: SocketAddr) {
+    /// Inserts the given peer into the restricted peers, for the given reason.
+    /// The restriction lasts `MINIMUM_RESTRICTION_IN_SECS`, doubling for each previous restriction of the peer,
+    /// up to `MAXIMUM_RESTRICTION_IN_SECS`.
+    pub fn insert_restricted_peer(&self, peer_ip: SocketAddr, reason: &str) {
+        // Compute the duration of the restriction, based on the number of previous restrictions.
+        let num_restrictions = self.peer_book.num_restrictions(&peer_ip).min(u64::BITS - 1);
+        let duration = Self::MINIMUM_RESTRICTION_IN_SECS
+            .saturating_mul(1 << num_restrictions)
+            .min(Self::MAXIMUM_RESTRICTION_IN_SECS);
+        debug!("Restricting '{peer_ip}' for {duration} seconds ({reason})");
         // Remove this peer from the candidate peers, if it exists.
         self.candidate_peers.write().remove(&peer_ip);
         // Add the peer to the restricted peers.
-        self.restricted_peers.write().insert(peer_ip, Instant::now());
+        self.restricted_peers.write().insert(peer_ip, Instant::now() + Duration::from_secs(duration));
+        // Record the restriction in the peer book.
+        self.peer_book.record_restriction(peer_ip, reason, duration);
+        #[cfg(feature = "metrics")]
+        self.update_metrics();
+    }
+
+    /// Removes the given peer from the restricted peers, and resets its score.
+    /// Returns `true` if the peer was restricted.
+    pub fn remove_restricted_peer(&self, peer_ip: SocketAddr) -> bool {
+        // Remove the peer from the restricted peers.
+        let was_restricted = self.restricted_peers.write().remove(&peer_ip).is_some();
+        // Clear the restriction in the peer book.
+        self.peer_book.clear_restriction(&peer_ip);
+        // Reset the score of the peer, so that it is not immediately restricted again.
+        self.peer_scores.remove(&peer_ip);
         #[cfg(feature = "metrics")]
         self.update_metrics();
+        was_restricted
+    }
+
+    /// Inserts the given peer IP into the trusted peers, which the heartbeat keeps connected.
+    /// Returns `true` if the peer was not already trusted.
+    pub fn insert_trusted_peer(&self, peer_ip: SocketAddr) -> bool {
+        // Remove the peer from the restricted peers, if it exists.
+        self.remove_restricted_peer(peer_ip);
+        self.trusted_peers.write().insert(peer_ip)
+    }
+
+    /// Removes the given peer IP from the trusted peers.
+    /// Returns `true` if the peer was trusted.
+    pub fn remove_trusted_peer(&self, peer_ip: SocketAddr) -> bool {
+        self.trusted_peers.write().remove(&peer_ip)
+    }
+
+    /// Records the given event in the score of the given peer.
+    /// If the score falls below the restriction threshold, the peer is restricted and disconnected.
+    /// Returns `true` if the peer was disconnected.
+    pub fn update_peer_score(&self, peer_ip: SocketAddr, event: PeerEvent) -> bool {
+        let score = self.peer_scores.record(peer_ip, event);
+        trace!("Updated the score of '{peer_ip}' to {score:.1} ({event})");
+        // Restrict the peer if its score is too low, unless the peer is trusted.
+        if score < PeerScores::RESTRICTION_THRESHOLD && !self.is_trusted(&peer_ip) && !self.is_restricted(&peer_ip) {
+            self.insert_restricted_peer(peer_ip, &format!("low score of {score:.1} after {event}"));
+            // Disconnect from this peer.
+            self.disconnect(peer_ip);
+            return true;
+        }
+        false
     }
 
     /// Updates the connected peer with the given function.
@@ -486,9 +719,19 @@ impl<N: Network> Router<N> {
         // Removes the bidirectional map between the listener address and (ambiguous) peer address.
         self.resolver.remove_peer(&peer_ip);
         // Remove this peer from the connected peers, if it exists.
-        self.connected_peers.write().remove(&peer_ip);
-        // Add the peer to the candidate peers.
-        self.candidate_peers.write().insert(peer_ip);
+        if self.connected_peers.write().remove(&peer_ip).is_some() {
+            // Record the last time the peer was seen in the peer book.
+            self.peer_book.record_seen(peer_ip);
+        }
+        // Update the score of the peer.
+        self.peer_scores.disconnect(&peer_ip);
+        // Forget the traffic reported for the peer, as its next connection starts from zero.
+        #[cfg(feature = "metrics")]
+        self.reported_traffic.lock().remove(&peer_ip);
+        // Add the peer to the candidate peers, unless it is restricted.
+        if !self.is_restricted(&peer_ip) {
+            self.candidate_peers.write().insert(peer_ip);
+        }
         #[cfg(feature = "metrics")]
         self.update_metrics();
     }
@@ -512,11 +755,20 @@ impl<N: Network> Router<N> {
         self.handles.lock().push(tokio::spawn(future));
     }
 
+    /// Persists the peer book.
+    pub fn save_peer_book(&self) {
+        if let Err(error) = self.peer_book.save() {
+            warn!("Failed to save the peer book - {error}");
+        }
+    }
+
     /// Shuts down the router.
     pub async fn shut_down(&self) {
         info!("Shutting down the router...");
         // Abort the tasks.
         self.handles.lock().iter().for_each(|handle| handle.abort());
+        // Save the peer book.
+        self.save_peer_book();
         // Close the listener.
         self.tcp.shut_down().await;
     }
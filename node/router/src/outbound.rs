@@ -30,6 +30,8 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
 
     /// Sends a "Ping" message to the given peer.
     fn send_ping(&self, peer_ip: SocketAddr, block_locators: Option<BlockLocators<N>>) {
+        // Record the time of the ping, to measure the latency of the peer.
+        self.router().peer_scores().record_ping(peer_ip);
         self.send(peer_ip, Message::Ping(Ping::new(self.router().node_type(), block_locators)));
     }
 
@@ -65,6 +67,11 @@ pub trait Outbound<N: Network>: Writing<Message = Message<N>> {
         }
         // Retrieve the message name.
         let name = message.name();
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter_with_labels(metrics::router::MESSAGES_SENT, 1, &[
+            ("message", name.to_string()),
+            ("peer_type", self.router().peer_type_label(&peer_ip)),
+        ]);
         // Send the message to the peer.
         trace!("Sending '{name}' to '{peer_ip}'");
         let result = self.unicast(peer_addr, message);
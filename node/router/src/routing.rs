@@ -35,6 +35,8 @@ pub trait Routing<N: Network>:
         self.enable_on_connect().await;
         // Enable the TCP listener. Note: This must be called after the above protocols.
         self.enable_listener().await;
+        // Restore the candidate and restricted peers from the peer book.
+        self.router().restore_from_peer_book();
         // Initialize the heartbeat.
         self.initialize_heartbeat();
     }
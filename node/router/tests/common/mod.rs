@@ -18,12 +18,14 @@ pub use router::*;
 
 use std::{
     env,
-    net::{IpAddr, Ipv4Addr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     str::FromStr,
 };
 
+use aleo_std::StorageMode;
 use snarkos_account::Account;
 use snarkos_node_router::{messages::NodeType, Router};
+use snarkos_node_tcp::{InboundLimits, OutboundLimits};
 use snarkvm::prelude::{block::Block, FromBytes, MainnetV0 as CurrentNetwork, Network};
 
 /// A helper macro to print the TCP listening address, along with the connected and connecting peers.
@@ -71,35 +73,73 @@ pub fn initialize_logger(level: u8) {
 /// Initializes a client router. Setting the `listening_port = 0` will result in a random port being assigned.
 #[allow(dead_code)]
 pub async fn client(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNetwork> {
-    Router::new(
-        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port),
+    client_with_limits(listening_port, max_peers, Default::default(), Default::default()).await
+}
+
+/// Initializes a client router with the given rate limits on inbound and outbound messages.
+#[allow(dead_code)]
+pub async fn client_with_limits(
+    listening_port: u16,
+    max_peers: u16,
+    inbound_limits: InboundLimits,
+    outbound_limits: OutboundLimits,
+) -> TestRouter<CurrentNetwork> {
+    let node_ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port);
+    client_listening_on(node_ip, max_peers, inbound_limits, outbound_limits).await
+}
+
+/// Initializes a client router listening on both IPv4 and IPv6, unless IPv6 is unavailable on the host.
+#[allow(dead_code)]
+pub async fn dual_stack_client(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNetwork> {
+    let node_ip = SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), listening_port);
+    client_listening_on(node_ip, max_peers, Default::default(), Default::default()).await
+}
+
+/// Initializes a client router listening on the given address.
+#[allow(dead_code)]
+async fn client_listening_on(
+    node_ip: SocketAddr,
+    max_peers: u16,
+    inbound_limits: InboundLimits,
+    outbound_limits: OutboundLimits,
+) -> TestRouter<CurrentNetwork> {
+    let directory = tempfile::tempdir().expect("couldn't create a storage directory");
+    let router = Router::new(
+        node_ip,
         NodeType::Client,
-        sample_account(),
+        sample_account().into(),
         &[],
         max_peers,
         true,
-        true,
+        StorageMode::Custom(directory.path().to_path_buf()),
+        None,
+        inbound_limits,
+        outbound_limits,
     )
     .await
-    .expect("couldn't create client router")
-    .into()
+    .expect("couldn't create client router");
+    TestRouter::new(router, directory)
 }
 
 /// Initializes a prover router. Setting the `listening_port = 0` will result in a random port being assigned.
 #[allow(dead_code)]
 pub async fn prover(listening_port: u16, max_peers: u16) -> TestRouter<CurrentNetwork> {
-    Router::new(
+    let directory = tempfile::tempdir().expect("couldn't create a storage directory");
+    let router = Router::new(
         SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port),
         NodeType::Prover,
-        sample_account(),
+        sample_account().into(),
         &[],
         max_peers,
         true,
-        true,
+        StorageMode::Custom(directory.path().to_path_buf()),
+        None,
+        Default::default(),
+        Default::default(),
     )
     .await
-    .expect("couldn't create prover router")
-    .into()
+    .expect("couldn't create prover router");
+    TestRouter::new(router, directory)
 }
 
 /// Initializes a validator router. Setting the `listening_port = 0` will result in a random port being assigned.
@@ -110,16 +150,20 @@ pub async fn validator(
     trusted_peers: &[SocketAddr],
     allow_external_peers: bool,
 ) -> TestRouter<CurrentNetwork> {
-    Router::new(
+    let directory = tempfile::tempdir().expect("couldn't create a storage directory");
+    let router = Router::new(
         SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listening_port),
         NodeType::Validator,
-        sample_account(),
+        sample_account().into(),
         trusted_peers,
         max_peers,
         allow_external_peers,
-        true,
+        StorageMode::Custom(directory.path().to_path_buf()),
+        None,
+        Default::default(),
+        Default::default(),
     )
     .await
-    .expect("couldn't create validator router")
-    .into()
+    .expect("couldn't create validator router");
+    TestRouter::new(router, directory)
 }
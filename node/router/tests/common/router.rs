@@ -34,6 +34,8 @@ use snarkos_node_tcp::{
     protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
     Connection,
     ConnectionSide,
+    RateLimit,
+    RateLimitViolation,
     Tcp,
     P2P,
 };
@@ -44,15 +46,18 @@ use snarkvm::prelude::{
 };
 
 use async_trait::async_trait;
-use std::{io, net::SocketAddr};
+use std::{io, net::SocketAddr, sync::Arc};
+use tempfile::TempDir;
 use tracing::*;
 
+/// A router for tests, which keeps its storage directory until it is dropped.
 #[derive(Clone)]
-pub struct TestRouter<N: Network>(Router<N>);
+pub struct TestRouter<N: Network>(Router<N>, Arc<TempDir>);
 
-impl<N: Network> From<Router<N>> for TestRouter<N> {
-    fn from(router: Router<N>) -> Self {
-        Self(router)
+impl<N: Network> TestRouter<N> {
+    /// Initializes a new test router, with the given storage directory.
+    pub fn new(router: Router<N>, directory: TempDir) -> Self {
+        Self(router, Arc::new(directory))
     }
 }
 
@@ -110,8 +115,8 @@ impl<N: Network> Writing for TestRouter<N> {
 
     /// Creates an [`Encoder`] used to write the outbound messages to the target stream.
     /// The `side` parameter indicates the connection side **from the node's perspective**.
-    fn codec(&self, _addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.router().codec(&addr)
     }
 }
 
@@ -122,8 +127,8 @@ impl<N: Network> Reading for TestRouter<N> {
 
     /// Creates a [`Decoder`] used to interpret messages from the network.
     /// The `side` param indicates the connection side **from the node's perspective**.
-    fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.router().codec(&peer_addr)
     }
 
     /// Processes a message received from the network.
@@ -137,6 +142,16 @@ impl<N: Network> Reading for TestRouter<N> {
         }
         Ok(())
     }
+
+    /// Returns the name of the type of the given inbound message and its per-peer rate limit, if the type is limited.
+    fn message_limit(&self, message: &Self::Message) -> Option<(&'static str, RateLimit)> {
+        self.router().message_limit(message)
+    }
+
+    /// Penalizes the peer at the given address for exceeding a rate limit.
+    fn rate_limit_exceeded(&self, peer_addr: SocketAddr, violation: RateLimitViolation) {
+        self.router().rate_limit_exceeded(peer_addr, violation)
+    }
 }
 
 #[async_trait]
@@ -18,6 +18,7 @@ use common::*;
 use snarkos_node_tcp::{protocols::Handshake, P2P};
 
 use core::time::Duration;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
 #[tokio::test]
 async fn test_connect_without_handshake() {
@@ -238,3 +239,51 @@ async fn test_connect_simultaneously_with_handshake() {
         assert_eq!(node1.number_of_connected_peers(), 1);
     }
 }
+
+#[tokio::test]
+async fn test_connect_over_both_ip_families() {
+    // Create 2 routers, the first of which listens on both IPv4 and IPv6.
+    let node0 = dual_stack_client(0, 2).await;
+    let node1 = client(0, 2).await;
+
+    // Enable handshake protocol.
+    node0.enable_handshake().await;
+    node1.enable_handshake().await;
+
+    // Start listening.
+    node0.tcp().enable_listener().await.unwrap();
+    node1.tcp().enable_listener().await.unwrap();
+
+    // Skip the test if IPv6 is unavailable on the host, as the listener then falls back to IPv4.
+    if !node0.local_ip().is_ipv6() {
+        return;
+    }
+    let node0_ipv4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), node0.local_ip().port());
+    let node0_ipv6 = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), node0.local_ip().port());
+
+    {
+        // Connect node1 to node0 over IPv4.
+        node1.connect(node0_ipv4);
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // Check the router level.
+        assert!(node1.is_connected(&node0_ipv4));
+        assert_eq!(node0.number_of_connected_peers(), 1);
+        assert_eq!(node1.number_of_connected_peers(), 1);
+    }
+    {
+        // Connect node1 to node0 again, over IPv6.
+        node1.connect(node0_ipv6);
+        // Sleep briefly.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        print_tcp!(node0);
+        print_tcp!(node1);
+
+        // Check the router level - the same node was not connected to twice.
+        assert!(!node1.is_connected(&node0_ipv6));
+        assert_eq!(node0.number_of_connected_peers(), 1);
+        assert_eq!(node1.number_of_connected_peers(), 1);
+    }
+}
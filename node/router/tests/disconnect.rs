@@ -108,3 +108,33 @@ async fn test_disconnect_with_handshake() {
     assert_eq!(node1.tcp().num_connected(), 1); // Router 1 has no way of knowing that Router 0 disconnected.
     assert_eq!(node1.tcp().num_connecting(), 0);
 }
+
+#[tokio::test]
+async fn test_disconnect_restricted_peer() {
+    // Create 2 routers.
+    let node0 = validator(0, 1, &[], true).await;
+    let node1 = client(0, 1).await;
+
+    // Enable handshake protocol.
+    node0.enable_handshake().await;
+    node1.enable_handshake().await;
+
+    // Start listening.
+    node0.tcp().enable_listener().await.unwrap();
+    node1.tcp().enable_listener().await.unwrap();
+
+    // Connect node0 to node1.
+    node0.connect(node1.local_ip());
+    // Sleep briefly.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(node0.number_of_connected_peers(), 1);
+
+    // Restrict node1, and then disconnect from it.
+    node0.insert_restricted_peer(node1.local_ip(), "test");
+    node0.disconnect(node1.local_ip()).await.unwrap();
+
+    // Ensure the restricted peer did not become a candidate peer again.
+    assert_eq!(node0.number_of_connected_peers(), 0);
+    assert!(node0.is_restricted(&node1.local_ip()));
+    assert!(!node0.candidate_peers().contains(&node1.local_ip()));
+}
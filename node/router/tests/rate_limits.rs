@@ -0,0 +1,97 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+use common::*;
+
+use snarkos_node_router::{
+    messages::{Message, PeerRequest, Pong},
+    Outbound,
+};
+use snarkos_node_tcp::{
+    protocols::{Handshake, Reading, Writing},
+    OutboundLimits,
+    RateLimit,
+    P2P,
+};
+use snarkvm::prelude::MainnetV0 as CurrentNetwork;
+
+use core::time::Duration;
+use std::time::Instant;
+
+/// Connects `node0` to `node1` with the handshake, reading, and writing protocols enabled on both.
+async fn connect(node0: &TestRouter<CurrentNetwork>, node1: &TestRouter<CurrentNetwork>) {
+    for node in [node0, node1] {
+        node.enable_handshake().await;
+        node.enable_reading().await;
+        node.enable_writing().await;
+        node.tcp().enable_listener().await.unwrap();
+    }
+
+    // Connect node0 to node1.
+    node0.connect(node1.local_ip());
+    // Sleep briefly.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    assert!(node0.is_connected(&node1.local_ip()));
+    assert!(node1.is_connected(&node0.local_ip()));
+}
+
+#[tokio::test]
+async fn test_inbound_rate_limit_restricts_peer() {
+    // Create 2 routers with the default limits, which allow 1 peer request per second.
+    let node0 = client(0, 1).await;
+    let node1 = client(0, 1).await;
+    connect(&node0, &node1).await;
+
+    // Flood node1 with peer requests.
+    for _ in 0..50 {
+        node0.send(node1.local_ip(), Message::PeerRequest(PeerRequest));
+    }
+    // Sleep briefly.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    print_tcp!(node0);
+    print_tcp!(node1);
+
+    // Ensure the excess requests were dropped, and the peer was restricted and disconnected.
+    assert!(node1.tcp().stats().rate_limited() > 0);
+    assert!(node1.is_restricted(&node0.local_ip()));
+    assert!(!node1.is_connected(&node0.local_ip()));
+    assert_eq!(node1.number_of_connected_peers(), 0);
+}
+
+#[tokio::test]
+async fn test_outbound_rate_limit_delays_messages() {
+    // Create 2 routers, the first of which sends at most 2 messages per second to each peer.
+    let outbound_limits = OutboundLimits { connection: RateLimit::new(Some(2), None), ..Default::default() };
+    let node0 = client_with_limits(0, 1, Default::default(), outbound_limits).await;
+    let node1 = client(0, 1).await;
+    connect(&node0, &node1).await;
+
+    // Send 5 messages, which exceeds the burst of 2 messages by 3.
+    let start = Instant::now();
+    let mut receivers = Vec::new();
+    for _ in 0..5 {
+        receivers.push(node0.send(node1.local_ip(), Message::Pong(Pong { is_fork: None })).unwrap());
+    }
+    for receiver in receivers {
+        receiver.await.unwrap().unwrap();
+    }
+
+    // Ensure the messages were delayed, rather than dropped.
+    assert!(start.elapsed() >= Duration::from_millis(900));
+    assert!(node1.is_connected(&node0.local_ip()));
+    assert_eq!(node1.tcp().stats().rate_limited(), 0);
+}
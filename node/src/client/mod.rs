@@ -23,12 +23,16 @@ use snarkos_node_router::{
     Heartbeat,
     Inbound,
     Outbound,
+    PeerEvent,
     Router,
     Routing,
 };
 use snarkos_node_sync::{BlockSync, BlockSyncMode};
 use snarkos_node_tcp::{
     protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
+    InboundLimits,
+    IpFamily,
+    OutboundLimits,
     P2P,
 };
 use snarkvm::{
@@ -83,6 +87,9 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         genesis: Block<N>,
         cdn: Option<String>,
         storage_mode: StorageMode,
+        preferred_ip_family: Option<IpFamily>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
@@ -115,11 +122,14 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         let router = Router::new(
             node_ip,
             NodeType::Client,
-            account,
+            account.into(),
             trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             allow_external_peers,
-            matches!(storage_mode, StorageMode::Development(_)),
+            storage_mode.clone(),
+            preferred_ip_family,
+            inbound_limits,
+            outbound_limits,
         )
         .await?;
         // Initialize the node.
@@ -136,7 +146,10 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, rest_rps, None, ledger.clone(), Arc::new(node.clone())).await?);
+            node.rest = Some(
+                Rest::start(rest_ip, rest_rps, None, ledger.clone(), Arc::new(node.clone()), (*node.sync).clone())
+                    .await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -155,6 +168,11 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
         &self.ledger
     }
 
+    /// Returns the block sync module.
+    pub fn block_sync(&self) -> &BlockSync<N> {
+        &self.sync
+    }
+
     /// Returns the REST server.
     pub fn rest(&self) -> &Option<Rest<N, C, Self>> {
         &self.rest
@@ -164,6 +182,12 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     /// Initializes the sync pool.
     fn initialize_sync(&self) {
+        // Penalize the peers that do not respond to their block requests in time.
+        let router = self.router.clone();
+        self.sync.set_timeout_handler(move |peer_ip| {
+            router.update_peer_score(peer_ip, PeerEvent::BlockRequestTimeout);
+        });
+
         // Start the sync loop.
         let node = self.clone();
         self.handles.lock().push(tokio::spawn(async move {
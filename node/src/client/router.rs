@@ -25,9 +25,10 @@ use snarkos_node_router::{
         PuzzleResponse,
         UnconfirmedTransaction,
     },
+    PeerEvent,
     Routing,
 };
-use snarkos_node_tcp::{Connection, ConnectionSide, Tcp};
+use snarkos_node_tcp::{Connection, ConnectionSide, RateLimit, RateLimitViolation, Tcp, CONTEXT_TARGET};
 use snarkvm::{
     ledger::narwhal::Data,
     prelude::{block::Transaction, Network},
@@ -35,6 +36,7 @@ use snarkvm::{
 
 use snarkos_node_sync::communication_service::CommunicationService;
 use std::{io, net::SocketAddr, time::Duration};
+use tracing::Instrument;
 
 impl<N: Network, C: ConsensusStorage<N>> P2P for Client<N, C> {
     /// Returns a reference to the TCP instance.
@@ -97,8 +99,8 @@ impl<N: Network, C: ConsensusStorage<N>> Writing for Client<N, C> {
 
     /// Creates an [`Encoder`] used to write the outbound messages to the target stream.
     /// The `side` parameter indicates the connection side **from the node's perspective**.
-    fn codec(&self, _addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.router().codec(&addr)
     }
 }
 
@@ -109,23 +111,38 @@ impl<N: Network, C: ConsensusStorage<N>> Reading for Client<N, C> {
 
     /// Creates a [`Decoder`] used to interpret messages from the network.
     /// The `side` param indicates the connection side **from the node's perspective**.
-    fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.router().codec(&peer_addr)
     }
 
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
+        // Process the message within the context of the peer, for structured logging.
+        let peer_ip = self.router().resolve_to_listener(&peer_addr).unwrap_or(peer_addr);
+        let span = info_span!(target: CONTEXT_TARGET, "peer", peer_ip = %peer_ip);
         // Process the message. Disconnect if the peer violated the protocol.
-        if let Err(error) = self.inbound(peer_addr, message).await {
+        if let Err(error) = self.inbound(peer_addr, message).instrument(span).await {
             if let Some(peer_ip) = self.router().resolve_to_listener(&peer_addr) {
                 warn!("Disconnecting from '{peer_ip}' - {error}");
                 Outbound::send(self, peer_ip, Message::Disconnect(DisconnectReason::ProtocolViolation.into()));
-                // Disconnect from this peer.
-                self.router().disconnect(peer_ip);
+                // Penalize the peer for the invalid message, and disconnect unless the penalty already did.
+                if !self.router().update_peer_score(peer_ip, PeerEvent::InvalidMessage) {
+                    self.router().disconnect(peer_ip);
+                }
             }
         }
         Ok(())
     }
+
+    /// Returns the name of the type of the given inbound message and its per-peer rate limit, if the type is limited.
+    fn message_limit(&self, message: &Self::Message) -> Option<(&'static str, RateLimit)> {
+        self.router().message_limit(message)
+    }
+
+    /// Penalizes the peer at the given address for exceeding a rate limit.
+    fn rate_limit_exceeded(&self, peer_addr: SocketAddr, violation: RateLimitViolation) {
+        self.router().rate_limit_exceeded(peer_addr, violation)
+    }
 }
 
 #[async_trait]
@@ -186,9 +203,14 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Client<N, C> {
 
     /// Handles a `BlockResponse` message.
     fn block_response(&self, peer_ip: SocketAddr, blocks: Vec<Block<N>>) -> bool {
+        let num_blocks = blocks.len();
         // Tries to advance with blocks from the sync module.
         match self.sync.advance_with_sync_blocks(peer_ip, blocks) {
-            Ok(()) => true,
+            Ok(()) => {
+                // Reward the peer for the useful blocks.
+                self.router.update_peer_score(peer_ip, PeerEvent::UsefulBlocks(num_blocks));
+                true
+            }
             Err(error) => {
                 warn!("{error}");
                 false
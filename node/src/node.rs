@@ -12,13 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{traits::NodeInterface, Client, Prover, Validator};
-use snarkos_account::Account;
-use snarkos_node_router::messages::NodeType;
+use crate::{traits::NodeInterface, Client, PoolConfig, Prover, Validator};
+use snarkos_account::{Account, Signer};
+use snarkos_node_consensus::{Consensus, MemoryPoolConfig};
+use snarkos_node_router::{messages::NodeType, Outbound, Router};
+use snarkos_node_sync::BlockSync;
+use snarkos_node_tcp::{InboundLimits, IpFamily, OutboundLimits};
 use snarkvm::prelude::{
     block::Block,
     store::helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
     Address,
+    Ledger,
     Network,
     PrivateKey,
     ViewKey,
@@ -44,7 +48,7 @@ impl<N: Network> Node<N> {
         bft_ip: Option<SocketAddr>,
         rest_ip: Option<SocketAddr>,
         rest_rps: u32,
-        account: Account<N>,
+        signer: Signer<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
         genesis: Block<N>,
@@ -52,6 +56,10 @@ impl<N: Network> Node<N> {
         storage_mode: StorageMode,
         allow_external_peers: bool,
         dev_txs: bool,
+        memory_pool: MemoryPoolConfig,
+        preferred_ip_family: Option<IpFamily>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
         Ok(Self::Validator(Arc::new(
             Validator::new(
@@ -59,7 +67,7 @@ impl<N: Network> Node<N> {
                 bft_ip,
                 rest_ip,
                 rest_rps,
-                account,
+                signer,
                 trusted_peers,
                 trusted_validators,
                 genesis,
@@ -67,6 +75,10 @@ impl<N: Network> Node<N> {
                 storage_mode,
                 allow_external_peers,
                 dev_txs,
+                memory_pool,
+                preferred_ip_family,
+                inbound_limits,
+                outbound_limits,
             )
             .await?,
         )))
@@ -75,12 +87,33 @@ impl<N: Network> Node<N> {
     /// Initializes a new prover node.
     pub async fn new_prover(
         node_ip: SocketAddr,
+        rest_ip: Option<SocketAddr>,
+        rest_rps: u32,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         storage_mode: StorageMode,
+        pool: Option<PoolConfig>,
+        preferred_ip_family: Option<IpFamily>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
-        Ok(Self::Prover(Arc::new(Prover::new(node_ip, account, trusted_peers, genesis, storage_mode).await?)))
+        Ok(Self::Prover(Arc::new(
+            Prover::new(
+                node_ip,
+                rest_ip,
+                rest_rps,
+                account,
+                trusted_peers,
+                genesis,
+                storage_mode,
+                pool,
+                preferred_ip_family,
+                inbound_limits,
+                outbound_limits,
+            )
+            .await?,
+        )))
     }
 
     /// Initializes a new client node.
@@ -93,9 +126,25 @@ impl<N: Network> Node<N> {
         genesis: Block<N>,
         cdn: Option<String>,
         storage_mode: StorageMode,
+        preferred_ip_family: Option<IpFamily>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
         Ok(Self::Client(Arc::new(
-            Client::new(node_ip, rest_ip, rest_rps, account, trusted_peers, genesis, cdn, storage_mode).await?,
+            Client::new(
+                node_ip,
+                rest_ip,
+                rest_rps,
+                account,
+                trusted_peers,
+                genesis,
+                cdn,
+                storage_mode,
+                preferred_ip_family,
+                inbound_limits,
+                outbound_limits,
+            )
+            .await?,
         )))
     }
 
@@ -108,8 +157,8 @@ impl<N: Network> Node<N> {
         }
     }
 
-    /// Returns the account private key of the node.
-    pub fn private_key(&self) -> &PrivateKey<N> {
+    /// Returns the account private key of the node, unless it is held by an external signer process.
+    pub fn private_key(&self) -> Option<&PrivateKey<N>> {
         match self {
             Self::Validator(node) => node.private_key(),
             Self::Prover(node) => node.private_key(),
@@ -117,8 +166,8 @@ impl<N: Network> Node<N> {
         }
     }
 
-    /// Returns the account view key of the node.
-    pub fn view_key(&self) -> &ViewKey<N> {
+    /// Returns the account view key of the node, unless it is held by an external signer process.
+    pub fn view_key(&self) -> Option<&ViewKey<N>> {
         match self {
             Self::Validator(node) => node.view_key(),
             Self::Prover(node) => node.view_key(),
@@ -143,4 +192,39 @@ impl<N: Network> Node<N> {
             Self::Client(node) => node.is_dev(),
         }
     }
+
+    /// Returns the router of the node.
+    pub fn router(&self) -> &Router<N> {
+        match self {
+            Self::Validator(node) => node.router(),
+            Self::Prover(node) => node.router(),
+            Self::Client(node) => node.router(),
+        }
+    }
+
+    /// Returns the block sync module of the node.
+    pub fn block_sync(&self) -> &BlockSync<N> {
+        match self {
+            Self::Validator(node) => node.block_sync(),
+            Self::Prover(node) => node.block_sync(),
+            Self::Client(node) => node.block_sync(),
+        }
+    }
+
+    /// Returns the ledger of the node, if the node maintains one.
+    pub fn ledger(&self) -> Option<&Ledger<N, ConsensusDB<N>>> {
+        match self {
+            Self::Validator(node) => Some(node.ledger()),
+            Self::Prover(_) => None,
+            Self::Client(node) => Some(node.ledger()),
+        }
+    }
+
+    /// Returns the consensus module of the node, if the node is a validator.
+    pub fn consensus(&self) -> Option<&Consensus<N>> {
+        match self {
+            Self::Validator(node) => Some(node.consensus()),
+            Self::Prover(_) | Self::Client(_) => None,
+        }
+    }
 }
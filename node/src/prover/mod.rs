@@ -12,8 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod pool;
+pub use pool::*;
+
+mod rest;
+
 mod router;
 
+mod stats;
+pub use stats::*;
+
 use crate::traits::NodeInterface;
 use snarkos_account::Account;
 use snarkos_node_bft::ledger_service::ProverLedgerService;
@@ -22,12 +30,16 @@ use snarkos_node_router::{
     Heartbeat,
     Inbound,
     Outbound,
+    PeerEvent,
     Router,
     Routing,
 };
 use snarkos_node_sync::{BlockSync, BlockSyncMode};
 use snarkos_node_tcp::{
     protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
+    InboundLimits,
+    IpFamily,
+    OutboundLimits,
     P2P,
 };
 use snarkvm::{
@@ -55,7 +67,7 @@ use std::{
         Arc,
     },
 };
-use tokio::task::JoinHandle;
+use tokio::{sync::mpsc, task::JoinHandle};
 
 /// A prover is a light node, capable of producing proofs for consensus.
 #[derive(Clone)]
@@ -76,6 +88,10 @@ pub struct Prover<N: Network, C: ConsensusStorage<N>> {
     puzzle_instances: Arc<AtomicU8>,
     /// The maximum number of puzzle instances.
     max_puzzle_instances: u8,
+    /// The pool coordinator, if the prover distributes work to pool workers.
+    pool: Option<PoolCoordinator<N>>,
+    /// The proving statistics.
+    stats: Arc<ProverStats>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The shutdown signal.
@@ -88,10 +104,16 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
     /// Initializes a new prover node.
     pub async fn new(
         node_ip: SocketAddr,
+        rest_ip: Option<SocketAddr>,
+        rest_rps: u32,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         storage_mode: StorageMode,
+        pool: Option<PoolConfig>,
+        preferred_ip_family: Option<IpFamily>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
@@ -105,16 +127,30 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         let sync = BlockSync::new(BlockSyncMode::Router, ledger_service.clone());
         // Determine if the prover should allow external peers.
         let allow_external_peers = true;
+        // Initialize the puzzle.
+        let puzzle = VM::<N, C>::new_puzzle()?;
+
+        // Initialize the pool coordinator.
+        let (pool, pool_solutions) = match pool {
+            Some(config) => {
+                let (pool, pool_solutions) = PoolCoordinator::start(config, account.address(), puzzle.clone()).await?;
+                (Some(pool), Some(pool_solutions))
+            }
+            None => (None, None),
+        };
 
         // Initialize the node router.
         let router = Router::new(
             node_ip,
             NodeType::Prover,
-            account,
+            account.into(),
             trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             allow_external_peers,
-            matches!(storage_mode, StorageMode::Development(_)),
+            storage_mode,
+            preferred_ip_family,
+            inbound_limits,
+            outbound_limits,
         )
         .await?;
         // Compute the maximum number of puzzle instances.
@@ -124,19 +160,36 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
             router,
             sync: Arc::new(sync),
             genesis,
-            puzzle: VM::<N, C>::new_puzzle()?,
+            puzzle,
             latest_epoch_hash: Default::default(),
             latest_block_header: Default::default(),
             puzzle_instances: Default::default(),
             max_puzzle_instances: u8::try_from(max_puzzle_instances)?,
+            pool,
+            stats: Default::default(),
             handles: Default::default(),
             shutdown,
             _phantom: Default::default(),
         };
+        // Penalize the peers that do not respond to their block requests in time.
+        let router = node.router.clone();
+        node.sync.set_timeout_handler(move |peer_ip| {
+            router.update_peer_score(peer_ip, PeerEvent::BlockRequestTimeout);
+        });
         // Initialize the routing.
         node.initialize_routing().await;
         // Initialize the puzzle.
         node.initialize_puzzle().await;
+        // Initialize the broadcasting of the pool solutions.
+        if let Some(pool_solutions) = pool_solutions {
+            node.initialize_pool_solutions(pool_solutions);
+        }
+        // Initialize the sampling of the proving statistics.
+        node.initialize_stats();
+        // Initialize the REST server.
+        if let Some(rest_ip) = rest_ip {
+            node.initialize_rest(rest_ip, rest_rps).await?;
+        }
         // Initialize the notification message loop.
         node.handles.lock().push(crate::start_notification_message_loop());
         // Pass the node to the signal handler.
@@ -156,6 +209,12 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Prover<N, C> {
         debug!("Shutting down the puzzle...");
         self.shutdown.store(true, Ordering::Relaxed);
 
+        // Shut down the pool coordinator.
+        if let Some(pool) = &self.pool {
+            debug!("Shutting down the pool coordinator...");
+            pool.shut_down();
+        }
+
         // Abort the tasks.
         debug!("Shutting down the prover...");
         self.handles.lock().iter().for_each(|handle| handle.abort());
@@ -168,6 +227,21 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Prover<N, C> {
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
+    /// Returns the block sync module.
+    pub fn block_sync(&self) -> &BlockSync<N> {
+        &self.sync
+    }
+
+    /// Returns a snapshot of the proving statistics.
+    pub fn stats(&self) -> ProverStatsSnapshot {
+        self.stats.snapshot()
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
+    /// The interval at which the proving statistics are sampled.
+    const STATS_INTERVAL_IN_SECS: u64 = 10;
+
     /// Initialize a new instance of the puzzle.
     async fn initialize_puzzle(&self) {
         for _ in 0..self.max_puzzle_instances {
@@ -232,6 +306,53 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         }
     }
 
+    /// Broadcasts the solutions found by the pool workers.
+    fn initialize_pool_solutions(&self, mut pool_solutions: mpsc::UnboundedReceiver<Solution<N>>) {
+        let prover = self.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            while let Some(solution) = pool_solutions.recv().await {
+                // Broadcast the solution.
+                prover.broadcast_solution(solution);
+            }
+        }));
+    }
+
+    /// Samples the proving statistics periodically, and updates the metrics.
+    fn initialize_stats(&self) {
+        let prover = self.clone();
+        self.handles.lock().push(tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(Self::STATS_INTERVAL_IN_SECS)).await;
+                prover.stats.sample_attempt_rate();
+                let stats = prover.stats.snapshot();
+                debug!(
+                    "Proving at {:.2} attempts/sec ({} solutions, best proof target {})",
+                    stats.attempts_per_sec, stats.total_solutions, stats.best_proof_target
+                );
+                #[cfg(feature = "metrics")]
+                {
+                    ProverStats::update_metrics(&stats);
+                    metrics::gauge(metrics::prover::PUZZLE_INSTANCES, prover.num_puzzle_instances() as f64);
+                    if let Some(pool) = &prover.pool {
+                        for (worker, shares) in pool.shares() {
+                            for (status, count) in [
+                                ("accepted", shares.accepted),
+                                ("rejected", shares.rejected),
+                                ("stale", shares.stale),
+                                ("solution", shares.solutions),
+                            ] {
+                                metrics::absolute_counter_with_labels(metrics::prover::POOL_SHARES, count, &[
+                                    ("worker", worker.clone()),
+                                    ("status", status.to_string()),
+                                ]);
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+    }
+
     /// Performs one iteration of the puzzle.
     fn puzzle_iteration<R: Rng + CryptoRng>(
         &self,
@@ -249,11 +370,13 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
             format!("(Coinbase Target {coinbase_target}, Proof Target {proof_target})").dimmed()
         );
 
-        // Compute the solution.
-        let result =
-            self.puzzle.prove(epoch_hash, self.address(), rng.gen(), Some(proof_target)).ok().and_then(|solution| {
-                self.puzzle.get_proof_target(&solution).ok().map(|solution_target| (solution_target, solution))
-            });
+        // Compute the solution, and record the attempt.
+        let result = self.puzzle.prove(epoch_hash, self.address(), rng.gen(), None).ok().and_then(|solution| {
+            let solution_target = solution.target();
+            self.stats.record_attempt(solution_target);
+            // Ensure the solution meets the proof target.
+            (solution_target >= proof_target).then_some((solution_target, solution))
+        });
 
         // Decrement the puzzle instances.
         self.decrement_puzzle_instances();
@@ -263,6 +386,9 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
 
     /// Broadcasts the solution to the network.
     fn broadcast_solution(&self, solution: Solution<N>) {
+        // Record the solution in the epoch of the latest block.
+        let block_height = self.latest_block_header.read().as_ref().map(|header| header.height()).unwrap_or(0);
+        self.stats.record_solution(block_height / N::NUM_BLOCKS_PER_EPOCH);
         // Prepare the unconfirmed solution message.
         let message = Message::UnconfirmedSolution(UnconfirmedSolution {
             solution_id: solution.id(),
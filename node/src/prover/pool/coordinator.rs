@@ -0,0 +1,515 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{subscription_proof, PoolJob, PoolMessage, MAX_POOL_MESSAGE_SIZE};
+use snarkvm::prelude::{
+    block::Header,
+    puzzle::{Puzzle, Solution, SolutionID},
+    Address,
+    Network,
+};
+
+use anyhow::{bail, ensure, Result};
+use futures_util::{SinkExt, StreamExt};
+use indexmap::{IndexMap, IndexSet};
+use parking_lot::{Mutex, RwLock};
+use rand::{rngs::OsRng, Rng};
+use serde::Serialize;
+use std::{net::SocketAddr, path::Path, sync::Arc};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, watch},
+    task::JoinHandle,
+};
+use tokio_util::codec::{FramedRead, FramedWrite, LinesCodec};
+
+/// The factor by which the share target is lower than the proof target.
+const SHARE_TARGET_DIVISOR: u64 = 4;
+/// The maximum length of a worker name.
+const MAX_WORKER_NAME_LENGTH: usize = 64;
+/// The minimum length of a worker secret.
+const MIN_WORKER_SECRET_LENGTH: usize = 16;
+
+/// The configuration of a pool coordinator.
+#[derive(Clone, Debug)]
+pub struct PoolConfig {
+    /// The IP address and port to hand out work to pool workers on.
+    pub listener_ip: SocketAddr,
+    /// The secret of each allowed worker, by worker name.
+    pub workers: IndexMap<String, String>,
+}
+
+impl PoolConfig {
+    /// Loads the pool configuration, given the path to a file of allowed workers,
+    /// which contains one `<name> <secret>` pair per line (blank lines and `#` comments are ignored).
+    pub fn load(listener_ip: SocketAddr, workers_path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(workers_path)?;
+        let mut workers = IndexMap::new();
+        for line in contents.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let Some((name, secret)) = line.split_once(char::is_whitespace) else {
+                bail!("Invalid pool worker entry (expected '<name> <secret>')");
+            };
+            let secret = secret.trim();
+            ensure!(name.len() <= MAX_WORKER_NAME_LENGTH, "Invalid name for pool worker '{name}'");
+            ensure!(secret.len() >= MIN_WORKER_SECRET_LENGTH, "The secret of pool worker '{name}' is too short");
+            ensure!(workers.insert(name.to_string(), secret.to_string()).is_none(), "Duplicate pool worker '{name}'");
+        }
+        ensure!(!workers.is_empty(), "No pool workers are allowed in '{}'", workers_path.display());
+        Ok(Self { listener_ip, workers })
+    }
+}
+
+/// The share accounting of a pool worker.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct WorkerShares {
+    /// The number of submissions that met the share target.
+    pub accepted: u64,
+    /// The number of submissions that were invalid, duplicated, or below the share target.
+    pub rejected: u64,
+    /// The number of submissions for a previous epoch.
+    pub stale: u64,
+    /// The number of accepted submissions that also met the proof target, and were broadcast.
+    pub solutions: u64,
+}
+
+/// The outcome of a submission to the pool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Submission {
+    /// The solution met the share target.
+    Share,
+    /// The solution met the proof target.
+    Solution,
+    /// The solution is for a previous epoch.
+    Stale,
+    /// The solution was rejected, for the given reason.
+    Rejected(String),
+}
+
+/// A pool coordinator, which hands out work to pool workers over TCP, and validates their solutions.
+///
+/// The workers authenticate with a challenge-response on their shared secret, and only the allowed workers
+/// are served and credited with shares. Note that the connection itself is not encrypted.
+#[derive(Clone)]
+pub struct PoolCoordinator<N: Network> {
+    /// The address that solutions must be produced for.
+    address: Address<N>,
+    /// The puzzle.
+    puzzle: Puzzle<N>,
+    /// The secret of each allowed worker, by worker name.
+    workers: Arc<IndexMap<String, String>>,
+    /// The sender of the latest job.
+    job: Arc<watch::Sender<Option<PoolJob<N>>>>,
+    /// The IDs of the solutions submitted for the current epoch.
+    submitted: Arc<Mutex<IndexSet<SolutionID<N>>>>,
+    /// The share accounting, for each allowed worker.
+    shares: Arc<RwLock<IndexMap<String, WorkerShares>>>,
+    /// The sender of the solutions that met the proof target.
+    solution_sender: mpsc::UnboundedSender<Solution<N>>,
+    /// The IP address and port of the listener.
+    listener_ip: SocketAddr,
+    /// The spawned handles.
+    handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+}
+
+impl<N: Network> PoolCoordinator<N> {
+    /// Starts a pool coordinator with the given configuration, and returns it along with a receiver
+    /// of the worker solutions that met the proof target, which should be broadcast to the network.
+    pub async fn start(
+        config: PoolConfig,
+        address: Address<N>,
+        puzzle: Puzzle<N>,
+    ) -> Result<(Self, mpsc::UnboundedReceiver<Solution<N>>)> {
+        // Bind the listener.
+        let listener = TcpListener::bind(config.listener_ip).await?;
+        let listener_ip = listener.local_addr()?;
+        info!("Listening for {} pool worker(s) on '{listener_ip}'", config.workers.len());
+
+        // Initialize the share accounting of the allowed workers, which are the only ones ever credited.
+        let shares = config.workers.keys().map(|worker| (worker.clone(), WorkerShares::default())).collect();
+
+        let (solution_sender, solution_receiver) = mpsc::unbounded_channel();
+        let coordinator = Self {
+            address,
+            puzzle,
+            workers: Arc::new(config.workers),
+            job: Arc::new(watch::channel(None).0),
+            submitted: Default::default(),
+            shares: Arc::new(RwLock::new(shares)),
+            solution_sender,
+            listener_ip,
+            handles: Default::default(),
+        };
+
+        // Accept the pool workers.
+        let coordinator_ = coordinator.clone();
+        coordinator.handles.lock().push(tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, peer_addr)) => {
+                        let coordinator = coordinator_.clone();
+                        let handle = tokio::spawn(async move {
+                            if let Err(error) = coordinator.handle_worker(stream).await {
+                                warn!("Pool worker '{peer_addr}' disconnected - {error}");
+                            }
+                        });
+                        // Track the worker task, so that it is aborted on shutdown, and forget the finished ones.
+                        let mut handles = coordinator_.handles.lock();
+                        handles.retain(|handle| !handle.is_finished());
+                        handles.push(handle);
+                    }
+                    Err(error) => warn!("Failed to accept a pool worker - {error}"),
+                }
+            }
+        }));
+
+        Ok((coordinator, solution_receiver))
+    }
+
+    /// Returns the IP address and port of the listener.
+    pub const fn listener_ip(&self) -> SocketAddr {
+        self.listener_ip
+    }
+
+    /// Returns the share accounting, for each allowed worker.
+    pub fn shares(&self) -> IndexMap<String, WorkerShares> {
+        self.shares.read().clone()
+    }
+
+    /// Updates the job for the workers, given the latest epoch hash and block header.
+    pub fn update_job(&self, epoch_hash: N::BlockHash, header: &Header<N>) {
+        let proof_target = header.proof_target();
+        let job = PoolJob {
+            epoch_hash,
+            address: self.address,
+            proof_target,
+            share_target: (proof_target / SHARE_TARGET_DIVISOR).max(1),
+        };
+        self.job.send_if_modified(|current| {
+            // Skip the update if the job is unchanged.
+            if current.as_ref() == Some(&job) {
+                return false;
+            }
+            // If the epoch changed, clear the submitted solutions.
+            if current.as_ref().map(|current| current.epoch_hash) != Some(epoch_hash) {
+                self.submitted.lock().clear();
+            }
+            *current = Some(job);
+            true
+        });
+    }
+
+    /// Shuts down the pool coordinator.
+    pub fn shut_down(&self) {
+        self.handles.lock().iter().for_each(|handle| handle.abort());
+    }
+}
+
+impl<N: Network> PoolCoordinator<N> {
+    /// Serves a pool worker, until it disconnects.
+    async fn handle_worker(&self, stream: TcpStream) -> Result<()> {
+        let (reader, writer) = stream.into_split();
+        let mut reader = FramedRead::new(reader, LinesCodec::new_with_max_length(MAX_POOL_MESSAGE_SIZE));
+        let mut writer = FramedWrite::new(writer, LinesCodec::new());
+
+        // Challenge the worker to prove that it holds the secret of an allowed worker.
+        let nonce = hex::encode(OsRng.gen::<[u8; 32]>());
+        writer.send(PoolMessage::<N>::Challenge { nonce: nonce.clone() }.to_line()?).await?;
+
+        // Ensure the worker subscribes first.
+        let (worker, proof) = match reader.next().await {
+            Some(line) => match PoolMessage::<N>::from_line(&line?)? {
+                PoolMessage::Subscribe { worker, proof } => (worker, proof),
+                message => bail!("Expected a 'Subscribe' message, found '{}'", message.name()),
+            },
+            None => return Ok(()),
+        };
+        // Ensure the worker is allowed, and authenticated.
+        let Some(secret) = self.workers.get(&worker) else {
+            bail!("Unknown pool worker '{}'", worker.chars().take(MAX_WORKER_NAME_LENGTH).collect::<String>());
+        };
+        ensure!(proof == subscription_proof(&nonce, secret), "Failed to authenticate pool worker '{worker}'");
+        info!("Pool worker '{worker}' subscribed");
+
+        // Send the latest job, and then every new job.
+        let mut job_receiver = self.job.subscribe();
+        let job = job_receiver.borrow_and_update().clone();
+        if let Some(job) = job {
+            writer.send(PoolMessage::Job(job).to_line()?).await?;
+        }
+
+        loop {
+            tokio::select! {
+                result = job_receiver.changed() => {
+                    result?;
+                    let job = job_receiver.borrow_and_update().clone();
+                    if let Some(job) = job {
+                        writer.send(PoolMessage::Job(job).to_line()?).await?;
+                    }
+                }
+                line = reader.next() => {
+                    let Some(line) = line else {
+                        debug!("Pool worker '{worker}' disconnected");
+                        return Ok(());
+                    };
+                    let solution = match PoolMessage::<N>::from_line(&line?)? {
+                        PoolMessage::Submit { solution } => solution,
+                        message => bail!("Expected a 'Submit' message, found '{}'", message.name()),
+                    };
+                    let solution_id = solution.id();
+                    let submission = self.process_submission(&worker, solution).await;
+                    let (accepted, reason) = match submission {
+                        Submission::Share | Submission::Solution => (true, None),
+                        Submission::Stale => (false, Some("stale".to_string())),
+                        Submission::Rejected(reason) => (false, Some(reason)),
+                    };
+                    writer.send(PoolMessage::<N>::Result { solution_id, accepted, reason }.to_line()?).await?;
+                }
+            }
+        }
+    }
+
+    /// Validates a solution from the given (authenticated) worker, updates its share accounting,
+    /// and forwards the solution to be broadcast if it meets the proof target.
+    async fn process_submission(&self, worker: &str, solution: Solution<N>) -> Submission {
+        let submission = self.check_submission(&solution).await;
+        // Update the share accounting.
+        if let Some(shares) = self.shares.write().get_mut(worker) {
+            match &submission {
+                Submission::Share => shares.accepted += 1,
+                Submission::Solution => {
+                    shares.accepted += 1;
+                    shares.solutions += 1;
+                }
+                Submission::Stale => shares.stale += 1,
+                Submission::Rejected(_) => shares.rejected += 1,
+            }
+        }
+        match &submission {
+            Submission::Solution => {
+                info!("Pool worker '{worker}' found a Solution '{}'", solution.id());
+                // Forward the solution to be broadcast.
+                if self.solution_sender.send(solution).is_err() {
+                    error!("Failed to forward the solution from pool worker '{worker}'");
+                }
+            }
+            Submission::Rejected(reason) => debug!("Rejected a submission from pool worker '{worker}' - {reason}"),
+            _ => trace!("Accepted a share from pool worker '{worker}'"),
+        }
+        submission
+    }
+
+    /// Checks a solution against the current job.
+    async fn check_submission(&self, solution: &Solution<N>) -> Submission {
+        let job = self.job.borrow().clone();
+        let Some(job) = job else {
+            return Submission::Rejected("no job available".to_string());
+        };
+        // Ensure the solution is for the current epoch.
+        if solution.epoch_hash() != job.epoch_hash {
+            return Submission::Stale;
+        }
+        // Ensure the solution is for the pool address.
+        if solution.address() != job.address {
+            return Submission::Rejected("invalid address".to_string());
+        }
+        // Ensure the solution was not already submitted, before spending time on verifying it.
+        if self.submitted.lock().contains(&solution.id()) {
+            return Submission::Rejected("duplicate solution".to_string());
+        }
+        // Compute the proof target of the solution.
+        let puzzle = self.puzzle.clone();
+        let solution_ = *solution;
+        let proof_target = match tokio::task::spawn_blocking(move || puzzle.get_proof_target(&solution_)).await {
+            Ok(Ok(proof_target)) => proof_target,
+            Ok(Err(error)) => return Submission::Rejected(format!("invalid solution - {error}")),
+            Err(error) => return Submission::Rejected(format!("failed to verify the solution - {error}")),
+        };
+        // Ensure the solution meets the share target.
+        if proof_target < job.share_target {
+            return Submission::Rejected("below the share target".to_string());
+        }
+        // Record the solution as submitted, now that it is verified, unless it was credited in the meantime.
+        if !self.submitted.lock().insert(solution.id()) {
+            return Submission::Rejected("duplicate solution".to_string());
+        }
+        match proof_target {
+            target if target >= job.proof_target => Submission::Solution,
+            target if target >= job.share_target => Submission::Share,
+            _ => Submission::Rejected("below the share target".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        prelude::{block::Block, store::helpers::memory::ConsensusMemory, FromBytes, MainnetV0, PrivateKey, TestRng},
+        synthesizer::VM,
+    };
+
+    type CurrentNetwork = MainnetV0;
+
+    /// The secret of the test worker.
+    const SECRET: &str = "a-sufficiently-long-secret";
+
+    /// Starts a coordinator, which allows a single worker named 'rig-1'.
+    async fn sample_coordinator(
+        rng: &mut TestRng,
+    ) -> (PoolCoordinator<CurrentNetwork>, mpsc::UnboundedReceiver<Solution<CurrentNetwork>>) {
+        let config = PoolConfig {
+            listener_ip: "127.0.0.1:0".parse().unwrap(),
+            workers: [("rig-1".to_string(), SECRET.to_string())].into_iter().collect(),
+        };
+        let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let puzzle = VM::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::new_puzzle().unwrap();
+        PoolCoordinator::start(config, address, puzzle).await.unwrap()
+    }
+
+    #[test]
+    fn test_load_config() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("workers.txt");
+        let listener_ip = "127.0.0.1:4150".parse().unwrap();
+
+        // Load the allowed workers, skipping the blank lines and comments.
+        std::fs::write(&path, format!("# The pool workers.\nrig-1 {SECRET}\n\nrig-2   {SECRET}-2\n")).unwrap();
+        let config = PoolConfig::load(listener_ip, &path).unwrap();
+        assert_eq!(config.listener_ip, listener_ip);
+        assert_eq!(config.workers.len(), 2);
+        assert_eq!(config.workers.get("rig-1").unwrap(), SECRET);
+        assert_eq!(config.workers.get("rig-2").unwrap(), &format!("{SECRET}-2"));
+
+        // Reject the invalid files.
+        for contents in
+            ["", "# No workers.\n", "rig-1\n", "rig-1 short\n", format!("rig-1 {SECRET}\nrig-1 {SECRET}\n").as_str()]
+        {
+            std::fs::write(&path, contents).unwrap();
+            assert!(PoolConfig::load(listener_ip, &path).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authentication() {
+        let rng = &mut TestRng::default();
+        let (coordinator, _solutions) = sample_coordinator(rng).await;
+
+        // Connects to the coordinator, and answers its challenge with the given secret.
+        let coordinator = &coordinator;
+        let subscribe = move |worker: &'static str, secret: &'static str| async move {
+            let stream = TcpStream::connect(coordinator.listener_ip()).await.unwrap();
+            let (reader, writer) = stream.into_split();
+            let mut reader = FramedRead::new(reader, LinesCodec::new());
+            let mut writer = FramedWrite::new(writer, LinesCodec::new());
+            let nonce = match PoolMessage::<CurrentNetwork>::from_line(&reader.next().await.unwrap().unwrap()) {
+                Ok(PoolMessage::Challenge { nonce }) => nonce,
+                message => panic!("Expected a challenge, found {message:?}"),
+            };
+            let proof = subscription_proof(&nonce, secret);
+            let message = PoolMessage::<CurrentNetwork>::Subscribe { worker: worker.to_string(), proof };
+            writer.send(message.to_line().unwrap()).await.unwrap();
+            // Set a job, so that an authenticated worker receives it.
+            let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+            coordinator.update_job(Default::default(), genesis.header());
+            // Return the next message, if the connection was not closed.
+            reader.next().await.and_then(|line| line.ok())
+        };
+
+        // Ensure an allowed worker with the right secret is served.
+        let line = subscribe("rig-1", SECRET).await.unwrap();
+        assert!(matches!(PoolMessage::<CurrentNetwork>::from_line(&line).unwrap(), PoolMessage::Job(..)));
+        // Ensure a worker with the wrong secret, or which is not allowed, is disconnected.
+        assert!(subscribe("rig-1", "the-wrong-secret-entirely").await.is_none());
+        assert!(subscribe("rig-2", SECRET).await.is_none());
+
+        // Ensure only the allowed worker is accounted for.
+        assert_eq!(coordinator.shares().keys().collect::<Vec<_>>(), vec!["rig-1"]);
+        coordinator.shut_down();
+    }
+
+    #[tokio::test]
+    async fn test_accepted_shares() {
+        let rng = &mut TestRng::default();
+        let (coordinator, mut solutions) = sample_coordinator(rng).await;
+        coordinator.shut_down();
+
+        // Set a job for which every solution is a share, but not a solution.
+        let epoch_hash = Default::default();
+        let job = PoolJob { epoch_hash, address: coordinator.address, proof_target: u64::MAX, share_target: 1 };
+        coordinator.job.send_replace(Some(job.clone()));
+
+        // Credit a share.
+        let solution = coordinator.puzzle.prove(epoch_hash, coordinator.address, 0, None).unwrap();
+        assert_eq!(coordinator.process_submission("rig-1", solution).await, Submission::Share);
+        assert_eq!(coordinator.shares().get("rig-1"), Some(&WorkerShares { accepted: 1, ..Default::default() }));
+        assert!(coordinator.submitted.lock().contains(&solution.id()));
+        assert!(solutions.try_recv().is_err());
+
+        // Ensure the same share is not credited twice.
+        let submission = coordinator.process_submission("rig-1", solution).await;
+        assert_eq!(submission, Submission::Rejected("duplicate solution".to_string()));
+        assert_eq!(
+            coordinator.shares().get("rig-1"),
+            Some(&WorkerShares { accepted: 1, rejected: 1, ..Default::default() })
+        );
+
+        // Set a job for which every solution is a solution.
+        coordinator.job.send_replace(Some(PoolJob { proof_target: 1, ..job }));
+
+        // Credit a solution, and forward it to be broadcast.
+        let solution = coordinator.puzzle.prove(epoch_hash, coordinator.address, 1, None).unwrap();
+        assert_eq!(coordinator.process_submission("rig-1", solution).await, Submission::Solution);
+        let expected = WorkerShares { accepted: 2, rejected: 1, stale: 0, solutions: 1 };
+        assert_eq!(coordinator.shares().get("rig-1"), Some(&expected));
+        assert_eq!(solutions.try_recv().unwrap(), solution);
+
+        // Ensure the submissions of an unknown worker are not accounted for.
+        let solution = coordinator.puzzle.prove(epoch_hash, coordinator.address, 2, None).unwrap();
+        assert_eq!(coordinator.process_submission("rig-2", solution).await, Submission::Solution);
+        assert_eq!(coordinator.shares().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_job_and_submission() {
+        let rng = &mut TestRng::default();
+
+        // Start the coordinator.
+        let (coordinator, _solutions) = sample_coordinator(rng).await;
+        let address = coordinator.address;
+        coordinator.shut_down();
+
+        // Update the job.
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        let epoch_hash = Default::default();
+        coordinator.update_job(epoch_hash, genesis.header());
+        let job = coordinator.job.borrow().clone().unwrap();
+        assert_eq!(job.address, address);
+        assert_eq!(job.proof_target, genesis.header().proof_target());
+        assert!(job.share_target <= job.proof_target);
+
+        // Reject a solution that was not produced for the pool address.
+        let other_address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let solution = coordinator.puzzle.prove(epoch_hash, other_address, 0, None).unwrap();
+        let submission = coordinator.process_submission("rig-1", solution).await;
+        assert_eq!(submission, Submission::Rejected("invalid address".to_string()));
+        assert_eq!(coordinator.shares().get("rig-1"), Some(&WorkerShares { rejected: 1, ..Default::default() }));
+        assert!(coordinator.submitted.lock().is_empty());
+
+        // Count a solution for a previous epoch as stale.
+        let solution = coordinator.puzzle.prove(genesis.hash(), address, 0, None).unwrap();
+        let submission = coordinator.process_submission("rig-1", solution).await;
+        assert_eq!(submission, Submission::Stale);
+        assert_eq!(coordinator.shares().get("rig-1").unwrap().stale, 1);
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{
+    puzzle::{Solution, SolutionID},
+    Address,
+    Network,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The unit of work handed out by the pool coordinator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PoolJob<N: Network> {
+    /// The epoch hash to prove against.
+    pub epoch_hash: N::BlockHash,
+    /// The address that solutions must be produced for.
+    pub address: Address<N>,
+    /// The proof target for a solution to be broadcast to the network.
+    pub proof_target: u64,
+    /// The (lower) proof target for a solution to be credited as a share.
+    pub share_target: u64,
+}
+
+/// A message exchanged between the pool coordinator and its workers, encoded as a line of JSON.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", bound = "")]
+pub enum PoolMessage<N: Network> {
+    /// Sent by the coordinator upon connection, with a nonce for the worker to authenticate with.
+    Challenge { nonce: String },
+    /// Sent by a worker to register with the coordinator, with the proof that it holds the worker secret.
+    Subscribe { worker: String, proof: String },
+    /// Sent by the coordinator whenever there is new work.
+    Job(PoolJob<N>),
+    /// Sent by a worker to submit a solution.
+    Submit { solution: Solution<N> },
+    /// Sent by the coordinator in response to a submission.
+    Result { solution_id: SolutionID<N>, accepted: bool, reason: Option<String> },
+}
+
+impl<N: Network> PoolMessage<N> {
+    /// Returns the message name.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Challenge { .. } => "Challenge",
+            Self::Subscribe { .. } => "Subscribe",
+            Self::Job(..) => "Job",
+            Self::Submit { .. } => "Submit",
+            Self::Result { .. } => "Result",
+        }
+    }
+
+    /// Serializes the message as a line of JSON (without the trailing newline).
+    pub fn to_line(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Deserializes the message from a line of JSON.
+    pub fn from_line(line: &str) -> Result<Self> {
+        Ok(serde_json::from_str(line)?)
+    }
+}
+
+/// Returns the proof that a worker holds the given secret, for the given challenge nonce.
+pub fn subscription_proof(nonce: &str, secret: &str) -> String {
+    hex::encode(Sha256::new().chain_update(nonce).chain_update(secret).finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{MainnetV0, PrivateKey, TestRng};
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_pool_message_round_trip() {
+        let rng = &mut TestRng::default();
+
+        let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let messages = [
+            PoolMessage::<CurrentNetwork>::Challenge { nonce: "00ff".to_string() },
+            PoolMessage::Subscribe { worker: "worker-1".to_string(), proof: subscription_proof("00ff", "secret") },
+            PoolMessage::Job(PoolJob { epoch_hash: Default::default(), address, proof_target: 100, share_target: 25 }),
+        ];
+        for message in messages {
+            let line = message.to_line().unwrap();
+            assert!(!line.contains('\n'));
+            assert_eq!(PoolMessage::from_line(&line).unwrap(), message);
+        }
+
+        // Ensure the message type is tagged.
+        let line = PoolMessage::<CurrentNetwork>::Challenge { nonce: "00ff".to_string() }.to_line().unwrap();
+        assert_eq!(line, r#"{"type":"challenge","nonce":"00ff"}"#);
+    }
+
+    #[test]
+    fn test_subscription_proof() {
+        let proof = subscription_proof("00ff", "secret");
+        assert_eq!(proof.len(), 64);
+        assert_eq!(proof, subscription_proof("00ff", "secret"));
+        assert_ne!(proof, subscription_proof("00fe", "secret"));
+        assert_ne!(proof, subscription_proof("00ff", "other secret"));
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{subscription_proof, PoolJob, PoolMessage, MAX_POOL_MESSAGE_SIZE};
+use snarkvm::{
+    prelude::{
+        puzzle::{Puzzle, Solution},
+        store::helpers::memory::ConsensusMemory,
+        Network,
+    },
+    synthesizer::VM,
+};
+
+use anyhow::{bail, Result};
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::RwLock;
+use rand::{rngs::OsRng, Rng};
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{net::TcpStream, sync::mpsc};
+use tokio_util::codec::{FramedRead, FramedWrite, LinesCodec};
+use zeroize::Zeroizing;
+
+/// The number of seconds to wait before reconnecting to the coordinator.
+const RECONNECT_DELAY_IN_SECS: u64 = 5;
+
+/// A pool worker, which proves the jobs handed out by a pool coordinator, and submits its solutions back.
+#[derive(Clone)]
+pub struct PoolWorker<N: Network> {
+    /// The name of the worker.
+    name: String,
+    /// The secret of the worker, shared with the coordinator.
+    secret: Arc<Zeroizing<String>>,
+    /// The IP address and port of the pool coordinator.
+    coordinator_ip: SocketAddr,
+    /// The number of puzzle instances.
+    num_instances: usize,
+    /// The puzzle.
+    puzzle: Puzzle<N>,
+    /// The current job.
+    job: Arc<RwLock<Option<PoolJob<N>>>>,
+    /// The number of accepted submissions.
+    num_accepted: Arc<AtomicU64>,
+    /// The number of rejected submissions.
+    num_rejected: Arc<AtomicU64>,
+}
+
+impl<N: Network> PoolWorker<N> {
+    /// Initializes a new pool worker.
+    pub fn new(
+        name: String,
+        secret: Zeroizing<String>,
+        coordinator_ip: SocketAddr,
+        num_instances: usize,
+    ) -> Result<Self> {
+        Ok(Self {
+            name,
+            secret: Arc::new(secret),
+            coordinator_ip,
+            num_instances: num_instances.max(1),
+            puzzle: VM::<N, ConsensusMemory<N>>::new_puzzle()?,
+            job: Default::default(),
+            num_accepted: Default::default(),
+            num_rejected: Default::default(),
+        })
+    }
+
+    /// Runs the pool worker, reconnecting to the coordinator whenever the connection is lost.
+    pub async fn run(&self) -> Result<()> {
+        // Start the puzzle instances, which submit their solutions through the channel.
+        let (solution_sender, mut solution_receiver) = mpsc::unbounded_channel();
+        for _ in 0..self.num_instances {
+            let worker = self.clone();
+            let solution_sender = solution_sender.clone();
+            tokio::spawn(async move { worker.puzzle_loop(solution_sender).await });
+        }
+
+        loop {
+            if let Err(error) = self.work(&mut solution_receiver).await {
+                warn!("Lost the connection to the pool coordinator '{}' - {error}", self.coordinator_ip);
+            }
+            // Discard the current job, as it may be outdated once reconnected.
+            self.job.write().take();
+            tokio::time::sleep(Duration::from_secs(RECONNECT_DELAY_IN_SECS)).await;
+        }
+    }
+
+    /// Connects to the coordinator, and exchanges jobs and solutions until the connection is lost.
+    async fn work(&self, solution_receiver: &mut mpsc::UnboundedReceiver<Solution<N>>) -> Result<()> {
+        let (reader, writer) = TcpStream::connect(self.coordinator_ip).await?.into_split();
+        let mut reader = FramedRead::new(reader, LinesCodec::new_with_max_length(MAX_POOL_MESSAGE_SIZE));
+        let mut writer = FramedWrite::new(writer, LinesCodec::new());
+
+        // Answer the challenge of the coordinator, and subscribe to it.
+        let nonce = match reader.next().await {
+            Some(line) => match PoolMessage::<N>::from_line(&line?)? {
+                PoolMessage::Challenge { nonce } => nonce,
+                message => bail!("Expected a 'Challenge' message, found '{}'", message.name()),
+            },
+            None => bail!("The connection was closed"),
+        };
+        let proof = subscription_proof(&nonce, &self.secret);
+        writer.send(PoolMessage::<N>::Subscribe { worker: self.name.clone(), proof }.to_line()?).await?;
+        info!("Connected to the pool coordinator '{}' as '{}'", self.coordinator_ip, self.name);
+
+        loop {
+            tokio::select! {
+                Some(solution) = solution_receiver.recv() => {
+                    writer.send(PoolMessage::Submit { solution }.to_line()?).await?;
+                }
+                line = reader.next() => {
+                    let Some(line) = line else {
+                        bail!("The connection was closed");
+                    };
+                    match PoolMessage::<N>::from_line(&line?)? {
+                        PoolMessage::Job(job) => {
+                            let (proof_target, share_target) = (job.proof_target, job.share_target);
+                            debug!("Received a new job (Proof Target {proof_target}, Share Target {share_target})");
+                            self.job.write().replace(job);
+                        }
+                        PoolMessage::Result { solution_id, accepted: true, .. } => {
+                            let num_accepted = self.num_accepted.fetch_add(1, Ordering::Relaxed) + 1;
+                            info!("Share '{solution_id}' was accepted ({num_accepted} accepted)");
+                        }
+                        PoolMessage::Result { solution_id, reason, .. } => {
+                            self.num_rejected.fetch_add(1, Ordering::Relaxed);
+                            warn!("Share '{solution_id}' was rejected - {}", reason.unwrap_or_default());
+                        }
+                        message => bail!("Unexpected '{}' message from the coordinator", message.name()),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Proves the current job, and sends every solution that meets the share target.
+    async fn puzzle_loop(&self, solution_sender: mpsc::UnboundedSender<Solution<N>>) {
+        loop {
+            // Read the current job.
+            let job = self.job.read().clone();
+            let Some(job) = job else {
+                // Sleep for a brief period of time, to await for a job.
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            };
+            // Execute the puzzle.
+            let puzzle = self.puzzle.clone();
+            let result =
+                tokio::task::spawn_blocking(move || puzzle.prove(job.epoch_hash, job.address, OsRng.gen(), None)).await;
+            let solution = match result {
+                Ok(Ok(solution)) => solution,
+                Ok(Err(error)) => {
+                    warn!("Failed to prove the pool job - {error}");
+                    continue;
+                }
+                Err(error) => {
+                    error!("The puzzle instance failed - {error}");
+                    continue;
+                }
+            };
+            // If the solution meets the share target, submit it.
+            if solution.target() >= job.share_target && solution_sender.send(solution).is_err() {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkos_node_rest::{auth_middleware, with_middleware};
+
+use axum::{extract::State, http::StatusCode, middleware, routing::get, Json};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+
+impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
+    /// Starts the REST server of the prover, which serves its proving statistics,
+    /// with the same middleware (and JWT authentication) as the REST server of the other nodes.
+    async fn initialize_rest(&self, rest_ip: SocketAddr, rest_rps: u32) -> Result<()> {
+        let router = axum::Router::new()
+            // All the endpoints before the call to `route_layer` are protected with JWT auth.
+            .route("/mainnet/node/address", get(Self::get_node_address))
+            .route("/mainnet/prover/pool/shares", get(Self::get_pool_shares))
+            .route_layer(middleware::from_fn(auth_middleware))
+            .route("/mainnet/peers/count", get(Self::get_peers_count))
+            .route("/mainnet/prover/stats", get(Self::get_prover_stats))
+            .with_state(self.clone());
+
+        let router = with_middleware(router, rest_rps)
+            // GET health checks (load balancers and orchestrators), which are not rate limited.
+            .route("/health", get(|| async { StatusCode::OK }));
+
+        let rest_listener = TcpListener::bind(rest_ip).await?;
+        self.handles.lock().push(tokio::spawn(async move {
+            axum::serve(rest_listener, router.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .expect("couldn't start rest server");
+        }));
+        Ok(())
+    }
+
+    // GET /mainnet/node/address
+    async fn get_node_address(State(prover): State<Self>) -> Json<Value> {
+        Json(json!(prover.address()))
+    }
+
+    // GET /mainnet/peers/count
+    async fn get_peers_count(State(prover): State<Self>) -> Json<Value> {
+        Json(json!(prover.router.number_of_connected_peers()))
+    }
+
+    // GET /mainnet/prover/stats
+    async fn get_prover_stats(State(prover): State<Self>) -> Json<Value> {
+        Json(json!({
+            "stats": prover.stats.snapshot(),
+            "puzzle_instances": prover.num_puzzle_instances(),
+            "max_puzzle_instances": prover.max_puzzle_instances,
+            "latest_epoch_hash": *prover.latest_epoch_hash.read(),
+            "latest_block_height": prover.latest_block_header.read().as_ref().map(|header| header.height()),
+        }))
+    }
+
+    // GET /mainnet/prover/pool/shares
+    async fn get_pool_shares(State(prover): State<Self>) -> Result<Json<Value>, (StatusCode, String)> {
+        match &prover.pool {
+            Some(pool) => Ok(Json(json!(pool.shares()))),
+            None => Err((StatusCode::NOT_FOUND, "The prover is not running a pool coordinator".to_string())),
+        }
+    }
+}
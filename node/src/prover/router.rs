@@ -14,20 +14,24 @@
 
 use super::*;
 
-use snarkos_node_router::messages::{
-    BlockRequest,
-    DisconnectReason,
-    Message,
-    MessageCodec,
-    Ping,
-    Pong,
-    PuzzleRequest,
-    UnconfirmedTransaction,
+use snarkos_node_router::{
+    messages::{
+        BlockRequest,
+        DisconnectReason,
+        Message,
+        MessageCodec,
+        Ping,
+        Pong,
+        PuzzleRequest,
+        UnconfirmedTransaction,
+    },
+    PeerEvent,
 };
-use snarkos_node_tcp::{Connection, ConnectionSide, Tcp};
+use snarkos_node_tcp::{Connection, ConnectionSide, RateLimit, RateLimitViolation, Tcp, CONTEXT_TARGET};
 use snarkvm::prelude::{block::Transaction, Network};
 
 use std::{io, net::SocketAddr};
+use tracing::Instrument;
 
 impl<N: Network, C: ConsensusStorage<N>> P2P for Prover<N, C> {
     /// Returns a reference to the TCP instance.
@@ -82,8 +86,8 @@ impl<N: Network, C: ConsensusStorage<N>> Writing for Prover<N, C> {
 
     /// Creates an [`Encoder`] used to write the outbound messages to the target stream.
     /// The `side` parameter indicates the connection side **from the node's perspective**.
-    fn codec(&self, _addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.router().codec(&addr)
     }
 }
 
@@ -94,23 +98,38 @@ impl<N: Network, C: ConsensusStorage<N>> Reading for Prover<N, C> {
 
     /// Creates a [`Decoder`] used to interpret messages from the network.
     /// The `side` param indicates the connection side **from the node's perspective**.
-    fn codec(&self, _peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
-        Default::default()
+    fn codec(&self, peer_addr: SocketAddr, _side: ConnectionSide) -> Self::Codec {
+        self.router().codec(&peer_addr)
     }
 
     /// Processes a message received from the network.
     async fn process_message(&self, peer_addr: SocketAddr, message: Self::Message) -> io::Result<()> {
+        // Process the message within the context of the peer, for structured logging.
+        let peer_ip = self.router().resolve_to_listener(&peer_addr).unwrap_or(peer_addr);
+        let span = info_span!(target: CONTEXT_TARGET, "peer", peer_ip = %peer_ip);
         // Process the message. Disconnect if the peer violated the protocol.
-        if let Err(error) = self.inbound(peer_addr, message).await {
+        if let Err(error) = self.inbound(peer_addr, message).instrument(span).await {
             if let Some(peer_ip) = self.router().resolve_to_listener(&peer_addr) {
                 warn!("Disconnecting from '{peer_addr}' - {error}");
                 Outbound::send(self, peer_ip, Message::Disconnect(DisconnectReason::ProtocolViolation.into()));
-                // Disconnect from this peer.
-                self.router().disconnect(peer_ip);
+                // Penalize the peer for the invalid message, and disconnect unless the penalty already did.
+                if !self.router().update_peer_score(peer_ip, PeerEvent::InvalidMessage) {
+                    self.router().disconnect(peer_ip);
+                }
             }
         }
         Ok(())
     }
+
+    /// Returns the name of the type of the given inbound message and its per-peer rate limit, if the type is limited.
+    fn message_limit(&self, message: &Self::Message) -> Option<(&'static str, RateLimit)> {
+        self.router().message_limit(message)
+    }
+
+    /// Penalizes the peer at the given address for exceeding a rate limit.
+    fn rate_limit_exceeded(&self, peer_addr: SocketAddr, violation: RateLimitViolation) {
+        self.router().rate_limit_exceeded(peer_addr, violation)
+    }
 }
 
 #[async_trait]
@@ -203,6 +222,10 @@ impl<N: Network, C: ConsensusStorage<N>> Inbound<N> for Prover<N, C> {
             header.proof_target()
         );
 
+        // Update the job of the pool workers.
+        if let Some(pool) = &self.pool {
+            pool.update_job(epoch_hash, &header);
+        }
         // Save the latest epoch hash in the node.
         self.latest_epoch_hash.write().replace(epoch_hash);
         // Save the latest block header in the node.
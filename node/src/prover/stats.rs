@@ -0,0 +1,167 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use indexmap::IndexMap;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+
+/// The number of recent epochs to keep the solution counts for.
+const NUM_RECENT_EPOCHS: usize = 10;
+
+/// The proving statistics of the prover.
+#[derive(Debug)]
+pub struct ProverStats {
+    /// The total number of puzzle attempts.
+    total_attempts: AtomicU64,
+    /// The total number of solutions found.
+    total_solutions: AtomicU64,
+    /// The greatest proof target achieved by an attempt.
+    best_proof_target: AtomicU64,
+    /// The number of solutions found, for each recent epoch.
+    epoch_solutions: RwLock<IndexMap<u32, u64>>,
+    /// The timestamp of the latest solution found, which is not necessarily confirmed in a block.
+    last_found_solution: RwLock<Option<Instant>>,
+    /// The timestamp and total number of attempts at the latest sample, and the resulting attempts per second.
+    attempt_rate: Mutex<(Instant, u64, f64)>,
+}
+
+/// A snapshot of the proving statistics of the prover.
+#[derive(Clone, Debug, Serialize)]
+pub struct ProverStatsSnapshot {
+    /// The number of puzzle attempts per second, as of the latest sample.
+    pub attempts_per_sec: f64,
+    /// The total number of puzzle attempts.
+    pub total_attempts: u64,
+    /// The total number of solutions found.
+    pub total_solutions: u64,
+    /// The number of solutions found, for each recent epoch.
+    pub solutions_per_epoch: IndexMap<u32, u64>,
+    /// The greatest proof target achieved by an attempt.
+    pub best_proof_target: u64,
+    /// The number of seconds since the latest solution was found (not confirmed), if one was found.
+    pub secs_since_last_found_solution: Option<u64>,
+}
+
+impl Default for ProverStats {
+    fn default() -> Self {
+        Self {
+            total_attempts: Default::default(),
+            total_solutions: Default::default(),
+            best_proof_target: Default::default(),
+            epoch_solutions: Default::default(),
+            last_found_solution: Default::default(),
+            attempt_rate: Mutex::new((Instant::now(), 0, 0.0)),
+        }
+    }
+}
+
+impl ProverStats {
+    /// Records a puzzle attempt with the given proof target.
+    pub fn record_attempt(&self, proof_target: u64) {
+        self.total_attempts.fetch_add(1, Ordering::Relaxed);
+        self.best_proof_target.fetch_max(proof_target, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter(metrics::prover::ATTEMPTS);
+    }
+
+    /// Records a solution found in the given epoch.
+    pub fn record_solution(&self, epoch: u32) {
+        self.total_solutions.fetch_add(1, Ordering::Relaxed);
+        *self.last_found_solution.write() = Some(Instant::now());
+        // Increment the solutions of the epoch, and forget the oldest epochs.
+        let mut epoch_solutions = self.epoch_solutions.write();
+        *epoch_solutions.entry(epoch).or_default() += 1;
+        epoch_solutions.sort_keys();
+        while epoch_solutions.len() > NUM_RECENT_EPOCHS {
+            epoch_solutions.shift_remove_index(0);
+        }
+        #[cfg(feature = "metrics")]
+        metrics::increment_counter(metrics::prover::SOLUTIONS);
+    }
+
+    /// Samples the number of attempts per second since the previous sample.
+    pub fn sample_attempt_rate(&self) {
+        let now = Instant::now();
+        let total_attempts = self.total_attempts.load(Ordering::Relaxed);
+        let mut attempt_rate = self.attempt_rate.lock();
+        let (timestamp, previous_attempts, _) = *attempt_rate;
+        let elapsed = now.saturating_duration_since(timestamp).as_secs_f64();
+        if elapsed > 0.0 {
+            *attempt_rate = (now, total_attempts, total_attempts.saturating_sub(previous_attempts) as f64 / elapsed);
+        }
+    }
+
+    /// Returns a snapshot of the proving statistics.
+    pub fn snapshot(&self) -> ProverStatsSnapshot {
+        ProverStatsSnapshot {
+            attempts_per_sec: self.attempt_rate.lock().2,
+            total_attempts: self.total_attempts.load(Ordering::Relaxed),
+            total_solutions: self.total_solutions.load(Ordering::Relaxed),
+            solutions_per_epoch: self.epoch_solutions.read().clone(),
+            best_proof_target: self.best_proof_target.load(Ordering::Relaxed),
+            secs_since_last_found_solution: self
+                .last_found_solution
+                .read()
+                .map(|timestamp| timestamp.elapsed().as_secs()),
+        }
+    }
+
+    /// Updates the Prometheus gauges with the given snapshot.
+    #[cfg(feature = "metrics")]
+    pub fn update_metrics(snapshot: &ProverStatsSnapshot) {
+        metrics::gauge(metrics::prover::ATTEMPTS_PER_SEC, snapshot.attempts_per_sec);
+        let epoch_solutions = snapshot.solutions_per_epoch.last().map(|(_, solutions)| *solutions).unwrap_or(0);
+        metrics::gauge(metrics::prover::EPOCH_SOLUTIONS, epoch_solutions as f64);
+        metrics::gauge(metrics::prover::BEST_PROOF_TARGET, snapshot.best_proof_target as f64);
+        if let Some(secs) = snapshot.secs_since_last_found_solution {
+            metrics::gauge(metrics::prover::LAST_FOUND_SOLUTION_AGE, secs as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prover_stats() {
+        let stats = ProverStats::default();
+
+        // Record a few attempts.
+        for proof_target in [5, 20, 10] {
+            stats.record_attempt(proof_target);
+        }
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.total_attempts, 3);
+        assert_eq!(snapshot.best_proof_target, 20);
+        assert_eq!(snapshot.total_solutions, 0);
+        assert_eq!(snapshot.secs_since_last_found_solution, None);
+
+        // Record solutions across more epochs than are kept.
+        for epoch in 0..=NUM_RECENT_EPOCHS as u32 {
+            stats.record_solution(epoch);
+        }
+        stats.record_solution(NUM_RECENT_EPOCHS as u32);
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.total_solutions, NUM_RECENT_EPOCHS as u64 + 2);
+        assert_eq!(snapshot.solutions_per_epoch.len(), NUM_RECENT_EPOCHS);
+        assert_eq!(snapshot.solutions_per_epoch.get(&0), None);
+        assert_eq!(snapshot.solutions_per_epoch.get(&(NUM_RECENT_EPOCHS as u32)), Some(&2));
+        assert_eq!(snapshot.secs_since_last_found_solution, Some(0));
+    }
+}
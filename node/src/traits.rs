@@ -33,13 +33,13 @@ pub trait NodeInterface<N: Network>: Routing<N> {
         self.router().node_type()
     }
 
-    /// Returns the account private key of the node.
-    fn private_key(&self) -> &PrivateKey<N> {
+    /// Returns the account private key of the node, unless it is held by an external signer process.
+    fn private_key(&self) -> Option<&PrivateKey<N>> {
         self.router().private_key()
     }
 
-    /// Returns the account view key of the node.
-    fn view_key(&self) -> &ViewKey<N> {
+    /// Returns the account view key of the node, unless it is held by an external signer process.
+    fn view_key(&self) -> Option<&ViewKey<N>> {
         self.router().view_key()
     }
 
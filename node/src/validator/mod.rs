@@ -15,21 +15,25 @@
 mod router;
 
 use crate::traits::NodeInterface;
-use snarkos_account::Account;
+use snarkos_account::Signer;
 use snarkos_node_bft::{helpers::init_primary_channels, ledger_service::CoreLedgerService, spawn_blocking};
-use snarkos_node_consensus::Consensus;
+use snarkos_node_consensus::{Consensus, MemoryPoolConfig};
 use snarkos_node_rest::Rest;
 use snarkos_node_router::{
     messages::{NodeType, PuzzleResponse, UnconfirmedSolution, UnconfirmedTransaction},
     Heartbeat,
     Inbound,
     Outbound,
+    PeerEvent,
     Router,
     Routing,
 };
 use snarkos_node_sync::{BlockSync, BlockSyncMode};
 use snarkos_node_tcp::{
     protocols::{Disconnect, Handshake, OnConnect, Reading, Writing},
+    InboundLimits,
+    IpFamily,
+    OutboundLimits,
     P2P,
 };
 use snarkvm::prelude::{
@@ -77,7 +81,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         bft_ip: Option<SocketAddr>,
         rest_ip: Option<SocketAddr>,
         rest_rps: u32,
-        account: Account<N>,
+        signer: Signer<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
         genesis: Block<N>,
@@ -85,6 +89,10 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         storage_mode: StorageMode,
         allow_external_peers: bool,
         dev_txs: bool,
+        memory_pool: MemoryPoolConfig,
+        preferred_ip_family: Option<IpFamily>,
+        inbound_limits: InboundLimits,
+        outbound_limits: OutboundLimits,
     ) -> Result<Self> {
         // Prepare the shutdown flag.
         let shutdown: Arc<AtomicBool> = Default::default();
@@ -112,8 +120,16 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         let sync = BlockSync::new(BlockSyncMode::Gateway, ledger_service.clone());
 
         // Initialize the consensus.
-        let mut consensus =
-            Consensus::new(account.clone(), ledger_service, bft_ip, trusted_validators, storage_mode.clone())?;
+        let mut consensus = Consensus::new(
+            signer.clone(),
+            ledger_service,
+            bft_ip,
+            trusted_validators,
+            storage_mode.clone(),
+            memory_pool,
+            inbound_limits.clone(),
+            outbound_limits,
+        )?;
         // Initialize the primary channels.
         let (primary_sender, primary_receiver) = init_primary_channels::<N>();
         // Start the consensus.
@@ -123,11 +139,14 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         let router = Router::new(
             node_ip,
             NodeType::Validator,
-            account,
+            signer,
             trusted_peers,
             Self::MAXIMUM_NUMBER_OF_PEERS as u16,
             allow_external_peers,
-            matches!(storage_mode, StorageMode::Development(_)),
+            storage_mode.clone(),
+            preferred_ip_family,
+            inbound_limits,
+            outbound_limits,
         )
         .await?;
 
@@ -141,13 +160,21 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             handles: Default::default(),
             shutdown,
         };
+        // Penalize the peers that do not respond to their block requests in time.
+        let router = node.router.clone();
+        node.sync.set_timeout_handler(move |peer_ip| {
+            router.update_peer_score(peer_ip, PeerEvent::BlockRequestTimeout);
+        });
         // Initialize the transaction pool.
         node.initialize_transaction_pool(storage_mode, dev_txs)?;
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest =
-                Some(Rest::start(rest_ip, rest_rps, Some(consensus), ledger.clone(), Arc::new(node.clone())).await?);
+            // Retrieve the block sync module of the BFT, which syncs the validator.
+            let sync = node.block_sync().clone();
+            node.rest = Some(
+                Rest::start(rest_ip, rest_rps, Some(consensus), ledger.clone(), Arc::new(node.clone()), sync).await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
@@ -164,6 +191,16 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         &self.ledger
     }
 
+    /// Returns the consensus module.
+    pub const fn consensus(&self) -> &Consensus<N> {
+        &self.consensus
+    }
+
+    /// Returns the block sync module of the BFT, which syncs the validator.
+    pub const fn block_sync(&self) -> &BlockSync<N> {
+        self.consensus.bft().primary().sync().block_sync()
+    }
+
     /// Returns the REST server.
     pub fn rest(&self) -> &Option<Rest<N, C, Self>> {
         &self.rest
@@ -376,6 +413,11 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
                 }
             }
         }
+        // The transactions are signed with the private key, which an external signer process does not share.
+        let Some(private_key) = self.private_key().copied() else {
+            warn!("Skipping the development transactions, as the private key is held by the signer");
+            return Ok(());
+        };
 
         let self_ = self.clone();
         self.spawn(async move {
@@ -391,7 +433,7 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
                 // Execute the transaction.
                 let self__ = self_.clone();
                 let transaction = match spawn_blocking!(self__.ledger.vm().execute(
-                    self__.private_key(),
+                    &private_key,
                     locator,
                     inputs.into_iter(),
                     None,
@@ -455,6 +497,7 @@ impl<N: Network, C: ConsensusStorage<N>> NodeInterface<N> for Validator<N, C> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use snarkos_account::Account;
     use snarkvm::prelude::{
         store::{helpers::memory::ConsensusMemory, ConsensusStore},
         MainnetV0,
@@ -494,7 +537,7 @@ mod tests {
             None,
             Some(rest),
             10,
-            account,
+            Signer::from(account),
             &[],
             &[],
             genesis,
@@ -502,6 +545,10 @@ mod tests {
             storage_mode,
             false,
             dev_txs,
+            MemoryPoolConfig::default(),
+            None,
+            Default::default(),
+            Default::default(),
         )
         .await
         .unwrap();
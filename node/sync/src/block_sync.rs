@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    helpers::{PeerPair, SyncRequest},
+    helpers::{PeerPair, SyncProgress, SyncRequest},
     locators::BlockLocators,
 };
 use snarkos_node_bft_ledger_service::LedgerService;
@@ -26,6 +26,7 @@ use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
 use parking_lot::{Mutex, RwLock};
 use rand::{prelude::IteratorRandom, CryptoRng, Rng};
+use serde::Serialize;
 use std::{
     collections::BTreeMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -44,9 +45,11 @@ const EXTRA_REDUNDANCY_FACTOR: usize = REDUNDANCY_FACTOR * 3;
 const NUM_SYNC_CANDIDATE_PEERS: usize = REDUNDANCY_FACTOR * 5;
 
 const BLOCK_REQUEST_TIMEOUT_IN_SECS: u64 = 600; // 600 seconds
-const MAX_BLOCK_REQUESTS: usize = 50; // 50 requests
 const MAX_BLOCK_REQUEST_TIMEOUTS: usize = 5; // 5 timeouts
 
+/// The maximum number of blocks requested from peers at once.
+pub const MAX_BLOCK_REQUESTS: usize = 50; // 50 requests
+
 /// The maximum number of blocks tolerated before the primary is considered behind its peers.
 pub const MAX_BLOCKS_BEHIND: u32 = 1; // blocks
 
@@ -54,7 +57,8 @@ pub const MAX_BLOCKS_BEHIND: u32 = 1; // blocks
 /// Note: This here does not need to be a real IP address, but it must be unique/distinct from all other connections.
 const DUMMY_SELF_IP: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 0);
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum BlockSyncMode {
     Router,
     Gateway,
@@ -72,6 +76,45 @@ impl BlockSyncMode {
     }
 }
 
+/// A snapshot of the block sync state, as reported to operators.
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockSyncStatus {
+    /// The block sync mode.
+    pub mode: BlockSyncMode,
+    /// Whether the node is synced up to the latest block (within the given tolerance).
+    pub is_synced: bool,
+    /// The latest block height of the node.
+    pub local_height: u32,
+    /// The greatest block height advertised by the connected peers.
+    pub network_height: u32,
+    /// The number of blocks the node is behind the network.
+    pub blocks_behind: u32,
+    /// The number of peers whose block locators are known.
+    pub num_peers: usize,
+    /// The number of block requests that are awaiting a response.
+    pub num_outstanding_requests: usize,
+    /// The number of block responses that are waiting to be processed.
+    pub num_pending_responses: usize,
+    /// The number of outstanding block requests, for each peer.
+    pub requests_per_peer: IndexMap<SocketAddr, usize>,
+    /// The number of recent block request timeouts, for each peer.
+    pub timeouts_per_peer: IndexMap<SocketAddr, usize>,
+    /// The observed rate at which the node advances, in blocks per second.
+    pub blocks_per_sec: Option<f64>,
+    /// The estimated number of seconds until the node catches up to the network.
+    pub eta_in_secs: Option<u64>,
+}
+
+/// The handler called with each peer IP that timed out on a block request.
+#[derive(Clone)]
+struct TimeoutHandler(Arc<dyn Fn(SocketAddr) + Send + Sync>);
+
+impl std::fmt::Debug for TimeoutHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TimeoutHandler")
+    }
+}
+
 /// A struct that keeps track of the current block sync state.
 ///
 /// # State
@@ -107,8 +150,12 @@ pub struct BlockSync<N: Network> {
     /// The map of (timed out) peer IPs to their request timestamps.
     /// This map is used to determine which peers to remove if they have timed out too many times.
     request_timeouts: Arc<RwLock<IndexMap<SocketAddr, Vec<Instant>>>>,
+    /// The handler called with each peer IP that timed out on a block request, if any.
+    timeout_handler: Arc<RwLock<Option<TimeoutHandler>>>,
     /// The boolean indicator of whether the node is synced up to the latest block (within the given tolerance).
     is_block_synced: Arc<AtomicBool>,
+    /// The sync progress, sampled to estimate the rate at which the node advances.
+    progress: Arc<RwLock<SyncProgress>>,
     /// The lock to guarantee advance_with_sync_blocks() is called only once at a time.
     advance_with_sync_blocks_lock: Arc<Mutex<()>>,
 }
@@ -125,7 +172,9 @@ impl<N: Network> BlockSync<N> {
             responses: Default::default(),
             request_timestamps: Default::default(),
             request_timeouts: Default::default(),
+            timeout_handler: Default::default(),
             is_block_synced: Default::default(),
+            progress: Default::default(),
             advance_with_sync_blocks_lock: Default::default(),
         }
     }
@@ -141,6 +190,63 @@ impl<N: Network> BlockSync<N> {
     pub fn is_block_synced(&self) -> bool {
         self.is_block_synced.load(Ordering::SeqCst)
     }
+
+    /// Returns the latest block height of each peer.
+    pub fn get_peer_heights(&self) -> IndexMap<SocketAddr, u32> {
+        self.locators.read().iter().map(|(peer_ip, locators)| (*peer_ip, locators.latest_locator_height())).collect()
+    }
+
+    /// Returns a snapshot of the block sync state.
+    pub fn status(&self) -> BlockSyncStatus {
+        // Retrieve the latest block height.
+        let local_height = self.canon.latest_block_height();
+        // Retrieve the greatest block height of the peers.
+        let locators = self.locators.read();
+        let network_height =
+            locators.values().map(|locators| locators.latest_locator_height()).max().unwrap_or(0).max(local_height);
+        let num_peers = locators.len();
+        drop(locators);
+        // Compute the number of blocks that we are behind by.
+        let blocks_behind = network_height - local_height;
+
+        // Count the outstanding block requests for each peer.
+        let mut num_outstanding_requests = 0;
+        let mut requests_per_peer = IndexMap::new();
+        for (_, _, sync_ips) in self.requests.read().values() {
+            if !sync_ips.is_empty() {
+                num_outstanding_requests += 1;
+            }
+            for sync_ip in sync_ips {
+                *requests_per_peer.entry(*sync_ip).or_default() += 1;
+            }
+        }
+        // Count the block request timeouts for each peer.
+        let timeouts_per_peer = self
+            .request_timeouts
+            .read()
+            .iter()
+            .map(|(peer_ip, timestamps)| (*peer_ip, timestamps.len()))
+            .filter(|(_, num_timeouts)| *num_timeouts > 0)
+            .collect();
+
+        // Estimate the time until the node catches up.
+        let progress = *self.progress.read();
+
+        BlockSyncStatus {
+            mode: self.mode,
+            is_synced: self.is_block_synced(),
+            local_height,
+            network_height,
+            blocks_behind,
+            num_peers,
+            num_outstanding_requests,
+            num_pending_responses: self.responses.read().len(),
+            requests_per_peer,
+            timeouts_per_peer,
+            blocks_per_sec: progress.blocks_per_sec(),
+            eta_in_secs: progress.eta_in_secs(blocks_behind),
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -277,6 +383,12 @@ impl<N: Network> BlockSync<N> {
         Ok(())
     }
 
+    /// Sets the handler called with each peer IP that times out on a block request.
+    /// Without a handler, a timeout only lowers the priority of the peer as a sync peer.
+    pub fn set_timeout_handler(&self, handler: impl Fn(SocketAddr) + Send + Sync + 'static) {
+        *self.timeout_handler.write() = Some(TimeoutHandler(Arc::new(handler)));
+    }
+
     /// Returns the next block to process, if one is ready.
     #[inline]
     pub fn process_next_block(&self, next_height: u32) -> Option<Block<N>> {
@@ -441,6 +553,8 @@ impl<N: Network> BlockSync<N> {
         let is_synced = num_blocks_behind <= max_blocks_behind;
         // Update the sync status.
         self.is_block_synced.store(is_synced, Ordering::SeqCst);
+        // Update the sync progress.
+        self.progress.write().update(Instant::now(), canon_height);
     }
 
     /// Inserts a block request for the given height.
@@ -664,11 +778,17 @@ impl<N: Network> BlockSync<N> {
             // Acquire the write lock on the request timeouts map.
             let mut request_timeouts = self.request_timeouts.write();
             // Add each timeout IP to the request timeouts map.
-            for timeout_ip in timeout_ips {
-                request_timeouts.entry(timeout_ip).or_default().push(now);
+            for timeout_ip in &timeout_ips {
+                request_timeouts.entry(*timeout_ip).or_default().push(now);
             }
         }
 
+        // Release the locks before calling the timeout handler.
+        drop((requests, responses, request_timestamps));
+        if let Some(handler) = self.timeout_handler.read().clone() {
+            timeout_ips.into_iter().for_each(|timeout_ip| (handler.0)(timeout_ip));
+        }
+
         num_timed_out_block_requests
     }
 
@@ -892,7 +1012,10 @@ mod tests {
 
     use indexmap::indexset;
     use snarkvm::ledger::committee::Committee;
-    use std::net::{IpAddr, Ipv4Addr};
+    use std::{
+        net::{IpAddr, Ipv4Addr},
+        time::Duration,
+    };
 
     type CurrentNetwork = snarkvm::prelude::MainnetV0;
 
@@ -1198,6 +1321,82 @@ mod tests {
         sync.insert_block_request(10, (None, None, indexset![sample_peer_ip(1)])).unwrap();
     }
 
+    #[test]
+    fn test_timeout_handler() {
+        let sync = sample_sync_at_height(0);
+        let peer_ip = sample_peer_ip(1);
+
+        // Record the peers passed to the timeout handler.
+        let timed_out = Arc::new(Mutex::new(Vec::new()));
+        let timed_out_clone = timed_out.clone();
+        sync.set_timeout_handler(move |peer_ip| timed_out_clone.lock().push(peer_ip));
+
+        // Insert a block request, and let it time out.
+        sync.insert_block_request(10, (None, None, indexset![peer_ip])).unwrap();
+        assert_eq!(sync.remove_timed_out_block_requests(), 0);
+        let timeout = std::time::Duration::from_secs(BLOCK_REQUEST_TIMEOUT_IN_SECS + 1);
+        sync.request_timestamps.write().insert(10, Instant::now().checked_sub(timeout).unwrap());
+        assert_eq!(sync.remove_timed_out_block_requests(), 1);
+
+        // Check that the handler was called with the peer.
+        assert_eq!(*timed_out.lock(), vec![peer_ip]);
+        assert!(sync.get_block_request(10).is_none());
+    }
+
+    #[test]
+    fn test_status() {
+        let sync = sample_sync_at_height(0);
+
+        // Check the status without peers.
+        let status = sync.status();
+        assert_eq!(status.network_height, 0);
+        assert_eq!(status.blocks_behind, 0);
+        assert_eq!(status.num_outstanding_requests, 0);
+        assert_eq!(status.eta_in_secs, Some(0));
+
+        // Add peers, and insert a block request to two of them.
+        sync.update_peer_locators(sample_peer_ip(1), sample_block_locators(10)).unwrap();
+        sync.update_peer_locators(sample_peer_ip(2), sample_block_locators(20)).unwrap();
+        sync.insert_block_request(1, (None, None, indexset![sample_peer_ip(1), sample_peer_ip(2)])).unwrap();
+        sync.insert_block_request(2, (None, None, indexset![sample_peer_ip(2)])).unwrap();
+
+        // Check the status reflects the peers and the block requests.
+        let status = sync.status();
+        assert_eq!(status.local_height, 0);
+        assert_eq!(status.network_height, 20);
+        assert_eq!(status.blocks_behind, 20);
+        assert_eq!(status.num_peers, 2);
+        assert_eq!(status.num_outstanding_requests, 2);
+        assert_eq!(status.requests_per_peer.get(&sample_peer_ip(1)), Some(&1));
+        assert_eq!(status.requests_per_peer.get(&sample_peer_ip(2)), Some(&2));
+        assert!(status.timeouts_per_peer.is_empty());
+        // The sync rate has not been sampled yet.
+        assert_eq!(status.eta_in_secs, None);
+    }
+
+    #[test]
+    fn test_sync_progress() {
+        let start = Instant::now();
+        let mut progress = SyncProgress::default();
+        assert_eq!(progress.blocks_per_sec(), None);
+
+        // Samples within the sampling interval are ignored.
+        progress.update(start, 0);
+        progress.update(start + Duration::from_secs(1), 5);
+        assert_eq!(progress.blocks_per_sec(), None);
+
+        // Advance by 100 blocks in 20 seconds.
+        progress.update(start + Duration::from_secs(20), 100);
+        assert_eq!(progress.blocks_per_sec(), Some(5.0));
+        assert_eq!(progress.eta_in_secs(0), Some(0));
+        assert_eq!(progress.eta_in_secs(51), Some(11));
+
+        // Stall, and check the rate decreases without dropping to zero.
+        progress.update(start + Duration::from_secs(40), 100);
+        let rate = progress.blocks_per_sec().unwrap();
+        assert!(rate > 0.0 && rate < 5.0);
+    }
+
     #[test]
     fn test_update_peer_locators() {
         let sync = sample_sync_at_height(0);
@@ -16,7 +16,12 @@ use snarkvm::prelude::Network;
 
 use core::hash::Hash;
 use indexmap::IndexSet;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Instant};
+
+/// The minimum number of seconds between samples of the sync progress.
+const SYNC_PROGRESS_INTERVAL_IN_SECS: f64 = 10.0; // 10 seconds
+/// The weight given to the latest sample when smoothing the block sync rate.
+const SYNC_RATE_SMOOTHING_FACTOR: f64 = 0.3;
 
 /// A tuple of the block hash (optional), previous block hash (optional), and sync IPs.
 pub type SyncRequest<N> = (Option<<N as Network>::BlockHash>, Option<<N as Network>::BlockHash>, IndexSet<SocketAddr>);
@@ -39,3 +44,48 @@ impl Hash for PeerPair {
         b.hash(state);
     }
 }
+
+/// The sync progress of the node, sampled periodically to estimate the rate at which it advances.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct SyncProgress {
+    /// The timestamp and block height of the latest sample.
+    sample: Option<(Instant, u32)>,
+    /// The smoothed number of blocks advanced per second.
+    blocks_per_sec: Option<f64>,
+}
+
+impl SyncProgress {
+    /// Records the block height at the given time, updating the block sync rate once the sampling interval has elapsed.
+    pub fn update(&mut self, now: Instant, height: u32) {
+        let Some((timestamp, previous_height)) = self.sample else {
+            self.sample = Some((now, height));
+            return;
+        };
+        // Ensure the sampling interval has elapsed.
+        let elapsed = now.saturating_duration_since(timestamp).as_secs_f64();
+        if elapsed < SYNC_PROGRESS_INTERVAL_IN_SECS {
+            return;
+        }
+        // Compute the block sync rate since the latest sample, and smooth it with the previous rate.
+        let rate = height.saturating_sub(previous_height) as f64 / elapsed;
+        self.blocks_per_sec = Some(match self.blocks_per_sec {
+            Some(previous_rate) => previous_rate + SYNC_RATE_SMOOTHING_FACTOR * (rate - previous_rate),
+            None => rate,
+        });
+        self.sample = Some((now, height));
+    }
+
+    /// Returns the smoothed number of blocks advanced per second, if it has been sampled.
+    pub const fn blocks_per_sec(&self) -> Option<f64> {
+        self.blocks_per_sec
+    }
+
+    /// Returns the estimated number of seconds to advance by the given number of blocks, if it can be estimated.
+    pub fn eta_in_secs(&self, num_blocks: u32) -> Option<u64> {
+        match (num_blocks, self.blocks_per_sec) {
+            (0, _) => Some(0),
+            (_, Some(rate)) if rate > 0.0 => Some((num_blocks as f64 / rate).ceil() as u64),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::{BufMut, Bytes, BytesMut};
+use std::io;
+
+/// The feature bit in the `version` of a handshake challenge request, which advertises support for compressed frames.
+/// Note: Older nodes only ensure the version is not outdated, so they accept, and ignore, the feature bits.
+pub const COMPRESSION_FEATURE: u32 = 1 << 16;
+/// The mask over the `version` of a handshake challenge request, which recovers the protocol version.
+pub const PROTOCOL_VERSION_MASK: u32 = COMPRESSION_FEATURE - 1;
+/// The minimum size of a payload, in bytes, for it to be compressed.
+pub const COMPRESSION_THRESHOLD: usize = 16 * 1024; // 16 KiB
+
+/// The zstd compression level.
+const COMPRESSION_LEVEL: i32 = 3;
+/// The frame header of an uncompressed payload.
+const HEADER_UNCOMPRESSED: u8 = 0;
+/// The frame header of a zstd-compressed payload.
+const HEADER_ZSTD: u8 = 1;
+
+/// Encodes the given payload into a frame with a one-byte header, compressing the payload if `compress` is set.
+///
+/// The payload is sent uncompressed if compression does not reduce its size.
+pub fn compress_frame(payload: &[u8], compress: bool) -> io::Result<Bytes> {
+    if compress {
+        let compressed = zstd::bulk::compress(payload, COMPRESSION_LEVEL)?;
+        if compressed.len() < payload.len() {
+            return Ok(frame(HEADER_ZSTD, &compressed));
+        }
+    }
+    Ok(frame(HEADER_UNCOMPRESSED, payload))
+}
+
+/// Decodes the payload from the given frame, ensuring the decompressed payload does not exceed `max_size` bytes.
+pub fn decompress_frame(mut frame: BytesMut, max_size: usize) -> io::Result<Bytes> {
+    if frame.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "missing frame header"));
+    }
+    let header = frame.split_to(1)[0];
+    match header {
+        HEADER_UNCOMPRESSED => Ok(frame.freeze()),
+        HEADER_ZSTD => {
+            // Read the size of the payload from the zstd frame, so that only the declared size is allocated.
+            // Note: The decompression fails if the payload exceeds its declared size.
+            let size = match zstd::zstd_safe::get_frame_content_size(&frame) {
+                Ok(Some(size)) => size,
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "missing or invalid frame content size")),
+            };
+            if size > max_size as u64 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("frame content size {size} is too large"),
+                ));
+            }
+            Ok(zstd::bulk::decompress(&frame, size as usize)?.into())
+        }
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown frame header {header}"))),
+    }
+}
+
+/// Returns a frame with the given header and payload.
+fn frame(header: u8, payload: &[u8]) -> Bytes {
+    let mut frame = BytesMut::with_capacity(1 + payload.len());
+    frame.put_u8(header);
+    frame.put_slice(payload);
+    frame.freeze()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_roundtrip() {
+        // A large and repetitive payload is compressed.
+        let payload = vec![7u8; 4 * COMPRESSION_THRESHOLD];
+        let frame = compress_frame(&payload, true).unwrap();
+        assert_eq!(frame[0], HEADER_ZSTD);
+        assert!(frame.len() < payload.len());
+        assert_eq!(decompress_frame(BytesMut::from(&frame[..]), payload.len()).unwrap(), payload);
+
+        // The payload is not compressed, unless requested.
+        let frame = compress_frame(&payload, false).unwrap();
+        assert_eq!(frame[0], HEADER_UNCOMPRESSED);
+        assert_eq!(decompress_frame(BytesMut::from(&frame[..]), payload.len()).unwrap(), payload);
+
+        // An incompressible payload is sent uncompressed.
+        let payload = (0..=255u8).collect::<Vec<_>>();
+        let frame = compress_frame(&payload, true).unwrap();
+        assert_eq!(frame[0], HEADER_UNCOMPRESSED);
+        assert_eq!(decompress_frame(BytesMut::from(&frame[..]), payload.len()).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_frame_limits() {
+        // A payload that decompresses beyond the maximum size is rejected.
+        let payload = vec![0u8; 4 * COMPRESSION_THRESHOLD];
+        let frame = compress_frame(&payload, true).unwrap();
+        assert!(decompress_frame(BytesMut::from(&frame[..]), payload.len() - 1).is_err());
+
+        // Malformed frames are rejected.
+        assert!(decompress_frame(BytesMut::new(), 1024).is_err());
+        assert!(decompress_frame(BytesMut::from(&[2u8, 0, 0][..]), 1024).is_err());
+        assert!(decompress_frame(BytesMut::from(&[HEADER_ZSTD, 0, 0][..]), 1024).is_err());
+    }
+
+    #[test]
+    fn test_frame_content_size() {
+        // A frame that declares a content size beyond the maximum size is rejected before decompression.
+        let payload = vec![0u8; 4 * COMPRESSION_THRESHOLD];
+        let frame = compress_frame(&payload, true).unwrap();
+        assert_eq!(zstd::zstd_safe::get_frame_content_size(&frame[1..]).ok(), Some(Some(payload.len() as u64)));
+        assert!(decompress_frame(BytesMut::from(&frame[..]), payload.len() - 1).is_err());
+
+        // A frame without a declared content size is rejected.
+        let mut encoder = zstd::stream::Encoder::new(vec![HEADER_ZSTD], COMPRESSION_LEVEL).unwrap();
+        encoder.include_contentsize(false).unwrap();
+        io::Write::write_all(&mut encoder, &payload).unwrap();
+        let frame = encoder.finish().unwrap();
+        assert_eq!(zstd::zstd_safe::get_frame_content_size(&frame[1..]).ok(), Some(None));
+        assert!(decompress_frame(BytesMut::from(&frame[..]), payload.len()).is_err());
+    }
+}
@@ -14,11 +14,12 @@
 
 use std::{
     io::{self, ErrorKind::*},
-    net::{IpAddr, Ipv4Addr, SocketAddr},
+    net::{IpAddr, SocketAddr},
 };
 
 #[cfg(doc)]
 use crate::protocols::{self, Handshake, Reading, Writing};
+use crate::RateLimit;
 
 /// The Tcp's configuration. See the source of [`Config::default`] for the defaults.
 #[derive(Debug, Clone)]
@@ -34,6 +35,10 @@ pub struct Config {
     ///
     /// note: If set to `None`, the Tcp will not listen for inbound connections at all.
     pub listener_ip: Option<IpAddr>,
+    /// If set to `true` and [`Config::listener_ip`] is the unspecified IPv6 address (`::`), the listener also accepts
+    /// IPv4 connections, and falls back to the unspecified IPv4 address if IPv6 is unavailable on the host.
+    /// If set to `false`, such a listener only accepts IPv6 connections.
+    pub dual_stack: bool,
     /// The desired listening port of the Tcp. If [`Config::allow_random_port`] is set to `true`, the Tcp
     /// will attempt to bind its listener to a different port if the desired one is not available.
     ///
@@ -54,6 +59,24 @@ pub struct Config {
     pub max_connections: u16,
     /// The maximum time (in milliseconds) allowed to establish a raw (before the [`Handshake`] protocol) TCP connection.
     pub connection_timeout_ms: u16,
+    /// The limit on inbound messages of a limited type ([`Reading::message_limit`]) from a single connection;
+    /// messages exceeding it are dropped.
+    ///
+    /// note: Tcp needs to implement the [`Reading`] protocol in order for it to have any effect.
+    pub inbound_limit: RateLimit,
+    /// The limit on inbound messages of a limited type ([`Reading::message_limit`]) from all connections combined;
+    /// messages exceeding it are dropped.
+    ///
+    /// note: Tcp needs to implement the [`Reading`] protocol in order for it to have any effect.
+    pub global_inbound_limit: RateLimit,
+    /// The limit on outbound messages to a single connection; sending is delayed in order to stay within it.
+    ///
+    /// note: Tcp needs to implement the [`Writing`] protocol in order for it to have any effect.
+    pub outbound_limit: RateLimit,
+    /// The limit on outbound messages to all connections combined; sending is delayed in order to stay within it.
+    ///
+    /// note: Tcp needs to implement the [`Writing`] protocol in order for it to have any effect.
+    pub global_outbound_limit: RateLimit,
 }
 
 impl Config {
@@ -74,22 +97,27 @@ impl Default for Config {
     fn default() -> Self {
         #[cfg(feature = "test")]
         fn default_ip() -> Option<IpAddr> {
-            Some(IpAddr::V4(Ipv4Addr::LOCALHOST))
+            Some(IpAddr::V4(std::net::Ipv4Addr::LOCALHOST))
         }
 
         #[cfg(not(feature = "test"))]
         fn default_ip() -> Option<IpAddr> {
-            Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+            Some(IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED))
         }
 
         Self {
             name: None,
             listener_ip: default_ip(),
+            dual_stack: true,
             desired_listening_port: None,
             allow_random_port: true,
             fatal_io_errors: vec![ConnectionReset, ConnectionAborted, BrokenPipe, InvalidData, UnexpectedEof],
             max_connections: 100,
             connection_timeout_ms: 1_000,
+            inbound_limit: Default::default(),
+            global_inbound_limit: Default::default(),
+            outbound_limit: Default::default(),
+            global_outbound_limit: Default::default(),
         }
     }
 }
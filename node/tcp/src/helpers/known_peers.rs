@@ -63,4 +63,11 @@ impl KnownPeers {
             stats.register_failure();
         }
     }
+
+    /// Registers an inbound message from the given address that was dropped for exceeding a rate limit.
+    pub fn register_rate_limited(&self, addr: SocketAddr) {
+        if let Some(stats) = self.0.read().get(&addr) {
+            stats.register_rate_limited();
+        }
+    }
 }
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod compression;
+pub use compression::*;
+
 mod config;
 pub use config::Config;
 
@@ -21,6 +24,10 @@ pub use connections::{Connection, ConnectionSide};
 mod known_peers;
 pub use known_peers::KnownPeers;
 
+mod rate_limit;
+pub(crate) use rate_limit::RateLimiter;
+pub use rate_limit::{InboundLimits, OutboundLimits, RateLimit, RateLimitViolation};
+
 mod stats;
 pub use stats::Stats;
 
@@ -0,0 +1,299 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// A limit on the rate of messages and bytes; a `None` (or zero) component is unlimited.
+///
+/// The limits are enforced with token buckets which hold up to one second's worth of tokens, so short
+/// bursts are tolerated. A message larger than the bytes limit is admitted whenever the bucket is full.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// The maximum number of messages per second.
+    pub messages_per_second: Option<u32>,
+    /// The maximum number of bytes per second.
+    pub bytes_per_second: Option<u64>,
+}
+
+impl RateLimit {
+    /// Initializes a new rate limit with the given number of messages and bytes per second.
+    pub const fn new(messages_per_second: Option<u32>, bytes_per_second: Option<u64>) -> Self {
+        Self { messages_per_second, bytes_per_second }
+    }
+
+    /// Returns `true` if neither messages nor bytes are limited.
+    pub fn is_unlimited(&self) -> bool {
+        self.messages_per_second.unwrap_or(0) == 0 && self.bytes_per_second.unwrap_or(0) == 0
+    }
+}
+
+impl FromStr for RateLimit {
+    type Err = io::Error;
+
+    /// Parses a rate limit of the form `<MESSAGES>[:<BYTES>]` per second, where `0` is unlimited.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidInput, format!("invalid rate limit '{s}'"));
+        let (messages, bytes) = s.split_once(':').unwrap_or((s, "0"));
+        let messages = messages.trim().parse::<u32>().map_err(|_| invalid())?;
+        let bytes = bytes.trim().parse::<u64>().map_err(|_| invalid())?;
+        Ok(Self::new(Some(messages).filter(|rate| *rate > 0), Some(bytes).filter(|rate| *rate > 0)))
+    }
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.messages_per_second.unwrap_or(0))?;
+        match self.bytes_per_second.unwrap_or(0) {
+            0 => Ok(()),
+            bytes => write!(f, ":{bytes}"),
+        }
+    }
+}
+
+/// The limits on inbound requests; messages that are not requests are not limited.
+///
+/// A request counts towards the limit for its type, the per-connection limit, and the global limit.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InboundLimits {
+    /// The limit on requests from a single connection.
+    pub connection: RateLimit,
+    /// The limit on requests from all connections combined.
+    pub global: RateLimit,
+    /// The limits on each type of request from a single connection, by name, overriding the defaults of the node.
+    pub requests: BTreeMap<String, RateLimit>,
+}
+
+impl InboundLimits {
+    /// Returns the limit on the given type of request, or the given default if it is not overridden.
+    pub fn request_limit(&self, name: &str, default: RateLimit) -> RateLimit {
+        self.requests.get(name).copied().unwrap_or(default)
+    }
+}
+
+/// The limits on outbound messages; sending is delayed in order to stay within them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OutboundLimits {
+    /// The limit on messages to a single connection.
+    pub connection: RateLimit,
+    /// The limit on messages to all connections combined.
+    pub global: RateLimit,
+}
+
+/// The scope of a rate limit that was exceeded by an inbound message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitViolation {
+    /// The per-connection limit was exceeded.
+    Connection,
+    /// The per-connection limit for the given message type was exceeded.
+    Message(&'static str),
+    /// The global limit, shared by all connections, was exceeded.
+    Global,
+}
+
+impl fmt::Display for RateLimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Connection => write!(f, "the connection rate limit"),
+            Self::Message(name) => write!(f, "the rate limit for '{name}'"),
+            Self::Global => write!(f, "the global rate limit"),
+        }
+    }
+}
+
+/// A token bucket refilling at a constant rate, with a capacity of one second's worth of tokens.
+#[derive(Debug)]
+struct TokenBucket {
+    /// The number of tokens added per second, which is also the capacity.
+    rate: f64,
+    /// The number of tokens as of `updated_at`; this is negative while a debt is being repaid.
+    tokens: f64,
+    /// The time of the last refill.
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    /// Initializes a new full bucket with the given rate.
+    fn new(rate: u64, now: Instant) -> Self {
+        Self { rate: rate as f64, tokens: rate as f64, updated_at: now }
+    }
+
+    /// Adds the tokens accumulated since the last refill.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.updated_at = now;
+    }
+
+    /// Returns `true` if the given amount can be taken; a full bucket admits any amount.
+    fn allows(&self, amount: f64) -> bool {
+        self.tokens >= amount || self.tokens >= self.rate
+    }
+
+    /// Takes the given amount, possibly leaving a debt.
+    fn take(&mut self, amount: f64) {
+        self.tokens -= amount;
+    }
+
+    /// Returns the time until the debt, if any, is repaid.
+    fn debt(&self) -> Duration {
+        match self.tokens < 0.0 {
+            true => Duration::from_secs_f64(-self.tokens / self.rate),
+            false => Duration::ZERO,
+        }
+    }
+}
+
+/// Enforces a [`RateLimit`] using a pair of token buckets.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    /// The bucket for the number of messages.
+    messages: Option<TokenBucket>,
+    /// The bucket for the number of bytes.
+    bytes: Option<TokenBucket>,
+}
+
+impl RateLimiter {
+    /// Initializes a new rate limiter for the given limit.
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        let now = Instant::now();
+        Self {
+            messages: limit.messages_per_second.filter(|rate| *rate > 0).map(|rate| TokenBucket::new(rate.into(), now)),
+            bytes: limit.bytes_per_second.filter(|rate| *rate > 0).map(|rate| TokenBucket::new(rate, now)),
+        }
+    }
+
+    /// Consumes the tokens for a message of the given size unconditionally, and returns the time
+    /// to wait before the next message in order to stay within the limit.
+    pub(crate) fn acquire(&mut self, size: usize) -> Duration {
+        self.acquire_at(size, Instant::now())
+    }
+
+    /// Returns an iterator over the buckets and the amount a message of the given size takes from each of them.
+    fn buckets(&mut self, size: usize) -> impl Iterator<Item = (&mut TokenBucket, f64)> + '_ {
+        self.messages.iter_mut().map(|bucket| (bucket, 1.0)).chain(self.bytes.iter_mut().map(move |b| (b, size as f64)))
+    }
+
+    /// Returns `true` if the limit allows a message of the given size at the given time, without consuming any tokens.
+    pub(crate) fn allows_at(&mut self, size: usize, now: Instant) -> bool {
+        let mut is_allowed = true;
+        for (bucket, amount) in self.buckets(size) {
+            bucket.refill(now);
+            is_allowed &= bucket.allows(amount);
+        }
+        is_allowed
+    }
+
+    /// Consumes the tokens for a message of the given size, which must have been allowed by [`Self::allows_at`].
+    pub(crate) fn take(&mut self, size: usize) {
+        self.buckets(size).for_each(|(bucket, amount)| bucket.take(amount));
+    }
+
+    /// Admits a message of the given size at the given time if the limit allows it, or returns `false`
+    /// without consuming any tokens.
+    #[cfg(test)]
+    fn try_acquire_at(&mut self, size: usize, now: Instant) -> bool {
+        let is_allowed = self.allows_at(size, now);
+        if is_allowed {
+            self.take(size);
+        }
+        is_allowed
+    }
+
+    /// Consumes the tokens for a message of the given size at the given time.
+    fn acquire_at(&mut self, size: usize, now: Instant) -> Duration {
+        let mut delay = Duration::ZERO;
+        for (bucket, amount) in self.buckets(size) {
+            bucket.refill(now);
+            bucket.take(amount);
+            delay = delay.max(bucket.debt());
+        }
+        delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited() {
+        let mut limiter = RateLimiter::new(RateLimit::default());
+        let now = Instant::now();
+        for _ in 0..10_000 {
+            assert!(limiter.try_acquire_at(usize::MAX, now));
+        }
+        assert_eq!(limiter.acquire_at(usize::MAX, now), Duration::ZERO);
+        assert!(RateLimit::new(Some(0), Some(0)).is_unlimited());
+    }
+
+    #[test]
+    fn test_messages_per_second() {
+        let mut limiter = RateLimiter::new(RateLimit::new(Some(10), None));
+        let now = Instant::now();
+        // The bucket starts full, allowing a burst of one second's worth of messages.
+        for _ in 0..10 {
+            assert!(limiter.try_acquire_at(1, now));
+        }
+        assert!(!limiter.try_acquire_at(1, now));
+        // The bucket refills at the configured rate.
+        let later = now + Duration::from_millis(250);
+        assert!(limiter.try_acquire_at(1, later));
+        assert!(limiter.try_acquire_at(1, later));
+        assert!(!limiter.try_acquire_at(1, later));
+    }
+
+    #[test]
+    fn test_bytes_per_second() {
+        let mut limiter = RateLimiter::new(RateLimit::new(Some(100), Some(1_000)));
+        let now = Instant::now();
+        assert!(limiter.try_acquire_at(600, now));
+        // A rejected message does not consume any tokens.
+        assert!(!limiter.try_acquire_at(600, now));
+        assert!(limiter.try_acquire_at(400, now));
+        assert!(!limiter.try_acquire_at(1, now));
+        // A message larger than the limit is admitted once the bucket is full.
+        let later = now + Duration::from_secs(1);
+        assert!(limiter.try_acquire_at(5_000, later));
+        assert!(!limiter.try_acquire_at(1, later + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!("200".parse::<RateLimit>().unwrap(), RateLimit::new(Some(200), None));
+        assert_eq!("200:1048576".parse::<RateLimit>().unwrap(), RateLimit::new(Some(200), Some(1_048_576)));
+        assert_eq!("0:1024".parse::<RateLimit>().unwrap(), RateLimit::new(None, Some(1_024)));
+        assert!("0".parse::<RateLimit>().unwrap().is_unlimited());
+        assert!("".parse::<RateLimit>().is_err());
+        assert!("-1".parse::<RateLimit>().is_err());
+        assert!("1:2:3".parse::<RateLimit>().is_err());
+        for limit in ["200", "200:1048576", "0:1024", "0"] {
+            assert_eq!(limit.parse::<RateLimit>().unwrap().to_string(), limit);
+        }
+    }
+
+    #[test]
+    fn test_acquire_delay() {
+        let mut limiter = RateLimiter::new(RateLimit::new(None, Some(1_000)));
+        let now = Instant::now();
+        assert_eq!(limiter.acquire_at(1_000, now), Duration::ZERO);
+        assert_eq!(limiter.acquire_at(500, now), Duration::from_millis(500));
+        assert_eq!(limiter.acquire_at(0, now + Duration::from_millis(500)), Duration::ZERO);
+    }
+}
@@ -27,6 +27,8 @@ pub struct Stats {
     bytes_received: AtomicU64,
     /// The number of failures.
     failures: AtomicU64,
+    /// The number of inbound messages dropped for exceeding a rate limit.
+    rate_limited: AtomicU64,
 }
 
 impl Stats {
@@ -51,6 +53,11 @@ impl Stats {
         self.failures.load(Relaxed)
     }
 
+    /// Returns the number of inbound messages dropped for exceeding a rate limit.
+    pub fn rate_limited(&self) -> u64 {
+        self.rate_limited.load(Relaxed)
+    }
+
     /// Registers a sent message of the provided `size` in bytes.
     pub fn register_sent_message(&self, size: usize) {
         self.msgs_sent.fetch_add(1, Relaxed);
@@ -67,4 +74,9 @@ impl Stats {
     pub fn register_failure(&self) {
         self.failures.fetch_add(1, Relaxed);
     }
+
+    /// Registers an inbound message dropped for exceeding a rate limit.
+    pub fn register_rate_limited(&self) {
+        self.rate_limited.fetch_add(1, Relaxed);
+    }
 }
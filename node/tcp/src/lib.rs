@@ -25,7 +25,14 @@ pub mod protocols;
 mod tcp;
 pub use tcp::Tcp;
 
-use std::net::IpAddr;
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+    str::FromStr,
+};
+
+/// The target of the spans that carry the context of a log entry, such as the peer IP, round, or height.
+pub const CONTEXT_TARGET: &str = "snarkos_context";
 
 /// A trait for objects containing a [`Tcp`]; it is required to implement protocols.
 pub trait P2P {
@@ -33,21 +40,116 @@ pub trait P2P {
     fn tcp(&self) -> &Tcp;
 }
 
+/// An IP address family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpFamily {
+    /// IPv4.
+    Ipv4,
+    /// IPv6.
+    Ipv6,
+}
+
+impl IpFamily {
+    /// Returns the family of the given address.
+    /// IPv4-mapped IPv6 addresses are considered IPv4 addresses.
+    pub fn of(addr: &SocketAddr) -> Self {
+        match to_canonical_addr(*addr).is_ipv4() {
+            true => Self::Ipv4,
+            false => Self::Ipv6,
+        }
+    }
+
+    /// Returns the other family.
+    pub fn other(&self) -> Self {
+        match self {
+            Self::Ipv4 => Self::Ipv6,
+            Self::Ipv6 => Self::Ipv4,
+        }
+    }
+
+    /// Returns `true` if the given address belongs to this family.
+    /// IPv4-mapped IPv6 addresses are considered IPv4 addresses.
+    pub fn contains(&self, addr: &SocketAddr) -> bool {
+        match self {
+            Self::Ipv4 => to_canonical_addr(*addr).is_ipv4(),
+            Self::Ipv6 => to_canonical_addr(*addr).is_ipv6(),
+        }
+    }
+}
+
+impl FromStr for IpFamily {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ipv4" | "v4" | "4" => Ok(Self::Ipv4),
+            "ipv6" | "v6" | "6" => Ok(Self::Ipv6),
+            _ => Err(format!("Invalid IP family '{s}' (expected 'ipv4' or 'ipv6')")),
+        }
+    }
+}
+
+impl fmt::Display for IpFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Ipv4 => write!(f, "ipv4"),
+            Self::Ipv6 => write!(f, "ipv6"),
+        }
+    }
+}
+
+/// Returns the given address, with an IPv4-mapped IPv6 address (`::ffff:a.b.c.d`) converted to its IPv4 form.
+///
+/// A dual-stack listener reports the addresses of its IPv4 peers in the mapped form.
+pub fn to_canonical_addr(addr: SocketAddr) -> SocketAddr {
+    SocketAddr::new(to_canonical_ip(addr.ip()), addr.port())
+}
+
+/// Returns the given IP address, with an IPv4-mapped IPv6 address converted to its IPv4 form.
+fn to_canonical_ip(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        ipv4 => ipv4,
+    }
+}
+
 /// Checks if the given IP address is a bogon address.
 ///
 /// A bogon address is an IP address that should not appear on the public Internet.
-/// This includes private addresses, loopback addresses, and link-local addresses.
+/// This includes private addresses (and IPv6 unique local addresses), loopback addresses, and link-local addresses.
 pub fn is_bogon_ip(ip: IpAddr) -> bool {
-    match ip {
+    match to_canonical_ip(ip) {
         IpAddr::V4(ipv4) => ipv4.is_loopback() || ipv4.is_private() || ipv4.is_link_local(),
-        IpAddr::V6(ipv6) => ipv6.is_loopback(),
+        IpAddr::V6(ipv6) => {
+            // The unique local addresses are `fc00::/7`, and the link-local unicast addresses are `fe80::/10`.
+            let first_segment = ipv6.segments()[0];
+            ipv6.is_loopback() || (first_segment & 0xfe00) == 0xfc00 || (first_segment & 0xffc0) == 0xfe80
+        }
     }
 }
 
-/// Checks if the given IP address is unspecified or broadcast.
+/// Checks if the given IP address is unspecified, broadcast, or (for IPv6) multicast.
 pub fn is_unspecified_or_broadcast_ip(ip: IpAddr) -> bool {
-    match ip {
+    match to_canonical_ip(ip) {
         IpAddr::V4(ipv4) => ipv4.is_unspecified() || ipv4.is_broadcast(),
-        ipv6 => ipv6.is_unspecified(),
+        IpAddr::V6(ipv6) => ipv6.is_unspecified() || ipv6.is_multicast(),
     }
 }
+
+/// Returns the public IP address of the given family that the host reaches the Internet from, if it has one.
+///
+/// No packets are sent, as connecting a UDP socket only selects the route, and thus the local address, to the target.
+pub fn routable_ip(family: IpFamily) -> Option<IpAddr> {
+    // Any public address would do as the target; these are those of well-known DNS resolvers.
+    let (local_addr, target_addr): (SocketAddr, SocketAddr) = match family {
+        IpFamily::Ipv4 => ((Ipv4Addr::UNSPECIFIED, 0).into(), (Ipv4Addr::new(8, 8, 8, 8), 53).into()),
+        IpFamily::Ipv6 => {
+            ((Ipv6Addr::UNSPECIFIED, 0).into(), (Ipv6Addr::new(0x2001, 0x4860, 0x4860, 0, 0, 0, 0, 0x8888), 53).into())
+        }
+    };
+    let socket = UdpSocket::bind(local_addr).ok()?;
+    socket.connect(target_addr).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    // A host behind a NAT only knows its private address, which is of no use to its peers.
+    (!is_bogon_ip(ip) && !is_unspecified_or_broadcast_ip(ip)).then_some(ip)
+}
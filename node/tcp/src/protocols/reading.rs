@@ -17,6 +17,9 @@ use crate::{protocols::Handshake, Config};
 use crate::{
     protocols::{ProtocolHandler, ReturnableConnection},
     ConnectionSide,
+    RateLimit,
+    RateLimitViolation,
+    RateLimiter,
     Tcp,
     P2P,
 };
@@ -24,7 +27,7 @@ use crate::{
 use async_trait::async_trait;
 use bytes::BytesMut;
 use futures_util::StreamExt;
-use std::{io, net::SocketAddr};
+use std::{collections::HashMap, io, net::SocketAddr, time::Instant};
 use tokio::{
     io::AsyncRead,
     sync::{mpsc, oneshot},
@@ -39,6 +42,10 @@ use tracing::*;
 /// which is immediately queued (with a [`Reading::MESSAGE_QUEUE_DEPTH`] limit) to be processed by
 /// [`Reading::process_message`]. The configured fatal IO errors result in an immediate disconnect
 /// (in order to e.g. avoid accidentally reading "borked" messages).
+///
+/// Inbound messages of a limited type ([`Reading::message_limit`]) that exceed the limit for their type,
+/// the per-connection limit ([`Config::inbound_limit`]), or the global limit ([`Config::global_inbound_limit`])
+/// are dropped before being queued, and reported via [`Reading::rate_limit_exceeded`].
 #[async_trait]
 pub trait Reading: P2P
 where
@@ -95,6 +102,21 @@ where
 
     /// Processes an inbound message. Can be used to update state, send replies etc.
     async fn process_message(&self, source: SocketAddr, message: Self::Message) -> io::Result<()>;
+
+    /// Returns the name of the type of the given inbound message and the per-connection rate limit for that type,
+    /// if the type is limited. Messages sharing a name share a limit. Only the messages of a limited type count
+    /// towards the per-connection and global limits, so e.g. responses can be exempted from all limits.
+    ///
+    /// The default implementation limits all messages by the per-connection and global limits only.
+    fn message_limit(&self, _message: &Self::Message) -> Option<(&'static str, RateLimit)> {
+        Some(("message", RateLimit::default()))
+    }
+
+    /// Called after an inbound message from the given address is dropped for exceeding a rate limit.
+    /// Can be used to penalize or disconnect the peer; it must not block, as it is called from the reading task.
+    ///
+    /// The default implementation does nothing.
+    fn rate_limit_exceeded(&self, _source: SocketAddr, _violation: RateLimitViolation) {}
 }
 
 /// This trait is used to restrict access to methods that would otherwise be public in [`Reading`].
@@ -109,6 +131,16 @@ trait ReadingInternal: Reading {
         framed: FramedRead<T, Self::Codec>,
         addr: SocketAddr,
     ) -> FramedRead<T, CountingCodec<Self::Codec>>;
+
+    /// Checks an inbound message of the given size against the per-message-type, per-connection,
+    /// and global rate limits, in that order, and only then consumes its tokens from all of them.
+    fn check_rate_limits(
+        &self,
+        message: &Self::Message,
+        size: usize,
+        conn_limiter: &mut RateLimiter,
+        message_limiters: &mut HashMap<&'static str, RateLimiter>,
+    ) -> Result<(), RateLimitViolation>;
 }
 
 #[async_trait]
@@ -156,8 +188,9 @@ impl<R: Reading> ReadingInternal for R {
         let (tx_reader, rx_reader) = oneshot::channel::<()>();
 
         // the task for reading messages from a stream
-        let node = self.tcp().clone();
+        let self_clone = self.clone();
         let reader_task = tokio::spawn(async move {
+            let node = self_clone.tcp();
             trace!(parent: node.span(), "spawned a task for reading messages from {addr}");
             tx_reader.send(()).unwrap(); // safe; the channel was just opened
 
@@ -165,9 +198,23 @@ impl<R: Reading> ReadingInternal for R {
             // this task gets aborted, so there is no need for a dedicated timeout
             let _ = rx_conn_ready.await;
 
+            // the per-connection rate limiters, for all messages and for each limited message type
+            let mut conn_limiter = RateLimiter::new(node.config().inbound_limit);
+            let mut message_limiters = HashMap::new();
+
             while let Some(bytes) = framed.next().await {
                 match bytes {
-                    Ok(msg) => {
+                    Ok((msg, size)) => {
+                        // drop the message if it exceeds a rate limit
+                        if let Err(violation) =
+                            self_clone.check_rate_limits(&msg, size, &mut conn_limiter, &mut message_limiters)
+                        {
+                            debug!(parent: node.span(), "dropping a message from {addr}, as it exceeds {violation}");
+                            node.known_peers().register_rate_limited(addr);
+                            node.stats().register_rate_limited();
+                            self_clone.rate_limit_exceeded(addr, violation);
+                            continue;
+                        }
                         // send the message for further processing
                         if let Err(e) = inbound_message_sender.try_send(msg) {
                             error!(parent: node.span(), "can't process a message from {addr}: {e}");
@@ -204,9 +251,40 @@ impl<R: Reading> ReadingInternal for R {
     ) -> FramedRead<T, CountingCodec<Self::Codec>> {
         framed.map_decoder(|codec| CountingCodec { codec, node: self.tcp().clone(), addr, acc: 0 })
     }
+
+    fn check_rate_limits(
+        &self,
+        message: &Self::Message,
+        size: usize,
+        conn_limiter: &mut RateLimiter,
+        message_limiters: &mut HashMap<&'static str, RateLimiter>,
+    ) -> Result<(), RateLimitViolation> {
+        // messages of a type without a limit are exempt from all limits
+        let Some((name, limit)) = self.message_limit(message) else {
+            return Ok(());
+        };
+        let message_limiter = message_limiters.entry(name).or_insert_with(|| RateLimiter::new(limit));
+        let mut global_limiter = self.tcp().inbound_limiter.lock();
+
+        // check all the limits first, so that a dropped message doesn't consume any tokens
+        let now = Instant::now();
+        if !message_limiter.allows_at(size, now) {
+            return Err(RateLimitViolation::Message(name));
+        }
+        if !conn_limiter.allows_at(size, now) {
+            return Err(RateLimitViolation::Connection);
+        }
+        if !global_limiter.allows_at(size, now) {
+            return Err(RateLimitViolation::Global);
+        }
+        message_limiter.take(size);
+        conn_limiter.take(size);
+        global_limiter.take(size);
+        Ok(())
+    }
 }
 
-/// A wrapper [`Decoder`] that also counts the inbound messages.
+/// A wrapper [`Decoder`] that also counts the inbound messages, and attaches their size to them.
 struct CountingCodec<D: Decoder> {
     codec: D,
     node: Tcp,
@@ -216,7 +294,7 @@ struct CountingCodec<D: Decoder> {
 
 impl<D: Decoder> Decoder for CountingCodec<D> {
     type Error = D::Error;
-    type Item = D::Item;
+    type Item = (D::Item, usize);
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         let initial_buf_len = src.len();
@@ -236,6 +314,6 @@ impl<D: Decoder> Decoder for CountingCodec<D> {
             }
         }
 
-        Ok(ret)
+        Ok(ret.map(|message| (message, read_len)))
     }
 }
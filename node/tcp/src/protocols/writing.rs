@@ -30,6 +30,7 @@ use crate::{
     protocols::{Protocol, ProtocolHandler, ReturnableConnection},
     Connection,
     ConnectionSide,
+    RateLimiter,
     P2P,
 };
 
@@ -37,6 +38,9 @@ type WritingSenders = Arc<RwLock<HashMap<SocketAddr, mpsc::Sender<WrappedMessage
 
 /// Can be used to specify and enable writing, i.e. sending outbound messages. If the [`Handshake`]
 /// protocol is enabled too, it goes into force only after the handshake has been concluded.
+///
+/// Outbound messages are delayed as needed to stay within the per-connection ([`Config::outbound_limit`])
+/// and global ([`Config::global_outbound_limit`]) rate limits.
 #[async_trait]
 pub trait Writing: P2P
 where
@@ -213,6 +217,9 @@ impl<W: Writing> WritingInternal for W {
             // move the cleanup into the task that gets aborted on disconnect
             let _auto_cleanup = auto_cleanup;
 
+            // the per-connection rate limiter
+            let mut conn_limiter = RateLimiter::new(node.config().outbound_limit);
+
             while let Some(wrapped_msg) = outbound_message_receiver.recv().await {
                 let msg = wrapped_msg.msg.downcast().unwrap();
 
@@ -222,6 +229,12 @@ impl<W: Writing> WritingInternal for W {
                         node.known_peers().register_sent_message(addr, len);
                         node.stats().register_sent_message(len);
                         trace!(parent: node.span(), "sent {}B to {}", len, addr);
+
+                        // delay the next message in order to stay within the rate limits
+                        let delay = conn_limiter.acquire(len).max(node.outbound_limiter.lock().acquire(len));
+                        if !delay.is_zero() {
+                            tokio::time::sleep(delay).await;
+                        }
                     }
                     Err(e) => {
                         node.known_peers().register_failure(addr);
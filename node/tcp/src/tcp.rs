@@ -16,7 +16,7 @@ use std::{
     collections::HashSet,
     fmt,
     io,
-    net::{IpAddr, SocketAddr},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
     ops::Deref,
     sync::{
         atomic::{AtomicUsize, Ordering::*},
@@ -27,6 +27,7 @@ use std::{
 
 use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
+use socket2::{Domain, Protocol, Socket, Type};
 use tokio::{
     io::split,
     net::{TcpListener, TcpStream},
@@ -41,6 +42,7 @@ use crate::{
     protocols::{Protocol, Protocols},
     Config,
     KnownPeers,
+    RateLimiter,
     Stats,
 };
 
@@ -77,6 +79,10 @@ pub struct InnerTcp {
     known_peers: KnownPeers,
     /// Collects statistics related to the node itself.
     stats: Stats,
+    /// Enforces the global limit on inbound messages.
+    pub(crate) inbound_limiter: Mutex<RateLimiter>,
+    /// Enforces the global limit on outbound messages.
+    pub(crate) outbound_limiter: Mutex<RateLimiter>,
     /// The node's tasks.
     pub(crate) tasks: Mutex<Vec<JoinHandle<()>>>,
 }
@@ -92,6 +98,10 @@ impl Tcp {
         // Create a tracing span containing the node's name.
         let span = crate::helpers::create_span(config.name.as_deref().unwrap());
 
+        // Initialize the global rate limiters.
+        let inbound_limiter = Mutex::new(RateLimiter::new(config.global_inbound_limit));
+        let outbound_limiter = Mutex::new(RateLimiter::new(config.global_outbound_limit));
+
         // Initialize the Tcp stack.
         let tcp = Tcp(Arc::new(InnerTcp {
             span,
@@ -102,6 +112,8 @@ impl Tcp {
             connections: Default::default(),
             known_peers: Default::default(),
             stats: Default::default(),
+            inbound_limiter,
+            outbound_limiter,
             tasks: Default::default(),
         }));
 
@@ -298,11 +310,11 @@ impl Tcp {
         // Initialize the TCP listener.
         let listener = self.create_listener(listener_ip).await?;
 
-        // Discover the port, if it was unspecified.
-        let port = listener.local_addr()?.port();
+        // Discover the listening address, as the port may have been unspecified,
+        // and a dual-stack listener may have fallen back to IPv4.
+        let listening_addr = listener.local_addr()?;
 
         // Set the listening IP address.
-        let listening_addr = (listener_ip, port).into();
         self.listening_addr.set(listening_addr).expect("The node's listener was started more than once");
 
         // Use a channel to know when the listening task is ready.
@@ -316,7 +328,7 @@ impl Tcp {
             loop {
                 // Await for a new connection.
                 match listener.accept().await {
-                    Ok((stream, addr)) => tcp.handle_connection(stream, addr),
+                    Ok((stream, addr)) => tcp.handle_connection(stream, crate::to_canonical_addr(addr)),
                     Err(e) => error!(parent: tcp.span(), "Failed to accept a connection: {e}"),
                 }
             }
@@ -335,7 +347,7 @@ impl Tcp {
             // Construct the desired listening IP address.
             let desired_listening_addr = SocketAddr::new(listener_ip, port);
             // If a desired listening port is set, try to bind to it.
-            match TcpListener::bind(desired_listening_addr).await {
+            match self.bind(desired_listening_addr) {
                 Ok(listener) => listener,
                 Err(e) => {
                     if self.config().allow_random_port {
@@ -344,7 +356,7 @@ impl Tcp {
                             "Trying any listening port, as the desired port is unavailable: {e}"
                         );
                         let random_available_addr = SocketAddr::new(listener_ip, 0);
-                        TcpListener::bind(random_available_addr).await?
+                        self.bind(random_available_addr)?
                     } else {
                         error!(parent: self.span(), "The desired listening port is unavailable: {e}");
                         return Err(e);
@@ -353,7 +365,7 @@ impl Tcp {
             }
         } else if self.config().allow_random_port {
             let random_available_addr = SocketAddr::new(listener_ip, 0);
-            TcpListener::bind(random_available_addr).await?
+            self.bind(random_available_addr)?
         } else {
             panic!("As 'listener_ip' is set, either 'desired_listening_port' or 'allow_random_port' must be set");
         };
@@ -361,6 +373,35 @@ impl Tcp {
         Ok(listener)
     }
 
+    /// Binds a TCP listener to the given address. If [`Config::dual_stack`] is set, the unspecified IPv6
+    /// address is bound in dual-stack mode, falling back to the unspecified IPv4 address if IPv6 is unavailable.
+    fn bind(&self, addr: SocketAddr) -> io::Result<TcpListener> {
+        let is_dual_stack = self.config().dual_stack && addr.ip() == IpAddr::V6(Ipv6Addr::UNSPECIFIED);
+
+        match Self::bind_socket(addr, !is_dual_stack) {
+            Err(e) if is_dual_stack && e.kind() != io::ErrorKind::AddrInUse => {
+                warn!(parent: self.span(), "Listening on IPv4 only, as IPv6 is unavailable: {e}");
+                Self::bind_socket(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), addr.port()), false)
+            }
+            result => result,
+        }
+    }
+
+    /// Binds a TCP listener to the given address; `only_v6` determines if an IPv6 listener rejects IPv4 connections.
+    fn bind_socket(addr: SocketAddr, only_v6: bool) -> io::Result<TcpListener> {
+        let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+        if addr.is_ipv6() {
+            socket.set_only_v6(only_v6)?;
+        }
+        // Allow the address to be reused right after the listener is closed, as `TcpListener::bind` does.
+        #[cfg(unix)]
+        socket.set_reuse_address(true)?;
+        socket.bind(&addr.into())?;
+        socket.listen(1024)?;
+        socket.set_nonblocking(true)?;
+        TcpListener::from_std(socket.into())
+    }
+
     /// Handles a new inbound connection.
     fn handle_connection(&self, stream: TcpStream, addr: SocketAddr) {
         debug!(parent: self.span(), "Received a connection from {addr}");
@@ -550,6 +591,38 @@ mod tests {
         assert!(!tcp.is_connecting(peer_ip));
     }
 
+    #[tokio::test]
+    async fn test_dual_stack() {
+        let tcp = Tcp::new(Config {
+            listener_ip: Some(IpAddr::V6(Ipv6Addr::UNSPECIFIED)),
+            desired_listening_port: Some(0),
+            ..Default::default()
+        });
+        let node_ip = tcp.enable_listener().await.unwrap();
+
+        // Initialize an IPv4 peer.
+        let peer = Tcp::new(Config {
+            listener_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            desired_listening_port: Some(0),
+            ..Default::default()
+        });
+        peer.enable_listener().await.unwrap();
+
+        // Connect to the node over IPv4, which works whether the listener is dual-stack or fell back to IPv4.
+        let node_ipv4 = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), node_ip.port());
+        peer.connect(node_ipv4).await.unwrap();
+        assert!(peer.is_connected(node_ipv4));
+
+        // Ensure the node sees the peer by its IPv4 address, rather than an IPv4-mapped IPv6 address.
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+        while tcp.num_connected() == 0 && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let connected_addrs = tcp.connected_addrs();
+        assert_eq!(connected_addrs.len(), 1);
+        assert!(connected_addrs[0].is_ipv4());
+    }
+
     #[tokio::test]
     async fn test_disconnect() {
         let tcp = Tcp::new(Config::default());
@@ -14,14 +14,17 @@
 
 use crate::common::test_peer::sample_genesis_block;
 use snarkos_account::Account;
-use snarkos_node::{Client, Prover, Validator};
+use snarkos_node::{consensus::MemoryPoolConfig, Client, Prover, Validator};
 use snarkvm::prelude::{store::helpers::memory::ConsensusMemory, MainnetV0 as CurrentNetwork};
 
 use aleo_std::StorageMode;
 use std::str::FromStr;
+use tempfile::TempDir;
 
-pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
-    Client::new(
+/// Initializes a client, along with its storage directory, which is removed when dropped.
+pub async fn client() -> (Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>>, TempDir) {
+    let directory = tempfile::tempdir().expect("couldn't create a storage directory");
+    let node = Client::new(
         "127.0.0.1:0".parse().unwrap(),
         None,
         10,
@@ -29,39 +32,61 @@ pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>>
         &[],
         sample_genesis_block(),
         None, // No CDN.
-        StorageMode::Production,
+        StorageMode::Custom(directory.path().to_path_buf()),
+        None, // No preferred IP family.
+        Default::default(),
+        Default::default(),
     )
     .await
-    .expect("couldn't create client instance")
+    .expect("couldn't create client instance");
+    (node, directory)
 }
 
-pub async fn prover() -> Prover<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
-    Prover::new(
+/// Initializes a prover, along with its storage directory, which is removed when dropped.
+pub async fn prover() -> (Prover<CurrentNetwork, ConsensusMemory<CurrentNetwork>>, TempDir) {
+    let directory = tempfile::tempdir().expect("couldn't create a storage directory");
+    let node = Prover::new(
         "127.0.0.1:0".parse().unwrap(),
+        None,
+        10,
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         sample_genesis_block(),
-        StorageMode::Production,
+        StorageMode::Custom(directory.path().to_path_buf()),
+        None, // No pool.
+        None, // No preferred IP family.
+        Default::default(),
+        Default::default(),
     )
     .await
-    .expect("couldn't create prover instance")
+    .expect("couldn't create prover instance");
+    (node, directory)
 }
 
-pub async fn validator() -> Validator<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
-    Validator::new(
+/// Initializes a validator, along with its storage directory, which is removed when dropped.
+pub async fn validator() -> (Validator<CurrentNetwork, ConsensusMemory<CurrentNetwork>>, TempDir) {
+    let directory = tempfile::tempdir().expect("couldn't create a storage directory");
+    let node = Validator::new(
         "127.0.0.1:0".parse().unwrap(),
         None,
         None,
         10,
-        Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
+        Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1")
+            .unwrap()
+            .into(),
         &[],
         &[],
         sample_genesis_block(), // Should load the current network's genesis block.
         None,                   // No CDN.
-        StorageMode::Production,
+        StorageMode::Custom(directory.path().to_path_buf()),
         true,  // This test requires validators to connect to peers.
         false, // No dev traffic in production mode.
+        MemoryPoolConfig::default(),
+        None, // No preferred IP family.
+        Default::default(),
+        Default::default(),
     )
     .await
-    .expect("couldn't create validator instance")
+    .expect("couldn't create validator instance");
+    (node, directory)
 }
@@ -44,7 +44,7 @@ macro_rules! test_disconnect {
             // $crate::common::initialise_logger(2);
 
             // Spin up a full node.
-            let node = $crate::$node_type().await;
+            let (node, _directory) = $crate::$node_type().await;
 
             // Spin up a test peer (synthetic node).
             let peer = $crate::TestPeer::$peer_type().await;
@@ -149,8 +149,8 @@ async fn duplicate_disconnect_attempts() {
     // common::initialise_logger(3);
 
     // Spin up 2 full nodes.
-    let node1 = validator().await;
-    let node2 = validator().await;
+    let (node1, _directory1) = validator().await;
+    let (node2, _directory2) = validator().await;
     let addr2 = node2.tcp().listening_addr().unwrap();
 
     // Connect node1 to node2.
@@ -99,7 +99,7 @@ macro_rules! test_handshake {
             // $crate::common::initialise_logger(2);
 
             // Spin up a full node.
-            let node = $crate::$node_type().await;
+            let (node, _directory) = $crate::$node_type().await;
 
             // Spin up a test peer (synthetic node).
             let peer = $crate::common::test_peer::TestPeer::$peer_type().await;
@@ -187,9 +187,9 @@ async fn simultaneous_connection_attempt() {
     // common::initialise_logger(3);
 
     // Spin up 2 full nodes.
-    let node1 = validator().await;
+    let (node1, _directory1) = validator().await;
     let addr1 = node1.listening_addr();
-    let node2 = validator().await;
+    let (node2, _directory2) = validator().await;
     let addr2 = node2.listening_addr();
 
     // Prepare connection attempts.
@@ -246,8 +246,8 @@ async fn duplicate_connection_attempts() {
     // common::initialise_logger(3);
 
     // Spin up 2 full nodes.
-    let node1 = validator().await;
-    let node2 = validator().await;
+    let (node1, _directory1) = validator().await;
+    let (node2, _directory2) = validator().await;
     let addr2 = node2.listening_addr();
 
     // Prepare connection attempts.
@@ -34,7 +34,7 @@ macro_rules! test_reject_unsolicited_peer_response {
                 #[tokio::test]
                 async fn [<$node_type _rejects_unsolicited_peer_response>]() {
                     // Spin up a full node.
-                    let node = $crate::common::node::$node_type().await;
+                    let (node, _directory) = $crate::common::node::$node_type().await;
 
                     // Spin up a test peer (synthetic node), it doesn't really matter what type it is.
                     let peer = TestPeer::validator().await;